@@ -154,6 +154,65 @@ pub enum Literal<'a> {
     /// # }
     /// ```
     String(Token<'a, Cow<'a, [u8]>>),
+
+    /// A string with embedded expressions, i.e. a double quoted
+    /// string or a heredoc containing at least one variable, or one
+    /// expression enclosed in `{$…}` or `${…}`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use std::borrow::Cow;
+    /// use tagua_parser::ast::{
+    ///     Expression,
+    ///     Literal,
+    ///     StringFragment,
+    ///     Variable
+    /// };
+    /// use tagua_parser::rules::literals::literal;
+    /// use tagua_parser::tokens::{
+    ///     Span,
+    ///     Token
+    /// };
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     literal(Span::new(b"\"foo $bar\"")),
+    ///     Ok((
+    ///         Span::new_at(b"", 10, 1, 11),
+    ///         Literal::InterpolatedString(Token::new(
+    ///             vec![
+    ///                 StringFragment::Literal(Token::new(
+    ///                     Cow::from(&b"foo "[..]),
+    ///                     Span::new_at(b"foo ", 1, 1, 2)
+    ///                 )),
+    ///                 StringFragment::Expression(Token::new(
+    ///                     Box::new(Expression::Variable(Variable(Span::new_at(b"bar", 6, 1, 7)))),
+    ///                     Span::new_at(b"$bar", 5, 1, 6)
+    ///                 ))
+    ///             ],
+    ///             Span::new(b"\"foo $bar\"")
+    ///         ))
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    InterpolatedString(Token<'a, Vec<StringFragment<'a>>>),
+}
+
+/// A fragment of an interpolated string.
+#[derive(Debug, PartialEq)]
+pub enum StringFragment<'a> {
+    /// A sequence of characters, with escape sequences already
+    /// interpreted. The span covers the fragment as written in the
+    /// source.
+    Literal(Token<'a, Cow<'a, [u8]>>),
+
+    /// An embedded expression, e.g. `$foo`, `$foo[42]`, `$foo->bar`,
+    /// `{$foo->bar()}` or `${foo}`. The span covers the whole
+    /// embedding, including the delimiters.
+    Expression(Token<'a, Box<Expression<'a>>>),
 }
 
 /// A variable.
@@ -348,11 +407,13 @@ pub enum Expression<'a> {
     ///     AnonymousFunction,
     ///     Arity,
     ///     Expression,
+    ///     FunctionKind,
     ///     Name,
     ///     Parameter,
     ///     DeclarationScope,
     ///     Statement,
     ///     Ty,
+    ///     Type,
     ///     Variable
     /// };
     /// use tagua_parser::rules::expressions::expression;
@@ -368,22 +429,28 @@ pub enum Expression<'a> {
     ///         Span::new_at(b"", 46, 1, 47),
     ///         Expression::AnonymousFunction(
     ///             AnonymousFunction {
+    ///                 attributes       : vec![],
     ///                 declaration_scope: DeclarationScope::Dynamic,
     ///                 inputs           : Arity::Finite(vec![
     ///                     Parameter {
-    ///                         ty   : Ty::Copy(Some(Name::Unqualified(Span::new_at(b"I", 10, 1, 11)))),
-    ///                         name : Variable(Span::new_at(b"x", 13, 1, 14)),
-    ///                         value: None
+    ///                         attributes: vec![],
+    ///                         promotion : None,
+    ///                         ty        : Ty::Copy(Some(Type::Name(Name::Unqualified(Span::new_at(b"I", 10, 1, 11))))),
+    ///                         name      : Variable(Span::new_at(b"x", 13, 1, 14)),
+    ///                         value     : None
     ///                     },
     ///                     Parameter {
-    ///                         ty   : Ty::Reference(Some(Name::Unqualified(Span::new_at(b"J", 16, 1, 17)))),
-    ///                         name : Variable(Span::new_at(b"y", 20, 1, 21)),
-    ///                         value: None
+    ///                         attributes: vec![],
+    ///                         promotion : None,
+    ///                         ty        : Ty::Reference(Some(Type::Name(Name::Unqualified(Span::new_at(b"J", 16, 1, 17))))),
+    ///                         name      : Variable(Span::new_at(b"y", 20, 1, 21)),
+    ///                         value     : None
     ///                     }
     ///                 ]),
-    ///                 output         : Ty::Copy(Some(Name::Unqualified(Span::new_at(b"O", 33, 1, 34)))),
-    ///                 enclosing_scope: Some(vec![Expression::Variable(Variable(Span::new_at(b"z", 29, 1, 30)))]),
-    ///                 body           : vec![Statement::Return]
+    ///                 output           : Ty::Copy(Some(Type::Name(Name::Unqualified(Span::new_at(b"O", 33, 1, 34))))),
+    ///                 enclosing_scope  : Some(vec![Expression::Variable(Variable(Span::new_at(b"z", 29, 1, 30)))]),
+    ///                 kind             : FunctionKind::Regular,
+    ///                 body             : vec![Statement::Return(None)]
     ///             }
     ///         )
     ///     ))
@@ -392,8 +459,10 @@ pub enum Expression<'a> {
     /// ```
     AnonymousFunction(AnonymousFunction<'a>),
 
-    /// A collection of heterogeneous pairs (key, value). The key is
-    /// optional.
+    /// A collection of heterogeneous items, i.e. pairs (key, value)
+    /// where the key is optional, or unpacked arrays, e.g. `[1, 'a' =>
+    /// &$x, ...$y]`. Both the short syntax `[…]` and the long syntax
+    /// `array(…)` are represented the same way.
     ///
     /// # Examples
     ///
@@ -401,6 +470,7 @@ pub enum Expression<'a> {
     /// # extern crate tagua_parser;
     /// use std::borrow::Cow;
     /// use tagua_parser::ast::{
+    ///     ArrayItem,
     ///     Expression,
     ///     Literal,
     ///     Variable
@@ -417,16 +487,16 @@ pub enum Expression<'a> {
     ///     Ok((
     ///         Span::new_at(b"", 35, 1, 36),
     ///         Expression::Array(vec![
-    ///             (
+    ///             ArrayItem::Pair(
     ///                 None,
     ///                 Expression::Literal(Literal::String(Token::new(Cow::from(&b"foo"[..]), Span::new_at(b"'foo'", 1, 1, 2))))
     ///             ),
-    ///             (
-    ///                 Some(Expression::Literal(Literal::Integer(Token::new(42i64, Span::new_at(b"42", 8, 1, 9))))),
+    ///             ArrayItem::Pair(
+    ///                 Some(Box::new(Expression::Literal(Literal::Integer(Token::new(42i64, Span::new_at(b"42", 8, 1, 9)))))),
     ///                 Expression::Literal(Literal::String(Token::new(Cow::from(&b"bar"[..]), Span::new_at(b"'bar'", 14, 1, 15))))
     ///             ),
-    ///             (
-    ///                 Some(Expression::Literal(Literal::String(Token::new(Cow::from(&b"baz"[..]), Span::new_at(b"'baz'", 21, 1, 22))))),
+    ///             ArrayItem::Pair(
+    ///                 Some(Box::new(Expression::Literal(Literal::String(Token::new(Cow::from(&b"baz"[..]), Span::new_at(b"'baz'", 21, 1, 22)))))),
     ///                 Expression::Variable(Variable(Span::new_at(b"qux", 31, 1, 32)))
     ///             )
     ///         ])
@@ -434,7 +504,132 @@ pub enum Expression<'a> {
     /// );
     /// # }
     /// ```
-    Array(Vec<(Option<Expression<'a>>, Expression<'a>)>),
+    Array(Vec<ArrayItem<'a>>),
+
+    /// Array access, aka dimension fetch, reads an element of an
+    /// array or a character of a string, e.g. `$foo[42]`. The index is
+    /// absent for `$foo[]`, which is used to append an element to an
+    /// array. The legacy curly braces syntax, e.g. `$foo{42}`, is
+    /// represented the same way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::ast::{
+    ///     DereferencableExpression,
+    ///     Expression,
+    ///     Literal,
+    ///     Variable
+    /// };
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::tokens::{
+    ///     Span,
+    ///     Token
+    /// };
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     expression(Span::new(b"$foo[42]")),
+    ///     Ok((
+    ///         Span::new_at(b"", 8, 1, 9),
+    ///         Expression::ArrayAccess(
+    ///             DereferencableExpression::Variable(Variable(Span::new_at(b"foo", 1, 1, 2))),
+    ///             Some(Box::new(Expression::Literal(Literal::Integer(Token::new(42i64, Span::new_at(b"42", 5, 1, 6))))))
+    ///         )
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    ArrayAccess(DereferencableExpression<'a>, Option<Box<Expression<'a>>>),
+
+    /// An arrow function is a short anonymous function, whose body is
+    /// a single expression. The variables of the enclosing scope are
+    /// captured by value automatically.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::ast::{
+    ///     Arity,
+    ///     ArrowFunction,
+    ///     DeclarationScope,
+    ///     Expression,
+    ///     FunctionKind,
+    ///     Parameter,
+    ///     Ty,
+    ///     Variable
+    /// };
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::tokens::Span;
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     expression(Span::new(b"fn ($x) => $x")),
+    ///     Ok((
+    ///         Span::new_at(b"", 13, 1, 14),
+    ///         Expression::ArrowFunction(
+    ///             ArrowFunction {
+    ///                 attributes       : vec![],
+    ///                 declaration_scope: DeclarationScope::Dynamic,
+    ///                 inputs           : Arity::Finite(vec![
+    ///                     Parameter {
+    ///                         attributes: vec![],
+    ///                         promotion : None,
+    ///                         ty        : Ty::Copy(None),
+    ///                         name      : Variable(Span::new_at(b"x", 5, 1, 6)),
+    ///                         value     : None
+    ///                     }
+    ///                 ]),
+    ///                 output           : Ty::Copy(None),
+    ///                 kind             : FunctionKind::Regular,
+    ///                 body             : Box::new(Expression::Variable(Variable(Span::new_at(b"x", 12, 1, 13))))
+    ///             }
+    ///         )
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    ArrowFunction(ArrowFunction<'a>),
+
+    /// A call of a function, or of any callable expression, e.g.
+    /// `foo(42)` or `$foo(42)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::ast::{
+    ///     Argument,
+    ///     Arguments,
+    ///     Expression,
+    ///     Name,
+    ///     Variable
+    /// };
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::tokens::{
+    ///     Span,
+    ///     Token
+    /// };
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     expression(Span::new(b"foo($x, ...$y)")),
+    ///     Ok((
+    ///         Span::new_at(b"", 14, 1, 15),
+    ///         Expression::Call(
+    ///             Box::new(Expression::Name(Name::Unqualified(Span::new(b"foo")))),
+    ///             Arguments::List(vec![
+    ///                 Argument::Positional(Expression::Variable(Variable(Span::new_at(b"x", 5, 1, 6)))),
+    ///                 Argument::Unpacked(Expression::Variable(Variable(Span::new_at(b"y", 12, 1, 13))))
+    ///             ])
+    ///         )
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    Call(Box<Expression<'a>>, Arguments<'a>),
 
     /// Class constant access is used to access a class constant given
     /// by a certain scope resolver.
@@ -466,6 +661,36 @@ pub enum Expression<'a> {
     /// ```
     ClassConstantAccess(ScopeResolver<'a>, Span<'a>),
 
+    /// Create a shallow copy of an object.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::ast::{
+    ///     Expression,
+    ///     Variable
+    /// };
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::tokens::{
+    ///     Span,
+    ///     Token
+    /// };
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     expression(Span::new(b"clone $foo")),
+    ///     Ok((
+    ///         Span::new_at(b"", 10, 1, 11),
+    ///         Expression::Clone(
+    ///             Box::new(Expression::Variable(Variable(Span::new_at(b"foo", 7, 1, 8))))
+    ///         )
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    Clone(Box<Expression<'a>>),
+
     /// Echo converts each of its expression's values into strings,
     /// concatenates them in order given, and writes the result to the
     /// output stream.
@@ -606,14 +831,48 @@ pub enum Expression<'a> {
     /// ```
     Exit(Option<Box<Expression<'a>>>),
 
+    /// Include or require a file, and evaluate it, e.g. `include
+    /// $path` or `require_once $path`.
+    ///
+    /// The path has a lower precedence than the logical operators,
+    /// e.g. `include $a or $b` includes `$a or $b`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::ast::{
+    ///     Expression,
+    ///     ImportKind,
+    ///     Variable
+    /// };
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::tokens::Span;
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     expression(Span::new(b"require_once $path")),
+    ///     Ok((
+    ///         Span::new_at(b"", 18, 1, 19),
+    ///         Expression::Import(
+    ///             ImportKind::RequireOnce,
+    ///             Box::new(Expression::Variable(Variable(Span::new_at(b"path", 14, 1, 15))))
+    ///         )
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    Import(ImportKind, Box<Expression<'a>>),
+
     /// Return `TRUE` if all expressions set and their values are not
-    /// `NULL`. Otherwise, it returns `FALSE`.
+    /// `NULL`. Otherwise, it returns `FALSE`. An expression is a
+    /// variable, an array element, or a property, possibly accessed
+    /// with the nullsafe operator, e.g. `isset($a['k'], $o?->p,
+    /// A::$s)`.
     ///
     /// # Examples
     ///
     /// ```
-    /// # extern crate smallvec;
-    /// # #[macro_use]
     /// # extern crate tagua_parser;
     /// use tagua_parser::ast::{
     ///     Expression,
@@ -630,18 +889,24 @@ pub enum Expression<'a> {
     ///     expression(Span::new(b"isset($foo, $bar)")),
     ///     Ok((
     ///         Span::new_at(b"", 17, 1, 18),
-    ///         Expression::Isset(smallvec![
-    ///             Variable(Span::new_at(b"foo", 7, 1, 8)),
-    ///             Variable(Span::new_at(b"bar", 13, 1, 14))
+    ///         Expression::Isset(vec![
+    ///             Expression::Variable(Variable(Span::new_at(b"foo", 7, 1, 8))),
+    ///             Expression::Variable(Variable(Span::new_at(b"bar", 13, 1, 14)))
     ///         ])
     ///     ))
     /// );
     /// # }
     /// ```
-    Isset(SmallVec<[Variable<'a>; 1]>),
+    Isset(Vec<Expression<'a>>),
 
     /// Match and assign one or more elements of the source array to
-    /// the target variables.
+    /// the target variables, aka destructuring.
+    ///
+    /// The short syntax `[…]` is equivalent to `list(…)` when it is a
+    /// destructuring target, i.e. the left operand of an assignment,
+    /// the value of a `foreach` loop, or a value of another list. A
+    /// value can be a nested list, or a reference, e.g. `['a' => [,
+    /// &$x]] = $y`. A skipped slot is represented by `None`.
     ///
     /// # Examples
     ///
@@ -723,6 +988,58 @@ pub enum Expression<'a> {
     /// );
     /// # }
     /// ```
+    ///
+    /// A nested list with the short syntax, on the left of an
+    /// assignment:
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::ast::{
+    ///     BinaryOperator,
+    ///     Expression,
+    ///     NAryOperation,
+    ///     Variable
+    /// };
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::tokens::Span;
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     expression(Span::new(b"[, [&$x]] = $y")),
+    ///     Ok((
+    ///         Span::new_at(b"", 14, 1, 15),
+    ///         Expression::NAryOperation(
+    ///             NAryOperation::Binary {
+    ///                 operator     : BinaryOperator::Assignment,
+    ///                 left_operand : Box::new(
+    ///                     NAryOperation::Nullary(
+    ///                         Box::new(
+    ///                             Expression::List(vec![
+    ///                                 None,
+    ///                                 Some((
+    ///                                     None,
+    ///                                     Expression::List(vec![
+    ///                                         Some((
+    ///                                             None,
+    ///                                             Expression::Reference(
+    ///                                                 Box::new(Expression::Variable(Variable(Span::new_at(b"x", 6, 1, 7))))
+    ///                                             )
+    ///                                         ))
+    ///                                     ])
+    ///                                 ))
+    ///                             ])
+    ///                         )
+    ///                     )
+    ///                 ),
+    ///                 right_operand: Box::new(
+    ///                     NAryOperation::Nullary(Box::new(Expression::Variable(Variable(Span::new_at(b"y", 13, 1, 14)))))
+    ///                 )
+    ///             }
+    ///         )
+    ///     ))
+    /// );
+    /// # }
+    /// ```
     List(Vec<Option<(Option<Expression<'a>>, Expression<'a>)>>),
 
     /// A literal.
@@ -754,53 +1071,45 @@ pub enum Expression<'a> {
     /// ```
     Literal(Literal<'a>),
 
-    /// A name.
+    /// A magic constant, i.e. a constant whose value depends on where
+    /// it is written, e.g. `__LINE__` or `C::class`.
     ///
     /// # Examples
     ///
     /// ```
-    /// # extern crate smallvec;
-    /// # #[macro_use]
     /// # extern crate tagua_parser;
     /// use tagua_parser::ast::{
     ///     Expression,
-    ///     Name
+    ///     MagicConstant
     /// };
     /// use tagua_parser::rules::expressions::expression;
-    /// use tagua_parser::tokens::{
-    ///     Span,
-    ///     Token
-    /// };
+    /// use tagua_parser::tokens::Span;
     ///
     /// # fn main() {
     /// assert_eq!(
-    ///     expression(Span::new(b"Foo\\Bar")),
+    ///     expression(Span::new(b"__LINE__")),
     ///     Ok((
-    ///         Span::new_at(b"", 7, 1, 8),
-    ///         Expression::Name(
-    ///             Name::Qualified(smallvec![
-    ///                 Span::new(b"Foo"),
-    ///                 Span::new_at(b"Bar", 4, 1, 5)
-    ///             ])
-    ///         )
+    ///         Span::new_at(b"", 8, 1, 9),
+    ///         Expression::MagicConstant(MagicConstant::Line(Span::new(b"__LINE__")))
     ///     ))
     /// );
     /// # }
     /// ```
-    Name(Name<'a>),
+    MagicConstant(MagicConstant<'a>),
 
-    /// A n-ary operation.
-    NAryOperation(NAryOperation<'a>),
-
-    /// Unlike `echo`, `print` can be used in any context allowing an
-    /// expression. It always returns the value `1`.
+    /// A match expression, with a subject, and a list of arms. The
+    /// expression of the first arm with a condition identical to the
+    /// subject is evaluated.
     ///
     /// # Examples
     ///
     /// ```
     /// # extern crate tagua_parser;
+    /// use std::borrow::Cow;
     /// use tagua_parser::ast::{
     ///     Expression,
+    ///     Literal,
+    ///     MatchArm,
     ///     Variable
     /// };
     /// use tagua_parser::rules::expressions::expression;
@@ -811,29 +1120,46 @@ pub enum Expression<'a> {
     ///
     /// # fn main() {
     /// assert_eq!(
-    ///     expression(Span::new(b"print $foo")),
+    ///     expression(Span::new(b"match ($x) { 1, 2 => 'a', default => 'b' }")),
     ///     Ok((
-    ///         Span::new_at(b"", 10, 1, 11),
-    ///         Expression::Print(
-    ///             Box::new(
-    ///                 Expression::Variable(Variable(Span::new_at(b"foo", 7, 1, 8))),
-    ///             )
+    ///         Span::new_at(b"", 42, 1, 43),
+    ///         Expression::Match(
+    ///             Box::new(Expression::Variable(Variable(Span::new_at(b"x", 8, 1, 9)))),
+    ///             vec![
+    ///                 MatchArm {
+    ///                     conditions: Some(vec![
+    ///                         Expression::Literal(Literal::Integer(Token::new(1i64, Span::new_at(b"1", 13, 1, 14)))),
+    ///                         Expression::Literal(Literal::Integer(Token::new(2i64, Span::new_at(b"2", 16, 1, 17))))
+    ///                     ]),
+    ///                     expression: Expression::Literal(Literal::String(Token::new(Cow::from(&b"a"[..]), Span::new_at(b"'a'", 21, 1, 22)))),
+    ///                     span      : Span::new_at(b"1, 2 => 'a'", 13, 1, 14)
+    ///                 },
+    ///                 MatchArm {
+    ///                     conditions: None,
+    ///                     expression: Expression::Literal(Literal::String(Token::new(Cow::from(&b"b"[..]), Span::new_at(b"'b'", 37, 1, 38)))),
+    ///                     span      : Span::new_at(b"default => 'b'", 26, 1, 27)
+    ///                 }
+    ///             ]
     ///         )
     ///     ))
     /// );
     /// # }
     /// ```
-    Print(Box<Expression<'a>>),
+    Match(Box<Expression<'a>>, Vec<MatchArm<'a>>),
 
-    /// Describe an expression assignment by reference.
+    /// A call of an object method, e.g. `$foo->bar(42)`.
     ///
     /// # Examples
     ///
     /// ```
     /// # extern crate tagua_parser;
     /// use tagua_parser::ast::{
+    ///     Argument,
+    ///     Arguments,
+    ///     DereferencableExpression,
     ///     Expression,
     ///     Literal,
+    ///     MemberName,
     ///     Variable
     /// };
     /// use tagua_parser::rules::expressions::expression;
@@ -844,24 +1170,23 @@ pub enum Expression<'a> {
     ///
     /// # fn main() {
     /// assert_eq!(
-    ///     expression(Span::new(b"[7 => &$foo]")),
+    ///     expression(Span::new(b"$foo->bar(42)")),
     ///     Ok((
-    ///         Span::new_at(b"", 12, 1, 13),
-    ///         Expression::Array(vec![
-    ///             (
-    ///                 Some(Expression::Literal(Literal::Integer(Token::new(7i64, Span::new_at(b"7", 1, 1, 2))))),
-    ///                 Expression::Reference(
-    ///                     Box::new(Expression::Variable(Variable(Span::new_at(b"foo", 8, 1, 9))))
-    ///                 )
-    ///             )
-    ///         ])
+    ///         Span::new_at(b"", 13, 1, 14),
+    ///         Expression::MethodCall(
+    ///             DereferencableExpression::Variable(Variable(Span::new_at(b"foo", 1, 1, 2))),
+    ///             MemberName::Name(Span::new_at(b"bar", 6, 1, 7)),
+    ///             Arguments::List(vec![
+    ///                 Argument::Positional(Expression::Literal(Literal::Integer(Token::new(42i64, Span::new_at(b"42", 10, 1, 11)))))
+    ///             ])
+    ///         )
     ///     ))
     /// );
     /// # }
     /// ```
-    Reference(Box<Expression<'a>>),
+    MethodCall(DereferencableExpression<'a>, MemberName<'a>, Arguments<'a>),
 
-    /// Unset a set of variables.
+    /// A name.
     ///
     /// # Examples
     ///
@@ -871,7 +1196,7 @@ pub enum Expression<'a> {
     /// # extern crate tagua_parser;
     /// use tagua_parser::ast::{
     ///     Expression,
-    ///     Variable
+    ///     Name
     /// };
     /// use tagua_parser::rules::expressions::expression;
     /// use tagua_parser::tokens::{
@@ -881,29 +1206,39 @@ pub enum Expression<'a> {
     ///
     /// # fn main() {
     /// assert_eq!(
-    ///     expression(Span::new(b"unset($foo, $bar)")),
+    ///     expression(Span::new(b"Foo\\Bar")),
     ///     Ok((
-    ///         Span::new_at(b"", 17, 1, 18),
-    ///         Expression::Unset(smallvec![
-    ///             Variable(Span::new_at(b"foo", 7, 1, 8)),
-    ///             Variable(Span::new_at(b"bar", 13, 1, 14))
-    ///         ])
+    ///         Span::new_at(b"", 7, 1, 8),
+    ///         Expression::Name(
+    ///             Name::Qualified(smallvec![
+    ///                 Span::new(b"Foo"),
+    ///                 Span::new_at(b"Bar", 4, 1, 5)
+    ///             ])
+    ///         )
     ///     ))
     /// );
     /// # }
     /// ```
-    Unset(SmallVec<[Variable<'a>; 1]>),
+    Name(Name<'a>),
 
-    /// A variable.
+    /// A n-ary operation.
+    NAryOperation(NAryOperation<'a>),
+
+    /// Create an object of a class. The arguments of the constructor
+    /// are absent for `new Foo`.
     ///
     /// # Examples
     ///
     /// ```
     /// # extern crate tagua_parser;
     /// use tagua_parser::ast::{
+    ///     Argument,
+    ///     Arguments,
     ///     Expression,
-    ///     Variable
-    /// };
+    ///     Literal,
+    ///     Name,
+    ///     ScopeResolver
+    /// };
     /// use tagua_parser::rules::expressions::expression;
     /// use tagua_parser::tokens::{
     ///     Span,
@@ -912,51 +1247,56 @@ pub enum Expression<'a> {
     ///
     /// # fn main() {
     /// assert_eq!(
-    ///     expression(Span::new(b"$foo")),
-    ///     Ok((Span::new_at(b"", 4, 1, 5), Expression::Variable(Variable(Span::new_at(b"foo", 1, 1, 2)))))
+    ///     expression(Span::new(b"new Foo(42)")),
+    ///     Ok((
+    ///         Span::new_at(b"", 11, 1, 12),
+    ///         Expression::New(
+    ///             ScopeResolver::ByName(Name::Unqualified(Span::new_at(b"Foo", 4, 1, 5))),
+    ///             Some(Arguments::List(vec![
+    ///                 Argument::Positional(Expression::Literal(Literal::Integer(Token::new(42i64, Span::new_at(b"42", 8, 1, 9)))))
+    ///             ]))
+    ///         )
+    ///     ))
     /// );
     /// # }
     /// ```
-    Variable(Variable<'a>),
-}
+    New(ScopeResolver<'a>, Option<Arguments<'a>>),
 
-/// A dereferencable expression.
-///
-/// A dereferencable expression can be used as the left hand side of
-/// dereferencing operators, such as `[]`, `->`, and `::`.
-#[derive(Debug, PartialEq)]
-pub enum DereferencableExpression<'a> {
-    /// A variable representing either an object, a string, or an
-    /// array.
+    /// A nullsafe call of an object method, e.g. `$foo?->bar(42)`.
+    /// The call is short-circuited if the object is `null`.
     ///
     /// # Examples
     ///
     /// ```
     /// # extern crate tagua_parser;
     /// use tagua_parser::ast::{
+    ///     Arguments,
     ///     DereferencableExpression,
+    ///     Expression,
+    ///     MemberName,
     ///     Variable
     /// };
-    /// use tagua_parser::rules::expressions::primaries::dereferencable_expression;
+    /// use tagua_parser::rules::expressions::expression;
     /// use tagua_parser::tokens::Span;
     ///
     /// # fn main() {
     /// assert_eq!(
-    ///     dereferencable_expression(Span::new(b"$foo")),
+    ///     expression(Span::new(b"$foo?->bar()")),
     ///     Ok((
-    ///         Span::new_at(b"", 4, 1, 5),
-    ///         DereferencableExpression::Variable(
-    ///             Variable(
-    ///                 Span::new_at(b"foo", 1, 1, 2)
-    ///             )
+    ///         Span::new_at(b"", 12, 1, 13),
+    ///         Expression::NullsafeMethodCall(
+    ///             DereferencableExpression::Variable(Variable(Span::new_at(b"foo", 1, 1, 2))),
+    ///             MemberName::Name(Span::new_at(b"bar", 7, 1, 8)),
+    ///             Arguments::List(vec![])
     ///         )
     ///     ))
     /// );
     /// # }
     /// ```
-    Variable(Variable<'a>),
+    NullsafeMethodCall(DereferencableExpression<'a>, MemberName<'a>, Arguments<'a>),
 
-    /// An expression evaluating to either an array or a string.
+    /// A nullsafe property access, e.g. `$foo?->bar`. The access is
+    /// short-circuited if the object is `null`.
     ///
     /// # Examples
     ///
@@ -965,44 +1305,39 @@ pub enum DereferencableExpression<'a> {
     /// use tagua_parser::ast::{
     ///     DereferencableExpression,
     ///     Expression,
+    ///     MemberName,
     ///     Variable
     /// };
-    /// use tagua_parser::rules::expressions::primaries::dereferencable_expression;
+    /// use tagua_parser::rules::expressions::expression;
     /// use tagua_parser::tokens::Span;
     ///
     /// # fn main() {
     /// assert_eq!(
-    ///     dereferencable_expression(Span::new(b"($foo)")),
+    ///     expression(Span::new(b"$foo?->bar")),
     ///     Ok((
-    ///         Span::new_at(b"", 6, 1, 7),
-    ///         DereferencableExpression::Expression(
-    ///             Box::new(
-    ///                 Expression::Variable(
-    ///                     Variable(
-    ///                         Span::new_at(b"foo", 2, 1, 3)
-    ///                     )
-    ///                 )
-    ///             )
+    ///         Span::new_at(b"", 10, 1, 11),
+    ///         Expression::NullsafePropertyAccess(
+    ///             DereferencableExpression::Variable(Variable(Span::new_at(b"foo", 1, 1, 2))),
+    ///             MemberName::Name(Span::new_at(b"bar", 7, 1, 8))
     ///         )
     ///     ))
     /// );
     /// # }
     /// ```
-    Expression(Box<Expression<'a>>),
+    NullsafePropertyAccess(DereferencableExpression<'a>, MemberName<'a>),
 
-    /// An array representing a callable.
+    /// Unlike `echo`, `print` can be used in any context allowing an
+    /// expression. It always returns the value `1`.
     ///
     /// # Examples
     ///
     /// ```
     /// # extern crate tagua_parser;
-    /// use std::borrow::Cow;
     /// use tagua_parser::ast::{
-    ///     DereferencableExpression,
     ///     Expression,
-    ///     Literal
+    ///     Variable
     /// };
-    /// use tagua_parser::rules::expressions::primaries::dereferencable_expression;
+    /// use tagua_parser::rules::expressions::expression;
     /// use tagua_parser::tokens::{
     ///     Span,
     ///     Token
@@ -1010,49 +1345,34 @@ pub enum DereferencableExpression<'a> {
     ///
     /// # fn main() {
     /// assert_eq!(
-    ///     dereferencable_expression(Span::new(b"['C', 'f']")),
+    ///     expression(Span::new(b"print $foo")),
     ///     Ok((
     ///         Span::new_at(b"", 10, 1, 11),
-    ///         DereferencableExpression::Array(
+    ///         Expression::Print(
     ///             Box::new(
-    ///                 Expression::Array(vec![
-    ///                     (
-    ///                         None,
-    ///                         Expression::Literal(
-    ///                             Literal::String(
-    ///                                 Token::new(Cow::from(&b"C"[..]), Span::new_at(b"'C'", 1, 1, 2))
-    ///                             )
-    ///                         )
-    ///                     ),
-    ///                     (
-    ///                         None,
-    ///                         Expression::Literal(
-    ///                             Literal::String(
-    ///                                 Token::new(Cow::from(&b"f"[..]), Span::new_at(b"'f'", 6, 1, 7))
-    ///                             )
-    ///                         )
-    ///                     )
-    ///                 ])
+    ///                 Expression::Variable(Variable(Span::new_at(b"foo", 7, 1, 8))),
     ///             )
     ///         )
     ///     ))
     /// );
     /// # }
     /// ```
-    Array(Box<Expression<'a>>),
+    Print(Box<Expression<'a>>),
 
-    /// A string representing a qualified name.
+    /// Property access, aka property fetch, reads a property of an
+    /// object, e.g. `$foo->bar`.
     ///
     /// # Examples
     ///
     /// ```
     /// # extern crate tagua_parser;
-    /// use std::borrow::Cow;
     /// use tagua_parser::ast::{
     ///     DereferencableExpression,
-    ///     Literal
+    ///     Expression,
+    ///     MemberName,
+    ///     Variable
     /// };
-    /// use tagua_parser::rules::expressions::primaries::dereferencable_expression;
+    /// use tagua_parser::rules::expressions::expression;
     /// use tagua_parser::tokens::{
     ///     Span,
     ///     Token
@@ -1060,54 +1380,32 @@ pub enum DereferencableExpression<'a> {
     ///
     /// # fn main() {
     /// assert_eq!(
-    ///     dereferencable_expression(Span::new(b"'C'")),
+    ///     expression(Span::new(b"$foo->bar")),
     ///     Ok((
-    ///         Span::new_at(b"", 3, 1, 4),
-    ///         DereferencableExpression::String(
-    ///             Literal::String(
-    ///                 Token::new(Cow::from(&b"C"[..]), Span::new_at(b"'C'", 0, 1, 1))
-    ///             )
+    ///         Span::new_at(b"", 9, 1, 10),
+    ///         Expression::PropertyAccess(
+    ///             DereferencableExpression::Variable(Variable(Span::new_at(b"foo", 1, 1, 2))),
+    ///             MemberName::Name(Span::new_at(b"bar", 6, 1, 7))
     ///         )
     ///     ))
     /// );
     /// # }
     /// ```
-    String(Literal<'a>),
-}
+    PropertyAccess(DereferencableExpression<'a>, MemberName<'a>),
 
-/// A type declaration.
-///
-/// A type holds two informations: Name, and copy or reference. A type
-/// can be native (fully qualified name), or user-defined with an
-/// interface or a class (unqualified, qualified, or fully qualified
-/// name). Note that the name is an `Option`: A binding can have no
-/// type, but still hold the copy or reference constraint.
-///
-/// A type can be a copy type, it means the value must be
-/// copied (at least on write), or it can be a reference type, it
-/// means this is a new binding over an existing binding.
-#[derive(Debug, PartialEq)]
-pub enum Ty<'a> {
-    /// A type representing a set of values passed by copy.
-    ///
-    /// The option is required because the type is not necessarily
-    /// specified, which is equivalent to the `mixed` type, i.e. all
-    /// possible types.
+    /// Describe an expression assignment by reference.
     ///
     /// # Examples
     ///
-    /// This example shows a named copy type:
-    ///
     /// ```
     /// # extern crate tagua_parser;
     /// use tagua_parser::ast::{
-    ///     Arity,
-    ///     Name,
-    ///     Parameter,
-    ///     Ty,
+    ///     ArrayItem,
+    ///     Expression,
+    ///     Literal,
     ///     Variable
     /// };
-    /// use tagua_parser::rules::statements::function::parameters;
+    /// use tagua_parser::rules::expressions::expression;
     /// use tagua_parser::tokens::{
     ///     Span,
     ///     Token
@@ -1115,71 +1413,72 @@ pub enum Ty<'a> {
     ///
     /// # fn main() {
     /// assert_eq!(
-    ///     parameters(Span::new(b"(I $x)")),
+    ///     expression(Span::new(b"[7 => &$foo]")),
     ///     Ok((
-    ///         Span::new_at(b"", 6, 1, 7),
-    ///         Arity::Finite(vec![
-    ///             Parameter {
-    ///                 ty   : Ty::Copy(Some(Name::Unqualified(Span::new_at(b"I", 1, 1, 2)))),
-    ///                 name : Variable(Span::new_at(b"x", 4, 1, 5)),
-    ///                 value: None
-    ///             }
+    ///         Span::new_at(b"", 12, 1, 13),
+    ///         Expression::Array(vec![
+    ///             ArrayItem::Pair(
+    ///                 Some(Box::new(Expression::Literal(Literal::Integer(Token::new(7i64, Span::new_at(b"7", 1, 1, 2)))))),
+    ///                 Expression::Reference(
+    ///                     Box::new(Expression::Variable(Variable(Span::new_at(b"foo", 8, 1, 9))))
+    ///                 )
+    ///             )
     ///         ])
     ///     ))
     /// );
     /// # }
     /// ```
+    Reference(Box<Expression<'a>>),
+
+    /// A call of a static method of a class, e.g. `Foo::bar()`.
     ///
-    /// This example shows an unnamed copy type, it means all types by copy:
+    /// # Examples
     ///
     /// ```
     /// # extern crate tagua_parser;
-    /// # use tagua_parser::ast::{
-    /// #     Arity,
-    /// #     Parameter,
-    /// #     Ty,
-    /// #     Variable
-    /// # };
-    /// # use tagua_parser::rules::statements::function::parameters;
-    /// # use tagua_parser::tokens::Span;
-    /// #
+    /// use tagua_parser::ast::{
+    ///     Arguments,
+    ///     Expression,
+    ///     MemberName,
+    ///     Name,
+    ///     ScopeResolver
+    /// };
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::tokens::{
+    ///     Span,
+    ///     Token
+    /// };
+    ///
     /// # fn main() {
     /// assert_eq!(
-    ///     parameters(Span::new(b"($x)")),
+    ///     expression(Span::new(b"Foo::bar()")),
     ///     Ok((
-    ///         Span::new_at(b"", 4, 1, 5),
-    ///         Arity::Finite(vec![
-    ///             Parameter {
-    ///                 ty   : Ty::Copy(None),
-    ///                 name : Variable(Span::new_at(b"x", 2, 1, 3)),
-    ///                 value: None
-    ///             }
-    ///         ])
+    ///         Span::new_at(b"", 10, 1, 11),
+    ///         Expression::StaticMethodCall(
+    ///             ScopeResolver::ByName(Name::Unqualified(Span::new(b"Foo"))),
+    ///             MemberName::Name(Span::new_at(b"bar", 5, 1, 6)),
+    ///             Arguments::List(vec![])
+    ///         )
     ///     ))
     /// );
     /// # }
     /// ```
-    Copy(Option<Name<'a>>),
+    StaticMethodCall(ScopeResolver<'a>, MemberName<'a>, Arguments<'a>),
 
-    /// A type representing a set of values that includes the `NULL`
-    /// value, passed by copy.
-    ///
-    /// Contrary to `Copy`, there is no option because a nullable type
-    /// must have a name. It is not possible to have a “nullable mixed
-    /// type”.
+    /// Static property access reads a static property of a class,
+    /// e.g. `Foo::$bar`.
     ///
     /// # Examples
     ///
     /// ```
     /// # extern crate tagua_parser;
     /// use tagua_parser::ast::{
-    ///     Arity,
+    ///     Expression,
     ///     Name,
-    ///     Parameter,
-    ///     Ty,
+    ///     ScopeResolver,
     ///     Variable
     /// };
-    /// use tagua_parser::rules::statements::function::parameters;
+    /// use tagua_parser::rules::expressions::expression;
     /// use tagua_parser::tokens::{
     ///     Span,
     ///     Token
@@ -1187,111 +1486,107 @@ pub enum Ty<'a> {
     ///
     /// # fn main() {
     /// assert_eq!(
-    ///     parameters(Span::new(b"(?I $x)")),
+    ///     expression(Span::new(b"Foo::$bar")),
     ///     Ok((
-    ///         Span::new_at(b"", 7, 1, 8),
-    ///         Arity::Finite(vec![
-    ///             Parameter {
-    ///                 ty   : Ty::NullableCopy(Name::Unqualified(Span::new_at(b"I", 2, 1, 3))),
-    ///                 name : Variable(Span::new_at(b"x", 5, 1, 6)),
-    ///                 value: None
-    ///             }
-    ///         ])
+    ///         Span::new_at(b"", 9, 1, 10),
+    ///         Expression::StaticPropertyAccess(
+    ///             ScopeResolver::ByName(Name::Unqualified(Span::new(b"Foo"))),
+    ///             Variable(Span::new_at(b"bar", 6, 1, 7))
+    ///         )
     ///     ))
     /// );
     /// # }
     /// ```
-    NullableCopy(Name<'a>),
+    StaticPropertyAccess(ScopeResolver<'a>, Variable<'a>),
 
-    /// A type representing a set of values passed by reference.
-    ///
-    /// The option is required for the same reason than `Copy`.
+    /// Throw an exception. Since PHP 8, `throw` is an expression, so
+    /// it can be used in any context allowing an expression, e.g. as
+    /// the right operand of `??`.
     ///
     /// # Examples
     ///
-    /// This example shows a named reference type:
-    ///
     /// ```
     /// # extern crate tagua_parser;
     /// use tagua_parser::ast::{
-    ///     Arity,
-    ///     Name,
-    ///     Parameter,
-    ///     Ty,
+    ///     BinaryOperator,
+    ///     Expression,
+    ///     NAryOperation,
     ///     Variable
     /// };
-    /// use tagua_parser::rules::statements::function::parameters;
-    /// use tagua_parser::tokens::{
-    ///     Span,
-    ///     Token
-    /// };
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::tokens::Span;
     ///
     /// # fn main() {
     /// assert_eq!(
-    ///     parameters(Span::new(b"(I &$x)")),
+    ///     expression(Span::new(b"$x ?? throw $e")),
     ///     Ok((
-    ///         Span::new_at(b"", 7, 1, 8),
-    ///         Arity::Finite(vec![
-    ///             Parameter {
-    ///                 ty   : Ty::Reference(Some(Name::Unqualified(Span::new_at(b"I", 1, 1, 2)))),
-    ///                 name : Variable(Span::new_at(b"x", 5, 1, 6)),
-    ///                 value: None
+    ///         Span::new_at(b"", 14, 1, 15),
+    ///         Expression::NAryOperation(
+    ///             NAryOperation::Binary {
+    ///                 operator     : BinaryOperator::Coalesce,
+    ///                 left_operand : Box::new(
+    ///                     NAryOperation::Nullary(Box::new(Expression::Variable(Variable(Span::new_at(b"x", 1, 1, 2)))))
+    ///                 ),
+    ///                 right_operand: Box::new(
+    ///                     NAryOperation::Nullary(
+    ///                         Box::new(
+    ///                             Expression::Throw(
+    ///                                 Box::new(Expression::Variable(Variable(Span::new_at(b"e", 13, 1, 14))))
+    ///                             )
+    ///                         )
+    ///                     )
+    ///                 )
     ///             }
-    ///         ])
+    ///         )
     ///     ))
     /// );
     /// # }
     /// ```
+    Throw(Box<Expression<'a>>),
+
+    /// Unset a set of variables, array elements, or properties, e.g.
+    /// `unset($a['k'], $o->p)`.
     ///
-    /// This example shows an unnamed reference type, it means all types by reference:
+    /// # Examples
     ///
     /// ```
     /// # extern crate tagua_parser;
-    /// # use tagua_parser::ast::{
-    /// #     Arity,
-    /// #     Parameter,
-    /// #     Ty,
-    /// #     Variable
-    /// # };
-    /// # use tagua_parser::rules::statements::function::parameters;
-    /// # use tagua_parser::tokens::Span;
-    /// #
+    /// use tagua_parser::ast::{
+    ///     Expression,
+    ///     Variable
+    /// };
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::tokens::{
+    ///     Span,
+    ///     Token
+    /// };
+    ///
     /// # fn main() {
     /// assert_eq!(
-    ///     parameters(Span::new(b"(&$x)")),
+    ///     expression(Span::new(b"unset($foo, $bar)")),
     ///     Ok((
-    ///         Span::new_at(b"", 5, 1, 6),
-    ///         Arity::Finite(vec![
-    ///             Parameter {
-    ///                 ty   : Ty::Reference(None),
-    ///                 name : Variable(Span::new_at(b"x", 3, 1, 4)),
-    ///                 value: None
-    ///             }
+    ///         Span::new_at(b"", 17, 1, 18),
+    ///         Expression::Unset(vec![
+    ///             Expression::Variable(Variable(Span::new_at(b"foo", 7, 1, 8))),
+    ///             Expression::Variable(Variable(Span::new_at(b"bar", 13, 1, 14)))
     ///         ])
     ///     ))
     /// );
     /// # }
     /// ```
-    Reference(Option<Name<'a>>),
+    Unset(Vec<Expression<'a>>),
 
-    /// A type representing a set of values that includes the `NULL`
-    /// values, passed by reference.
-    ///
-    /// Contrary to `Reference`, there is no option for the same
-    /// reason than `NullableCopy`.
+    /// A variable.
     ///
     /// # Examples
     ///
     /// ```
     /// # extern crate tagua_parser;
     /// use tagua_parser::ast::{
-    ///     Arity,
-    ///     Name,
-    ///     Parameter,
-    ///     Ty,
+    ///     Expression,
     ///     Variable
     /// };
-    /// use tagua_parser::rules::statements::function::parameters;
+    /// use tagua_parser::rules::expressions::expression;
     /// use tagua_parser::tokens::{
     ///     Span,
     ///     Token
@@ -1299,44 +1594,1146 @@ pub enum Ty<'a> {
     ///
     /// # fn main() {
     /// assert_eq!(
-    ///     parameters(Span::new(b"(?I &$x)")),
-    ///     Ok((
-    ///         Span::new_at(b"", 8, 1, 9),
-    ///         Arity::Finite(vec![
-    ///             Parameter {
-    ///                 ty   : Ty::NullableReference(Name::Unqualified(Span::new_at(b"I", 2, 1, 3))),
-    ///                 name : Variable(Span::new_at(b"x", 6, 1, 7)),
-    ///                 value: None
-    ///             }
-    ///         ])
-    ///     ))
+    ///     expression(Span::new(b"$foo")),
+    ///     Ok((Span::new_at(b"", 4, 1, 5), Expression::Variable(Variable(Span::new_at(b"foo", 1, 1, 2)))))
     /// );
     /// # }
     /// ```
-    NullableReference(Name<'a>),
-}
+    Variable(Variable<'a>),
 
-/// A parameter, aka input, of a function.
-///
+    /// Yield a value, with an optional key, from a generator, e.g.
+    /// `yield`, `yield $v` or `yield $k => $v`.
+    ///
+    /// The first expression is the key, the second is the value. A key
+    /// is always followed by a value. The value has a lower precedence
+    /// than the assignment, but a higher precedence than the `and`,
+    /// `or` and `xor` logical operators, e.g. `yield $x = 1 and $y`
+    /// is equivalent to `(yield ($x = 1)) and $y`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::ast::{
+    ///     Expression,
+    ///     Variable
+    /// };
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::tokens::Span;
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     expression(Span::new(b"yield $k => $v")),
+    ///     Ok((
+    ///         Span::new_at(b"", 14, 1, 15),
+    ///         Expression::Yield(
+    ///             Some(Box::new(Expression::Variable(Variable(Span::new_at(b"k", 7, 1, 8))))),
+    ///             Some(Box::new(Expression::Variable(Variable(Span::new_at(b"v", 13, 1, 14)))))
+    ///         )
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    Yield(Option<Box<Expression<'a>>>, Option<Box<Expression<'a>>>),
+
+    /// Delegate a generator to another generator, or to a traversable
+    /// expression, e.g. `yield from $g`.
+    ///
+    /// The delegated expression has the same precedence as the value
+    /// of `Yield`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::ast::{
+    ///     Expression,
+    ///     Variable
+    /// };
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::tokens::Span;
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     expression(Span::new(b"yield from $g")),
+    ///     Ok((
+    ///         Span::new_at(b"", 13, 1, 14),
+    ///         Expression::YieldFrom(
+    ///             Box::new(Expression::Variable(Variable(Span::new_at(b"g", 12, 1, 13))))
+    ///         )
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    YieldFrom(Box<Expression<'a>>),
+}
+
+/// An item of an array.
+#[derive(Debug, PartialEq)]
+pub enum ArrayItem<'a> {
+    /// A value with an optional key, e.g. `'a' => $x` or `$x`. A value
+    /// by reference, e.g. `&$x`, is an `Expression::Reference`.
+    Pair(Option<Box<Expression<'a>>>, Expression<'a>),
+
+    /// An unpacked array or `Traversable`, e.g. `...$x`. String keys
+    /// are preserved.
+    Unpacked(Expression<'a>),
+}
+
+/// A dereferencable expression.
+///
+/// A dereferencable expression can be used as the left hand side of
+/// dereferencing operators, such as `[]`, `{}`, `->`, `?->`, and `::`.
+#[derive(Debug, PartialEq)]
+pub enum DereferencableExpression<'a> {
+    /// A variable representing either an object, a string, or an
+    /// array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::ast::{
+    ///     DereferencableExpression,
+    ///     Variable
+    /// };
+    /// use tagua_parser::rules::expressions::primaries::dereferencable_expression;
+    /// use tagua_parser::tokens::Span;
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     dereferencable_expression(Span::new(b"$foo")),
+    ///     Ok((
+    ///         Span::new_at(b"", 4, 1, 5),
+    ///         DereferencableExpression::Variable(
+    ///             Variable(
+    ///                 Span::new_at(b"foo", 1, 1, 2)
+    ///             )
+    ///         )
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    Variable(Variable<'a>),
+
+    /// An expression evaluating to either an array or a string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::ast::{
+    ///     DereferencableExpression,
+    ///     Expression,
+    ///     Variable
+    /// };
+    /// use tagua_parser::rules::expressions::primaries::dereferencable_expression;
+    /// use tagua_parser::tokens::Span;
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     dereferencable_expression(Span::new(b"($foo)")),
+    ///     Ok((
+    ///         Span::new_at(b"", 6, 1, 7),
+    ///         DereferencableExpression::Expression(
+    ///             Box::new(
+    ///                 Expression::Variable(
+    ///                     Variable(
+    ///                         Span::new_at(b"foo", 2, 1, 3)
+    ///                     )
+    ///                 )
+    ///             )
+    ///         )
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    Expression(Box<Expression<'a>>),
+
+    /// An array representing a callable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use std::borrow::Cow;
+    /// use tagua_parser::ast::{
+    ///     ArrayItem,
+    ///     DereferencableExpression,
+    ///     Expression,
+    ///     Literal
+    /// };
+    /// use tagua_parser::rules::expressions::primaries::dereferencable_expression;
+    /// use tagua_parser::tokens::{
+    ///     Span,
+    ///     Token
+    /// };
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     dereferencable_expression(Span::new(b"['C', 'f']")),
+    ///     Ok((
+    ///         Span::new_at(b"", 10, 1, 11),
+    ///         DereferencableExpression::Array(
+    ///             Box::new(
+    ///                 Expression::Array(vec![
+    ///                     ArrayItem::Pair(
+    ///                         None,
+    ///                         Expression::Literal(
+    ///                             Literal::String(
+    ///                                 Token::new(Cow::from(&b"C"[..]), Span::new_at(b"'C'", 1, 1, 2))
+    ///                             )
+    ///                         )
+    ///                     ),
+    ///                     ArrayItem::Pair(
+    ///                         None,
+    ///                         Expression::Literal(
+    ///                             Literal::String(
+    ///                                 Token::new(Cow::from(&b"f"[..]), Span::new_at(b"'f'", 6, 1, 7))
+    ///                             )
+    ///                         )
+    ///                     )
+    ///                 ])
+    ///             )
+    ///         )
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    Array(Box<Expression<'a>>),
+
+    /// A string representing a qualified name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use std::borrow::Cow;
+    /// use tagua_parser::ast::{
+    ///     DereferencableExpression,
+    ///     Literal
+    /// };
+    /// use tagua_parser::rules::expressions::primaries::dereferencable_expression;
+    /// use tagua_parser::tokens::{
+    ///     Span,
+    ///     Token
+    /// };
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     dereferencable_expression(Span::new(b"'C'")),
+    ///     Ok((
+    ///         Span::new_at(b"", 3, 1, 4),
+    ///         DereferencableExpression::String(
+    ///             Literal::String(
+    ///                 Token::new(Cow::from(&b"C"[..]), Span::new_at(b"'C'", 0, 1, 1))
+    ///             )
+    ///         )
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    String(Literal<'a>),
+}
+
+/// A type declaration.
+///
+/// A type declaration holds two informations: A type expression, and
+/// copy or reference. Note that the type expression is an `Option`:
+/// A binding can have no type, but still hold the copy or reference
+/// constraint. Parameters and outputs of functions share this
+/// representation.
+///
+/// A type can be a copy type, it means the value must be
+/// copied (at least on write), or it can be a reference type, it
+/// means this is a new binding over an existing binding.
+#[derive(Debug, PartialEq)]
+pub enum Ty<'a> {
+    /// A type representing a set of values passed by copy.
+    ///
+    /// The option is required because the type is not necessarily
+    /// specified, which is equivalent to the `mixed` type, i.e. all
+    /// possible types.
+    ///
+    /// # Examples
+    ///
+    /// This example shows a named copy type:
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::ast::{
+    ///     Arity,
+    ///     Name,
+    ///     Parameter,
+    ///     Ty,
+    ///     Type,
+    ///     Variable
+    /// };
+    /// use tagua_parser::rules::statements::function::parameters;
+    /// use tagua_parser::tokens::{
+    ///     Span,
+    ///     Token
+    /// };
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     parameters(Span::new(b"(I $x)")),
+    ///     Ok((
+    ///         Span::new_at(b"", 6, 1, 7),
+    ///         Arity::Finite(vec![
+    ///             Parameter {
+    ///                 attributes: vec![],
+    ///                 promotion : None,
+    ///                 ty        : Ty::Copy(Some(Type::Name(Name::Unqualified(Span::new_at(b"I", 1, 1, 2))))),
+    ///                 name      : Variable(Span::new_at(b"x", 4, 1, 5)),
+    ///                 value     : None
+    ///             }
+    ///         ])
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    ///
+    /// This example shows an unnamed copy type, it means all types by copy:
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// # use tagua_parser::ast::{
+    /// #     Arity,
+    /// #     Parameter,
+    /// #     Ty,
+    /// #     Variable
+    /// # };
+    /// # use tagua_parser::rules::statements::function::parameters;
+    /// # use tagua_parser::tokens::Span;
+    /// #
+    /// # fn main() {
+    /// assert_eq!(
+    ///     parameters(Span::new(b"($x)")),
+    ///     Ok((
+    ///         Span::new_at(b"", 4, 1, 5),
+    ///         Arity::Finite(vec![
+    ///             Parameter {
+    ///                 attributes: vec![],
+    ///                 promotion : None,
+    ///                 ty        : Ty::Copy(None),
+    ///                 name      : Variable(Span::new_at(b"x", 2, 1, 3)),
+    ///                 value     : None
+    ///             }
+    ///         ])
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    Copy(Option<Type<'a>>),
+
+    /// A type representing a set of values passed by reference.
+    ///
+    /// The option is required for the same reason than `Copy`.
+    ///
+    /// # Examples
+    ///
+    /// This example shows a named reference type:
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::ast::{
+    ///     Arity,
+    ///     Name,
+    ///     Parameter,
+    ///     Ty,
+    ///     Type,
+    ///     Variable
+    /// };
+    /// use tagua_parser::rules::statements::function::parameters;
+    /// use tagua_parser::tokens::{
+    ///     Span,
+    ///     Token
+    /// };
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     parameters(Span::new(b"(I &$x)")),
+    ///     Ok((
+    ///         Span::new_at(b"", 7, 1, 8),
+    ///         Arity::Finite(vec![
+    ///             Parameter {
+    ///                 attributes: vec![],
+    ///                 promotion : None,
+    ///                 ty        : Ty::Reference(Some(Type::Name(Name::Unqualified(Span::new_at(b"I", 1, 1, 2))))),
+    ///                 name      : Variable(Span::new_at(b"x", 5, 1, 6)),
+    ///                 value     : None
+    ///             }
+    ///         ])
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    ///
+    /// This example shows an unnamed reference type, it means all types by reference:
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// # use tagua_parser::ast::{
+    /// #     Arity,
+    /// #     Parameter,
+    /// #     Ty,
+    /// #     Variable
+    /// # };
+    /// # use tagua_parser::rules::statements::function::parameters;
+    /// # use tagua_parser::tokens::Span;
+    /// #
+    /// # fn main() {
+    /// assert_eq!(
+    ///     parameters(Span::new(b"(&$x)")),
+    ///     Ok((
+    ///         Span::new_at(b"", 5, 1, 6),
+    ///         Arity::Finite(vec![
+    ///             Parameter {
+    ///                 attributes: vec![],
+    ///                 promotion : None,
+    ///                 ty        : Ty::Reference(None),
+    ///                 name      : Variable(Span::new_at(b"x", 3, 1, 4)),
+    ///                 value     : None
+    ///             }
+    ///         ])
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    Reference(Option<Type<'a>>),
+}
+
+/// A type expression.
+///
+/// A native type, like `int`, `void` or `null`, is represented by a
+/// fully qualified name. A class type, like `self` or `A\B`, is
+/// represented by its name as written. `static` is represented by an
+/// unqualified name.
+#[derive(Debug, PartialEq)]
+pub enum Type<'a> {
+    /// A single type, e.g. `int` or `A\B`.
+    Name(Name<'a>),
+
+    /// A single type that includes the `NULL` value, e.g. `?A`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::ast::{
+    ///     Arity,
+    ///     Name,
+    ///     Parameter,
+    ///     Ty,
+    ///     Type,
+    ///     Variable
+    /// };
+    /// use tagua_parser::rules::statements::function::parameters;
+    /// use tagua_parser::tokens::Span;
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     parameters(Span::new(b"(?I &$x)")),
+    ///     Ok((
+    ///         Span::new_at(b"", 8, 1, 9),
+    ///         Arity::Finite(vec![
+    ///             Parameter {
+    ///                 attributes: vec![],
+    ///                 promotion : None,
+    ///                 ty        : Ty::Reference(Some(Type::Nullable(Name::Unqualified(Span::new_at(b"I", 2, 1, 3))))),
+    ///                 name      : Variable(Span::new_at(b"x", 6, 1, 7)),
+    ///                 value     : None
+    ///             }
+    ///         ])
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    Nullable(Name<'a>),
+
+    /// A union of types, e.g. `A|B|null`. A member of a union is a
+    /// single type, or an intersection of types delimited by
+    /// parenthesis, e.g. `(A&B)|null`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate smallvec;
+    /// # #[macro_use]
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::ast::{Name, Type};
+    /// use tagua_parser::rules::statements::function::type_expression;
+    /// use tagua_parser::tokens::Span;
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     type_expression(Span::new(b"(A&B)|null")),
+    ///     Ok((
+    ///         Span::new_at(b"", 10, 1, 11),
+    ///         Type::Union(vec![
+    ///             Type::Intersection(vec![
+    ///                 Name::Unqualified(Span::new_at(b"A", 1, 1, 2)),
+    ///                 Name::Unqualified(Span::new_at(b"B", 3, 1, 4))
+    ///             ]),
+    ///             Type::Name(Name::FullyQualified(smallvec![Span::new_at(b"null", 6, 1, 7)]))
+    ///         ])
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    Union(Vec<Type<'a>>),
+
+    /// An intersection of class types, e.g. `A&B`.
+    Intersection(Vec<Name<'a>>),
+}
+
+/// A parameter, aka input, of a function.
+///
 /// A parameter can:
 ///
-///   * be typed or not,
-///   * be passed by copy, or by reference,
-///   * have a default constant value.
+///   * be typed or not,
+///   * be passed by copy, or by reference,
+///   * have a default constant value.
+///
+/// # Examples
+///
+/// ```
+/// use tagua_parser::ast::{
+///     Arity,
+///     Expression,
+///     Literal,
+///     Name,
+///     Parameter,
+///     Ty,
+///     Type,
+///     Variable
+/// };
+/// use tagua_parser::rules::statements::function::parameters;
+/// use tagua_parser::tokens::{
+///     Span,
+///     Token
+/// };
+///
+/// # fn main() {
+/// assert_eq!(
+///     parameters(Span::new(b"($x = 42, I &$y)")),
+///     Ok((
+///         Span::new_at(b"", 16, 1, 17),
+///         Arity::Finite(vec![
+///             Parameter {
+///                 attributes: vec![],
+///                 promotion : None,
+///                 ty        : Ty::Copy(None),
+///                 name      : Variable(Span::new_at(b"x", 2, 1, 3)),
+///                 value     : Some(Expression::Literal(Literal::Integer(Token::new(42i64, Span::new_at(b"42", 6, 1, 7)))))
+///             },
+///             Parameter {
+///                 attributes: vec![],
+///                 promotion : None,
+///                 ty        : Ty::Reference(Some(Type::Name(Name::Unqualified(Span::new_at(b"I", 10, 1, 11))))),
+///                 name      : Variable(Span::new_at(b"y", 14, 1, 15)),
+///                 value     : None
+///             }
+///         ])
+///     ))
+/// );
+/// # }
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct Parameter<'a> {
+    /// Attributes of the parameter.
+    pub attributes: Vec<Attribute<'a>>,
+
+    /// Promotion of the parameter to a property, only in a
+    /// constructor.
+    pub promotion: Option<Promotion>,
+
+    /// Type of the parameter.
+    pub ty: Ty<'a>,
+
+    /// Name of the parameter.
+    pub name: Variable<'a>,
+
+    /// Default value of the parameter.
+    pub value: Option<Expression<'a>>,
+}
+
+/// A promotion of a constructor parameter to a property, e.g. `public
+/// readonly int $x`. A promoted parameter declares a property with
+/// the same name, type, visibility and modifiers.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tagua_parser;
+/// use tagua_parser::ast::{
+///     Arity,
+///     Modifier,
+///     Parameter,
+///     Promotion,
+///     Ty,
+///     Variable,
+///     Visibility
+/// };
+/// use tagua_parser::rules::statements::function::parameters;
+/// use tagua_parser::tokens::Span;
+///
+/// # fn main() {
+/// assert_eq!(
+///     parameters(Span::new(b"(readonly $x)")),
+///     Ok((
+///         Span::new_at(b"", 13, 1, 14),
+///         Arity::Finite(vec![
+///             Parameter {
+///                 attributes: vec![],
+///                 promotion : Some(Promotion {
+///                     visibility: Visibility::Public,
+///                     modifiers : vec![Modifier::Readonly]
+///                 }),
+///                 ty        : Ty::Copy(None),
+///                 name      : Variable(Span::new_at(b"x", 11, 1, 12)),
+///                 value     : None
+///             }
+///         ])
+///     ))
+/// );
+/// # }
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct Promotion {
+    /// Visibility of the property, public by default.
+    pub visibility: Visibility,
+
+    /// Modifiers of the property, i.e. `readonly`.
+    pub modifiers: Vec<Modifier>,
+}
+
+/// Arity of a function.
+#[derive(Debug, PartialEq)]
+pub enum Arity<'a> {
+    /// A function with no parameter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::ast::Arity;
+    /// use tagua_parser::rules::statements::function::parameters;
+    /// use tagua_parser::tokens::{
+    ///     Span,
+    ///     Token
+    /// };
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     parameters(Span::new(b"()")),
+    ///     Ok((Span::new_at(b"", 2, 1, 3), Arity::Constant))
+    /// );
+    /// # }
+    /// ```
+    Constant,
+
+    /// A function with a finite number of parameters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::ast::{
+    ///     Arity,
+    ///     Parameter,
+    ///     Ty,
+    ///     Variable
+    /// };
+    /// use tagua_parser::rules::statements::function::parameters;
+    /// use tagua_parser::tokens::{
+    ///     Span,
+    ///     Token
+    /// };
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     parameters(Span::new(b"($x, $y)")),
+    ///     Ok((
+    ///         Span::new_at(b"", 8, 1, 9),
+    ///         Arity::Finite(vec![
+    ///             Parameter {
+    ///                 attributes: vec![],
+    ///                 promotion : None,
+    ///                 ty        : Ty::Copy(None),
+    ///                 name      : Variable(Span::new_at(b"x", 2, 1, 3)),
+    ///                 value     : None
+    ///             },
+    ///             Parameter {
+    ///                 attributes: vec![],
+    ///                 promotion : None,
+    ///                 ty        : Ty::Copy(None),
+    ///                 name      : Variable(Span::new_at(b"y", 6, 1, 7)),
+    ///                 value     : None
+    ///             }
+    ///         ])
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    Finite(Vec<Parameter<'a>>),
+
+    /// A variadic function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::ast::{
+    ///     Arity,
+    ///     Parameter,
+    ///     Ty,
+    ///     Variable
+    /// };
+    /// use tagua_parser::rules::statements::function::parameters;
+    /// use tagua_parser::tokens::{
+    ///     Span,
+    ///     Token
+    /// };
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     parameters(Span::new(b"($x, ...$y)")),
+    ///     Ok((
+    ///         Span::new_at(b"", 11, 1, 12),
+    ///         Arity::Infinite(vec![
+    ///             Parameter {
+    ///                 attributes: vec![],
+    ///                 promotion : None,
+    ///                 ty        : Ty::Copy(None),
+    ///                 name      : Variable(Span::new_at(b"x", 2, 1, 3)),
+    ///                 value     : None
+    ///             },
+    ///             Parameter {
+    ///                 attributes: vec![],
+    ///                 promotion : None,
+    ///                 ty        : Ty::Copy(None),
+    ///                 name      : Variable(Span::new_at(b"y", 9, 1, 10)),
+    ///                 value     : None
+    ///             }
+    ///         ])
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    Infinite(Vec<Parameter<'a>>),
+}
+
+/// A function declaration.
+///
+/// # Examples
+///
+/// ```
+/// use tagua_parser::ast::{
+///     Arity,
+///     Function,
+///     FunctionKind,
+///     Name,
+///     Parameter,
+///     Statement,
+///     Ty,
+///     Type,
+///     Variable
+/// };
+/// use tagua_parser::rules::statements::function::function;
+/// use tagua_parser::tokens::{
+///     Span,
+///     Token
+/// };
+///
+/// # fn main() {
+/// assert_eq!(
+///     function(Span::new(b"function f(I $x): O { return; }")),
+///     Ok((
+///         Span::new_at(b"", 31, 1, 32),
+///         Statement::Function(
+///             Function {
+///                 attributes: vec![],
+///                 name      : Span::new_at(b"f", 9, 1, 10),
+///                 inputs    : Arity::Finite(vec![
+///                     Parameter {
+///                         attributes: vec![],
+///                         promotion : None,
+///                         ty        : Ty::Copy(Some(Type::Name(Name::Unqualified(Span::new_at(b"I", 11, 1, 12))))),
+///                         name      : Variable(Span::new_at(b"x", 14, 1, 15)),
+///                         value     : None
+///                     }
+///                 ]),
+///                 output    : Ty::Copy(Some(Type::Name(Name::Unqualified(Span::new_at(b"O", 18, 1, 19))))),
+///                 kind      : FunctionKind::Regular,
+///                 body      : vec![Statement::Return(None)]
+///             }
+///         )
+///     ))
+/// );
+/// # }
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct Function<'a> {
+    /// Attributes of the function.
+    pub attributes: Vec<Attribute<'a>>,
+
+    /// Name of the function.
+    pub name: Span<'a>,
+
+    /// Inputs, aka parameters, of the function.
+    pub inputs: Arity<'a>,
+
+    /// Output type of the function.
+    pub output: Ty<'a>,
+
+    /// Kind of the function, i.e. whether it is a generator.
+    pub kind: FunctionKind,
+
+    /// Body of the function, i.e. a set of statements.
+    pub body: Vec<Statement<'a>>,
+}
+
+/// An anonymous function.
+///
+/// An anonymous function is defined like, and behaves like, a named
+/// function `Function` except that the former has no name, and an
+/// enclosed scope.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tagua_parser;
+/// use tagua_parser::ast::{
+///     AnonymousFunction,
+///     Arity,
+///     Expression,
+///     FunctionKind,
+///     Name,
+///     Parameter,
+///     DeclarationScope,
+///     Statement,
+///     Ty,
+///     Type,
+///     Variable
+/// };
+/// use tagua_parser::rules::expressions::primaries::anonymous_function;
+/// use tagua_parser::tokens::{
+///     Span,
+///     Token
+/// };
+///
+/// # fn main() {
+/// assert_eq!(
+///     anonymous_function(Span::new(b"static function &(I ...$x) use (&$y, $z): O { return; }")),
+///     Ok((
+///         Span::new_at(b"", 55, 1, 56),
+///         Expression::AnonymousFunction(
+///             AnonymousFunction {
+///                 attributes       : vec![],
+///                 declaration_scope: DeclarationScope::Static,
+///                 inputs           : Arity::Infinite(vec![
+///                     Parameter {
+///                         attributes: vec![],
+///                         promotion : None,
+///                         ty        : Ty::Copy(Some(Type::Name(Name::Unqualified(Span::new_at(b"I", 18, 1, 19))))),
+///                         name      : Variable(Span::new_at(b"x", 24, 1, 25)),
+///                         value     : None
+///                     }
+///                 ]),
+///                 output           : Ty::Reference(Some(Type::Name(Name::Unqualified(Span::new_at(b"O", 42, 1, 43))))),
+///                 enclosing_scope  : Some(vec![
+///                     Expression::Reference(
+///                         Box::new(
+///                             Expression::Variable(Variable(Span::new_at(b"y", 34, 1, 35)))
+///                         )
+///                     ),
+///                     Expression::Variable(Variable(Span::new_at(b"z", 38, 1, 39)))
+///                 ]),
+///                 kind             : FunctionKind::Regular,
+///                 body             : vec![Statement::Return(None)]
+///             }
+///         )
+///     ))
+/// );
+/// # }
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct AnonymousFunction<'a> {
+    /// Attributes of the anonymous function.
+    pub attributes: Vec<Attribute<'a>>,
+
+    /// Declaration scope of the anonymous function.
+    pub declaration_scope: DeclarationScope,
+
+    /// Inputs, aka parameters, of the anonymous function.
+    pub inputs: Arity<'a>,
+
+    /// Output type of the anonymous function.
+    pub output: Ty<'a>,
+
+    /// Enclosed scope (list of variables to be accessible from the
+    /// body) of the anonymous function.
+    pub enclosing_scope: Option<Vec<Expression<'a>>>,
+
+    /// Kind of the anonymous function, i.e. whether it is a
+    /// generator.
+    pub kind: FunctionKind,
+
+    /// Body of the anonymous function, i.e. a set of statements.
+    pub body: Vec<Statement<'a>>,
+}
+
+/// The kind of a function.
+#[derive(Debug, PartialEq)]
+pub enum FunctionKind {
+    /// A regular function, returning a value.
+    Regular,
+
+    /// A generator, i.e. a function whose body contains `yield` or
+    /// `yield from`, returning a `Generator` object.
+    Generator,
+}
+
+/// An arrow function.
+///
+/// An arrow function is defined like an anonymous function
+/// `AnonymousFunction`, except that its body is a single expression,
+/// and that it has no explicit enclosed scope.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate smallvec;
+/// # #[macro_use]
+/// # extern crate tagua_parser;
+/// use tagua_parser::ast::{
+///     Arity,
+///     ArrowFunction,
+///     DeclarationScope,
+///     Expression,
+///     FunctionKind,
+///     Name,
+///     Parameter,
+///     Ty,
+///     Type,
+///     Variable
+/// };
+/// use tagua_parser::rules::expressions::primaries::arrow_function;
+/// use tagua_parser::tokens::Span;
+///
+/// # fn main() {
+/// assert_eq!(
+///     arrow_function(Span::new(b"static fn &(int $x): int => $x")),
+///     Ok((
+///         Span::new_at(b"", 30, 1, 31),
+///         Expression::ArrowFunction(
+///             ArrowFunction {
+///                 attributes       : vec![],
+///                 declaration_scope: DeclarationScope::Static,
+///                 inputs           : Arity::Finite(vec![
+///                     Parameter {
+///                         attributes: vec![],
+///                         promotion : None,
+///                         ty        : Ty::Copy(Some(Type::Name(Name::FullyQualified(smallvec![Span::new_at(b"int", 12, 1, 13)])))),
+///                         name      : Variable(Span::new_at(b"x", 17, 1, 18)),
+///                         value     : None
+///                     }
+///                 ]),
+///                 output           : Ty::Reference(Some(Type::Name(Name::FullyQualified(smallvec![Span::new_at(b"int", 21, 1, 22)])))),
+///                 kind             : FunctionKind::Regular,
+///                 body             : Box::new(Expression::Variable(Variable(Span::new_at(b"x", 29, 1, 30))))
+///             }
+///         )
+///     ))
+/// );
+/// # }
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct ArrowFunction<'a> {
+    /// Attributes of the arrow function.
+    pub attributes: Vec<Attribute<'a>>,
+
+    /// Declaration scope of the arrow function.
+    pub declaration_scope: DeclarationScope,
+
+    /// Inputs, aka parameters, of the arrow function.
+    pub inputs: Arity<'a>,
+
+    /// Output type of the arrow function.
+    pub output: Ty<'a>,
+
+    /// Kind of the arrow function, i.e. whether it is a generator.
+    pub kind: FunctionKind,
+
+    /// Body of the arrow function, i.e. an expression.
+    pub body: Box<Expression<'a>>,
+}
+
+/// An arm of a match expression.
+#[derive(Debug, PartialEq)]
+pub struct MatchArm<'a> {
+    /// Conditions of the arm, or `None` if this is the `default` arm.
+    pub conditions: Option<Vec<Expression<'a>>>,
+
+    /// Expression of the arm, evaluated if one of the conditions is
+    /// identical to the subject.
+    pub expression: Expression<'a>,
+
+    /// Span of the arm, from its first condition to the end of its
+    /// expression, e.g. to report an unhandled value.
+    pub span: Span<'a>,
+}
+
+/// A magic constant.
+///
+/// Magic constants are case-insensitive. The span of a magic constant
+/// is kept to compute its value, see
+/// `resolution::resolve_magic_constants`.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tagua_parser;
+/// use tagua_parser::ast::{
+///     Expression,
+///     MagicConstant,
+///     Name,
+///     ScopeResolver
+/// };
+/// use tagua_parser::rules::expressions::expression;
+/// use tagua_parser::tokens::Span;
+///
+/// # fn main() {
+/// assert_eq!(
+///     expression(Span::new(b"C::class")),
+///     Ok((
+///         Span::new_at(b"", 8, 1, 9),
+///         Expression::MagicConstant(
+///             MagicConstant::ClassName(
+///                 ScopeResolver::ByName(Name::Unqualified(Span::new(b"C"))),
+///                 Span::new_at(b"class", 3, 1, 4)
+///             )
+///         )
+///     ))
+/// );
+/// # }
+/// ```
+#[derive(Debug, PartialEq)]
+pub enum MagicConstant<'a> {
+    /// The name of the enclosing class, i.e. `__CLASS__`.
+    Class(Span<'a>),
+
+    /// The name of a class, e.g. `C::class` or `self::class`. The
+    /// span is the span of `class`.
+    ClassName(ScopeResolver<'a>, Span<'a>),
+
+    /// The directory of the current file, i.e. `__DIR__`.
+    Directory(Span<'a>),
+
+    /// The path of the current file, i.e. `__FILE__`.
+    File(Span<'a>),
+
+    /// The name of the enclosing function, i.e. `__FUNCTION__`.
+    Function(Span<'a>),
+
+    /// The current line number, i.e. `__LINE__`.
+    Line(Span<'a>),
+
+    /// The name of the enclosing method, i.e. `__METHOD__`.
+    Method(Span<'a>),
+
+    /// The name of the current namespace, i.e. `__NAMESPACE__`.
+    Namespace(Span<'a>),
+
+    /// The name of the enclosing trait, i.e. `__TRAIT__`.
+    Trait(Span<'a>),
+}
+
+/// An attribute, e.g. `#[A(42)]`.
+///
+/// Attributes are declared by groups, like `#[A, B(42)]`, before a
+/// function, an anonymous function, a class, an interface, a trait,
+/// an enumeration, a member, an enumeration case, or a parameter. The
+/// groups are flattened into a single list of attributes.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate smallvec;
+/// # #[macro_use]
+/// # extern crate tagua_parser;
+/// use tagua_parser::Result;
+/// use tagua_parser::ast::{Argument, Arguments, Attribute, Expression, Literal, Name};
+/// use tagua_parser::rules::attributes::attributes;
+/// use tagua_parser::tokens::{
+///     Span,
+///     Token
+/// };
+///
+/// # fn main() {
+/// assert_eq!(
+///     attributes(Span::new(b"#[A, B\\C(42)] #[D]")),
+///     Ok((
+///         Span::new_at(b"", 18, 1, 19),
+///         vec![
+///             Attribute {
+///                 name     : Name::Unqualified(Span::new_at(b"A", 2, 1, 3)),
+///                 arguments: None
+///             },
+///             Attribute {
+///                 name     : Name::Qualified(smallvec![Span::new_at(b"B", 5, 1, 6), Span::new_at(b"C", 7, 1, 8)]),
+///                 arguments: Some(
+///                     Arguments::List(vec![
+///                         Argument::Positional(
+///                             Expression::Literal(Literal::Integer(Token::new(42, Span::new_at(b"42", 9, 1, 10))))
+///                         )
+///                     ])
+///                 )
+///             },
+///             Attribute {
+///                 name     : Name::Unqualified(Span::new_at(b"D", 16, 1, 17)),
+///                 arguments: None
+///             }
+///         ]
+///     ))
+/// );
+/// # }
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct Attribute<'a> {
+    /// Name of the attribute, i.e. the name of a class.
+    pub name: Name<'a>,
+
+    /// Arguments of the attribute, if any.
+    pub arguments: Option<Arguments<'a>>,
+}
+
+/// A class declaration.
 ///
 /// # Examples
 ///
 /// ```
+/// # extern crate smallvec;
+/// # #[macro_use]
+/// # extern crate tagua_parser;
 /// use tagua_parser::ast::{
 ///     Arity,
+///     Class,
+///     ClassMember,
 ///     Expression,
+///     FunctionKind,
 ///     Literal,
+///     Modifier,
 ///     Name,
-///     Parameter,
+///     Statement,
 ///     Ty,
-///     Variable
+///     Type,
+///     Variable,
+///     Visibility
 /// };
-/// use tagua_parser::rules::statements::function::parameters;
+/// use tagua_parser::rules::statements::class::class;
 /// use tagua_parser::tokens::{
 ///     Span,
 ///     Token
@@ -1344,160 +2741,221 @@ pub enum Ty<'a> {
 ///
 /// # fn main() {
 /// assert_eq!(
-///     parameters(Span::new(b"($x = 42, I &$y)")),
+///     class(Span::new(b"abstract class C extends D implements I, J { const A = 1; protected static ?int $x = 2, $y; abstract public function f(): int; }")),
 ///     Ok((
-///         Span::new_at(b"", 16, 1, 17),
-///         Arity::Finite(vec![
-///             Parameter {
-///                 ty   : Ty::Copy(None),
-///                 name : Variable(Span::new_at(b"x", 2, 1, 3)),
-///                 value: Some(Expression::Literal(Literal::Integer(Token::new(42i64, Span::new_at(b"42", 6, 1, 7)))))
-///             },
-///             Parameter {
-///                 ty   : Ty::Reference(Some(Name::Unqualified(Span::new_at(b"I", 10, 1, 11)))),
-///                 name : Variable(Span::new_at(b"y", 14, 1, 15)),
-///                 value: None
+///         Span::new_at(b"", 128, 1, 129),
+///         Statement::Class(
+///             Class {
+///                 attributes: vec![],
+///                 modifiers : vec![Modifier::Abstract],
+///                 name      : Span::new_at(b"C", 15, 1, 16),
+///                 extends   : Some(Name::Unqualified(Span::new_at(b"D", 25, 1, 26))),
+///                 implements: vec![
+///                     Name::Unqualified(Span::new_at(b"I", 38, 1, 39)),
+///                     Name::Unqualified(Span::new_at(b"J", 41, 1, 42))
+///                 ],
+///                 members   : vec![
+///                     ClassMember::Constant {
+///                         attributes: vec![],
+///                         visibility: Visibility::Public,
+///                         modifiers : vec![],
+///                         constants : vec![
+///                             (
+///                                 Span::new_at(b"A", 51, 1, 52),
+///                                 Expression::Literal(Literal::Integer(Token::new(1i64, Span::new_at(b"1", 55, 1, 56))))
+///                             )
+///                         ]
+///                     },
+///                     ClassMember::Property {
+///                         attributes: vec![],
+///                         visibility: Visibility::Protected,
+///                         modifiers : vec![Modifier::Static],
+///                         ty        : Box::new(Ty::Copy(Some(Type::Nullable(Name::FullyQualified(smallvec![Span::new_at(b"int", 76, 1, 77)]))))),
+///                         properties: vec![
+///                             (
+///                                 Variable(Span::new_at(b"x", 81, 1, 82)),
+///                                 Some(Expression::Literal(Literal::Integer(Token::new(2i64, Span::new_at(b"2", 85, 1, 86)))))
+///                             ),
+///                             (
+///                                 Variable(Span::new_at(b"y", 89, 1, 90)),
+///                                 None
+///                             )
+///                         ]
+///                     },
+///                     ClassMember::Method {
+///                         attributes: vec![],
+///                         visibility: Visibility::Public,
+///                         modifiers : vec![Modifier::Abstract],
+///                         name      : Span::new_at(b"f", 117, 1, 118),
+///                         inputs    : Arity::Constant,
+///                         output    : Box::new(Ty::Copy(Some(Type::Name(Name::FullyQualified(smallvec![Span::new_at(b"int", 122, 1, 123)]))))),
+///                         kind      : FunctionKind::Regular,
+///                         body      : None
+///                     }
+///                 ]
 ///             }
-///         ])
+///         )
 ///     ))
 /// );
 /// # }
 /// ```
 #[derive(Debug, PartialEq)]
-pub struct Parameter<'a> {
-    /// Type of the parameter.
-    pub ty: Ty<'a>,
+pub struct Class<'a> {
+    /// Attributes of the class.
+    pub attributes: Vec<Attribute<'a>>,
 
-    /// Name of the parameter.
-    pub name: Variable<'a>,
+    /// Modifiers of the class, i.e. `abstract`, `final` or `readonly`.
+    pub modifiers: Vec<Modifier>,
 
-    /// Default value of the parameter.
-    pub value: Option<Expression<'a>>,
+    /// Name of the class.
+    pub name: Span<'a>,
+
+    /// The parent class, if any.
+    pub extends: Option<Name<'a>>,
+
+    /// The implemented interfaces.
+    pub implements: Vec<Name<'a>>,
+
+    /// Members of the class, in order of appearance.
+    pub members: Vec<ClassMember<'a>>,
 }
 
-/// Arity of a function.
+/// A member of a class.
+///
+/// When no visibility is declared, the member is public. The `var`
+/// keyword is an alias of the `public` visibility.
 #[derive(Debug, PartialEq)]
-pub enum Arity<'a> {
-    /// A function with no parameter.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// # extern crate tagua_parser;
-    /// use tagua_parser::ast::Arity;
-    /// use tagua_parser::rules::statements::function::parameters;
-    /// use tagua_parser::tokens::{
-    ///     Span,
-    ///     Token
-    /// };
-    ///
-    /// # fn main() {
-    /// assert_eq!(
-    ///     parameters(Span::new(b"()")),
-    ///     Ok((Span::new_at(b"", 2, 1, 3), Arity::Constant))
-    /// );
-    /// # }
-    /// ```
-    Constant,
+pub enum ClassMember<'a> {
+    /// A list of constants, e.g. `const A = 1, B = 2;`.
+    Constant {
+        /// The attributes of the constants.
+        attributes: Vec<Attribute<'a>>,
 
-    /// A function with a finite number of parameters.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// # extern crate tagua_parser;
-    /// use tagua_parser::ast::{
-    ///     Arity,
-    ///     Parameter,
-    ///     Ty,
-    ///     Variable
-    /// };
-    /// use tagua_parser::rules::statements::function::parameters;
-    /// use tagua_parser::tokens::{
-    ///     Span,
-    ///     Token
-    /// };
-    ///
-    /// # fn main() {
-    /// assert_eq!(
-    ///     parameters(Span::new(b"($x, $y)")),
-    ///     Ok((
-    ///         Span::new_at(b"", 8, 1, 9),
-    ///         Arity::Finite(vec![
-    ///             Parameter {
-    ///                 ty   : Ty::Copy(None),
-    ///                 name : Variable(Span::new_at(b"x", 2, 1, 3)),
-    ///                 value: None
-    ///             },
-    ///             Parameter {
-    ///                 ty   : Ty::Copy(None),
-    ///                 name : Variable(Span::new_at(b"y", 6, 1, 7)),
-    ///                 value: None
-    ///             }
-    ///         ])
-    ///     ))
-    /// );
-    /// # }
-    /// ```
-    Finite(Vec<Parameter<'a>>),
+        /// The visibility of the constants.
+        visibility: Visibility,
 
-    /// A variadic function.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// # extern crate tagua_parser;
-    /// use tagua_parser::ast::{
-    ///     Arity,
-    ///     Parameter,
-    ///     Ty,
-    ///     Variable
-    /// };
-    /// use tagua_parser::rules::statements::function::parameters;
-    /// use tagua_parser::tokens::{
-    ///     Span,
-    ///     Token
-    /// };
-    ///
-    /// # fn main() {
-    /// assert_eq!(
-    ///     parameters(Span::new(b"($x, ...$y)")),
-    ///     Ok((
-    ///         Span::new_at(b"", 11, 1, 12),
-    ///         Arity::Infinite(vec![
-    ///             Parameter {
-    ///                 ty   : Ty::Copy(None),
-    ///                 name : Variable(Span::new_at(b"x", 2, 1, 3)),
-    ///                 value: None
-    ///             },
-    ///             Parameter {
-    ///                 ty   : Ty::Copy(None),
-    ///                 name : Variable(Span::new_at(b"y", 9, 1, 10)),
-    ///                 value: None
-    ///             }
-    ///         ])
-    ///     ))
-    /// );
-    /// # }
-    /// ```
-    Infinite(Vec<Parameter<'a>>),
+        /// The modifiers of the constants, i.e. `final`.
+        modifiers: Vec<Modifier>,
+
+        /// The constants, as pairs of a name and a constant value.
+        constants: Vec<(Span<'a>, Expression<'a>)>,
+    },
+
+    /// A list of properties sharing the same type, e.g. `public int
+    /// $x = 1, $y;`.
+    Property {
+        /// The attributes of the properties.
+        attributes: Vec<Attribute<'a>>,
+
+        /// The visibility of the properties.
+        visibility: Visibility,
+
+        /// The modifiers of the properties, i.e. `static` or
+        /// `readonly`.
+        modifiers: Vec<Modifier>,
+
+        /// The type of the properties.
+        ty: Box<Ty<'a>>,
+
+        /// The properties, as pairs of a name and an optional
+        /// constant default value.
+        properties: Vec<(Variable<'a>, Option<Expression<'a>>)>,
+    },
+
+    /// A method.
+    Method {
+        /// The attributes of the method.
+        attributes: Vec<Attribute<'a>>,
+
+        /// The visibility of the method.
+        visibility: Visibility,
+
+        /// The modifiers of the method, i.e. `abstract`, `final` or
+        /// `static`.
+        modifiers: Vec<Modifier>,
+
+        /// Name of the method.
+        name: Span<'a>,
+
+        /// Inputs, aka parameters, of the method.
+        inputs: Arity<'a>,
+
+        /// Output type of the method.
+        output: Box<Ty<'a>>,
+
+        /// Kind of the method, i.e. whether it is a generator. An
+        /// abstract method is regular.
+        kind: FunctionKind,
+
+        /// Body of the method, or `None` for an abstract method.
+        body: Option<Vec<Statement<'a>>>,
+    },
+
+    /// A use of traits, with optional adaptations, e.g. `use A, B {
+    /// A::f insteadof B; }`.
+    TraitUse {
+        /// The used traits.
+        traits: Vec<Name<'a>>,
+
+        /// The adaptations of the methods of the used traits.
+        adaptations: Vec<TraitAdaptation<'a>>,
+    },
 }
 
-/// A function declaration.
+/// The visibility of a class member.
+#[derive(Debug, PartialEq)]
+pub enum Visibility {
+    /// The member is accessible from everywhere.
+    Public,
+
+    /// The member is accessible from the class, and from its parents
+    /// and children.
+    Protected,
+
+    /// The member is accessible from the class only.
+    Private,
+}
+
+/// A modifier of a class or of a class member.
+#[derive(Debug, PartialEq)]
+pub enum Modifier {
+    /// The class or the method is incomplete, and must be extended
+    /// or implemented.
+    Abstract,
+
+    /// The class cannot be extended, or the member cannot be
+    /// overridden.
+    Final,
+
+    /// The property, or all the properties of the class, can be
+    /// initialized only once.
+    Readonly,
+
+    /// The member belongs to the class instead of its instances.
+    Static,
+}
+
+/// An enumeration declaration. An enumeration is pure when it has no
+/// backing type, otherwise it is backed and all its cases have a value.
 ///
 /// # Examples
 ///
 /// ```
+/// # extern crate smallvec;
+/// # #[macro_use]
+/// # extern crate tagua_parser;
+/// use std::borrow::Cow;
 /// use tagua_parser::ast::{
-///     Arity,
-///     Function,
+///     ClassMember,
+///     Enum,
+///     EnumCase,
+///     Expression,
+///     Literal,
 ///     Name,
-///     Parameter,
 ///     Statement,
-///     Ty,
-///     Variable
+///     Visibility
 /// };
-/// use tagua_parser::rules::statements::function::function;
+/// use tagua_parser::rules::statements::class::enum_declaration;
 /// use tagua_parser::tokens::{
 ///     Span,
 ///     Token
@@ -1505,21 +2963,35 @@ pub enum Arity<'a> {
 ///
 /// # fn main() {
 /// assert_eq!(
-///     function(Span::new(b"function f(I $x): O { return; }")),
+///     enum_declaration(Span::new(b"enum Suit: string implements HasLabel { case Hearts = 'H'; const X = 1; }")),
 ///     Ok((
-///         Span::new_at(b"", 31, 1, 32),
-///         Statement::Function(
-///             Function {
-///                 name  : Span::new_at(b"f", 9, 1, 10),
-///                 inputs: Arity::Finite(vec![
-///                     Parameter {
-///                         ty   : Ty::Copy(Some(Name::Unqualified(Span::new_at(b"I", 11, 1, 12)))),
-///                         name : Variable(Span::new_at(b"x", 14, 1, 15)),
-///                         value: None
+///         Span::new_at(b"", 73, 1, 74),
+///         Statement::Enum(
+///             Enum {
+///                 attributes  : vec![],
+///                 name        : Span::new_at(b"Suit", 5, 1, 6),
+///                 backing_type: Some(Name::FullyQualified(smallvec![Span::new_at(b"string", 11, 1, 12)])),
+///                 implements  : vec![Name::Unqualified(Span::new_at(b"HasLabel", 29, 1, 30))],
+///                 cases       : vec![
+///                     EnumCase {
+///                         attributes: vec![],
+///                         name      : Span::new_at(b"Hearts", 45, 1, 46),
+///                         value     : Some(Expression::Literal(Literal::String(Token::new(Cow::from(&b"H"[..]), Span::new_at(b"'H'", 54, 1, 55)))))
 ///                     }
-///                 ]),
-///                 output: Ty::Copy(Some(Name::Unqualified(Span::new_at(b"O", 18, 1, 19)))),
-///                 body  : vec![Statement::Return]
+///                 ],
+///                 members     : vec![
+///                     ClassMember::Constant {
+///                         attributes: vec![],
+///                         visibility: Visibility::Public,
+///                         modifiers : vec![],
+///                         constants : vec![
+///                             (
+///                                 Span::new_at(b"X", 65, 1, 66),
+///                                 Expression::Literal(Literal::Integer(Token::new(1i64, Span::new_at(b"1", 69, 1, 70))))
+///                             )
+///                         ]
+///                     }
+///                 ]
 ///             }
 ///         )
 ///     ))
@@ -1527,72 +2999,154 @@ pub enum Arity<'a> {
 /// # }
 /// ```
 #[derive(Debug, PartialEq)]
-pub struct Function<'a> {
-    /// Name of the function.
+pub struct Enum<'a> {
+    /// Attributes of the enumeration.
+    pub attributes: Vec<Attribute<'a>>,
+
+    /// Name of the enumeration.
     pub name: Span<'a>,
 
-    /// Inputs, aka parameters, of the function.
-    pub inputs: Arity<'a>,
+    /// The backing type, i.e. `int` or `string`, if the enumeration
+    /// is backed.
+    pub backing_type: Option<Name<'a>>,
 
-    /// Output type of the function.
-    pub output: Ty<'a>,
+    /// The implemented interfaces.
+    pub implements: Vec<Name<'a>>,
 
-    /// Body of the function, i.e. a set of statements.
-    pub body: Vec<Statement<'a>>,
+    /// The cases of the enumeration, in order of appearance.
+    pub cases: Vec<EnumCase<'a>>,
+
+    /// The other members of the enumeration, i.e. constants, methods
+    /// and uses of traits, in order of appearance.
+    pub members: Vec<ClassMember<'a>>,
 }
 
-/// An anonymous function.
-///
-/// An anonymous function is defined like, and behaves like, a named
-/// function `Function` except that the former has no name, and an
-/// enclosed scope.
+/// A case of an enumeration, e.g. `case Hearts = 'H';`.
+#[derive(Debug, PartialEq)]
+pub struct EnumCase<'a> {
+    /// Attributes of the case.
+    pub attributes: Vec<Attribute<'a>>,
+
+    /// Name of the case.
+    pub name: Span<'a>,
+
+    /// The constant value of the case, if the enumeration is backed.
+    pub value: Option<Expression<'a>>,
+}
+
+/// An interface declaration.
 ///
 /// # Examples
 ///
 /// ```
 /// # extern crate tagua_parser;
 /// use tagua_parser::ast::{
-///     AnonymousFunction,
 ///     Arity,
-///     Expression,
+///     ClassMember,
+///     FunctionKind,
+///     Interface,
 ///     Name,
-///     Parameter,
-///     DeclarationScope,
 ///     Statement,
 ///     Ty,
-///     Variable
+///     Visibility
 /// };
-/// use tagua_parser::rules::expressions::primaries::anonymous_function;
-/// use tagua_parser::tokens::{
-///     Span,
-///     Token
+/// use tagua_parser::rules::statements::class::interface;
+/// use tagua_parser::tokens::Span;
+///
+/// # fn main() {
+/// assert_eq!(
+///     interface(Span::new(b"interface I extends J, K { function f(); }")),
+///     Ok((
+///         Span::new_at(b"", 42, 1, 43),
+///         Statement::Interface(
+///             Interface {
+///                 attributes: vec![],
+///                 name      : Span::new_at(b"I", 10, 1, 11),
+///                 extends   : vec![
+///                     Name::Unqualified(Span::new_at(b"J", 20, 1, 21)),
+///                     Name::Unqualified(Span::new_at(b"K", 23, 1, 24))
+///                 ],
+///                 members   : vec![
+///                     ClassMember::Method {
+///                         attributes: vec![],
+///                         visibility: Visibility::Public,
+///                         modifiers : vec![],
+///                         name      : Span::new_at(b"f", 36, 1, 37),
+///                         inputs    : Arity::Constant,
+///                         output    : Box::new(Ty::Copy(None)),
+///                         kind      : FunctionKind::Regular,
+///                         body      : None
+///                     }
+///                 ]
+///             }
+///         )
+///     ))
+/// );
+/// # }
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct Interface<'a> {
+    /// Attributes of the interface.
+    pub attributes: Vec<Attribute<'a>>,
+
+    /// Name of the interface.
+    pub name: Span<'a>,
+
+    /// The extended interfaces.
+    pub extends: Vec<Name<'a>>,
+
+    /// Members of the interface, i.e. constants and methods without a
+    /// body.
+    pub members: Vec<ClassMember<'a>>,
+}
+
+/// A trait declaration.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tagua_parser;
+/// use tagua_parser::ast::{
+///     ClassMember,
+///     Name,
+///     Statement,
+///     Trait,
+///     TraitAdaptation,
+///     Visibility
 /// };
+/// use tagua_parser::rules::statements::class::trait_declaration;
+/// use tagua_parser::tokens::Span;
 ///
 /// # fn main() {
 /// assert_eq!(
-///     anonymous_function(Span::new(b"static function &(I ...$x) use (&$y, $z): O { return; }")),
+///     trait_declaration(Span::new(b"trait T { use A, B { A::f insteadof B; B::f as protected g; } }")),
 ///     Ok((
-///         Span::new_at(b"", 55, 1, 56),
-///         Expression::AnonymousFunction(
-///             AnonymousFunction {
-///                 declaration_scope: DeclarationScope::Static,
-///                 inputs           : Arity::Infinite(vec![
-///                     Parameter {
-///                         ty   : Ty::Copy(Some(Name::Unqualified(Span::new_at(b"I", 18, 1, 19)))),
-///                         name : Variable(Span::new_at(b"x", 24, 1, 25)),
-///                         value: None
+///         Span::new_at(b"", 63, 1, 64),
+///         Statement::Trait(
+///             Trait {
+///                 attributes: vec![],
+///                 name      : Span::new_at(b"T", 6, 1, 7),
+///                 members   : vec![
+///                     ClassMember::TraitUse {
+///                         traits     : vec![
+///                             Name::Unqualified(Span::new_at(b"A", 14, 1, 15)),
+///                             Name::Unqualified(Span::new_at(b"B", 17, 1, 18))
+///                         ],
+///                         adaptations: vec![
+///                             TraitAdaptation::Insteadof {
+///                                 trait_name: Name::Unqualified(Span::new_at(b"A", 21, 1, 22)),
+///                                 method    : Span::new_at(b"f", 24, 1, 25),
+///                                 insteadof : vec![Name::Unqualified(Span::new_at(b"B", 36, 1, 37))]
+///                             },
+///                             TraitAdaptation::Alias {
+///                                 trait_name: Some(Name::Unqualified(Span::new_at(b"B", 39, 1, 40))),
+///                                 method    : Span::new_at(b"f", 42, 1, 43),
+///                                 visibility: Some(Visibility::Protected),
+///                                 alias     : Some(Span::new_at(b"g", 57, 1, 58))
+///                             }
+///                         ]
 ///                     }
-///                 ]),
-///                 output         : Ty::Reference(Some(Name::Unqualified(Span::new_at(b"O", 42, 1, 43)))),
-///                 enclosing_scope: Some(vec![
-///                     Expression::Reference(
-///                         Box::new(
-///                             Expression::Variable(Variable(Span::new_at(b"y", 34, 1, 35)))
-///                         )
-///                     ),
-///                     Expression::Variable(Variable(Span::new_at(b"z", 38, 1, 39)))
-///                 ]),
-///                 body: vec![Statement::Return]
+///                 ]
 ///             }
 ///         )
 ///     ))
@@ -1600,22 +3154,49 @@ pub struct Function<'a> {
 /// # }
 /// ```
 #[derive(Debug, PartialEq)]
-pub struct AnonymousFunction<'a> {
-    /// Declaration scope of the anonymous function.
-    pub declaration_scope: DeclarationScope,
+pub struct Trait<'a> {
+    /// Attributes of the trait.
+    pub attributes: Vec<Attribute<'a>>,
 
-    /// Inputs, aka parameters, of the anonymous function.
-    pub inputs: Arity<'a>,
+    /// Name of the trait.
+    pub name: Span<'a>,
 
-    /// Output type of the anonymous function.
-    pub output: Ty<'a>,
+    /// Members of the trait.
+    pub members: Vec<ClassMember<'a>>,
+}
 
-    /// Enclosed scope (list of variables to be accessible from the
-    /// body) of the anonymous function.
-    pub enclosing_scope: Option<Vec<Expression<'a>>>,
+/// An adaptation of a method of a used trait.
+#[derive(Debug, PartialEq)]
+pub enum TraitAdaptation<'a> {
+    /// Use the method of a trait instead of the methods with the same
+    /// name from other traits, e.g. `A::f insteadof B, C;`.
+    Insteadof {
+        /// The trait owning the method to use.
+        trait_name: Name<'a>,
+
+        /// The method to use.
+        method: Span<'a>,
+
+        /// The traits whose method is excluded.
+        insteadof: Vec<Name<'a>>,
+    },
+
+    /// Change the visibility of a method, or add an alias to a
+    /// method, or both, e.g. `A::f as protected g;`. At least one of
+    /// the visibility or the alias is present.
+    Alias {
+        /// The trait owning the method, if specified.
+        trait_name: Option<Name<'a>>,
+
+        /// The aliased method.
+        method: Span<'a>,
+
+        /// The new visibility of the method, if any.
+        visibility: Option<Visibility>,
 
-    /// Body of the anonymous function, i.e. a set of statements.
-    pub body: Vec<Statement<'a>>,
+        /// The alias of the method, if any.
+        alias: Option<Span<'a>>,
+    },
 }
 
 /// A n-ary operation.
@@ -1685,134 +3266,770 @@ pub enum UnaryOperator {
     /// `!$x`.
     Negate,
 
-    /// `+$x`.
-    Plus,
-}
+    /// `+$x`.
+    Plus,
+
+    /// `$x--`.
+    PostDecrement,
+
+    /// `$x++`.
+    PostIncrement,
+}
+
+/// A binary operator.
+#[derive(Debug, PartialEq)]
+pub enum BinaryOperator {
+    /// `$x = $y`.
+    Assignment,
+
+    /// `$x & $y`.
+    BitwiseAnd,
+
+    /// `$x &= $y`.
+    BitwiseAndAssignment,
+
+    /// `$x | $y`.
+    BitwiseOr,
+
+    /// `$x |= $y`.
+    BitwiseOrAssignment,
+
+    /// `$x << $y`.
+    BitwiseShiftLeft,
+
+    /// `$x <<= $y`.
+    BitwiseShiftLeftAssignment,
+
+    /// `$x >> $y`.
+    BitwiseShiftRight,
+
+    /// `$x >>= $y`.
+    BitwiseShiftRightAssignment,
+
+    /// `$x ^ $y`.
+    BitwiseXor,
+
+    /// `$x ^= $y`.
+    BitwiseXorAssignment,
+
+    /// `$x ?? $y`.
+    Coalesce,
+
+    /// `$x ??= $y`.
+    CoalesceAssignment,
+
+    /// `$x <=> $y`.
+    Comparison,
+
+    /// `$x ?: $z`, i.e. `$x ? $y : $z` with `$y` being optional.
+    Conditional,
+
+    /// `$x / $y`.
+    Division,
+
+    /// `$x /= $y`.
+    DivisionAssignment,
+
+    /// `$x . $y`.
+    Dot,
+
+    /// `$x .= $y`.
+    DotAssignment,
+
+    /// `$x == $y`.
+    Equal,
+
+    /// `$x > $y`.
+    GreaterThan,
+
+    /// `$x >= $y`.
+    GreaterThanOrEqualTo,
+
+    /// `$x === $y`.
+    Identical,
+
+    /// `$x instanceof $y`.
+    InstanceOf,
+
+    /// `$x < $y`.
+    LessThan,
+
+    /// `$x <= $y`.
+    LessThanOrEqualTo,
+
+    /// `$x && $y` or `$x and $y`.
+    LogicalAnd,
+
+    /// `$x || $y` or `$x or $y`.
+    LogicalOr,
+
+    /// `$x xor $y`.
+    LogicalXor,
+
+    /// `$x - $y`.
+    Minus,
+
+    /// `$x -= $y`.
+    MinusAssignment,
+
+    /// `$x % $y`.
+    Modulo,
+
+    /// `$x %= $y`.
+    ModuloAssignment,
+
+    /// `$x * $y`.
+    Multiplication,
+
+    /// `$x *= $y`.
+    MultiplicationAssignment,
+
+    /// `$x != $y` or `$x <> $y`.
+    NotEqual,
+
+    /// `$x !== $y`.
+    NotIdentical,
+
+    /// `$x + $y`.
+    Plus,
+
+    /// `$x += $y`.
+    PlusAssignment,
+
+    /// `$x ** $y`.
+    Power,
+
+    /// `$x **= $y`.
+    PowerAssignment,
+}
+
+/// A ternary operator.
+#[derive(Debug, PartialEq)]
+pub enum TernaryOperator {
+    /// `$x ? $y : $z`.
+    Conditional,
+}
+
+/// A cast type.
+///
+/// A datum can be casted into another type by using the `(x) $y`
+/// notation where `x` is the targeted type. Some type aliases exist,
+/// like `bool` and `boolean`. In this case, they are represented by
+/// the same variant.
+#[derive(Debug, PartialEq)]
+pub enum CastType {
+    /// `(array)`.
+    Array,
+
+    /// `(binary)`.
+    Binary,
+
+    /// `(bool)` or `(boolean)`.
+    Boolean,
+
+    /// `(double)`, `(float)`, or `(real)`.
+    Float,
+
+    /// `(int)` or `(integer)`.
+    Integer,
+
+    /// `(object)`.
+    Object,
+
+    /// `(string)`.
+    String,
+}
+
+/// A program, i.e. the top-level statements of a whole file.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tagua_parser;
+/// use tagua_parser::ast::{
+///     Arity,
+///     Function,
+///     FunctionKind,
+///     Program,
+///     Statement,
+///     Ty
+/// };
+/// use tagua_parser::rules::program;
+/// use tagua_parser::tokens::Span;
+///
+/// # fn main() {
+/// assert_eq!(
+///     program(Span::new(b"<?php function f() {}")),
+///     Ok(
+///         Program {
+///             statements: vec![
+///                 Statement::Function(
+///                     Function {
+///                         attributes: vec![],
+///                         name      : Span::new_at(b"f", 15, 1, 16),
+///                         inputs    : Arity::Constant,
+///                         output    : Ty::Copy(None),
+///                         kind      : FunctionKind::Regular,
+///                         body      : vec![]
+///                     }
+///                 )
+///             ]
+///         }
+///     )
+/// );
+/// # }
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct Program<'a> {
+    /// The top-level statements, in order of appearance.
+    pub statements: Vec<Statement<'a>>,
+}
+
+/// A statement.
+#[derive(Debug, PartialEq)]
+pub enum Statement<'a> {
+    /// A group of statements delimited by curly braces, e.g. `{ $x;
+    /// $y; }`.
+    Block(Vec<Statement<'a>>),
+
+    /// Break the execution of the enclosing loops or `switch`
+    /// structures. The level is optional, e.g. `break 2;`; it is 1
+    /// by default.
+    Break(Option<Token<'a, i64>>),
+
+    /// A class declaration.
+    Class(Class<'a>),
+
+    /// Skip the rest of the current iteration of the enclosing loops.
+    /// The level is optional, e.g. `continue 2;`; it is 1 by default.
+    Continue(Option<Token<'a, i64>>),
+
+    /// A `declare` structure, setting execution directives for its
+    /// body, e.g. `declare(ticks=1) { … }`. When the body is empty,
+    /// e.g. `declare(strict_types=1);`, the directives apply to the
+    /// rest of the file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::ast::{
+    ///     ControlSyntax,
+    ///     Expression,
+    ///     Literal,
+    ///     Statement
+    /// };
+    /// use tagua_parser::rules::statements::statement;
+    /// use tagua_parser::tokens::{
+    ///     Span,
+    ///     Token
+    /// };
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     statement(Span::new(b"declare(strict_types=1);")),
+    ///     Ok((
+    ///         Span::new_at(b"", 24, 1, 25),
+    ///         Statement::Declare {
+    ///             directives: vec![
+    ///                 (
+    ///                     Span::new_at(b"strict_types", 8, 1, 9),
+    ///                     Expression::Literal(Literal::Integer(Token::new(1i64, Span::new_at(b"1", 21, 1, 22))))
+    ///                 )
+    ///             ],
+    ///             body      : vec![Statement::Empty],
+    ///             syntax    : ControlSyntax::Standard
+    ///         }
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    Declare {
+        /// The directives, as pairs of a name and a constant value.
+        directives: Vec<(Span<'a>, Expression<'a>)>,
+
+        /// The body the directives apply to.
+        body: Vec<Statement<'a>>,
+
+        /// The syntax of the structure.
+        syntax: ControlSyntax,
+    },
+
+    /// A `do … while` loop, where the body is executed at least once.
+    DoWhile {
+        /// The body of the loop.
+        body: Vec<Statement<'a>>,
+
+        /// The condition to continue the loop.
+        condition: Expression<'a>,
+    },
+
+    /// An empty statement, i.e. a lone `;`.
+    Empty,
+
+    /// An enumeration declaration.
+    Enum(Enum<'a>),
+
+    /// An expression used as a statement, e.g. `$x = 42;` or `<?= $x
+    /// ?>`.
+    Expression(Expression<'a>),
+
+    /// A `for` loop, e.g. `for ($i = 0; $i < 7; ++$i) { … }`. Each
+    /// part is a possibly empty list of expressions.
+    For {
+        /// The expressions evaluated once before the loop.
+        initializers: Vec<Expression<'a>>,
+
+        /// The expressions evaluated before each iteration; the
+        /// last one is the condition to continue the loop.
+        conditions: Vec<Expression<'a>>,
+
+        /// The expressions evaluated after each iteration.
+        steps: Vec<Expression<'a>>,
+
+        /// The body of the loop.
+        body: Vec<Statement<'a>>,
+
+        /// The syntax of the structure.
+        syntax: ControlSyntax,
+    },
+
+    /// A `foreach` loop.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::ast::{
+    ///     ControlSyntax,
+    ///     Expression,
+    ///     Statement,
+    ///     Variable
+    /// };
+    /// use tagua_parser::rules::statements::statement;
+    /// use tagua_parser::tokens::Span;
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     statement(Span::new(b"foreach ($x as $k => &$v) {}")),
+    ///     Ok((
+    ///         Span::new_at(b"", 28, 1, 29),
+    ///         Statement::Foreach {
+    ///             subject: Expression::Variable(Variable(Span::new_at(b"x", 10, 1, 11))),
+    ///             key    : Some(Box::new(Expression::Variable(Variable(Span::new_at(b"k", 16, 1, 17))))),
+    ///             value  : Box::new(Expression::Reference(
+    ///                 Box::new(Expression::Variable(Variable(Span::new_at(b"v", 23, 1, 24))))
+    ///             )),
+    ///             body   : vec![],
+    ///             syntax : ControlSyntax::Standard
+    ///         }
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    Foreach {
+        /// The iterated expression.
+        subject: Expression<'a>,
+
+        /// The optional key of the current element.
+        key: Option<Box<Expression<'a>>>,
+
+        /// The value of the current element, possibly by reference,
+        /// or a destructuring pattern like `list($x, $y)`.
+        value: Box<Expression<'a>>,
+
+        /// The body of the loop.
+        body: Vec<Statement<'a>>,
+
+        /// The syntax of the structure.
+        syntax: ControlSyntax,
+    },
+
+    /// A function declaration.
+    Function(Function<'a>),
+
+    /// Import global variables into the current scope, e.g. `global
+    /// $x, $y;`.
+    Global(Vec<Variable<'a>>),
+
+    /// A conditional statement.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::ast::{
+    ///     ControlSyntax,
+    ///     Expression,
+    ///     Statement,
+    ///     Variable
+    /// };
+    /// use tagua_parser::rules::statements::statement;
+    /// use tagua_parser::tokens::Span;
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     statement(Span::new(b"if ($x) $y; elseif ($z) {} else { return; }")),
+    ///     Ok((
+    ///         Span::new_at(b"", 43, 1, 44),
+    ///         Statement::If {
+    ///             condition: Expression::Variable(Variable(Span::new_at(b"x", 5, 1, 6))),
+    ///             body     : vec![
+    ///                 Statement::Expression(Expression::Variable(Variable(Span::new_at(b"y", 9, 1, 10))))
+    ///             ],
+    ///             else_ifs : vec![
+    ///                 (
+    ///                     Expression::Variable(Variable(Span::new_at(b"z", 21, 1, 22))),
+    ///                     vec![]
+    ///                 )
+    ///             ],
+    ///             else_body: Some(vec![Statement::Return(None)]),
+    ///             syntax   : ControlSyntax::Standard
+    ///         }
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    If {
+        /// The condition.
+        condition: Expression<'a>,
+
+        /// The body executed if the condition is truthy.
+        body: Vec<Statement<'a>>,
+
+        /// The `elseif` clauses, as pairs of a condition and a body.
+        else_ifs: Vec<(Expression<'a>, Vec<Statement<'a>>)>,
 
-/// A binary operator.
-#[derive(Debug, PartialEq)]
-pub enum BinaryOperator {
-    /// `$x & $y`.
-    BitwiseAnd,
+        /// The body of the optional `else` clause.
+        else_body: Option<Vec<Statement<'a>>>,
 
-    /// `$x | $y`.
-    BitwiseOr,
+        /// The syntax of the structure.
+        syntax: ControlSyntax,
+    },
 
-    /// `$x << $y`.
-    BitwiseShiftLeft,
+    /// Inline HTML, i.e. raw text outside of a PHP block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::ast::Statement;
+    /// use tagua_parser::rules::statements::inline_html;
+    /// use tagua_parser::tokens::Span;
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     inline_html(Span::new(b"?>\n<p>Hello</p>")),
+    ///     Ok((
+    ///         Span::new_at(b"", 15, 2, 13),
+    ///         Statement::InlineHtml(Span::new_at(b"<p>Hello</p>", 3, 2, 1))
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    InlineHtml(Span<'a>),
 
-    /// `$x >> $y`.
-    BitwiseShiftRight,
+    /// An interface declaration.
+    Interface(Interface<'a>),
 
-    /// `$x ^ $y`.
-    BitwiseXor,
+    /// A namespace declaration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate smallvec;
+    /// # #[macro_use]
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::ast::Statement;
+    /// use tagua_parser::rules::statements::namespace::namespace_declaration;
+    /// use tagua_parser::tokens::Span;
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     namespace_declaration(Span::new(b"namespace Foo\\Bar { }")),
+    ///     Ok((
+    ///         Span::new_at(b"", 21, 1, 22),
+    ///         Statement::Namespace {
+    ///             name: Some(smallvec![
+    ///                 Span::new_at(b"Foo", 10, 1, 11),
+    ///                 Span::new_at(b"Bar", 14, 1, 15)
+    ///             ]),
+    ///             body: Some(vec![])
+    ///         }
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    Namespace {
+        /// The segments of the namespace name, or `None` for the
+        /// global namespace, e.g. `namespace { … }`.
+        name: Option<SmallVec<[Span<'a>; 5]>>,
 
-    /// `$x ?? $y`.
-    Coalesce,
+        /// The statements of a braced namespace, or `None` when the
+        /// namespace applies to the following statements, e.g.
+        /// `namespace Foo;`.
+        body: Option<Vec<Statement<'a>>>,
+    },
 
-    /// `$x <=> $y`.
-    Comparison,
+    /// A return, with an optional value, e.g. `return $x;`.
+    Return(Option<Expression<'a>>),
 
-    /// `$x ?: $z`, i.e. `$x ? $y : $z` with `$y` being optional.
-    Conditional,
+    /// Declare static variables, i.e. variables keeping their value
+    /// between calls of a function, with an optional initial value,
+    /// e.g. `static $x = 42, $y;`.
+    Static(Vec<(Variable<'a>, Option<Expression<'a>>)>),
 
-    /// `$x / $y`.
-    Division,
+    /// A `switch` structure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::ast::{
+    ///     ControlSyntax,
+    ///     Expression,
+    ///     Literal,
+    ///     Statement,
+    ///     SwitchCase,
+    ///     Variable
+    /// };
+    /// use tagua_parser::rules::statements::statement;
+    /// use tagua_parser::tokens::{
+    ///     Span,
+    ///     Token
+    /// };
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     statement(Span::new(b"switch ($x): case 42: break; default: endswitch;")),
+    ///     Ok((
+    ///         Span::new_at(b"", 48, 1, 49),
+    ///         Statement::Switch {
+    ///             subject: Expression::Variable(Variable(Span::new_at(b"x", 9, 1, 10))),
+    ///             cases  : vec![
+    ///                 SwitchCase {
+    ///                     condition: Some(Expression::Literal(Literal::Integer(Token::new(42i64, Span::new_at(b"42", 18, 1, 19))))),
+    ///                     body     : vec![Statement::Break(None)]
+    ///                 },
+    ///                 SwitchCase {
+    ///                     condition: None,
+    ///                     body     : vec![]
+    ///                 }
+    ///             ],
+    ///             syntax : ControlSyntax::Alternative
+    ///         }
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    Switch {
+        /// The expression to compare against the cases.
+        subject: Expression<'a>,
 
-    /// `$x . $y`.
-    Dot,
+        /// The cases, in order of appearance.
+        cases: Vec<SwitchCase<'a>>,
 
-    /// `$x == $y`.
-    Equal,
+        /// The syntax of the structure.
+        syntax: ControlSyntax,
+    },
 
-    /// `$x > $y`.
-    GreaterThan,
+    /// A trait declaration.
+    Trait(Trait<'a>),
 
-    /// `$x >= $y`.
-    GreaterThanOrEqualTo,
+    /// A `try` statement, handling the exceptions thrown by its body.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::ast::{
+    ///     CatchClause,
+    ///     Name,
+    ///     Statement,
+    ///     Variable
+    /// };
+    /// use tagua_parser::rules::statements::statement;
+    /// use tagua_parser::tokens::Span;
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     statement(Span::new(b"try { return; } catch (E $e) {}")),
+    ///     Ok((
+    ///         Span::new_at(b"", 31, 1, 32),
+    ///         Statement::Try {
+    ///             span   : Span::new(b"try { return; }"),
+    ///             body   : vec![Statement::Return(None)],
+    ///             catches: vec![
+    ///                 CatchClause {
+    ///                     span    : Span::new_at(b"catch (E $e) {}", 16, 1, 17),
+    ///                     types   : vec![Name::Unqualified(Span::new_at(b"E", 23, 1, 24))],
+    ///                     variable: Some(Variable(Span::new_at(b"e", 26, 1, 27))),
+    ///                     body    : vec![]
+    ///                 }
+    ///             ],
+    ///             finally: None
+    ///         }
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    Try {
+        /// The span of the `try` clause, e.g. `try { … }`.
+        span: Span<'a>,
 
-    /// `$x === $y`.
-    Identical,
+        /// The body of the `try` clause.
+        body: Vec<Statement<'a>>,
 
-    /// `$x instanceof $y`.
-    InstanceOf,
+        /// The `catch` clauses, in order of appearance.
+        catches: Vec<CatchClause<'a>>,
 
-    /// `$x < $y`.
-    LessThan,
+        /// The optional `finally` clause.
+        finally: Option<FinallyClause<'a>>,
+    },
 
-    /// `$x <= $y`.
-    LessThanOrEqualTo,
+    /// Imports of names, e.g. `use A\B as C, D;`. The imports of a
+    /// group use are flattened: `use A\{B, C};` is equivalent to
+    /// `use A\B, A\C;`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate smallvec;
+    /// # #[macro_use]
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::ast::{Statement, Use, UseKind};
+    /// use tagua_parser::rules::statements::namespace::use_declaration;
+    /// use tagua_parser::tokens::Span;
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     use_declaration(Span::new(b"use A\\{B, function c as d};")),
+    ///     Ok((
+    ///         Span::new_at(b"", 27, 1, 28),
+    ///         Statement::Use(vec![
+    ///             Use {
+    ///                 kind : UseKind::Class,
+    ///                 name : smallvec![Span::new_at(b"A", 4, 1, 5), Span::new_at(b"B", 7, 1, 8)],
+    ///                 alias: None
+    ///             },
+    ///             Use {
+    ///                 kind : UseKind::Function,
+    ///                 name : smallvec![Span::new_at(b"A", 4, 1, 5), Span::new_at(b"c", 19, 1, 20)],
+    ///                 alias: Some(Span::new_at(b"d", 24, 1, 25))
+    ///             }
+    ///         ])
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    Use(Vec<Use<'a>>),
 
-    /// `$x && $y`.
-    LogicalAnd,
+    /// A `while` loop.
+    While {
+        /// The condition to continue the loop.
+        condition: Expression<'a>,
 
-    /// `$x || $y`.
-    LogicalOr,
+        /// The body of the loop.
+        body: Vec<Statement<'a>>,
 
-    /// `$x - $y`.
-    Minus,
+        /// The syntax of the structure.
+        syntax: ControlSyntax,
+    },
+}
 
-    /// `$x % $y`.
-    Modulo,
+/// A case of a `switch` structure.
+#[derive(Debug, PartialEq)]
+pub struct SwitchCase<'a> {
+    /// The expression to compare against the subject of the `switch`
+    /// structure, or `None` for the `default` case.
+    pub condition: Option<Expression<'a>>,
 
-    /// `$x * $y`.
-    Multiplication,
+    /// The statements of the case.
+    pub body: Vec<Statement<'a>>,
+}
 
-    /// `$x != $y` or `$x <> $y`.
-    NotEqual,
+/// A `catch` clause of a `try` statement.
+#[derive(Debug, PartialEq)]
+pub struct CatchClause<'a> {
+    /// The span of the clause, e.g. `catch (E $e) { … }`.
+    pub span: Span<'a>,
 
-    /// `$x !== $y`.
-    NotIdentical,
+    /// The caught types, e.g. `A` and `B` in `catch (A | B $e)`.
+    pub types: Vec<Name<'a>>,
 
-    /// `$x + $y`.
-    Plus,
+    /// The variable receiving the exception, if any.
+    pub variable: Option<Variable<'a>>,
+
+    /// The body of the clause.
+    pub body: Vec<Statement<'a>>,
 }
 
-/// A ternary operator.
+/// A `finally` clause of a `try` statement.
 #[derive(Debug, PartialEq)]
-pub enum TernaryOperator {
-    /// `$x ? $y : $z`.
-    Conditional,
+pub struct FinallyClause<'a> {
+    /// The span of the clause, e.g. `finally { … }`.
+    pub span: Span<'a>,
+
+    /// The body of the clause.
+    pub body: Vec<Statement<'a>>,
 }
 
-/// A cast type.
-///
-/// A datum can be casted into another type by using the `(x) $y`
-/// notation where `x` is the targeted type. Some type aliases exist,
-/// like `bool` and `boolean`. In this case, they are represented by
-/// the same variant.
+/// An import of a name.
 #[derive(Debug, PartialEq)]
-pub enum CastType {
-    /// `(array)`.
-    Array,
+pub struct Use<'a> {
+    /// The kind of the imported name.
+    pub kind: UseKind,
 
-    /// `(binary)`.
-    Binary,
+    /// The segments of the imported name, without the leading `\` if
+    /// any.
+    pub name: SmallVec<[Span<'a>; 5]>,
 
-    /// `(bool)` or `(boolean)`.
-    Boolean,
+    /// The alias of the imported name, if any, e.g. `use A\B as C;`.
+    pub alias: Option<Span<'a>>,
+}
 
-    /// `(double)`, `(float)`, or `(real)`.
-    Float,
+/// The kind of an imported name.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UseKind {
+    /// A class, an interface, a trait, an enumeration or a namespace,
+    /// e.g. `use A\B;`.
+    Class,
 
-    /// `(int)` or `(integer)`.
-    Integer,
+    /// A function, e.g. `use function A\f;`.
+    Function,
 
-    /// `(object)`.
-    Object,
+    /// A constant, e.g. `use const A\C;`.
+    Constant,
+}
 
-    /// `(string)`.
-    String,
+/// The kind of an included or required file.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ImportKind {
+    /// An included file, e.g. `include $path`.
+    Include,
+
+    /// A file included only if not already included, e.g.
+    /// `include_once $path`.
+    IncludeOnce,
+
+    /// A required file, e.g. `require $path`.
+    Require,
+
+    /// A file required only if not already included, e.g.
+    /// `require_once $path`.
+    RequireOnce,
 }
 
-/// A statement.
+/// The syntax of a control structure.
 #[derive(Debug, PartialEq)]
-pub enum Statement<'a> {
-    /// A function declaration.
-    Function(Function<'a>),
+pub enum ControlSyntax {
+    /// The standard syntax, where the body is a single statement,
+    /// usually a block, e.g. `while (…) { … }`.
+    Standard,
 
-    /// A return.
-    Return,
+    /// The alternative syntax, where the body starts with a colon and
+    /// ends with a dedicated keyword, e.g. `while (…): … endwhile;`.
+    Alternative,
 }
 
 /// A declaration scope.
@@ -1843,6 +4060,104 @@ pub enum RelativeScope {
     ToStatic,
 }
 
+/// The name of an object member, i.e. a property or a method.
+#[derive(Debug, PartialEq)]
+pub enum MemberName<'a> {
+    /// A name, e.g. `bar` in `$foo->bar`.
+    Name(Span<'a>),
+
+    /// An expression evaluating to a name, e.g. `$bar` in
+    /// `$foo->$bar`, or `'bar'` in `$foo->{'bar'}`.
+    Expression(Box<Expression<'a>>),
+}
+
+/// An argument of a call.
+#[derive(Debug, PartialEq)]
+pub enum Argument<'a> {
+    /// A positional argument, e.g. `$x` in `f($x)`.
+    Positional(Expression<'a>),
+
+    /// An unpacked argument, e.g. `...$x` in `f(...$x)`. The
+    /// expression is expected to be an array or a `Traversable`.
+    Unpacked(Expression<'a>),
+
+    /// A named argument, e.g. `x: 42` in `f(x: 42)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::ast::{
+    ///     Argument,
+    ///     Arguments,
+    ///     Expression,
+    ///     Literal,
+    ///     Name
+    /// };
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::tokens::{
+    ///     Span,
+    ///     Token
+    /// };
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     expression(Span::new(b"f(x: 42)")),
+    ///     Ok((
+    ///         Span::new_at(b"", 8, 1, 9),
+    ///         Expression::Call(
+    ///             Box::new(Expression::Name(Name::Unqualified(Span::new(b"f")))),
+    ///             Arguments::List(vec![
+    ///                 Argument::Named(
+    ///                     Span::new_at(b"x", 2, 1, 3),
+    ///                     Expression::Literal(Literal::Integer(Token::new(42i64, Span::new_at(b"42", 5, 1, 6))))
+    ///                 )
+    ///             ])
+    ///         )
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    Named(Span<'a>, Expression<'a>),
+}
+
+/// The arguments of a call.
+#[derive(Debug, PartialEq)]
+pub enum Arguments<'a> {
+    /// A list of arguments, possibly empty.
+    List(Vec<Argument<'a>>),
+
+    /// The first-class callable syntax, i.e. `(...)`, which creates a
+    /// closure from the callee instead of calling it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::ast::{
+    ///     Arguments,
+    ///     Expression,
+    ///     Name
+    /// };
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::tokens::Span;
+    ///
+    /// # fn main() {
+    /// assert_eq!(
+    ///     expression(Span::new(b"strlen(...)")),
+    ///     Ok((
+    ///         Span::new_at(b"", 11, 1, 12),
+    ///         Expression::Call(
+    ///             Box::new(Expression::Name(Name::Unqualified(Span::new(b"strlen")))),
+    ///             Arguments::FirstClassCallable
+    ///         )
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    FirstClassCallable,
+}
+
 /// A scope resolution qualifier.
 #[derive(Debug, PartialEq)]
 pub enum ScopeResolver<'a> {
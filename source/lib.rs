@@ -45,7 +45,8 @@
 //!
 //!   * The `tokens` module declares all the lexemes,
 //!   * The `rules` module declares the grammar as a set of rules,
-//!   * The `ast` module contains the structure that will constitute the AST.
+//!   * The `ast` module contains the structure that will constitute the AST,
+//!   * The `resolution` module resolves the names of an AST.
 //!
 //! The parser is based on [nom](https://github.com/Geal/nom). nom is a parser
 //! combinator library with a focus on safe parsing, streaming patterns, and as
@@ -76,11 +77,15 @@ pub mod internal;
 #[macro_use]
 pub mod macros;
 pub mod ast;
+pub mod resolution;
 pub mod rules;
 pub mod tokens;
+pub mod visitor;
 
 pub use self::internal::*;
 
+use std::result::Result as StdResult;
+
 /// Complete parsing of a datum starting by the sentence symbol of the grammar.
 ///
 /// The grammar is a set of rules. By definition, it has a sentence symbol,
@@ -99,3 +104,32 @@ pub use self::internal::*;
 pub fn parse(input: tokens::Span) -> ast::Expression {
     rules::root(input)
 }
+
+/// Complete parsing of a whole file, i.e. a datum made of top-level
+/// statements.
+///
+/// Contrary to `parse`, this function does not panic: If the entire
+/// datum cannot be consumed, the span where the parser has stopped is
+/// returned as an error.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tagua_parser;
+/// use tagua_parser::parse_program;
+/// use tagua_parser::tokens::Span;
+///
+/// # fn main() {
+/// let file = Span::new(b"<p><?php function f() {} ?></p>");
+///
+/// assert!(parse_program(file).is_ok());
+///
+/// match parse_program(Span::new(b"<?php function f() {} !")) {
+///     Err(error) => assert_eq!(error.span.offset, 22),
+///     Ok(_) => unreachable!()
+/// }
+/// # }
+/// ```
+pub fn parse_program(input: tokens::Span) -> StdResult<ast::Program, rules::ProgramError> {
+    rules::program(input)
+}
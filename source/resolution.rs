@@ -0,0 +1,938 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2017, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Name resolution.
+//!
+//! The names of an AST are written relatively to the current namespace
+//! and to the imports declared with `use`. This module walks an AST
+//! and resolves every name to a fully qualified name, following the
+//! PHP rules:
+//!
+//!   * A fully qualified name, like `\A\B`, is already resolved,
+//!   * A relative name, like `namespace\A\B`, is prefixed by the
+//!     current namespace,
+//!   * A qualified name, like `A\B`, is resolved with the class
+//!     imports if its first segment is imported, else it is prefixed
+//!     by the current namespace,
+//!   * An unqualified name, like `A`, is resolved with the imports of
+//!     its kind (class, function or constant). An unqualified class
+//!     name that is not imported is prefixed by the current namespace,
+//!     while an unqualified function or constant name that is not
+//!     imported falls back to the global namespace at runtime,
+//!   * The `self`, `parent` and `static` class names are relative to
+//!     the enclosing class, and are resolved at runtime.
+//!
+//! The AST is left untouched: Resolutions are collected in a side table
+//! keyed by the span of the first segment of the names.
+//!
+//! The same walk optionally resolves the magic constants, like
+//! `__LINE__` or `C::class`, to their values, given the path of the
+//! file of the AST, see `resolve_magic_constants`.
+
+use super::ast::{
+    ClassMember, Expression, Literal, MagicConstant, Name, Program, RelativeScope, ScopeResolver,
+    Statement, UseKind,
+};
+use super::tokens;
+use super::tokens::{Span, Token};
+use super::visitor::{
+    walk_class_member, walk_expression, walk_statement, walk_statements, Visitor,
+};
+use smallvec::SmallVec;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::mem;
+use std::path::{Path, PathBuf};
+
+/// A resolved name.
+#[derive(Debug, PartialEq)]
+pub enum ResolvedName<'a> {
+    /// A fully qualified name, as a list of segments, without the
+    /// leading `\`.
+    FullyQualified(SmallVec<[Span<'a>; 5]>),
+
+    /// An unqualified function or constant name, in a namespace, that
+    /// is not imported. The name is prefixed by the current namespace.
+    /// At runtime, this name is used if it exists, else the last
+    /// segment is used, as a name in the global namespace.
+    WithFallback(SmallVec<[Span<'a>; 5]>),
+
+    /// A name relative to the enclosing class, i.e. `self`, `parent`
+    /// or `static`.
+    Relative(RelativeScope),
+}
+
+/// The resolved names, keyed by the span of the first segment of the
+/// names. The names of declared classes, interfaces, traits,
+/// enumerations and functions are resolved too, keyed by their span.
+pub type ResolvedNames<'a> = HashMap<Span<'a>, ResolvedName<'a>>;
+
+/// Resolve all the names of a program.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate smallvec;
+/// # #[macro_use]
+/// # extern crate tagua_parser;
+/// use tagua_parser::parse_program;
+/// use tagua_parser::resolution::{resolve_names, ResolvedName};
+/// use tagua_parser::tokens::Span;
+///
+/// # fn main() {
+/// let program = parse_program(Span::new(b"<?php namespace A; use B\\C; new C; f();")).unwrap();
+/// let names = resolve_names(&program);
+///
+/// assert_eq!(
+///     names[&Span::new_at(b"C", 32, 1, 33)],
+///     ResolvedName::FullyQualified(smallvec![
+///         Span::new_at(b"B", 23, 1, 24),
+///         Span::new_at(b"C", 25, 1, 26)
+///     ])
+/// );
+/// assert_eq!(
+///     names[&Span::new_at(b"f", 35, 1, 36)],
+///     ResolvedName::WithFallback(smallvec![
+///         Span::new_at(b"A", 16, 1, 17),
+///         Span::new_at(b"f", 35, 1, 36)
+///     ])
+/// );
+/// # }
+/// ```
+pub fn resolve_names<'a>(program: &Program<'a>) -> ResolvedNames<'a> {
+    let mut resolver = Resolver::new(None);
+    walk_statements(&mut resolver, &program.statements);
+
+    resolver.names
+}
+
+/// The resolved magic constants, keyed by the span of the magic
+/// constants, or by the span of `class` for `C::class`. The value of
+/// a magic constant is a literal, located at the magic constant.
+///
+/// Magic constants whose value is only known at runtime are not
+/// resolved, i.e. `static::class`, `parent::class`, `$x::class`, and
+/// `__CLASS__` or `self::class` in a trait.
+pub type ResolvedMagicConstants<'a> = HashMap<Span<'a>, Literal<'a>>;
+
+/// Resolve all the magic constants of a program, written in the file
+/// `file`.
+///
+/// The path is used as is to compute `__FILE__` and `__DIR__`, so it
+/// is expected to be absolute. A magic constant outside of its scope,
+/// e.g. `__FUNCTION__` outside of a function, is an empty string.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tagua_parser;
+/// use std::borrow::Cow;
+/// use std::path::Path;
+/// use tagua_parser::ast::Literal;
+/// use tagua_parser::parse_program;
+/// use tagua_parser::resolution::resolve_magic_constants;
+/// use tagua_parser::tokens::{Span, Token};
+///
+/// # fn main() {
+/// let program = parse_program(Span::new(b"<?php namespace A;\nfunction f() { return [__FUNCTION__, __LINE__]; }")).unwrap();
+/// let constants = resolve_magic_constants(&program, Path::new("/src/a.php"));
+///
+/// let function = Span::new_at(b"__FUNCTION__", 42, 2, 24);
+/// let line = Span::new_at(b"__LINE__", 56, 2, 38);
+///
+/// assert_eq!(
+///     constants[&function],
+///     Literal::String(Token::new(Cow::from(&b"A\\f"[..]), function))
+/// );
+/// assert_eq!(constants[&line], Literal::Integer(Token::new(2i64, line)));
+/// # }
+/// ```
+pub fn resolve_magic_constants<'a>(
+    program: &Program<'a>,
+    file: &Path,
+) -> ResolvedMagicConstants<'a> {
+    let mut resolver = Resolver::new(Some(file.to_path_buf()));
+    walk_statements(&mut resolver, &program.statements);
+
+    resolver.magic_constants
+}
+
+/// The state of the resolution: The current namespace, the imports,
+/// and the resolved names. When a file is given, the state also
+/// contains the enclosing class and function, and the resolved magic
+/// constants.
+struct Resolver<'a> {
+    namespace: SmallVec<[Span<'a>; 5]>,
+    class_imports: HashMap<Vec<u8>, SmallVec<[Span<'a>; 5]>>,
+    function_imports: HashMap<Vec<u8>, SmallVec<[Span<'a>; 5]>>,
+    constant_imports: HashMap<Vec<u8>, SmallVec<[Span<'a>; 5]>>,
+    names: ResolvedNames<'a>,
+    file: Option<PathBuf>,
+    class: EnclosingClass,
+    function: Vec<u8>,
+    method: Vec<u8>,
+    magic_constants: ResolvedMagicConstants<'a>,
+}
+
+/// The class-like declaration enclosing an expression, with its
+/// fully qualified name.
+enum EnclosingClass {
+    None,
+    Class(Vec<u8>),
+    Trait(Vec<u8>),
+}
+
+impl<'a> Resolver<'a> {
+    fn new(file: Option<PathBuf>) -> Self {
+        Resolver {
+            namespace: SmallVec::new(),
+            class_imports: HashMap::new(),
+            function_imports: HashMap::new(),
+            constant_imports: HashMap::new(),
+            names: HashMap::new(),
+            file,
+            class: EnclosingClass::None,
+            function: vec![],
+            method: vec![],
+            magic_constants: HashMap::new(),
+        }
+    }
+
+    /// Enter a namespace. The imports are scoped to a namespace.
+    fn enter_namespace(&mut self, namespace: SmallVec<[Span<'a>; 5]>) {
+        self.namespace = namespace;
+        self.class_imports.clear();
+        self.function_imports.clear();
+        self.constant_imports.clear();
+    }
+
+    /// Prefix a list of segments by the current namespace.
+    fn in_namespace(&self, segments: &[Span<'a>]) -> SmallVec<[Span<'a>; 5]> {
+        let mut name = self.namespace.clone();
+        name.extend(segments.iter().cloned());
+
+        name
+    }
+
+    /// Walk a class-like declaration.
+    fn enter_class(&mut self, class: EnclosingClass, statement: &Statement<'a>) {
+        let class = mem::replace(&mut self.class, class);
+        walk_statement(self, statement);
+        self.class = class;
+    }
+
+    /// Enter a function, with the values of `__FUNCTION__` and
+    /// `__METHOD__`. The previous values are returned, to be given to
+    /// `leave_function`.
+    fn enter_function(&mut self, function: Vec<u8>, method: Vec<u8>) -> (Vec<u8>, Vec<u8>) {
+        (
+            mem::replace(&mut self.function, function),
+            mem::replace(&mut self.method, method),
+        )
+    }
+
+    fn leave_function(&mut self, (function, method): (Vec<u8>, Vec<u8>)) {
+        self.function = function;
+        self.method = method;
+    }
+
+    fn declare(&mut self, name: Span<'a>) {
+        let resolved_name = ResolvedName::FullyQualified(self.in_namespace(&[name]));
+        self.names.insert(name, resolved_name);
+    }
+
+    fn import(&mut self, kind: UseKind, name: &SmallVec<[Span<'a>; 5]>, alias: Option<Span<'a>>) {
+        let alias = match alias.or_else(|| name.last().cloned()) {
+            Some(alias) => alias.as_slice(),
+            None => return,
+        };
+
+        match kind {
+            UseKind::Class => {
+                self.class_imports
+                    .insert(alias.to_ascii_lowercase(), name.clone());
+            }
+
+            UseKind::Function => {
+                self.function_imports
+                    .insert(alias.to_ascii_lowercase(), name.clone());
+            }
+
+            UseKind::Constant => {
+                self.constant_imports.insert(alias.to_vec(), name.clone());
+            }
+        }
+    }
+
+    fn name(&mut self, name: &Name<'a>, kind: UseKind) {
+        let (key, resolved_name) = match *name {
+            Name::FullyQualified(ref segments) => {
+                (segments[0], ResolvedName::FullyQualified(segments.clone()))
+            }
+
+            Name::RelativeQualified(ref segments) => (
+                segments[0],
+                ResolvedName::FullyQualified(self.in_namespace(segments)),
+            ),
+
+            Name::Qualified(ref segments) => {
+                let resolved_name = match self
+                    .class_imports
+                    .get(&segments[0].as_slice().to_ascii_lowercase())
+                {
+                    Some(import) => {
+                        let mut resolved_name = import.clone();
+                        resolved_name.extend(segments[1..].iter().cloned());
+
+                        resolved_name
+                    }
+
+                    None => self.in_namespace(segments),
+                };
+
+                (segments[0], ResolvedName::FullyQualified(resolved_name))
+            }
+
+            Name::Unqualified(segment) => (segment, self.unqualified_name(segment, kind)),
+        };
+
+        self.names.insert(key, resolved_name);
+    }
+
+    fn unqualified_name(&self, name: Span<'a>, kind: UseKind) -> ResolvedName<'a> {
+        let slice = name.as_slice();
+
+        let import = match kind {
+            UseKind::Class => {
+                if let Some(scope) = relative_scope(slice) {
+                    return ResolvedName::Relative(scope);
+                }
+
+                self.class_imports.get(&slice.to_ascii_lowercase())
+            }
+
+            UseKind::Function => self.function_imports.get(&slice.to_ascii_lowercase()),
+
+            UseKind::Constant => self.constant_imports.get(slice),
+        };
+
+        match (import, kind) {
+            (Some(import), _) => ResolvedName::FullyQualified(import.clone()),
+
+            (None, UseKind::Class) => ResolvedName::FullyQualified(self.in_namespace(&[name])),
+
+            (None, _) => {
+                if self.namespace.is_empty() {
+                    ResolvedName::FullyQualified(smallvec![name])
+                } else {
+                    ResolvedName::WithFallback(self.in_namespace(&[name]))
+                }
+            }
+        }
+    }
+
+    /// Resolve a magic constant. Its scope, if any, is expected to be
+    /// resolved already.
+    fn magic_constant(&mut self, constant: &MagicConstant<'a>) {
+        let (span, value) = {
+            let file = match self.file {
+                Some(ref file) => file,
+                None => return,
+            };
+
+            match *constant {
+                MagicConstant::Line(span) => {
+                    let line = Literal::Integer(Token::new(i64::from(span.line), span));
+                    self.magic_constants.insert(span, line);
+
+                    return;
+                }
+
+                MagicConstant::File(span) => (span, path_to_bytes(file)),
+
+                MagicConstant::Directory(span) => (span, path_to_bytes(directory(file))),
+
+                MagicConstant::Function(span) => (span, self.function.clone()),
+
+                MagicConstant::Method(span) => (span, self.method.clone()),
+
+                MagicConstant::Namespace(span) => (span, join(&self.namespace)),
+
+                MagicConstant::Class(span) => match self.class {
+                    EnclosingClass::Class(ref class) => (span, class.clone()),
+                    EnclosingClass::Trait(_) => return,
+                    EnclosingClass::None => (span, vec![]),
+                },
+
+                MagicConstant::Trait(span) => match self.class {
+                    EnclosingClass::Trait(ref trait_name) => (span, trait_name.clone()),
+                    _ => (span, vec![]),
+                },
+
+                MagicConstant::ClassName(ref scope, span) => match self.class_name(scope) {
+                    Some(class) => (span, class),
+                    None => return,
+                },
+            }
+        };
+
+        self.magic_constants
+            .insert(span, Literal::String(Token::new(Cow::from(value), span)));
+    }
+
+    /// Compute the fully qualified name of the class designated by a
+    /// scope, if it is known at compile time. The scope is expected to
+    /// be resolved already.
+    fn class_name(&self, scope: &ScopeResolver<'a>) -> Option<Vec<u8>> {
+        let relative_scope = match *scope {
+            ScopeResolver::ByName(ref name) => {
+                let key = match *name {
+                    Name::Unqualified(segment) => segment,
+
+                    Name::Qualified(ref segments)
+                    | Name::RelativeQualified(ref segments)
+                    | Name::FullyQualified(ref segments) => segments[0],
+                };
+
+                match self.names.get(&key) {
+                    Some(&ResolvedName::FullyQualified(ref segments)) => {
+                        return Some(join(segments))
+                    }
+                    Some(&ResolvedName::Relative(ref scope)) => scope,
+                    _ => return None,
+                }
+            }
+
+            ScopeResolver::ByRelative(ref scope) => scope,
+
+            ScopeResolver::ByExpression(_) => return None,
+        };
+
+        match (relative_scope, &self.class) {
+            (&RelativeScope::ToSelf, &EnclosingClass::Class(ref class)) => Some(class.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for Resolver<'a> {
+    fn visit_statement(&mut self, statement: &Statement<'a>) {
+        match *statement {
+            Statement::Class(ref class) => {
+                self.declare(class.name);
+
+                let name = join(&self.in_namespace(&[class.name]));
+                self.enter_class(EnclosingClass::Class(name), statement);
+            }
+
+            Statement::Enum(ref enumeration) => {
+                self.declare(enumeration.name);
+
+                let name = join(&self.in_namespace(&[enumeration.name]));
+                self.enter_class(EnclosingClass::Class(name), statement);
+            }
+
+            Statement::Function(ref function) => {
+                self.declare(function.name);
+
+                let name = join(&self.in_namespace(&[function.name]));
+                let previous = self.enter_function(name.clone(), name);
+                walk_statement(self, statement);
+                self.leave_function(previous);
+            }
+
+            Statement::Interface(ref interface) => {
+                self.declare(interface.name);
+
+                let name = join(&self.in_namespace(&[interface.name]));
+                self.enter_class(EnclosingClass::Class(name), statement);
+            }
+
+            Statement::Namespace { ref name, ref body } => {
+                self.enter_namespace(name.clone().unwrap_or_else(SmallVec::new));
+
+                if let Some(ref body) = *body {
+                    walk_statements(self, body);
+                    self.enter_namespace(SmallVec::new());
+                }
+            }
+
+            Statement::Trait(ref trait_declaration) => {
+                self.declare(trait_declaration.name);
+
+                let name = join(&self.in_namespace(&[trait_declaration.name]));
+                self.enter_class(EnclosingClass::Trait(name), statement);
+            }
+
+            Statement::Use(ref uses) => {
+                for import in uses {
+                    self.import(import.kind, &import.name, import.alias);
+                }
+            }
+
+            _ => walk_statement(self, statement),
+        }
+    }
+
+    fn visit_class_member(&mut self, member: &ClassMember<'a>) {
+        match *member {
+            ClassMember::Method { name, .. } => {
+                let method = match self.class {
+                    EnclosingClass::Class(ref class) | EnclosingClass::Trait(ref class) => {
+                        let mut method = class.clone();
+                        method.extend_from_slice(tokens::STATIC_CALL);
+                        method.extend_from_slice(name.as_slice());
+
+                        method
+                    }
+
+                    EnclosingClass::None => name.as_slice().to_vec(),
+                };
+                let previous = self.enter_function(name.as_slice().to_vec(), method);
+                walk_class_member(self, member);
+                self.leave_function(previous);
+            }
+
+            _ => walk_class_member(self, member),
+        }
+    }
+
+    fn visit_expression(&mut self, expression: &Expression<'a>) {
+        match *expression {
+            Expression::AnonymousFunction(_) | Expression::ArrowFunction(_) => {
+                let previous = self.enter_function(CLOSURE.to_vec(), CLOSURE.to_vec());
+                walk_expression(self, expression);
+                self.leave_function(previous);
+            }
+
+            Expression::MagicConstant(ref constant) => {
+                walk_expression(self, expression);
+                self.magic_constant(constant);
+            }
+
+            _ => walk_expression(self, expression),
+        }
+    }
+
+    fn visit_name(&mut self, name: &Name<'a>, kind: UseKind) {
+        self.name(name, kind);
+    }
+}
+
+/// The value of `__FUNCTION__` and `__METHOD__` in an anonymous
+/// function or an arrow function.
+const CLOSURE: &[u8] = b"{closure}";
+
+/// Join the segments of a name with the namespace separator.
+fn join(segments: &[Span]) -> Vec<u8> {
+    let mut name = vec![];
+
+    for (index, segment) in segments.iter().enumerate() {
+        if index > 0 {
+            name.extend_from_slice(tokens::NAMESPACE_SEPARATOR);
+        }
+
+        name.extend_from_slice(segment.as_slice());
+    }
+
+    name
+}
+
+/// The directory of a file, i.e. `.` if the path has no parent.
+fn directory(file: &Path) -> &Path {
+    match file.parent() {
+        Some(directory) if directory != Path::new("") => directory,
+        _ => Path::new("."),
+    }
+}
+
+fn path_to_bytes(path: &Path) -> Vec<u8> {
+    path.to_string_lossy().into_owned().into_bytes()
+}
+
+/// Map a class name to a relative scope, if any. These names are
+/// case-insensitive.
+fn relative_scope(name: &[u8]) -> Option<RelativeScope> {
+    if name.eq_ignore_ascii_case(tokens::SELF) {
+        Some(RelativeScope::ToSelf)
+    } else if name.eq_ignore_ascii_case(tokens::PARENT) {
+        Some(RelativeScope::ToParent)
+    } else if name.eq_ignore_ascii_case(tokens::STATIC) {
+        Some(RelativeScope::ToStatic)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::ast::{Literal, RelativeScope};
+    use super::super::rules::program;
+    use super::super::tokens::{Span, Token};
+    use super::{resolve_magic_constants, resolve_names, ResolvedName};
+    use std::borrow::Cow;
+    use std::path::Path;
+
+    #[test]
+    fn case_class_names() {
+        let input = Span::new(b"<?php namespace A\\B; use C\\D, E\\F as G; new D; new G\\H; new I; new \\J; new namespace\\K; new self;");
+        let names = resolve_names(&program(input).unwrap());
+
+        let a = Span::new_at(b"A", 16, 1, 17);
+        let b = Span::new_at(b"B", 18, 1, 19);
+        let c = Span::new_at(b"C", 25, 1, 26);
+        let d = Span::new_at(b"D", 27, 1, 28);
+        let e = Span::new_at(b"E", 30, 1, 31);
+        let f = Span::new_at(b"F", 32, 1, 33);
+
+        assert_eq!(
+            names[&Span::new_at(b"D", 44, 1, 45)],
+            ResolvedName::FullyQualified(smallvec![c, d])
+        );
+        assert_eq!(
+            names[&Span::new_at(b"G", 51, 1, 52)],
+            ResolvedName::FullyQualified(smallvec![e, f, Span::new_at(b"H", 53, 1, 54)])
+        );
+        assert_eq!(
+            names[&Span::new_at(b"I", 60, 1, 61)],
+            ResolvedName::FullyQualified(smallvec![a, b, Span::new_at(b"I", 60, 1, 61)])
+        );
+        assert_eq!(
+            names[&Span::new_at(b"J", 68, 1, 69)],
+            ResolvedName::FullyQualified(smallvec![Span::new_at(b"J", 68, 1, 69)])
+        );
+        assert_eq!(
+            names[&Span::new_at(b"K", 85, 1, 86)],
+            ResolvedName::FullyQualified(smallvec![a, b, Span::new_at(b"K", 85, 1, 86)])
+        );
+        assert_eq!(names.get(&Span::new_at(b"self", 92, 1, 93)), None);
+    }
+
+    #[test]
+    fn case_function_and_constant_names_in_a_namespace() {
+        let input = Span::new(
+            b"<?php namespace A; use function B\\f; use const C\\X; f(); g(); X; Y; \\h();",
+        );
+        let names = resolve_names(&program(input).unwrap());
+
+        let a = Span::new_at(b"A", 16, 1, 17);
+        let g = Span::new_at(b"g", 57, 1, 58);
+        let y = Span::new_at(b"Y", 65, 1, 66);
+
+        assert_eq!(
+            names[&Span::new_at(b"f", 52, 1, 53)],
+            ResolvedName::FullyQualified(smallvec![
+                Span::new_at(b"B", 32, 1, 33),
+                Span::new_at(b"f", 34, 1, 35)
+            ])
+        );
+        assert_eq!(names[&g], ResolvedName::WithFallback(smallvec![a, g]));
+        assert_eq!(
+            names[&Span::new_at(b"X", 62, 1, 63)],
+            ResolvedName::FullyQualified(smallvec![
+                Span::new_at(b"C", 47, 1, 48),
+                Span::new_at(b"X", 49, 1, 50)
+            ])
+        );
+        assert_eq!(names[&y], ResolvedName::WithFallback(smallvec![a, y]));
+        assert_eq!(
+            names[&Span::new_at(b"h", 69, 1, 70)],
+            ResolvedName::FullyQualified(smallvec![Span::new_at(b"h", 69, 1, 70)])
+        );
+    }
+
+    #[test]
+    fn case_function_and_constant_names_in_the_global_namespace() {
+        let input = Span::new(b"<?php g(); Y; use function B\\f; F();");
+        let names = resolve_names(&program(input).unwrap());
+
+        let g = Span::new_at(b"g", 6, 1, 7);
+        let y = Span::new_at(b"Y", 11, 1, 12);
+
+        assert_eq!(names[&g], ResolvedName::FullyQualified(smallvec![g]));
+        assert_eq!(names[&y], ResolvedName::FullyQualified(smallvec![y]));
+        assert_eq!(
+            names[&Span::new_at(b"F", 32, 1, 33)],
+            ResolvedName::FullyQualified(smallvec![
+                Span::new_at(b"B", 27, 1, 28),
+                Span::new_at(b"f", 29, 1, 30)
+            ])
+        );
+    }
+
+    #[test]
+    fn case_case_sensitivity_of_imports() {
+        let input = Span::new(b"<?php namespace A; use const B\\X; use B\\C; new c; x;");
+        let names = resolve_names(&program(input).unwrap());
+
+        let a = Span::new_at(b"A", 16, 1, 17);
+        let x = Span::new_at(b"x", 50, 1, 51);
+
+        assert_eq!(
+            names[&Span::new_at(b"c", 47, 1, 48)],
+            ResolvedName::FullyQualified(smallvec![
+                Span::new_at(b"B", 38, 1, 39),
+                Span::new_at(b"C", 40, 1, 41)
+            ])
+        );
+        assert_eq!(names[&x], ResolvedName::WithFallback(smallvec![a, x]));
+    }
+
+    #[test]
+    fn case_imports_are_scoped_to_namespaces() {
+        let input = Span::new(b"<?php namespace A { use B\\C; new C; } namespace { new C; }");
+        let names = resolve_names(&program(input).unwrap());
+
+        assert_eq!(
+            names[&Span::new_at(b"C", 33, 1, 34)],
+            ResolvedName::FullyQualified(smallvec![
+                Span::new_at(b"B", 24, 1, 25),
+                Span::new_at(b"C", 26, 1, 27)
+            ])
+        );
+        assert_eq!(
+            names[&Span::new_at(b"C", 54, 1, 55)],
+            ResolvedName::FullyQualified(smallvec![Span::new_at(b"C", 54, 1, 55)])
+        );
+    }
+
+    #[test]
+    fn case_declarations_and_types() {
+        let input = Span::new(b"<?php namespace A; use B\\C; class D extends C implements E { public function f(C $x, ?F $y): self { return $x instanceof C; } }");
+        let names = resolve_names(&program(input).unwrap());
+
+        let a = Span::new_at(b"A", 16, 1, 17);
+        let b = Span::new_at(b"B", 23, 1, 24);
+        let c = Span::new_at(b"C", 25, 1, 26);
+        let d = Span::new_at(b"D", 34, 1, 35);
+        let e = Span::new_at(b"E", 57, 1, 58);
+        let f = Span::new_at(b"F", 86, 1, 87);
+
+        assert_eq!(names[&d], ResolvedName::FullyQualified(smallvec![a, d]));
+        assert_eq!(
+            names[&Span::new_at(b"C", 44, 1, 45)],
+            ResolvedName::FullyQualified(smallvec![b, c])
+        );
+        assert_eq!(names[&e], ResolvedName::FullyQualified(smallvec![a, e]));
+        assert_eq!(
+            names[&Span::new_at(b"C", 79, 1, 80)],
+            ResolvedName::FullyQualified(smallvec![b, c])
+        );
+        assert_eq!(names[&f], ResolvedName::FullyQualified(smallvec![a, f]));
+        assert_eq!(
+            names[&Span::new_at(b"self", 93, 1, 94)],
+            ResolvedName::Relative(RelativeScope::ToSelf)
+        );
+        assert_eq!(
+            names[&Span::new_at(b"C", 121, 1, 122)],
+            ResolvedName::FullyQualified(smallvec![b, c])
+        );
+    }
+
+    #[test]
+    fn case_union_and_intersection_types() {
+        let input = Span::new(b"<?php namespace A; function f((B&C)|null $x): static|D {}");
+        let names = resolve_names(&program(input).unwrap());
+
+        let a = Span::new_at(b"A", 16, 1, 17);
+        let b = Span::new_at(b"B", 31, 1, 32);
+        let c = Span::new_at(b"C", 33, 1, 34);
+        let null = Span::new_at(b"null", 36, 1, 37);
+        let d = Span::new_at(b"D", 53, 1, 54);
+
+        assert_eq!(names[&b], ResolvedName::FullyQualified(smallvec![a, b]));
+        assert_eq!(names[&c], ResolvedName::FullyQualified(smallvec![a, c]));
+        assert_eq!(names[&null], ResolvedName::FullyQualified(smallvec![null]));
+        assert_eq!(names[&d], ResolvedName::FullyQualified(smallvec![a, d]));
+        assert_eq!(
+            names[&Span::new_at(b"static", 46, 1, 47)],
+            ResolvedName::Relative(RelativeScope::ToStatic)
+        );
+    }
+
+    #[test]
+    fn case_attributes() {
+        let input = Span::new(b"<?php namespace A; use B\\C; #[C(D)] function f(#[E] $x) {}");
+        let names = resolve_names(&program(input).unwrap());
+
+        let a = Span::new_at(b"A", 16, 1, 17);
+        let d = Span::new_at(b"D", 32, 1, 33);
+        let e = Span::new_at(b"E", 49, 1, 50);
+
+        assert_eq!(
+            names[&Span::new_at(b"C", 30, 1, 31)],
+            ResolvedName::FullyQualified(smallvec![
+                Span::new_at(b"B", 23, 1, 24),
+                Span::new_at(b"C", 25, 1, 26)
+            ])
+        );
+        assert_eq!(names[&d], ResolvedName::WithFallback(smallvec![a, d]));
+        assert_eq!(names[&e], ResolvedName::FullyQualified(smallvec![a, e]));
+    }
+
+    #[test]
+    fn case_arrow_function() {
+        let input = Span::new(b"<?php namespace A; fn (B $x): C => new D;");
+        let names = resolve_names(&program(input).unwrap());
+
+        let a = Span::new_at(b"A", 16, 1, 17);
+        let b = Span::new_at(b"B", 23, 1, 24);
+        let c = Span::new_at(b"C", 30, 1, 31);
+        let d = Span::new_at(b"D", 39, 1, 40);
+
+        assert_eq!(names[&b], ResolvedName::FullyQualified(smallvec![a, b]));
+        assert_eq!(names[&c], ResolvedName::FullyQualified(smallvec![a, c]));
+        assert_eq!(names[&d], ResolvedName::FullyQualified(smallvec![a, d]));
+    }
+
+    #[test]
+    fn case_match() {
+        let input = Span::new(b"<?php namespace A; match (B) { C => D, default => E };");
+        let names = resolve_names(&program(input).unwrap());
+
+        let a = Span::new_at(b"A", 16, 1, 17);
+        let b = Span::new_at(b"B", 26, 1, 27);
+        let c = Span::new_at(b"C", 31, 1, 32);
+        let d = Span::new_at(b"D", 36, 1, 37);
+        let e = Span::new_at(b"E", 50, 1, 51);
+
+        assert_eq!(names[&b], ResolvedName::WithFallback(smallvec![a, b]));
+        assert_eq!(names[&c], ResolvedName::WithFallback(smallvec![a, c]));
+        assert_eq!(names[&d], ResolvedName::WithFallback(smallvec![a, d]));
+        assert_eq!(names[&e], ResolvedName::WithFallback(smallvec![a, e]));
+    }
+
+    #[test]
+    fn case_import_and_yield() {
+        let input = Span::new(
+            b"<?php namespace A; function f() { include B; yield C => D; yield from E; }",
+        );
+        let names = resolve_names(&program(input).unwrap());
+
+        let a = Span::new_at(b"A", 16, 1, 17);
+        let b = Span::new_at(b"B", 42, 1, 43);
+        let c = Span::new_at(b"C", 51, 1, 52);
+        let d = Span::new_at(b"D", 56, 1, 57);
+        let e = Span::new_at(b"E", 70, 1, 71);
+
+        assert_eq!(names[&b], ResolvedName::WithFallback(smallvec![a, b]));
+        assert_eq!(names[&c], ResolvedName::WithFallback(smallvec![a, c]));
+        assert_eq!(names[&d], ResolvedName::WithFallback(smallvec![a, d]));
+        assert_eq!(names[&e], ResolvedName::WithFallback(smallvec![a, e]));
+    }
+
+    #[test]
+    fn case_interpolated_string() {
+        let input = Span::new(b"<?php namespace A; \"{$x[B]} {$y->f(new C)}\";");
+        let names = resolve_names(&program(input).unwrap());
+
+        let a = Span::new_at(b"A", 16, 1, 17);
+        let b = Span::new_at(b"B", 24, 1, 25);
+        let c = Span::new_at(b"C", 39, 1, 40);
+
+        assert_eq!(names.len(), 2);
+        assert_eq!(names[&b], ResolvedName::WithFallback(smallvec![a, b]));
+        assert_eq!(names[&c], ResolvedName::FullyQualified(smallvec![a, c]));
+    }
+
+    #[test]
+    fn case_magic_constants_in_a_method() {
+        let input = Span::new(
+            b"<?php namespace A; class C { function m() { return [__CLASS__, __METHOD__, __FUNCTION__, __NAMESPACE__, __TRAIT__, self::class]; } }",
+        );
+        let constants = resolve_magic_constants(&program(input).unwrap(), Path::new("/a.php"));
+
+        let class = Span::new_at(b"__CLASS__", 52, 1, 53);
+        let method = Span::new_at(b"__METHOD__", 63, 1, 64);
+        let function = Span::new_at(b"__FUNCTION__", 75, 1, 76);
+        let namespace = Span::new_at(b"__NAMESPACE__", 89, 1, 90);
+        let trait_name = Span::new_at(b"__TRAIT__", 104, 1, 105);
+        let self_class = Span::new_at(b"class", 121, 1, 122);
+
+        assert_eq!(constants[&class], string(b"A\\C", class));
+        assert_eq!(constants[&method], string(b"A\\C::m", method));
+        assert_eq!(constants[&function], string(b"m", function));
+        assert_eq!(constants[&namespace], string(b"A", namespace));
+        assert_eq!(constants[&trait_name], string(b"", trait_name));
+        assert_eq!(constants[&self_class], string(b"A\\C", self_class));
+    }
+
+    #[test]
+    fn case_magic_constants_in_a_closure_in_a_trait() {
+        let input = Span::new(
+            b"<?php trait T { function m() { return function () { return [__CLASS__, __TRAIT__, __METHOD__, self::class]; }; } }",
+        );
+        let constants = resolve_magic_constants(&program(input).unwrap(), Path::new("/a.php"));
+
+        let trait_name = Span::new_at(b"__TRAIT__", 71, 1, 72);
+        let method = Span::new_at(b"__METHOD__", 82, 1, 83);
+
+        assert_eq!(constants.len(), 2);
+        assert_eq!(constants[&trait_name], string(b"T", trait_name));
+        assert_eq!(constants[&method], string(b"{closure}", method));
+    }
+
+    #[test]
+    fn case_magic_constants_of_the_file() {
+        let input = Span::new(
+            b"<?php\nuse B\\C;\n__FILE__; __DIR__; __LINE__; C::class; static::class; __FUNCTION__;",
+        );
+        let constants = resolve_magic_constants(&program(input).unwrap(), Path::new("/src/a.php"));
+
+        let file = Span::new_at(b"__FILE__", 15, 3, 1);
+        let directory = Span::new_at(b"__DIR__", 25, 3, 11);
+        let line = Span::new_at(b"__LINE__", 34, 3, 20);
+        let class = Span::new_at(b"class", 47, 3, 33);
+        let function = Span::new_at(b"__FUNCTION__", 69, 3, 55);
+
+        assert_eq!(constants.len(), 5);
+        assert_eq!(constants[&file], string(b"/src/a.php", file));
+        assert_eq!(constants[&directory], string(b"/src", directory));
+        assert_eq!(constants[&line], Literal::Integer(Token::new(3i64, line)));
+        assert_eq!(constants[&class], string(b"B\\C", class));
+        assert_eq!(constants[&function], string(b"", function));
+    }
+
+    #[test]
+    fn case_magic_constants_are_not_resolved_without_a_file() {
+        let input = Span::new(b"<?php __LINE__; C::class;");
+        let names = resolve_names(&program(input).unwrap());
+
+        let c = Span::new_at(b"C", 16, 1, 17);
+
+        assert_eq!(names.len(), 1);
+        assert_eq!(names[&c], ResolvedName::FullyQualified(smallvec![c]));
+    }
+
+    fn string<'a>(value: &'a [u8], span: Span<'a>) -> Literal<'a> {
+        Literal::String(Token::new(Cow::from(value), span))
+    }
+}
@@ -0,0 +1,211 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2017, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of attribute rules.
+//!
+//! Attributes have been introduced in PHP 8.0, after the
+//! specification. An attribute is a class name with optional
+//! arguments, declared in a group like `#[A, B(42)]`.
+
+use super::super::ast::Attribute;
+use super::super::tokens;
+use super::super::tokens::Span;
+use super::expressions::primaries::arguments;
+use super::tokens::qualified_name;
+
+named_attr!(
+    #[doc="
+        Recognize a list of attribute groups, possibly empty. The
+        attributes of all the groups are flattened into a single list.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{Argument, Arguments, Attribute, Expression, Name, Variable};
+        use tagua_parser::rules::attributes::attributes;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            attributes(Span::new(b\"#[A] #[B($x)] function\")),
+            Ok((
+                Span::new_at(b\" function\", 13, 1, 14),
+                vec![
+                    Attribute {
+                        name     : Name::Unqualified(Span::new_at(b\"A\", 2, 1, 3)),
+                        arguments: None
+                    },
+                    Attribute {
+                        name     : Name::Unqualified(Span::new_at(b\"B\", 7, 1, 8)),
+                        arguments: Some(
+                            Arguments::List(vec![
+                                Argument::Positional(Expression::Variable(Variable(Span::new_at(b\"x\", 10, 1, 11))))
+                            ])
+                        )
+                    }
+                ]
+            ))
+        );
+        # }
+        ```
+    "],
+    pub attributes<Span, Vec<Attribute>>,
+    fold_many0!(
+        complete!(first!(attribute_group)),
+        Vec::new(),
+        |mut accumulator: Vec<_>, group| {
+            accumulator.extend(group);
+
+            accumulator
+        }
+    )
+);
+
+named!(
+    attribute_group<Span, Vec<Attribute>>,
+    do_parse!(
+        tag!(tokens::ATTRIBUTE_OPEN) >>
+        accumulator: map!(first!(attribute), |attribute| vec![attribute]) >>
+        result: fold_into_vector_many0!(
+            preceded!(
+                first!(tag!(tokens::COMMA)),
+                first!(attribute)
+            ),
+            accumulator
+        ) >>
+        opt!(complete!(first!(tag!(tokens::COMMA)))) >>
+        first!(tag!(tokens::ATTRIBUTE_CLOSE)) >>
+        (result)
+    )
+);
+
+named!(
+    attribute<Span, Attribute>,
+    do_parse!(
+        name: qualified_name >>
+        arguments: opt!(complete!(first!(arguments))) >>
+        (
+            Attribute {
+                name     : name,
+                arguments: arguments
+            }
+        )
+    )
+);
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::ast::{Argument, Arguments, Attribute, Expression, Name, Variable};
+    use super::super::super::tokens::Span;
+    use super::attributes;
+
+    #[test]
+    fn case_no_attribute() {
+        let input = Span::new(b"function");
+        let output = Ok((input, vec![]));
+
+        assert_eq!(attributes(input), output);
+    }
+
+    #[test]
+    fn case_attribute_without_arguments() {
+        let input = Span::new(b"#[A]");
+        let output = Ok((
+            Span::new_at(b"", 4, 1, 5),
+            vec![Attribute {
+                name: Name::Unqualified(Span::new_at(b"A", 2, 1, 3)),
+                arguments: None,
+            }],
+        ));
+
+        assert_eq!(attributes(input), output);
+    }
+
+    #[test]
+    fn case_group_of_attributes_with_trailing_comma() {
+        let input = Span::new(b"#[ A(), \\B(x: $y), ]");
+        let output = Ok((
+            Span::new_at(b"", 20, 1, 21),
+            vec![
+                Attribute {
+                    name: Name::Unqualified(Span::new_at(b"A", 3, 1, 4)),
+                    arguments: Some(Arguments::List(vec![])),
+                },
+                Attribute {
+                    name: Name::FullyQualified(smallvec![Span::new_at(b"B", 9, 1, 10)]),
+                    arguments: Some(Arguments::List(vec![Argument::Named(
+                        Span::new_at(b"x", 11, 1, 12),
+                        Expression::Variable(Variable(Span::new_at(b"y", 15, 1, 16))),
+                    )])),
+                },
+            ],
+        ));
+
+        assert_eq!(attributes(input), output);
+    }
+
+    #[test]
+    fn case_groups_of_attributes_separated_by_comments() {
+        let input = Span::new(b"#[A] # comment\n/* comment */ #[B]");
+        let output = Ok((
+            Span::new_at(b"", 33, 2, 19),
+            vec![
+                Attribute {
+                    name: Name::Unqualified(Span::new_at(b"A", 2, 1, 3)),
+                    arguments: None,
+                },
+                Attribute {
+                    name: Name::Unqualified(Span::new_at(b"B", 31, 2, 17)),
+                    arguments: None,
+                },
+            ],
+        ));
+
+        assert_eq!(attributes(input), output);
+    }
+
+    #[test]
+    fn case_invalid_empty_group() {
+        let input = Span::new(b"#[]");
+        let output = Ok((input, vec![]));
+
+        assert_eq!(attributes(input), output);
+    }
+
+    #[test]
+    fn case_invalid_unclosed_group() {
+        let input = Span::new(b"#[A function");
+        let output = Ok((input, vec![]));
+
+        assert_eq!(attributes(input), output);
+    }
+}
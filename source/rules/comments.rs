@@ -43,6 +43,9 @@ named_attr!(
         Recognize all kind of comments.
 
         A comment can be a single line (`//` or `#`) or a delimited block (`/* … */`).
+        A single line comment ends at the end of the line, or right before
+        a close tag (`?>`). A `#` followed by `[` is not a comment but the
+        beginning of an attribute group (`#[…]`).
 
         # Examples
 
@@ -80,8 +83,14 @@ named!(
 named!(
     comment_single_line<Span, Span>,
     preceded!(
-        alt!(tag!(tokens::INLINE_COMMENT) | tag!(tokens::INLINE_COMMENT_HASH)),
-        regex!(r"^(?-u).*?(\r\n|\n|$)")
+        alt!(
+            tag!(tokens::INLINE_COMMENT)
+          | terminated!(
+                tag!(tokens::INLINE_COMMENT_HASH),
+                not!(complete!(tag!(tokens::LEFT_SQUARE_BRACKET)))
+            )
+        ),
+        regex!(r"^(?-u)([^?\n]|\?+[^?>\n])*(\?+(\n|$)|\n)?")
     )
 );
 
@@ -148,6 +157,30 @@ mod tests {
         assert_eq!(comment(input), output);
     }
 
+    #[test]
+    fn case_comment_single_line_double_slash_before_close_tag() {
+        let input = Span::new(b"// foo ?>bar");
+        let output = Ok((
+            Span::new_at(b"?>bar", 7, 1, 8),
+            Span::new_at(b" foo ", 2, 1, 3),
+        ));
+
+        assert_eq!(comment_single_line(input), output);
+        assert_eq!(comment(input), output);
+    }
+
+    #[test]
+    fn case_comment_single_line_double_slash_with_question_marks() {
+        let input = Span::new(b"// foo? bar??\nbaz");
+        let output = Ok((
+            Span::new_at(b"baz", 14, 2, 1),
+            Span::new_at(b" foo? bar??\n", 2, 1, 3),
+        ));
+
+        assert_eq!(comment_single_line(input), output);
+        assert_eq!(comment(input), output);
+    }
+
     #[test]
     fn case_comment_single_line_hash_empty() {
         let input = Span::new(b"#");
@@ -205,6 +238,29 @@ mod tests {
         assert_eq!(comment(input), output);
     }
 
+    #[test]
+    fn case_comment_single_line_hash_before_close_tag() {
+        let input = Span::new(b"# foo?>");
+        let output = Ok((Span::new_at(b"?>", 5, 1, 6), Span::new_at(b" foo", 1, 1, 2)));
+
+        assert_eq!(comment_single_line(input), output);
+        assert_eq!(comment(input), output);
+    }
+
+    #[test]
+    fn case_invalid_comment_single_line_hash_attribute() {
+        let input = Span::new(b"#[A]");
+
+        assert_eq!(
+            comment_single_line(input),
+            Err(Error::Error(Context::Code(input, ErrorKind::Alt)))
+        );
+        assert_eq!(
+            comment(input),
+            Err(Error::Error(Context::Code(input, ErrorKind::Alt)))
+        );
+    }
+
     #[test]
     fn case_comment_delimited_empty() {
         let input = Span::new(b"/**/xyz");
@@ -36,15 +36,72 @@
 //! operators
 //! section](https://github.com/php/php-langspec/blob/master/spec/10-expressions.md#assignment-operators).
 
-use super::super::super::ast::{BinaryOperator, Expression, NAryOperation, TernaryOperator};
+use super::super::super::ast::{
+    BinaryOperator, CastType, Expression, NAryOperation, TernaryOperator, UnaryOperator,
+};
 use super::super::super::tokens;
 use super::super::super::tokens::Span;
 use super::super::expressions::expression;
 use super::super::tokens::qualified_name;
+use super::primaries::{into_destructuring_target, primary};
+
+named_attr!(
+    #[doc="
+        Recognize all logical expressions, i.e. expressions with the
+        `or`, `xor`, and `and` operators, that have the lowest
+        precedence.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{BinaryOperator, Expression, NAryOperation, Variable};
+        use tagua_parser::rules::expressions::assignment::logical_expression;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            logical_expression(Span::new(b\"$x = $y or $z\")),
+            Ok((
+                Span::new_at(b\"\", 13, 1, 14),
+                Expression::NAryOperation(
+                    NAryOperation::Binary {
+                        operator     : BinaryOperator::LogicalOr,
+                        left_operand : Box::new(
+                            NAryOperation::Binary {
+                                operator     : BinaryOperator::Assignment,
+                                left_operand : Box::new(
+                                    NAryOperation::Nullary(Box::new(Expression::Variable(Variable(Span::new_at(b\"x\", 1, 1, 2)))))
+                                ),
+                                right_operand: Box::new(
+                                    NAryOperation::Nullary(Box::new(Expression::Variable(Variable(Span::new_at(b\"y\", 6, 1, 7)))))
+                                )
+                            }
+                        ),
+                        right_operand: Box::new(
+                            NAryOperation::Nullary(Box::new(Expression::Variable(Variable(Span::new_at(b\"z\", 12, 1, 13)))))
+                        )
+                    }
+                )
+            ))
+        );
+        # }
+        ```
+    "],
+    pub logical_expression<Span, Expression>,
+    map_res!(
+        logical_or_keyword,
+        nary_expression_mapper
+    )
+);
 
 named_attr!(
     #[doc="
         Recognize all assignment expressions.
+
+        An assignment expression is a conditional expression, where
+        the assignments themselves are recognized on their left
+        operand, see the `leaf` parser.
     "],
     pub assignment<Span, Expression>,
     map_res!(
@@ -55,7 +112,17 @@ named_attr!(
 
 #[inline]
 fn nary_expression_mapper<'a>(nary_operation: NAryOperation<'a>) -> Result<Expression<'a>, ()> {
-    Ok(Expression::NAryOperation(nary_operation))
+    Ok(into_expression(nary_operation))
+}
+
+/// Unwrap a nullary operation into its expression, so that an
+/// expression without operator is not represented as an operation.
+#[inline]
+fn into_expression(nary_operation: NAryOperation) -> Expression {
+    match nary_operation {
+        NAryOperation::Nullary(expression) => *expression,
+        nary_operation => Expression::NAryOperation(nary_operation),
+    }
 }
 
 named!(
@@ -126,6 +193,34 @@ macro_rules! left_to_right_binary_operation {
         );
     );
 
+    (
+        $parser_name:ident:
+        $operand:ident with
+        keyword $operator_token:ident as $operator_representation:ident
+    ) => (
+        named!(
+            $parser_name<Span, NAryOperation>,
+            do_parse!(
+                left_operand: $operand >>
+                result: fold_many0!(
+                    preceded!(
+                        first!(complete!(keyword!(tokens::$operator_token))),
+                        first!($operand)
+                    ),
+                    left_operand,
+                    |accumulator, right_operand| {
+                        NAryOperation::Binary {
+                            operator     : BinaryOperator::$operator_representation,
+                            left_operand : Box::new(accumulator),
+                            right_operand: Box::new(right_operand)
+                        }
+                    }
+                ) >>
+                (result)
+            )
+        );
+    );
+
     (
         $parser_name:ident:
         $operand:ident with
@@ -169,31 +264,53 @@ macro_rules! right_to_left_binary_operation {
         $parser_name:ident:
         $operand:ident with
         $operator_token:ident as $operator_representation:ident
+    ) => (
+        right_to_left_binary_operation!(
+            $parser_name:
+            $operand with
+            $operator_token as $operator_representation
+            to $operand
+        );
+    );
+
+    (
+        $parser_name:ident:
+        $operand:ident with
+        $operator_token:ident as $operator_representation:ident
+        to $right_operand:ident
     ) => (
         named!(
             $parser_name<Span, NAryOperation>,
-            alt_complete!(
-                do_parse!(
-                    left_operand: $operand >>
-                    right_operand: preceded!(
+            do_parse!(
+                left_operand: $operand >>
+                right_operand: opt!(
+                    preceded!(
                         first!(tag!(tokens::$operator_token)),
-                        first!($parser_name)
-                    ) >>
-                    (
-                        NAryOperation::Binary {
-                            operator     : BinaryOperator::$operator_representation,
-                            left_operand : Box::new(left_operand),
-                            right_operand: Box::new(right_operand)
-                        }
+                        first!($right_operand)
                     )
+                ) >>
+                (
+                    match right_operand {
+                        Some(right_operand) => {
+                            NAryOperation::Binary {
+                                operator     : BinaryOperator::$operator_representation,
+                                left_operand : Box::new(left_operand),
+                                right_operand: Box::new(right_operand)
+                            }
+                        },
+
+                        None => left_operand
+                    }
                 )
-              | $operand
             )
         );
     )
 }
 
-right_to_left_binary_operation!(coalesce   : logical_or  with COALESCE    as Coalesce);
+left_to_right_binary_operation!(logical_or_keyword : logical_xor_keyword with keyword OR  as LogicalOr);
+left_to_right_binary_operation!(logical_xor_keyword: logical_and_keyword with keyword XOR as LogicalXor);
+left_to_right_binary_operation!(logical_and_keyword: conditional         with keyword AND as LogicalAnd);
+right_to_left_binary_operation!(coalesce   : logical_or  with COALESCE    as Coalesce to coalesce);
 left_to_right_binary_operation!(logical_or : logical_and with BOOLEAN_OR  as LogicalOr);
 left_to_right_binary_operation!(logical_and: bitwise_or  with BOOLEAN_AND as LogicalAnd);
 left_to_right_binary_operation!(bitwise_or : bitwise_xor with BITWISE_OR  as BitwiseOr);
@@ -207,20 +324,21 @@ left_to_right_binary_operation!(
         NOT_IDENTICAL as NotIdentical,
         EQUAL         as Equal,
         NOT_EQUAL     as NotEqual,
+        COMPARE       as Comparison,
         NOT_EQUAL_BIS as NotEqual
     )
 );
 left_to_right_binary_operation!(
     relational:
-    shift with
+    concatenation with
     (
-        COMPARE                  as Comparison,
         LESS_THAN_OR_EQUAL_TO    as LessThanOrEqualTo,
         GREATER_THAN_OR_EQUAL_TO as GreaterThanOrEqualTo,
         LESS_THAN                as LessThan,
         GREATER_THAN             as GreaterThan
     )
 );
+left_to_right_binary_operation!(concatenation: shift with CONCATENATE as Dot);
 left_to_right_binary_operation!(
     shift:
     additive with
@@ -233,14 +351,13 @@ left_to_right_binary_operation!(
     additive:
     multiplicative with
     (
-        ADD         as Plus,
-        SUBSTRACT   as Minus,
-        CONCATENATE as Dot
+        ADD       as Plus,
+        SUBSTRACT as Minus
     )
 );
 left_to_right_binary_operation!(
     multiplicative:
-    instanceof with
+    logical_not with
     (
         MULTIPLY as Multiplication,
         DIVIDE   as Division,
@@ -249,57 +366,283 @@ left_to_right_binary_operation!(
 );
 
 named!(
-    instanceof<Span, NAryOperation>,
+    logical_not<Span, NAryOperation>,
     alt_complete!(
         do_parse!(
-            subject: expression >>
-            type_designator: preceded!(
-                first!(tag!(tokens::INSTANCEOF)),
+            tag!(tokens::BOOLEAN_NOT) >>
+            operand: first!(logical_not) >>
+            (
+                NAryOperation::Unary {
+                    operator: UnaryOperator::Negate,
+                    operand : Box::new(operand)
+                }
+            )
+        )
+      | instanceof
+    )
+);
+
+named!(
+    instanceof<Span, NAryOperation>,
+    do_parse!(
+        left_operand: unary_operation >>
+        result: fold_many0!(
+            preceded!(
+                first!(complete!(keyword!(tokens::INSTANCEOF))),
                 first!(
-                    alt!(
+                    alt_complete!(
                         qualified_name => {
                             |qualified_name| {
                                 Expression::Name(qualified_name)
                             }
                         }
-                      | expression
+                      | primary
                     )
                 )
-            ) >>
-            (
+            ),
+            left_operand,
+            |accumulator, type_designator| {
                 NAryOperation::Binary {
                     operator     : BinaryOperator::InstanceOf,
-                    left_operand : Box::new(NAryOperation::Nullary(Box::new(subject))),
+                    left_operand : Box::new(accumulator),
                     right_operand: Box::new(NAryOperation::Nullary(Box::new(type_designator)))
                 }
+            }
+        ) >>
+        (result)
+    )
+);
+
+named_attr!(
+    #[doc="
+        Recognize a unary operation, i.e. a prefix operator followed by
+        a unary operation, or a power operation.
+
+        The prefix operators are `++`, `--`, `+`, `-`, `@`, `~`, and
+        the casts. They are right-associative, and they have a lower
+        precedence than the power operator, e.g. `-$x ** 2` is
+        equivalent to `-($x ** 2)`. The postfix operators are
+        recognized on the leaves, see the `leaf` parser.
+
+        The operand of a prefix operator can be a logical not, e.g.
+        `(bool) !$x`, see the `unary_operand` parser.
+    "],
+    unary_operation<Span, NAryOperation>,
+    alt_complete!(
+        do_parse!(
+            operator: prefix_operator >>
+            operand: first!(unary_operand) >>
+            (
+                NAryOperation::Unary {
+                    operator: operator,
+                    operand : Box::new(operand)
+                }
             )
         )
-      | unary_operation
+      | power
     )
 );
 
-named!(
-    unary_operation<Span, NAryOperation>,
-    call!(leaf)
+named_attr!(
+    #[doc="
+        Recognize the operand of a prefix operator, or the right
+        operand of the power operator.
+
+        The logical not has a lower precedence than `instanceof`, so
+        it is not a unary operation. It is recognized when it directly
+        follows an operator, e.g. `-!$x` is equivalent to `-(!$x)`,
+        and `2 ** !$x` to `2 ** (!$x)`.
+    "],
+    unary_operand<Span, NAryOperation>,
+    alt_complete!(
+        unary_operation
+      | logical_not
+    )
 );
 
 named!(
+    prefix_operator<Span, UnaryOperator>,
+    alt_complete!(
+        tag!(tokens::INCREMENT)     => { |_| UnaryOperator::Increment }
+      | tag!(tokens::DECREMENT)     => { |_| UnaryOperator::Decrement }
+      | tag!(tokens::ADD)           => { |_| UnaryOperator::Plus }
+      | tag!(tokens::SUBSTRACT)     => { |_| UnaryOperator::Minus }
+      | tag!(tokens::ERROR_CONTROL) => { |_| UnaryOperator::ErrorControl }
+      | tag!(tokens::BITWISE_NOT)   => { |_| UnaryOperator::BitwiseComplement }
+      | cast                        => { |cast_type| UnaryOperator::Cast(cast_type) }
+    )
+);
+
+named_attr!(
+    #[doc="
+        Recognize a cast, e.g. `(int)`.
+
+        The cast type is case-insensitive, and it can be surrounded by
+        spaces or tabulations, e.g. `( INT )`. Aliases, like `bool`
+        and `boolean`, are represented by the same cast type.
+    "],
+    cast<Span, CastType>,
+    delimited!(
+        terminated!(
+            tag!(tokens::LEFT_PARENTHESIS),
+            opt!(is_a!(" \t"))
+        ),
+        alt_complete!(
+            keyword!(b"array")   => { |_| CastType::Array }
+          | keyword!(b"binary")  => { |_| CastType::Binary }
+          | keyword!(b"boolean") => { |_| CastType::Boolean }
+          | keyword!(b"bool")    => { |_| CastType::Boolean }
+          | keyword!(b"double")  => { |_| CastType::Float }
+          | keyword!(b"float")   => { |_| CastType::Float }
+          | keyword!(b"real")    => { |_| CastType::Float }
+          | keyword!(b"integer") => { |_| CastType::Integer }
+          | keyword!(b"int")     => { |_| CastType::Integer }
+          | keyword!(b"object")  => { |_| CastType::Object }
+          | keyword!(b"string")  => { |_| CastType::String }
+        ),
+        preceded!(
+            opt!(is_a!(" \t")),
+            tag!(tokens::RIGHT_PARENTHESIS)
+        )
+    )
+);
+
+right_to_left_binary_operation!(power: leaf with POW as Power to unary_operand);
+
+named_attr!(
+    #[doc="
+        Recognize the leaf of an operation tree, i.e. a primary
+        expression, optionally followed by a postfix operator (`++` or
+        `--`), or by an assignment operator.
+
+        The assignment is recognized here because its left operand
+        is a primary expression, while its right operand is an
+        assignment expression (with a lower precedence), e.g. `!$x =
+        f()` is equivalent to `!($x = f())`. The assignment is
+        right-associative.
+
+        A list followed by an assignment operator by value is a
+        destructuring target, e.g. `[$x, $y] = $z`, and not an array.
+    "],
     leaf<Span, NAryOperation>,
-    map_res!(
-        expression,
-        |expression| -> Result<NAryOperation, ()> {
-            Ok(NAryOperation::Nullary(Box::new(expression)))
+    map_opt!(
+        do_parse!(
+            left_operand: primary >>
+            postfix_operator: opt!(
+                first!(
+                    alt_complete!(
+                        tag!(tokens::INCREMENT) => { |_| UnaryOperator::PostIncrement }
+                      | tag!(tokens::DECREMENT) => { |_| UnaryOperator::PostDecrement }
+                    )
+                )
+            ) >>
+            assignment: cond!(
+                postfix_operator.is_none(),
+                alt_complete!(
+                    do_parse!(
+                        first!(assignment_operator_by_value) >>
+                        first!(tag!(tokens::REFERENCE)) >>
+                        right_operand: first!(leaf) >>
+                        (
+                            BinaryOperator::Assignment,
+                            NAryOperation::Nullary(
+                                Box::new(
+                                    Expression::Reference(
+                                        Box::new(into_expression(right_operand))
+                                    )
+                                )
+                            )
+                        )
+                    )
+                  | do_parse!(
+                        operator: first!(assignment_operator) >>
+                        right_operand: first!(conditional) >>
+                        (operator, right_operand)
+                    )
+                )
+            ) >>
+            ((left_operand, postfix_operator, assignment))
+        ),
+        |(operand, postfix_operator, assignment)| into_leaf(operand, postfix_operator, assignment)
+    )
+);
+
+#[inline]
+fn into_leaf<'a>(
+    operand: Expression<'a>,
+    postfix_operator: Option<UnaryOperator>,
+    assignment: Option<(BinaryOperator, NAryOperation<'a>)>,
+) -> Option<NAryOperation<'a>> {
+    // An array on the left of an assignment is a destructuring target.
+    let operand = match (operand, &assignment) {
+        (operand @ Expression::Array(_), &Some((BinaryOperator::Assignment, _))) => {
+            into_destructuring_target(operand)?
         }
+
+        (operand, _) => operand,
+    };
+    let operand = NAryOperation::Nullary(Box::new(operand));
+
+    Some(match (postfix_operator, assignment) {
+        (Some(operator), _) => NAryOperation::Unary {
+            operator,
+            operand: Box::new(operand),
+        },
+
+        (None, Some((operator, right_operand))) => NAryOperation::Binary {
+            operator,
+            left_operand: Box::new(operand),
+            right_operand: Box::new(right_operand),
+        },
+
+        (None, None) => operand,
+    })
+}
+
+named!(
+    assignment_operator<Span, BinaryOperator>,
+    alt_complete!(
+        tag!(tokens::POW_AND_ASSIGN)                 => { |_| BinaryOperator::PowerAssignment }
+      | tag!(tokens::COALESCE_AND_ASSIGN)            => { |_| BinaryOperator::CoalesceAssignment }
+      | tag!(tokens::BITWISE_LEFT_SHIFT_AND_ASSIGN)  => { |_| BinaryOperator::BitwiseShiftLeftAssignment }
+      | tag!(tokens::BITWISE_RIGHT_SHIFT_AND_ASSIGN) => { |_| BinaryOperator::BitwiseShiftRightAssignment }
+      | tag!(tokens::ADD_AND_ASSIGN)                 => { |_| BinaryOperator::PlusAssignment }
+      | tag!(tokens::SUBSTRACT_AND_ASSIGN)           => { |_| BinaryOperator::MinusAssignment }
+      | tag!(tokens::MULTIPLY_AND_ASSIGN)            => { |_| BinaryOperator::MultiplicationAssignment }
+      | tag!(tokens::DIVIDE_AND_ASSIGN)              => { |_| BinaryOperator::DivisionAssignment }
+      | tag!(tokens::CONCATENATE_AND_ASSIGN)         => { |_| BinaryOperator::DotAssignment }
+      | tag!(tokens::MODULO_AND_ASSIGN)              => { |_| BinaryOperator::ModuloAssignment }
+      | tag!(tokens::BITWISE_AND_AND_ASSIGN)         => { |_| BinaryOperator::BitwiseAndAssignment }
+      | tag!(tokens::BITWISE_OR_AND_ASSIGN)          => { |_| BinaryOperator::BitwiseOrAssignment }
+      | tag!(tokens::BITWISE_XOR_AND_ASSIGN)         => { |_| BinaryOperator::BitwiseXorAssignment }
+      | assignment_operator_by_value                 => { |_| BinaryOperator::Assignment }
+    )
+);
+
+named!(
+    assignment_operator_by_value<Span, Span>,
+    terminated!(
+        tag!(tokens::ASSIGN),
+        not!(
+            alt_complete!(
+                tag!(tokens::ASSIGN)
+              | tag!(tokens::GREATER_THAN)
+            )
+        )
     )
 );
 
 #[cfg(test)]
 mod tests {
     use super::super::super::super::ast::{
-        BinaryOperator, Expression, Literal, NAryOperation, Name, TernaryOperator, Variable,
+        Arguments, ArrayItem, BinaryOperator, CastType, DereferencableExpression, Expression,
+        Literal, MemberName, NAryOperation, Name, TernaryOperator, UnaryOperator, Variable,
     };
+    use super::super::super::super::internal::{Context, Error, ErrorKind};
     use super::super::super::super::tokens::{Span, Token};
-    use super::assignment;
+    use super::super::expression;
+    use super::{assignment, logical_expression};
+    use std::borrow::Cow;
 
     /// Build a nullary operation.
     macro_rules! nullary_operation {
@@ -308,6 +651,23 @@ mod tests {
         };
     }
 
+    /// Build a unary operation.
+    macro_rules! unary_operation {
+        ($operator:ident($argument:expr), $operand:expr) => {
+            NAryOperation::Unary {
+                operator: UnaryOperator::$operator($argument),
+                operand: Box::new($operand),
+            }
+        };
+
+        ($operator:ident, $operand:expr) => {
+            NAryOperation::Unary {
+                operator: UnaryOperator::$operator,
+                operand: Box::new($operand),
+            }
+        };
+    }
+
     /// Build a binary operation.
     macro_rules! binary_operation {
         ($operator:ident, $left_operand:expr, $right_operand:expr) => {
@@ -338,6 +698,64 @@ mod tests {
         };
     }
 
+    /// Build a variable expression.
+    macro_rules! variable {
+        ($span:expr) => {
+            Expression::Variable(Variable($span))
+        };
+    }
+
+    /// Build a test case for a cast.
+    macro_rules! test_cast {
+        ($test_case_name:ident: ($string:expr, $cast_type:ident)) => {
+            #[test]
+            fn $test_case_name() {
+                let input = Span::new($string);
+                let length = $string.len();
+                let output = Ok((
+                    Span::new_at(b"", length, 1, length as u32 + 1),
+                    Expression::NAryOperation(unary_operation!(
+                        Cast(CastType::$cast_type),
+                        nullary_operation!(variable!(Span::new_at(
+                            b"x",
+                            length - 1,
+                            1,
+                            length as u32
+                        )))
+                    )),
+                ));
+
+                assert_eq!(assignment(input), output);
+                assert_eq!(expression(input), output);
+            }
+        };
+    }
+
+    /// Build a test case for an assignment operator.
+    macro_rules! test_assignment_operator {
+        ($test_case_name:ident: ($string:expr, $operator:ident)) => {
+            #[test]
+            fn $test_case_name() {
+                let input = Span::new($string);
+                let length = $string.len();
+                let output = Ok((
+                    Span::new_at(b"", length, 1, length as u32 + 1),
+                    Expression::NAryOperation(binary_operation!(
+                        $operator,
+                        nullary_operation!(variable!(Span::new_at(b"x", 1, 1, 2))),
+                        nullary_operation!(integer!(
+                            7,
+                            Span::new_at(b"7", length - 1, 1, length as u32)
+                        ))
+                    )),
+                ));
+
+                assert_eq!(assignment(input), output);
+                assert_eq!(expression(input), output);
+            }
+        };
+    }
+
     #[test]
     fn case_conditional() {
         let input = Span::new(b"1 ? 2 : 3 ? 4 : 5");
@@ -867,4 +1285,708 @@ mod tests {
 
         assert_eq!(assignment(input), output);
     }
+
+    #[test]
+    fn case_expression_without_operator() {
+        let input = Span::new(b"42");
+        let output = Ok((Span::new_at(b"", 2, 1, 3), integer!(42, input)));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_precedence_additive_multiplicative() {
+        let input = Span::new(b"1 + 2 * 3 - 4");
+        let output = Ok((
+            Span::new_at(b"", 13, 1, 14),
+            Expression::NAryOperation(binary_operation!(
+                Minus,
+                binary_operation!(
+                    Plus,
+                    nullary_operation!(integer!(1, Span::new(b"1"))),
+                    binary_operation!(
+                        Multiplication,
+                        nullary_operation!(integer!(2, Span::new_at(b"2", 4, 1, 5))),
+                        nullary_operation!(integer!(3, Span::new_at(b"3", 8, 1, 9)))
+                    )
+                ),
+                nullary_operation!(integer!(4, Span::new_at(b"4", 12, 1, 13)))
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_precedence_concatenation_additive() {
+        let input = Span::new(b"1 . 2 + 3");
+        let output = Ok((
+            Span::new_at(b"", 9, 1, 10),
+            Expression::NAryOperation(binary_operation!(
+                Dot,
+                nullary_operation!(integer!(1, Span::new(b"1"))),
+                binary_operation!(
+                    Plus,
+                    nullary_operation!(integer!(2, Span::new_at(b"2", 4, 1, 5))),
+                    nullary_operation!(integer!(3, Span::new_at(b"3", 8, 1, 9)))
+                )
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_precedence_parenthesis() {
+        let input = Span::new(b"(1 + 2) * 3");
+        let output = Ok((
+            Span::new_at(b"", 11, 1, 12),
+            Expression::NAryOperation(binary_operation!(
+                Multiplication,
+                nullary_operation!(Expression::NAryOperation(binary_operation!(
+                    Plus,
+                    nullary_operation!(integer!(1, Span::new_at(b"1", 1, 1, 2))),
+                    nullary_operation!(integer!(2, Span::new_at(b"2", 5, 1, 6)))
+                ))),
+                nullary_operation!(integer!(3, Span::new_at(b"3", 10, 1, 11)))
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_power() {
+        let input = Span::new(b"2 ** 3 ** 4");
+        let output = Ok((
+            Span::new_at(b"", 11, 1, 12),
+            Expression::NAryOperation(binary_operation!(
+                Power,
+                nullary_operation!(integer!(2, Span::new(b"2"))),
+                binary_operation!(
+                    Power,
+                    nullary_operation!(integer!(3, Span::new_at(b"3", 5, 1, 6))),
+                    nullary_operation!(integer!(4, Span::new_at(b"4", 10, 1, 11)))
+                )
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_power_multiplicative() {
+        let input = Span::new(b"2 * 3 ** 4");
+        let output = Ok((
+            Span::new_at(b"", 10, 1, 11),
+            Expression::NAryOperation(binary_operation!(
+                Multiplication,
+                nullary_operation!(integer!(2, Span::new(b"2"))),
+                binary_operation!(
+                    Power,
+                    nullary_operation!(integer!(3, Span::new_at(b"3", 4, 1, 5))),
+                    nullary_operation!(integer!(4, Span::new_at(b"4", 9, 1, 10)))
+                )
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_logical_not() {
+        let input = Span::new(b"!!1 * 2");
+        let output = Ok((
+            Span::new_at(b"", 7, 1, 8),
+            Expression::NAryOperation(binary_operation!(
+                Multiplication,
+                unary_operation!(
+                    Negate,
+                    unary_operation!(
+                        Negate,
+                        nullary_operation!(integer!(1, Span::new_at(b"1", 2, 1, 3)))
+                    )
+                ),
+                nullary_operation!(integer!(2, Span::new_at(b"2", 6, 1, 7)))
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_logical_not_instanceof() {
+        let input = Span::new(b"!$x instanceof C");
+        let output = Ok((
+            Span::new_at(b"", 16, 1, 17),
+            Expression::NAryOperation(unary_operation!(
+                Negate,
+                binary_operation!(
+                    InstanceOf,
+                    nullary_operation!(variable!(Span::new_at(b"x", 2, 1, 3))),
+                    nullary_operation!(Expression::Name(Name::Unqualified(Span::new_at(
+                        b"C", 15, 1, 16
+                    ))))
+                )
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_instanceof_left_operand() {
+        let input = Span::new(b"1 + $x INSTANCEOF C");
+        let output = Ok((
+            Span::new_at(b"", 19, 1, 20),
+            Expression::NAryOperation(binary_operation!(
+                Plus,
+                nullary_operation!(integer!(1, Span::new(b"1"))),
+                binary_operation!(
+                    InstanceOf,
+                    nullary_operation!(variable!(Span::new_at(b"x", 5, 1, 6))),
+                    nullary_operation!(Expression::Name(Name::Unqualified(Span::new_at(
+                        b"C", 18, 1, 19
+                    ))))
+                )
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_logical_keywords() {
+        let input = Span::new(b"1 or 2 xor 3 AND 4");
+        let output = Ok((
+            Span::new_at(b"", 18, 1, 19),
+            Expression::NAryOperation(binary_operation!(
+                LogicalOr,
+                nullary_operation!(integer!(1, Span::new(b"1"))),
+                binary_operation!(
+                    LogicalXor,
+                    nullary_operation!(integer!(2, Span::new_at(b"2", 5, 1, 6))),
+                    binary_operation!(
+                        LogicalAnd,
+                        nullary_operation!(integer!(3, Span::new_at(b"3", 11, 1, 12))),
+                        nullary_operation!(integer!(4, Span::new_at(b"4", 17, 1, 18)))
+                    )
+                )
+            )),
+        ));
+
+        assert_eq!(logical_expression(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_logical_keyword_and_logical_operator() {
+        let input = Span::new(b"1 and 2 || 3");
+        let output = Ok((
+            Span::new_at(b"", 12, 1, 13),
+            Expression::NAryOperation(binary_operation!(
+                LogicalAnd,
+                nullary_operation!(integer!(1, Span::new(b"1"))),
+                binary_operation!(
+                    LogicalOr,
+                    nullary_operation!(integer!(2, Span::new_at(b"2", 6, 1, 7))),
+                    nullary_operation!(integer!(3, Span::new_at(b"3", 11, 1, 12)))
+                )
+            )),
+        ));
+
+        assert_eq!(logical_expression(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_assignment() {
+        let input = Span::new(b"$x = $y = 7");
+        let output = Ok((
+            Span::new_at(b"", 11, 1, 12),
+            Expression::NAryOperation(binary_operation!(
+                Assignment,
+                nullary_operation!(variable!(Span::new_at(b"x", 1, 1, 2))),
+                binary_operation!(
+                    Assignment,
+                    nullary_operation!(variable!(Span::new_at(b"y", 6, 1, 7))),
+                    nullary_operation!(integer!(7, Span::new_at(b"7", 10, 1, 11)))
+                )
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_assignment_by_reference() {
+        let input = Span::new(b"$x =& $y");
+        let output = Ok((
+            Span::new_at(b"", 8, 1, 9),
+            Expression::NAryOperation(binary_operation!(
+                Assignment,
+                nullary_operation!(variable!(Span::new_at(b"x", 1, 1, 2))),
+                nullary_operation!(Expression::Reference(Box::new(variable!(Span::new_at(
+                    b"y", 7, 1, 8
+                )))))
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_assignment_to_a_property() {
+        let input = Span::new(b"$x->y = f()");
+        let output = Ok((
+            Span::new_at(b"", 11, 1, 12),
+            Expression::NAryOperation(binary_operation!(
+                Assignment,
+                nullary_operation!(Expression::PropertyAccess(
+                    DereferencableExpression::Variable(Variable(Span::new_at(b"x", 1, 1, 2))),
+                    MemberName::Name(Span::new_at(b"y", 4, 1, 5))
+                )),
+                nullary_operation!(Expression::Call(
+                    Box::new(Expression::Name(Name::Unqualified(Span::new_at(
+                        b"f", 8, 1, 9
+                    )))),
+                    Arguments::List(vec![])
+                ))
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_assignment_with_a_conditional_right_operand() {
+        let input = Span::new(b"$x = 1 ?: 2 + 3");
+        let output = Ok((
+            Span::new_at(b"", 15, 1, 16),
+            Expression::NAryOperation(binary_operation!(
+                Assignment,
+                nullary_operation!(variable!(Span::new_at(b"x", 1, 1, 2))),
+                binary_operation!(
+                    Conditional,
+                    nullary_operation!(integer!(1, Span::new_at(b"1", 5, 1, 6))),
+                    binary_operation!(
+                        Plus,
+                        nullary_operation!(integer!(2, Span::new_at(b"2", 10, 1, 11))),
+                        nullary_operation!(integer!(3, Span::new_at(b"3", 14, 1, 15)))
+                    )
+                )
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_assignment_in_a_right_operand() {
+        let input = Span::new(b"!$x && $y = 7");
+        let output = Ok((
+            Span::new_at(b"", 13, 1, 14),
+            Expression::NAryOperation(binary_operation!(
+                LogicalAnd,
+                unary_operation!(
+                    Negate,
+                    nullary_operation!(variable!(Span::new_at(b"x", 2, 1, 3)))
+                ),
+                binary_operation!(
+                    Assignment,
+                    nullary_operation!(variable!(Span::new_at(b"y", 8, 1, 9))),
+                    nullary_operation!(integer!(7, Span::new_at(b"7", 12, 1, 13)))
+                )
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_assignment_with_a_lower_precedence_than_logical_keywords() {
+        let input = Span::new(b"$x = 1 and 2");
+        let output = Ok((
+            Span::new_at(b"", 12, 1, 13),
+            Expression::NAryOperation(binary_operation!(
+                LogicalAnd,
+                binary_operation!(
+                    Assignment,
+                    nullary_operation!(variable!(Span::new_at(b"x", 1, 1, 2))),
+                    nullary_operation!(integer!(1, Span::new_at(b"1", 5, 1, 6)))
+                ),
+                nullary_operation!(integer!(2, Span::new_at(b"2", 11, 1, 12)))
+            )),
+        ));
+
+        assert_eq!(logical_expression(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_equal_is_not_an_assignment() {
+        let input = Span::new(b"$x == 7");
+        let output = Ok((
+            Span::new_at(b"", 7, 1, 8),
+            Expression::NAryOperation(binary_operation!(
+                Equal,
+                nullary_operation!(variable!(Span::new_at(b"x", 1, 1, 2))),
+                nullary_operation!(integer!(7, Span::new_at(b"7", 6, 1, 7)))
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_assignment_to_a_list() {
+        let input = Span::new(b"['a' => [$x, , $y]] = $z");
+        let output = Ok((
+            Span::new_at(b"", 24, 1, 25),
+            Expression::NAryOperation(binary_operation!(
+                Assignment,
+                nullary_operation!(Expression::List(vec![Some((
+                    Some(Expression::Literal(Literal::String(Token::new(
+                        Cow::from(&b"a"[..]),
+                        Span::new_at(b"'a'", 1, 1, 2),
+                    )))),
+                    Expression::List(vec![
+                        Some((None, variable!(Span::new_at(b"x", 10, 1, 11)))),
+                        None,
+                        Some((None, variable!(Span::new_at(b"y", 16, 1, 17)))),
+                    ]),
+                ))])),
+                nullary_operation!(variable!(Span::new_at(b"z", 23, 1, 24)))
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_array_compared_is_not_a_list() {
+        let input = Span::new(b"[$x, $y] == $z");
+        let output = Ok((
+            Span::new_at(b"", 14, 1, 15),
+            Expression::NAryOperation(binary_operation!(
+                Equal,
+                nullary_operation!(Expression::Array(vec![
+                    ArrayItem::Pair(None, variable!(Span::new_at(b"x", 2, 1, 3))),
+                    ArrayItem::Pair(None, variable!(Span::new_at(b"y", 6, 1, 7))),
+                ])),
+                nullary_operation!(variable!(Span::new_at(b"z", 13, 1, 14)))
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_assignment_to_an_array_of_literals() {
+        let input = Span::new(b"[1, 2] = $z");
+        let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
+
+        assert_eq!(expression(input), output);
+    }
+
+    test_assignment_operator!(case_assignment_operator_assign:               (b"$x = 7", Assignment));
+    test_assignment_operator!(case_assignment_operator_bitwise_and:          (b"$x &= 7", BitwiseAndAssignment));
+    test_assignment_operator!(case_assignment_operator_bitwise_or:           (b"$x |= 7", BitwiseOrAssignment));
+    test_assignment_operator!(case_assignment_operator_bitwise_shift_left:   (b"$x <<= 7", BitwiseShiftLeftAssignment));
+    test_assignment_operator!(case_assignment_operator_bitwise_shift_right:  (b"$x >>= 7", BitwiseShiftRightAssignment));
+    test_assignment_operator!(case_assignment_operator_bitwise_xor:          (b"$x ^= 7", BitwiseXorAssignment));
+    test_assignment_operator!(case_assignment_operator_coalesce:             (b"$x ??= 7", CoalesceAssignment));
+    test_assignment_operator!(case_assignment_operator_division:             (b"$x /= 7", DivisionAssignment));
+    test_assignment_operator!(case_assignment_operator_dot:                  (b"$x .= 7", DotAssignment));
+    test_assignment_operator!(case_assignment_operator_minus:                (b"$x -= 7", MinusAssignment));
+    test_assignment_operator!(case_assignment_operator_modulo:               (b"$x %= 7", ModuloAssignment));
+    test_assignment_operator!(case_assignment_operator_multiplication:       (b"$x *= 7", MultiplicationAssignment));
+    test_assignment_operator!(case_assignment_operator_plus:                 (b"$x += 7", PlusAssignment));
+    test_assignment_operator!(case_assignment_operator_power:                (b"$x **= 7", PowerAssignment));
+
+    #[test]
+    fn case_unary_prefix_operators() {
+        let input = Span::new(b"-+~@--++$x");
+        let output = Ok((
+            Span::new_at(b"", 10, 1, 11),
+            Expression::NAryOperation(unary_operation!(
+                Minus,
+                unary_operation!(
+                    Plus,
+                    unary_operation!(
+                        BitwiseComplement,
+                        unary_operation!(
+                            ErrorControl,
+                            unary_operation!(
+                                Decrement,
+                                unary_operation!(
+                                    Increment,
+                                    nullary_operation!(variable!(Span::new_at(b"x", 9, 1, 10)))
+                                )
+                            )
+                        )
+                    )
+                )
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_unary_minus_with_a_lower_precedence_than_power() {
+        let input = Span::new(b"-2 ** -3");
+        let output = Ok((
+            Span::new_at(b"", 8, 1, 9),
+            Expression::NAryOperation(unary_operation!(
+                Minus,
+                binary_operation!(
+                    Power,
+                    nullary_operation!(integer!(2, Span::new_at(b"2", 1, 1, 2))),
+                    unary_operation!(
+                        Minus,
+                        nullary_operation!(integer!(3, Span::new_at(b"3", 7, 1, 8)))
+                    )
+                )
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_unary_minus_in_a_binary_operation() {
+        let input = Span::new(b"1 - -2");
+        let output = Ok((
+            Span::new_at(b"", 6, 1, 7),
+            Expression::NAryOperation(binary_operation!(
+                Minus,
+                nullary_operation!(integer!(1, Span::new(b"1"))),
+                unary_operation!(
+                    Minus,
+                    nullary_operation!(integer!(2, Span::new_at(b"2", 5, 1, 6)))
+                )
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_unary_error_control_with_an_assignment() {
+        let input = Span::new(b"@$x = 7");
+        let output = Ok((
+            Span::new_at(b"", 7, 1, 8),
+            Expression::NAryOperation(unary_operation!(
+                ErrorControl,
+                binary_operation!(
+                    Assignment,
+                    nullary_operation!(variable!(Span::new_at(b"x", 2, 1, 3))),
+                    nullary_operation!(integer!(7, Span::new_at(b"7", 6, 1, 7)))
+                )
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_unary_postfix_increment() {
+        let input = Span::new(b"$x++ + 1");
+        let output = Ok((
+            Span::new_at(b"", 8, 1, 9),
+            Expression::NAryOperation(binary_operation!(
+                Plus,
+                unary_operation!(
+                    PostIncrement,
+                    nullary_operation!(variable!(Span::new_at(b"x", 1, 1, 2)))
+                ),
+                nullary_operation!(integer!(1, Span::new_at(b"1", 7, 1, 8)))
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_unary_postfix_decrement() {
+        let input = Span::new(b"-$x --");
+        let output = Ok((
+            Span::new_at(b"", 6, 1, 7),
+            Expression::NAryOperation(unary_operation!(
+                Minus,
+                unary_operation!(
+                    PostDecrement,
+                    nullary_operation!(variable!(Span::new_at(b"x", 2, 1, 3)))
+                )
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_unary_postfix_and_prefix() {
+        let input = Span::new(b"++$x++");
+        let output = Ok((
+            Span::new_at(b"", 6, 1, 7),
+            Expression::NAryOperation(unary_operation!(
+                Increment,
+                unary_operation!(
+                    PostIncrement,
+                    nullary_operation!(variable!(Span::new_at(b"x", 3, 1, 4)))
+                )
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_unary_minus_with_a_logical_not() {
+        let input = Span::new(b"-!$x");
+        let output = Ok((
+            Span::new_at(b"", 4, 1, 5),
+            Expression::NAryOperation(unary_operation!(
+                Minus,
+                unary_operation!(
+                    Negate,
+                    nullary_operation!(variable!(Span::new_at(b"x", 3, 1, 4)))
+                )
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_unary_error_control_with_a_logical_not() {
+        let input = Span::new(b"@!$x");
+        let output = Ok((
+            Span::new_at(b"", 4, 1, 5),
+            Expression::NAryOperation(unary_operation!(
+                ErrorControl,
+                unary_operation!(
+                    Negate,
+                    nullary_operation!(variable!(Span::new_at(b"x", 3, 1, 4)))
+                )
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_cast_with_a_logical_not() {
+        let input = Span::new(b"(bool)!$x");
+        let output = Ok((
+            Span::new_at(b"", 9, 1, 10),
+            Expression::NAryOperation(unary_operation!(
+                Cast(CastType::Boolean),
+                unary_operation!(
+                    Negate,
+                    nullary_operation!(variable!(Span::new_at(b"x", 8, 1, 9)))
+                )
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_power_with_a_logical_not() {
+        let input = Span::new(b"2 ** !$x");
+        let output = Ok((
+            Span::new_at(b"", 8, 1, 9),
+            Expression::NAryOperation(binary_operation!(
+                Power,
+                nullary_operation!(integer!(2, Span::new(b"2"))),
+                unary_operation!(
+                    Negate,
+                    nullary_operation!(variable!(Span::new_at(b"x", 7, 1, 8)))
+                )
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_cast_with_a_lower_precedence_than_power() {
+        let input = Span::new(b"(int) $x ** 2");
+        let output = Ok((
+            Span::new_at(b"", 13, 1, 14),
+            Expression::NAryOperation(unary_operation!(
+                Cast(CastType::Integer),
+                binary_operation!(
+                    Power,
+                    nullary_operation!(variable!(Span::new_at(b"x", 7, 1, 8))),
+                    nullary_operation!(integer!(2, Span::new_at(b"2", 12, 1, 13)))
+                )
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_cast_is_not_a_parenthesized_expression() {
+        let input = Span::new(b"(objects) + 1");
+        let output = Ok((
+            Span::new_at(b"", 13, 1, 14),
+            Expression::NAryOperation(binary_operation!(
+                Plus,
+                nullary_operation!(Expression::Name(Name::Unqualified(Span::new_at(
+                    b"objects", 1, 1, 2
+                )))),
+                nullary_operation!(integer!(1, Span::new_at(b"1", 12, 1, 13)))
+            )),
+        ));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    test_cast!(case_cast_array:               (b"(array)$x", Array));
+    test_cast!(case_cast_binary:              (b"(binary)$x", Binary));
+    test_cast!(case_cast_bool:                (b"(bool)$x", Boolean));
+    test_cast!(case_cast_boolean:             (b"(boolean)$x", Boolean));
+    test_cast!(case_cast_double:              (b"(double)$x", Float));
+    test_cast!(case_cast_float:               (b"(float)$x", Float));
+    test_cast!(case_cast_real:                (b"(real)$x", Float));
+    test_cast!(case_cast_int:                 (b"(int)$x", Integer));
+    test_cast!(case_cast_integer:             (b"(integer)$x", Integer));
+    test_cast!(case_cast_object:              (b"(object)$x", Object));
+    test_cast!(case_cast_string:              (b"(string)$x", String));
+    test_cast!(case_cast_case_insensitive:    (b"(StRiNg)$x", String));
+    test_cast!(case_cast_with_whitespaces:    (b"(\t int  )$x", Integer));
 }
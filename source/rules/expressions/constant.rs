@@ -35,23 +35,272 @@
 //! Specification in the [Grammar chapter, Expressions
 //! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#constant-expressions).
 
-use super::super::super::ast::{Expression, Literal};
+use super::super::super::ast::{
+    Argument, Arguments, ArrayItem, BinaryOperator, Expression, NAryOperation, RelativeScope,
+    ScopeResolver, UnaryOperator,
+};
 use super::super::super::tokens::Span;
-use super::super::literals::literal;
-use super::primaries::array;
+use super::assignment::assignment;
 
 named_attr!(
     #[doc="
         Recognize all kind of constant expressions.
+
+        A constant expression is an expression that can be evaluated
+        at compile-time: literals, arrays, constants, class constants,
+        magic constants, `new` with constant arguments, and unary,
+        binary or ternary operations on them. Any other expression,
+        e.g. a variable, a call, or an assignment, is rejected.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{BinaryOperator, Expression, NAryOperation, Name, RelativeScope, ScopeResolver};
+        use tagua_parser::rules::expressions::constant::constant_expression;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            constant_expression(Span::new(b\"self::A ?? PHP_EOL\")),
+            Ok((
+                Span::new_at(b\"\", 18, 1, 19),
+                Expression::NAryOperation(
+                    NAryOperation::Binary {
+                        operator: BinaryOperator::Coalesce,
+                        left_operand: Box::new(
+                            NAryOperation::Nullary(
+                                Box::new(
+                                    Expression::ClassConstantAccess(
+                                        ScopeResolver::ByRelative(RelativeScope::ToSelf),
+                                        Span::new_at(b\"A\", 6, 1, 7)
+                                    )
+                                )
+                            )
+                        ),
+                        right_operand: Box::new(
+                            NAryOperation::Nullary(
+                                Box::new(
+                                    Expression::Name(Name::Unqualified(Span::new_at(b\"PHP_EOL\", 11, 1, 12)))
+                                )
+                            )
+                        )
+                    }
+                )
+            ))
+        );
+        # }
+        ```
     "],
     pub constant_expression<Span, Expression>,
-    alt!(
-        literal => { literal_mapper }
-      | array
+    map_opt!(
+        assignment,
+        into_constant_expression
     )
 );
 
 #[inline]
-fn literal_mapper(literal: Literal) -> Expression {
-    Expression::Literal(literal)
+fn into_constant_expression(expression: Expression) -> Option<Expression> {
+    if is_constant(&expression) {
+        Some(expression)
+    } else {
+        None
+    }
+}
+
+fn is_constant(expression: &Expression) -> bool {
+    match *expression {
+        Expression::Literal(_) | Expression::MagicConstant(_) | Expression::Name(_) => true,
+
+        Expression::ClassConstantAccess(ref scope, _) => is_constant_scope(scope),
+
+        Expression::Array(ref items) => items.iter().all(|item| match *item {
+            ArrayItem::Pair(ref key, ref value) => {
+                key.as_ref().map_or(true, |key| is_constant(key)) && is_constant(value)
+            }
+
+            ArrayItem::Unpacked(ref value) => is_constant(value),
+        }),
+
+        Expression::New(ScopeResolver::ByName(_), ref arguments) => match *arguments {
+            None => true,
+
+            Some(Arguments::List(ref arguments)) => {
+                arguments.iter().all(|argument| match *argument {
+                    Argument::Positional(ref value)
+                    | Argument::Unpacked(ref value)
+                    | Argument::Named(_, ref value) => is_constant(value),
+                })
+            }
+
+            Some(Arguments::FirstClassCallable) => false,
+        },
+
+        Expression::NAryOperation(ref operation) => is_constant_operation(operation),
+
+        _ => false,
+    }
+}
+
+/// `static` is resolved at runtime, and so is an expression.
+fn is_constant_scope(scope: &ScopeResolver) -> bool {
+    match *scope {
+        ScopeResolver::ByName(_) => true,
+        ScopeResolver::ByRelative(RelativeScope::ToSelf)
+        | ScopeResolver::ByRelative(RelativeScope::ToParent) => true,
+        _ => false,
+    }
+}
+
+fn is_constant_operation(operation: &NAryOperation) -> bool {
+    match *operation {
+        NAryOperation::Nullary(ref operand) => is_constant(operand),
+
+        NAryOperation::Unary {
+            ref operator,
+            ref operand,
+        } => match *operator {
+            UnaryOperator::BitwiseComplement
+            | UnaryOperator::Minus
+            | UnaryOperator::Negate
+            | UnaryOperator::Plus => is_constant_operation(operand),
+            _ => false,
+        },
+
+        NAryOperation::Binary {
+            ref operator,
+            ref left_operand,
+            ref right_operand,
+        } => {
+            is_constant_binary_operator(operator)
+                && is_constant_operation(left_operand)
+                && is_constant_operation(right_operand)
+        }
+
+        NAryOperation::Ternary {
+            ref left_operand,
+            ref middle_operand,
+            ref right_operand,
+            ..
+        } => {
+            is_constant_operation(left_operand)
+                && is_constant(middle_operand)
+                && is_constant_operation(right_operand)
+        }
+    }
+}
+
+/// Assignments and `instanceof` are not allowed.
+fn is_constant_binary_operator(operator: &BinaryOperator) -> bool {
+    match *operator {
+        BinaryOperator::Assignment
+        | BinaryOperator::BitwiseAndAssignment
+        | BinaryOperator::BitwiseOrAssignment
+        | BinaryOperator::BitwiseShiftLeftAssignment
+        | BinaryOperator::BitwiseShiftRightAssignment
+        | BinaryOperator::BitwiseXorAssignment
+        | BinaryOperator::CoalesceAssignment
+        | BinaryOperator::DivisionAssignment
+        | BinaryOperator::DotAssignment
+        | BinaryOperator::InstanceOf
+        | BinaryOperator::MinusAssignment
+        | BinaryOperator::ModuloAssignment
+        | BinaryOperator::MultiplicationAssignment
+        | BinaryOperator::PlusAssignment
+        | BinaryOperator::PowerAssignment => false,
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::super::ast::{
+        Argument, Arguments, Expression, Literal, MagicConstant, NAryOperation, Name,
+        ScopeResolver, TernaryOperator,
+    };
+    use super::super::super::super::internal::{Context, Error, ErrorKind};
+    use super::super::super::super::tokens::{Span, Token};
+    use super::constant_expression;
+
+    #[test]
+    fn case_constant_expression_magic_constant() {
+        let input = Span::new(b"__LINE__");
+        let output = Ok((
+            Span::new_at(b"", 8, 1, 9),
+            Expression::MagicConstant(MagicConstant::Line(Span::new(b"__LINE__"))),
+        ));
+
+        assert_eq!(constant_expression(input), output);
+    }
+
+    #[test]
+    fn case_constant_expression_ternary_operation() {
+        let input = Span::new(b"A ? B : C");
+        let output = Ok((
+            Span::new_at(b"", 9, 1, 10),
+            Expression::NAryOperation(NAryOperation::Ternary {
+                operator: TernaryOperator::Conditional,
+                left_operand: Box::new(NAryOperation::Nullary(Box::new(Expression::Name(
+                    Name::Unqualified(Span::new(b"A")),
+                )))),
+                middle_operand: Box::new(Expression::Name(Name::Unqualified(Span::new_at(
+                    b"B", 4, 1, 5,
+                )))),
+                right_operand: Box::new(NAryOperation::Nullary(Box::new(Expression::Name(
+                    Name::Unqualified(Span::new_at(b"C", 8, 1, 9)),
+                )))),
+            }),
+        ));
+
+        assert_eq!(constant_expression(input), output);
+    }
+
+    #[test]
+    fn case_constant_expression_new() {
+        let input = Span::new(b"new Foo(1)");
+        let output = Ok((
+            Span::new_at(b"", 10, 1, 11),
+            Expression::New(
+                ScopeResolver::ByName(Name::Unqualified(Span::new_at(b"Foo", 4, 1, 5))),
+                Some(Arguments::List(vec![Argument::Positional(
+                    Expression::Literal(Literal::Integer(Token::new(
+                        1i64,
+                        Span::new_at(b"1", 8, 1, 9),
+                    ))),
+                )])),
+            ),
+        ));
+
+        assert_eq!(constant_expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_constant_expression_variable() {
+        let input = Span::new(b"$x");
+
+        assert_eq!(
+            constant_expression(input),
+            Err(Error::Error(Context::Code(input, ErrorKind::MapOpt)))
+        );
+    }
+
+    #[test]
+    fn case_invalid_constant_expression_cast() {
+        let input = Span::new(b"(int) 1");
+
+        assert_eq!(
+            constant_expression(input),
+            Err(Error::Error(Context::Code(input, ErrorKind::MapOpt)))
+        );
+    }
+
+    #[test]
+    fn case_invalid_constant_expression_new_static() {
+        let input = Span::new(b"new static");
+
+        assert_eq!(
+            constant_expression(input),
+            Err(Error::Error(Context::Code(input, ErrorKind::MapOpt)))
+        );
+    }
 }
@@ -79,5 +79,5 @@ named_attr!(
         ```
     "],
     pub expression<Span, Expression>,
-    call!(primaries::primary)
+    call!(assignment::logical_expression)
 );
@@ -36,21 +36,31 @@
 //! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#primary-expressions).
 
 use super::super::super::ast::{
-    AnonymousFunction, Arity, DeclarationScope, DereferencableExpression, Expression, Literal,
-    Name, RelativeScope, ScopeResolver, Statement, Ty, Variable,
+    AnonymousFunction, Argument, Arguments, Arity, ArrayItem, ArrowFunction, Attribute,
+    DeclarationScope, DereferencableExpression, Expression, ImportKind, Literal, MagicConstant,
+    MatchArm, MemberName, Name, RelativeScope, ScopeResolver, Statement, Ty, Variable,
 };
-use super::super::super::internal::{Context, Error, ErrorKind};
+use super::super::super::internal::{Context, Error, ErrorKind, Result};
 use super::super::super::tokens;
 use super::super::super::tokens::{Span, Token};
-use super::super::literals::{literal, string_single_quoted};
+use super::super::attributes::attributes;
+use super::super::literals::{literal, string};
 use super::super::statements::compound_statement;
-use super::super::statements::function::{native_type, parameters};
+use super::super::statements::function::{
+    arrow_function_kind, checked_parameters, function_kind, output_type,
+};
 use super::super::tokens::{name, qualified_name, variable};
+use super::super::whitespaces::whitespace;
+use super::assignment::assignment;
 use super::expression;
-use smallvec::SmallVec;
+use nom::Slice;
 use std::result::Result as StdResult;
 
 /// Intrinsic errors.
+///
+/// An invalid operand of `isset` or `unset` is a failure, not an
+/// error: the construct is unambiguous once `isset(` or `unset(` is
+/// recognized, so the error must not be discarded by an alternative.
 pub enum IntrinsicError {
     /// The exit code is reserved (only 255 is reserved to PHP).
     ReservedExitCode,
@@ -60,6 +70,20 @@ pub enum IntrinsicError {
 
     /// The list constructor must contain at least one item.
     ListIsEmpty,
+
+    /// An operand of `isset` must be a variable, an array element, or
+    /// a property, e.g. not `1 + 2`.
+    InvalidIssetOperand,
+
+    /// An operand of `unset` must be a variable, an array element, or
+    /// a property, without the nullsafe operator.
+    InvalidUnsetOperand,
+}
+
+/// Match errors.
+pub enum MatchError {
+    /// A match expression has more than one `default` arm.
+    MultipleDefaultArms,
 }
 
 named_attr!(
@@ -100,13 +124,34 @@ named_attr!(
     "],
     pub primary<Span, Expression>,
     alt_complete!(
-        class_constant_access
-      | variable              => { variable_mapper }
-      | constant_access       => { constant_access_mapper }
+        do_parse!(
+            operand: postfix_operand >>
+            result: fold_many0!(
+                complete!(first!(postfix_operation)),
+                operand,
+                into_postfix_expression
+            ) >>
+            (result)
+        )
+      | new
+      | clone
       | literal               => { literal_mapper }
       | array
       | intrinsic
       | anonymous_function
+      | arrow_function
+      | match_expression
+    )
+);
+
+named!(
+    postfix_operand<Span, Expression>,
+    alt_complete!(
+        relative_scope_operand
+      | variable              => { variable_mapper }
+      | string                => { literal_mapper }
+      | constant_access       => { constant_access_mapper }
+      | array
       | preceded!(
             tag!(tokens::LEFT_PARENTHESIS),
             terminated!(
@@ -117,6 +162,15 @@ named_attr!(
     )
 );
 
+named!(
+    relative_scope_operand<Span, Expression>,
+    do_parse!(
+        scope: relative_scope >>
+        operation: first!(static_operation) >>
+        (into_static_expression(ScopeResolver::ByRelative(scope), operation))
+    )
+);
+
 #[inline]
 fn variable_mapper(variable: Variable) -> Expression {
     Expression::Variable(variable)
@@ -124,7 +178,40 @@ fn variable_mapper(variable: Variable) -> Expression {
 
 #[inline]
 fn constant_access_mapper(name: Name) -> Expression {
-    Expression::Name(name)
+    match name {
+        Name::Unqualified(span) => match into_magic_constant(span) {
+            Some(constant) => Expression::MagicConstant(constant),
+            None => Expression::Name(name),
+        },
+
+        name => Expression::Name(name),
+    }
+}
+
+/// Map an unqualified name to a magic constant, if any. Magic
+/// constants are case-insensitive.
+fn into_magic_constant(name: Span) -> Option<MagicConstant> {
+    let slice = name.as_slice();
+
+    if slice.eq_ignore_ascii_case(tokens::MAGIC_CLASS) {
+        Some(MagicConstant::Class(name))
+    } else if slice.eq_ignore_ascii_case(tokens::MAGIC_DIRECTORY) {
+        Some(MagicConstant::Directory(name))
+    } else if slice.eq_ignore_ascii_case(tokens::MAGIC_FILE) {
+        Some(MagicConstant::File(name))
+    } else if slice.eq_ignore_ascii_case(tokens::MAGIC_FUNCTION) {
+        Some(MagicConstant::Function(name))
+    } else if slice.eq_ignore_ascii_case(tokens::MAGIC_LINE) {
+        Some(MagicConstant::Line(name))
+    } else if slice.eq_ignore_ascii_case(tokens::MAGIC_METHOD) {
+        Some(MagicConstant::Method(name))
+    } else if slice.eq_ignore_ascii_case(tokens::MAGIC_NAMESPACE) {
+        Some(MagicConstant::Namespace(name))
+    } else if slice.eq_ignore_ascii_case(tokens::MAGIC_TRAIT) {
+        Some(MagicConstant::Trait(name))
+    } else {
+        None
+    }
 }
 
 #[inline]
@@ -132,9 +219,458 @@ fn literal_mapper(literal: Literal) -> Expression {
     Expression::Literal(literal)
 }
 
+/// A postfix operation, applied on the expression on its left.
+enum PostfixOperation<'a> {
+    /// `[…]` or `{…}`.
+    ArrayAccess(Option<Box<Expression<'a>>>),
+
+    /// `(…)`.
+    Call(Arguments<'a>),
+
+    /// `->…(…)`.
+    MethodCall(MemberName<'a>, Arguments<'a>),
+
+    /// `?->…(…)`.
+    NullsafeMethodCall(MemberName<'a>, Arguments<'a>),
+
+    /// `?->…`.
+    NullsafePropertyAccess(MemberName<'a>),
+
+    /// `->…`.
+    PropertyAccess(MemberName<'a>),
+
+    /// `::…`.
+    Static(StaticOperation<'a>),
+}
+
+/// A postfix operation applied on a scope resolver.
+enum StaticOperation<'a> {
+    /// `::NAME`.
+    ClassConstantAccess(Span<'a>),
+
+    /// `::…(…)`.
+    MethodCall(MemberName<'a>, Arguments<'a>),
+
+    /// `::$…`.
+    PropertyAccess(Variable<'a>),
+}
+
+named!(
+    postfix_operation<Span, PostfixOperation>,
+    alt_complete!(
+        array_access_operation
+      | string_offset_access_operation
+      | arguments             => { PostfixOperation::Call }
+      | object_operation
+      | nullsafe_object_operation
+      | static_operation      => { PostfixOperation::Static }
+    )
+);
+
+named!(
+    array_access_operation<Span, PostfixOperation>,
+    do_parse!(
+        tag!(tokens::LEFT_SQUARE_BRACKET) >>
+        index: opt!(first!(expression)) >>
+        first!(tag!(tokens::RIGHT_SQUARE_BRACKET)) >>
+        (PostfixOperation::ArrayAccess(index.map(Box::new)))
+    )
+);
+
+named!(
+    string_offset_access_operation<Span, PostfixOperation>,
+    do_parse!(
+        tag!(tokens::LEFT_CURLY_BRACKET) >>
+        index: first!(expression) >>
+        first!(tag!(tokens::RIGHT_CURLY_BRACKET)) >>
+        (PostfixOperation::ArrayAccess(Some(Box::new(index))))
+    )
+);
+
+named!(
+    object_operation<Span, PostfixOperation>,
+    do_parse!(
+        tag!(tokens::DYNAMIC_CALL) >>
+        name: first!(member_name) >>
+        arguments: opt!(complete!(first!(arguments))) >>
+        (
+            match arguments {
+                Some(arguments) => PostfixOperation::MethodCall(name, arguments),
+                None => PostfixOperation::PropertyAccess(name)
+            }
+        )
+    )
+);
+
+named!(
+    nullsafe_object_operation<Span, PostfixOperation>,
+    do_parse!(
+        tag!(tokens::NULLSAFE_DYNAMIC_CALL) >>
+        name: first!(member_name) >>
+        arguments: opt!(complete!(first!(arguments))) >>
+        (
+            match arguments {
+                Some(arguments) => PostfixOperation::NullsafeMethodCall(name, arguments),
+                None => PostfixOperation::NullsafePropertyAccess(name)
+            }
+        )
+    )
+);
+
+named!(
+    static_operation<Span, StaticOperation>,
+    preceded!(
+        tag!(tokens::STATIC_CALL),
+        first!(
+            alt_complete!(
+                do_parse!(
+                    variable: variable >>
+                    arguments: opt!(complete!(first!(arguments))) >>
+                    (
+                        match arguments {
+                            Some(arguments) => StaticOperation::MethodCall(
+                                MemberName::Expression(Box::new(Expression::Variable(variable))),
+                                arguments
+                            ),
+                            None => StaticOperation::PropertyAccess(variable)
+                        }
+                    )
+                )
+              | do_parse!(
+                    name: name >>
+                    arguments: opt!(complete!(first!(arguments))) >>
+                    (
+                        match arguments {
+                            Some(arguments) => StaticOperation::MethodCall(
+                                MemberName::Name(name),
+                                arguments
+                            ),
+                            None => StaticOperation::ClassConstantAccess(name)
+                        }
+                    )
+                )
+              | do_parse!(
+                    name: member_name_expression >>
+                    arguments: first!(arguments) >>
+                    (StaticOperation::MethodCall(name, arguments))
+                )
+            )
+        )
+    )
+);
+
+named!(
+    member_name<Span, MemberName>,
+    alt_complete!(
+        name                   => { MemberName::Name }
+      | variable               => {
+            |variable| MemberName::Expression(Box::new(Expression::Variable(variable)))
+        }
+      | member_name_expression
+    )
+);
+
+named!(
+    member_name_expression<Span, MemberName>,
+    do_parse!(
+        tag!(tokens::LEFT_CURLY_BRACKET) >>
+        expression: first!(expression) >>
+        first!(tag!(tokens::RIGHT_CURLY_BRACKET)) >>
+        (MemberName::Expression(Box::new(expression)))
+    )
+);
+
+#[inline]
+fn into_postfix_expression<'a>(
+    operand: Expression<'a>,
+    operation: PostfixOperation<'a>,
+) -> Expression<'a> {
+    match operation {
+        PostfixOperation::ArrayAccess(index) => {
+            Expression::ArrayAccess(into_dereferencable(operand), index)
+        }
+
+        PostfixOperation::Call(arguments) => Expression::Call(Box::new(operand), arguments),
+
+        PostfixOperation::MethodCall(name, arguments) => {
+            Expression::MethodCall(into_dereferencable(operand), name, arguments)
+        }
+
+        PostfixOperation::NullsafeMethodCall(name, arguments) => {
+            Expression::NullsafeMethodCall(into_dereferencable(operand), name, arguments)
+        }
+
+        PostfixOperation::NullsafePropertyAccess(name) => {
+            Expression::NullsafePropertyAccess(into_dereferencable(operand), name)
+        }
+
+        PostfixOperation::PropertyAccess(name) => {
+            Expression::PropertyAccess(into_dereferencable(operand), name)
+        }
+
+        PostfixOperation::Static(operation) => {
+            into_static_expression(into_scope_resolver(operand), operation)
+        }
+    }
+}
+
+#[inline]
+fn into_static_expression<'a>(
+    scope: ScopeResolver<'a>,
+    operation: StaticOperation<'a>,
+) -> Expression<'a> {
+    match operation {
+        StaticOperation::ClassConstantAccess(name) => class_constant_access_mapper(scope, name),
+
+        StaticOperation::MethodCall(name, arguments) => {
+            Expression::StaticMethodCall(scope, name, arguments)
+        }
+
+        StaticOperation::PropertyAccess(variable) => {
+            Expression::StaticPropertyAccess(scope, variable)
+        }
+    }
+}
+
+fn into_dereferencable(expression: Expression) -> DereferencableExpression {
+    match expression {
+        Expression::Variable(variable) => DereferencableExpression::Variable(variable),
+        array @ Expression::Array(_) => DereferencableExpression::Array(Box::new(array)),
+        Expression::Literal(string @ Literal::String(_))
+        | Expression::Literal(string @ Literal::InterpolatedString(_)) => {
+            DereferencableExpression::String(string)
+        }
+        expression => DereferencableExpression::Expression(Box::new(expression)),
+    }
+}
+
+fn into_scope_resolver(expression: Expression) -> ScopeResolver {
+    match expression {
+        Expression::Name(name) => ScopeResolver::ByName(name),
+        expression => ScopeResolver::ByExpression(into_dereferencable(expression)),
+    }
+}
+
+named_attr!(
+    #[doc="
+        Recognize the arguments of a call, including the parenthesis.
+
+        An argument can be positional, unpacked (`...$x`), or named
+        (`x: $y`). A trailing comma is allowed. The first-class
+        callable syntax, i.e. `(...)`, is recognized too.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{Argument, Arguments, Expression, Variable};
+        use tagua_parser::rules::expressions::primaries::arguments;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            arguments(Span::new(b\"($x, y: $z,)\")),
+            Ok((
+                Span::new_at(b\"\", 12, 1, 13),
+                Arguments::List(vec![
+                    Argument::Positional(Expression::Variable(Variable(Span::new_at(b\"x\", 2, 1, 3)))),
+                    Argument::Named(
+                        Span::new_at(b\"y\", 5, 1, 6),
+                        Expression::Variable(Variable(Span::new_at(b\"z\", 9, 1, 10)))
+                    )
+                ])
+            ))
+        );
+        # }
+        ```
+    "],
+    pub arguments<Span, Arguments>,
+    preceded!(
+        tag!(tokens::LEFT_PARENTHESIS),
+        alt_complete!(
+            terminated!(
+                first!(tag!(tokens::ELLIPSIS)),
+                first!(tag!(tokens::RIGHT_PARENTHESIS))
+            ) => { |_| Arguments::FirstClassCallable }
+          | first!(tag!(tokens::RIGHT_PARENTHESIS)) => { |_| Arguments::List(vec![]) }
+          | terminated!(
+                argument_list,
+                first!(tag!(tokens::RIGHT_PARENTHESIS))
+            )
+        )
+    )
+);
+
+named!(
+    argument_list<Span, Arguments>,
+    do_parse!(
+        accumulator: map_res!(
+            first!(argument),
+            into_vector_mapper
+        ) >>
+        result: fold_into_vector_many0!(
+            preceded!(
+                first!(tag!(tokens::COMMA)),
+                first!(argument)
+            ),
+            accumulator
+        ) >>
+        opt!(complete!(first!(tag!(tokens::COMMA)))) >>
+        (Arguments::List(result))
+    )
+);
+
+named!(
+    argument<Span, Argument>,
+    alt_complete!(
+        preceded!(
+            tag!(tokens::ELLIPSIS),
+            first!(expression)
+        ) => { Argument::Unpacked }
+      | do_parse!(
+            name: name >>
+            first!(
+                terminated!(
+                    tag!(tokens::NAMED_ARGUMENT),
+                    not!(tag!(tokens::NAMED_ARGUMENT))
+                )
+            ) >>
+            value: first!(expression) >>
+            (Argument::Named(name, value))
+        )
+      | expression => { Argument::Positional }
+    )
+);
+
+named_attr!(
+    #[doc="
+        Recognize an instanciation, e.g. `new Foo(42)`.
+
+        The arguments of the constructor are optional, e.g. `new Foo`.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{Arguments, Expression, RelativeScope, ScopeResolver};
+        use tagua_parser::rules::expressions::primaries::new;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            new(Span::new(b\"new static()\")),
+            Ok((
+                Span::new_at(b\"\", 12, 1, 13),
+                Expression::New(
+                    ScopeResolver::ByRelative(RelativeScope::ToStatic),
+                    Some(Arguments::List(vec![]))
+                )
+            ))
+        );
+        # }
+        ```
+    "],
+    pub new<Span, Expression>,
+    do_parse!(
+        keyword!(tokens::NEW) >>
+        class: first!(class_type_designator) >>
+        arguments: opt!(complete!(first!(arguments))) >>
+        (Expression::New(class, arguments))
+    )
+);
+
+named!(
+    class_type_designator<Span, ScopeResolver>,
+    alt_complete!(
+        terminated!(
+            relative_scope,
+            not!(name)
+        )                               => { scope_resolution_relative_mapper }
+      | qualified_name                  => { scope_resolution_name_mapper }
+      | new_dereferencable_expression   => { scope_resolution_dereferencable_mapper }
+    )
+);
+
+named!(
+    new_dereferencable_expression<Span, DereferencableExpression>,
+    alt_complete!(
+        do_parse!(
+            operand: variable >>
+            result: fold_many0!(
+                complete!(
+                    first!(
+                        alt_complete!(
+                            array_access_operation
+                          | preceded!(
+                                tag!(tokens::DYNAMIC_CALL),
+                                first!(member_name)
+                            ) => { PostfixOperation::PropertyAccess }
+                          | preceded!(
+                                tag!(tokens::STATIC_CALL),
+                                first!(variable)
+                            ) => {
+                                |variable| PostfixOperation::Static(StaticOperation::PropertyAccess(variable))
+                            }
+                        )
+                    )
+                ),
+                Expression::Variable(operand),
+                into_postfix_expression
+            ) >>
+            (into_dereferencable(result))
+        )
+      | preceded!(
+            tag!(tokens::LEFT_PARENTHESIS),
+            terminated!(
+                first!(expression),
+                first!(tag!(tokens::RIGHT_PARENTHESIS))
+            )
+        ) => { dereferencable_sub_expression_mapper }
+    )
+);
+
+named_attr!(
+    #[doc="
+        Recognize a clone, e.g. `clone $foo`.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{DereferencableExpression, Expression, MemberName, Variable};
+        use tagua_parser::rules::expressions::primaries::clone;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            clone(Span::new(b\"clone $foo->bar\")),
+            Ok((
+                Span::new_at(b\"\", 15, 1, 16),
+                Expression::Clone(
+                    Box::new(
+                        Expression::PropertyAccess(
+                            DereferencableExpression::Variable(Variable(Span::new_at(b\"foo\", 7, 1, 8))),
+                            MemberName::Name(Span::new_at(b\"bar\", 12, 1, 13))
+                        )
+                    )
+                )
+            ))
+        );
+        # }
+        ```
+    "],
+    pub clone<Span, Expression>,
+    do_parse!(
+        keyword!(tokens::CLONE) >>
+        operand: first!(primary) >>
+        (Expression::Clone(Box::new(operand)))
+    )
+);
+
 named_attr!(
     #[doc="
-        Recognize a class constant access.
+        Recognize a class constant access, e.g. `C::FOO`, or a class
+        name, e.g. `C::class`.
     "],
     pub class_constant_access<Span, Expression>,
     do_parse!(
@@ -149,7 +685,11 @@ named_attr!(
 
 #[inline]
 fn class_constant_access_mapper<'a>(scope: ScopeResolver<'a>, name: Span<'a>) -> Expression<'a> {
-    Expression::ClassConstantAccess(scope, name)
+    if name.as_slice().eq_ignore_ascii_case(tokens::CLASS) {
+        Expression::MagicConstant(MagicConstant::ClassName(scope, name))
+    } else {
+        Expression::ClassConstantAccess(scope, name)
+    }
 }
 
 named_attr!(
@@ -206,7 +746,7 @@ named_attr!(
             )
         )                    => { dereferencable_sub_expression_mapper }
       | array                => { dereferencable_array_mapper }
-      | string_single_quoted => { dereferencable_string_mapper }
+      | string               => { dereferencable_string_mapper }
     )
 );
 
@@ -267,12 +807,16 @@ named_attr!(
     #[doc="
         Recognize an array.
 
+        The short syntax `[…]` and the long syntax `array(…)` are
+        equivalent. An item is a value with an optional key, a value
+        by reference (`&$x`), or an unpacked array (`...$x`).
+
         # Examples
 
         ```
         use std::borrow::Cow;
         use tagua_parser::Result;
-        use tagua_parser::ast::{Expression, Literal, Variable};
+        use tagua_parser::ast::{ArrayItem, Expression, Literal, Variable};
         use tagua_parser::rules::expressions::primaries::array;
         use tagua_parser::tokens::{
             Span,
@@ -285,12 +829,12 @@ named_attr!(
             Ok((
                 Span::new_at(b\"\", 19, 1, 20),
                 Expression::Array(vec![
-                    (
+                    ArrayItem::Pair(
                         None,
                         Expression::Literal(Literal::Integer(Token::new(42i64, Span::new_at(b\"42\", 1, 1, 2))))
                     ),
-                    (
-                        Some(Expression::Literal(Literal::String(Token::new(Cow::from(&b\"foo\"[..]), Span::new_at(b\"'foo'\", 5, 1, 6))))),
+                    ArrayItem::Pair(
+                        Some(Box::new(Expression::Literal(Literal::String(Token::new(Cow::from(&b\"foo\"[..]), Span::new_at(b\"'foo'\", 5, 1, 6)))))),
                         Expression::Variable(Variable(Span::new_at(b\"bar\", 15, 1, 16)))
                     )
                 ])
@@ -308,9 +852,12 @@ named_attr!(
                     first!(tag!(tokens::RIGHT_SQUARE_BRACKET)),
                     empty_array_mapper
                 )
-              | terminated!(
-                    array_pairs,
-                    first!(tag!(tokens::RIGHT_SQUARE_BRACKET))
+              | map_opt!(
+                    terminated!(
+                        array_items,
+                        first!(tag!(tokens::RIGHT_SQUARE_BRACKET))
+                    ),
+                    into_short_array
                 )
             )
         )
@@ -324,9 +871,12 @@ named_attr!(
                     first!(tag!(tokens::RIGHT_PARENTHESIS)),
                     empty_array_mapper
                 )
-              | terminated!(
-                    array_pairs,
-                    first!(tag!(tokens::RIGHT_PARENTHESIS))
+              | map_opt!(
+                    terminated!(
+                        array_items,
+                        first!(tag!(tokens::RIGHT_PARENTHESIS))
+                    ),
+                    into_array
                 )
             )
         )
@@ -334,44 +884,66 @@ named_attr!(
 );
 
 named!(
-    array_pairs<Span, Expression>,
+    array_items<Span, Vec<Option<ArrayItem>>>,
     do_parse!(
         accumulator: map_res!(
-            first!(array_pair),
+            opt!(first!(array_item)),
             into_vector_mapper
         ) >>
         result: fold_into_vector_many0!(
             preceded!(
                 first!(tag!(tokens::COMMA)),
-                first!(array_pair)
+                opt!(first!(array_item))
             ),
             accumulator
         ) >>
-        opt!(first!(tag!(tokens::COMMA))) >>
-        (into_array(result))
+        (trim_trailing_slot(result))
     )
 );
 
 named!(
-    array_pair<Span, (Option<Expression>, Expression)>,
-    do_parse!(
-        key: opt!(
-            terminated!(
-                expression,
-                first!(tag!(tokens::MAP))
-            )
-        ) >>
-        value: alt!(
-            map_res!(
-                preceded!(
-                    first!(tag!(tokens::REFERENCE)),
-                    first!(expression)
-                ),
-                value_by_reference_array_mapper
-            )
-          | first!(expression)
-        ) >>
-        ((key, value))
+    array_item<Span, ArrayItem>,
+    alt!(
+        preceded!(
+            tag!(tokens::ELLIPSIS),
+            first!(expression)
+        ) => { ArrayItem::Unpacked }
+      | array_pair
+    )
+);
+
+named!(
+    array_pair<Span, ArrayItem>,
+    alt!(
+        array_value_by_reference => { |value| ArrayItem::Pair(None, value) }
+      | do_parse!(
+            key_or_value: expression >>
+            value: opt!(
+                complete!(
+                    preceded!(
+                        first!(tag!(tokens::MAP)),
+                        first!(
+                            alt!(
+                                array_value_by_reference
+                              | expression
+                            )
+                        )
+                    )
+                )
+            ) >>
+            (into_array_pair(key_or_value, value))
+        )
+    )
+);
+
+named!(
+    array_value_by_reference<Span, Expression>,
+    map_res!(
+        preceded!(
+            tag!(tokens::REFERENCE),
+            first!(expression)
+        ),
+        value_by_reference_array_mapper
     )
 );
 
@@ -386,8 +958,121 @@ fn value_by_reference_array_mapper(expression: Expression) -> StdResult<Expressi
 }
 
 #[inline]
-fn into_array<'a>(expressions: Vec<(Option<Expression<'a>>, Expression<'a>)>) -> Expression<'a> {
-    Expression::Array(expressions)
+fn into_array_pair<'a>(
+    key_or_value: Expression<'a>,
+    value: Option<Expression<'a>>,
+) -> ArrayItem<'a> {
+    match value {
+        Some(value) => ArrayItem::Pair(Some(Box::new(key_or_value)), value),
+        None => ArrayItem::Pair(None, key_or_value),
+    }
+}
+
+/// Remove the empty slot left by a trailing comma, e.g. `[1, 2,]`.
+#[inline]
+fn trim_trailing_slot<T>(mut items: Vec<Option<T>>) -> Vec<Option<T>> {
+    if let Some(&None) = items.last() {
+        items.pop();
+    }
+
+    items
+}
+
+/// Items of a list, where `None` is a skipped slot.
+type ListItems<'a> = Vec<Option<(Option<Expression<'a>>, Expression<'a>)>>;
+
+/// Build an array if no slot is skipped.
+fn into_array(items: Vec<Option<ArrayItem>>) -> Option<Expression> {
+    if items.iter().any(Option::is_none) {
+        return None;
+    }
+
+    let mut array = Vec::with_capacity(items.len());
+    array.extend(items.into_iter().filter_map(|item| item));
+
+    Some(Expression::Array(array))
+}
+
+/// Build an array, or a list if a slot is skipped, e.g. `[, $x]`,
+/// since it can only be a destructuring target.
+fn into_short_array(items: Vec<Option<ArrayItem>>) -> Option<Expression> {
+    if items.iter().any(Option::is_none) {
+        into_list_items(items).map(Expression::List)
+    } else {
+        into_array(items)
+    }
+}
+
+/// Convert an array into a list, i.e. a destructuring target.
+///
+/// The short syntax `[…]` is recognized as an array, and it is
+/// converted into a list when it is the left operand of an
+/// assignment, or the value of a `foreach` loop. Nested arrays are
+/// converted too. `None` is returned if the expression is not a valid
+/// destructuring target, e.g. if a value is not a variable, if keyed
+/// and unkeyed items are mixed, or if an item is unpacked.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tagua_parser;
+/// use tagua_parser::ast::{ArrayItem, Expression, Variable};
+/// use tagua_parser::rules::expressions::primaries::into_destructuring_target;
+/// use tagua_parser::tokens::Span;
+///
+/// # fn main() {
+/// let array = Expression::Array(vec![
+///     ArrayItem::Pair(None, Expression::Variable(Variable(Span::new(b"x"))))
+/// ]);
+///
+/// assert_eq!(
+///     into_destructuring_target(array),
+///     Some(
+///         Expression::List(vec![
+///             Some((None, Expression::Variable(Variable(Span::new(b"x")))))
+///         ])
+///     )
+/// );
+/// # }
+/// ```
+pub fn into_destructuring_target(expression: Expression) -> Option<Expression> {
+    match expression {
+        Expression::Array(items) => {
+            into_list_items(items.into_iter().map(Some).collect()).map(Expression::List)
+        }
+
+        Expression::List(items) => Some(Expression::List(items)),
+
+        _ => None,
+    }
+}
+
+fn into_list_items<'a>(items: Vec<Option<ArrayItem<'a>>>) -> Option<ListItems<'a>> {
+    let keyed = match items.first() {
+        Some(&Some(ArrayItem::Pair(Some(_), _))) => true,
+        _ => false,
+    };
+    let mut list = Vec::with_capacity(items.len());
+
+    for item in items {
+        list.push(match item {
+            Some(item) => Some(if keyed {
+                into_keyed_list_item(item)?
+            } else {
+                into_unkeyed_list_item(item)?
+            }),
+
+            None if keyed => return None,
+
+            None => None,
+        });
+    }
+
+    if list.iter().any(Option::is_some) {
+        Some(list)
+    } else {
+        None
+    }
 }
 
 named_attr!(
@@ -437,7 +1122,10 @@ named!(
     intrinsic_construct<Span, Expression>,
     alt!(
         intrinsic_echo
-      | intrinsic_list
+      | preceded!(
+            peek!(keyword!(tokens::LIST)),
+            intrinsic_list
+        )
       | intrinsic_unset
     )
 );
@@ -449,7 +1137,11 @@ named!(
       | intrinsic_eval
       | intrinsic_exit
       | intrinsic_isset
+      | intrinsic_import
       | intrinsic_print
+      | intrinsic_throw
+      | intrinsic_yield_from
+      | intrinsic_yield
     )
 );
 
@@ -515,7 +1207,16 @@ fn into_echo(expressions: Vec<Expression>) -> Expression {
 
 named_attr!(
     #[doc="
-        Recognize a list.
+        Recognize a list, i.e. a destructuring target.
+
+        The short syntax `[…]` is equivalent to `list(…)`. Since it is
+        ambiguous with an array, an expression recognizes it as an
+        array, which is converted into a list where a destructuring
+        target is expected, i.e. on the left of an assignment, or as
+        the value of a `foreach` loop, see
+        `into_destructuring_target`. A value can be a nested list or a
+        reference (`&$x`), and a slot can be skipped in an unkeyed
+        list.
 
         # Examples
 
@@ -551,85 +1252,149 @@ named_attr!(
     "],
     pub intrinsic_list<Span, Expression>,
     map_res_and_input!(
-        preceded!(
-            preceded!(
-                keyword!(tokens::LIST),
-                first!(tag!(tokens::LEFT_PARENTHESIS))
-            ),
-            terminated!(
-                alt!(
-                    intrinsic_keyed_list
-                  | intrinsic_unkeyed_list
-                ),
-                first!(tag!(tokens::RIGHT_PARENTHESIS))
-            )
+        do_parse!(
+            closing_token: alt!(
+                tag!(tokens::LEFT_SQUARE_BRACKET) => { |_| tokens::RIGHT_SQUARE_BRACKET }
+              | preceded!(
+                    keyword!(tokens::LIST),
+                    first!(tag!(tokens::LEFT_PARENTHESIS))
+                ) => { |_| tokens::RIGHT_PARENTHESIS }
+            ) >>
+            items: terminated!(
+                intrinsic_list_items,
+                first!(tag!(closing_token))
+            ) >>
+            (into_list(closing_token, items))
         ),
         intrinsic_list_mapper
     )
 );
 
-named!(
-    intrinsic_keyed_list<Span, Expression>,
-    do_parse!(
-        accumulator: map_res!(
-            first!(intrinsic_keyed_list_item),
-            into_vector_mapper
-        ) >>
-        result: fold_into_vector_many0!(
+/// Recognize the items of a list. The first item is parsed once, and
+/// decides whether the list is keyed or unkeyed.
+fn intrinsic_list_items(input: Span) -> Result<Span, ListItems> {
+    let (input, head) = opt!(input, first!(intrinsic_list_item))?;
+
+    match head {
+        Some((Some(key), value)) => intrinsic_keyed_list_items(input, (Some(key), value)),
+        head => intrinsic_unkeyed_list_items(input, head),
+    }
+}
+
+fn intrinsic_keyed_list_items<'a>(
+    input: Span<'a>,
+    head: (Option<Expression<'a>>, Expression<'a>),
+) -> Result<Span<'a>, ListItems<'a>> {
+    terminated!(
+        input,
+        fold_into_vector_many0!(
             preceded!(
                 first!(tag!(tokens::COMMA)),
-                first!(intrinsic_keyed_list_item)
+                map!(first!(intrinsic_keyed_list_item), Some)
             ),
-            accumulator
-        ) >>
-        opt!(first!(tag!(tokens::COMMA))) >>
-        (into_list(result))
+            vec![Some(head)]
+        ),
+        opt!(first!(tag!(tokens::COMMA)))
     )
-);
+}
 
-named!(
-    intrinsic_unkeyed_list<Span, Expression>,
-    do_parse!(
-        accumulator: map_res!(
-            opt!(first!(intrinsic_unkeyed_list_item)),
-            into_vector_mapper
-        ) >>
-        result: fold_into_vector_many0!(
-            preceded!(
-                first!(tag!(tokens::COMMA)),
-                opt!(first!(intrinsic_unkeyed_list_item))
-            ),
-            accumulator
-        ) >>
-        (into_list(result))
+fn intrinsic_unkeyed_list_items<'a>(
+    input: Span<'a>,
+    head: Option<(Option<Expression<'a>>, Expression<'a>)>,
+) -> Result<Span<'a>, ListItems<'a>> {
+    fold_into_vector_many0!(
+        input,
+        preceded!(
+            first!(tag!(tokens::COMMA)),
+            opt!(first!(intrinsic_unkeyed_list_item))
+        ),
+        vec![head]
     )
+}
+
+named!(
+    intrinsic_list_item<Span, (Option<Expression>, Expression)>,
+    map_opt!(array_pair, into_list_item)
 );
 
 named!(
-    intrinsic_keyed_list_item<Span, Option<(Option<Expression>, Expression)>>,
-    do_parse!(
-        key: terminated!(
-            expression,
-            first!(tag!(tokens::MAP))
-        ) >>
-        value: first!(expression) >>
-        (Some((Some(key), value)))
-    )
+    intrinsic_keyed_list_item<Span, (Option<Expression>, Expression)>,
+    map_opt!(array_pair, into_keyed_list_item)
 );
 
 named!(
     intrinsic_unkeyed_list_item<Span, (Option<Expression>, Expression)>,
-    do_parse!(
-        value: expression >>
-        ((None, value))
-    )
+    map_opt!(array_pair, into_unkeyed_list_item)
 );
 
 #[inline]
-fn into_list<'a>(
-    expressions: Vec<Option<(Option<Expression<'a>>, Expression<'a>)>>,
-) -> Expression<'a> {
-    Expression::List(expressions)
+fn into_list_item(item: ArrayItem) -> Option<(Option<Expression>, Expression)> {
+    match item {
+        ArrayItem::Pair(key, value) => {
+            into_list_value(value).map(|value| (key.map(|key| *key), value))
+        }
+        ArrayItem::Unpacked(_) => None,
+    }
+}
+
+#[inline]
+fn into_keyed_list_item(item: ArrayItem) -> Option<(Option<Expression>, Expression)> {
+    match item {
+        ArrayItem::Pair(Some(key), value) => {
+            into_list_value(value).map(|value| (Some(*key), value))
+        }
+        _ => None,
+    }
+}
+
+#[inline]
+fn into_unkeyed_list_item(item: ArrayItem) -> Option<(Option<Expression>, Expression)> {
+    match item {
+        ArrayItem::Pair(None, value) => into_list_value(value).map(|value| (None, value)),
+        _ => None,
+    }
+}
+
+/// Convert a value of a list: a nested array becomes a nested list,
+/// otherwise the value must be a variable, possibly by reference.
+fn into_list_value(value: Expression) -> Option<Expression> {
+    match value {
+        Expression::Array(_) | Expression::List(_) => into_destructuring_target(value),
+
+        Expression::Reference(value) => {
+            if is_list_variable(&value) {
+                Some(Expression::Reference(value))
+            } else {
+                None
+            }
+        }
+
+        value => {
+            if is_list_variable(&value) {
+                Some(value)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+fn is_list_variable(expression: &Expression) -> bool {
+    match *expression {
+        Expression::Variable(_) | Expression::StaticPropertyAccess(..) => true,
+        Expression::ArrayAccess(ref array, _) => !dereferencable_expression_is_nullsafe(array),
+        Expression::PropertyAccess(ref object, _) => !dereferencable_expression_is_nullsafe(object),
+        _ => false,
+    }
+}
+
+#[inline]
+fn into_list<'a>(closing_token: &[u8], items: ListItems<'a>) -> Expression<'a> {
+    if closing_token == tokens::RIGHT_SQUARE_BRACKET {
+        Expression::List(trim_trailing_slot(items))
+    } else {
+        Expression::List(items)
+    }
 }
 
 #[inline]
@@ -653,72 +1418,163 @@ fn intrinsic_list_mapper<'a, 'b>(
     }
 }
 
-named_attr!(
-    #[doc="
-        Recognize an unset.
-
-        # Examples
-
-        ```
-        # extern crate smallvec;
-        # #[macro_use]
-        # extern crate tagua_parser;
-        use tagua_parser::Result;
-        use tagua_parser::ast::{Expression, Variable};
-        use tagua_parser::rules::expressions::primaries::intrinsic_unset;
-        use tagua_parser::tokens::{
-            Span,
-            Token
-        };
+/// Recognize an unset.
+///
+/// An operand is a variable, an array element, or a property, e.g.
+/// `unset($a['k'], $o->p, A::$s)`. Any other expression, or an access
+/// with the nullsafe operator, or a parenthesized operand, cannot be
+/// unset and is rejected with `IntrinsicError::InvalidUnsetOperand`,
+/// as a failure (see `IntrinsicError`).
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tagua_parser;
+/// use tagua_parser::Result;
+/// use tagua_parser::ast::{Expression, Variable};
+/// use tagua_parser::rules::expressions::primaries::intrinsic_unset;
+/// use tagua_parser::tokens::Span;
+///
+/// # fn main() {
+/// assert_eq!(
+///     intrinsic_unset(Span::new(b"unset($foo, $bar)")),
+///     Ok((
+///         Span::new_at(b"", 17, 1, 18),
+///         Expression::Unset(vec![
+///             Expression::Variable(Variable(Span::new_at(b"foo", 7, 1, 8))),
+///             Expression::Variable(Variable(Span::new_at(b"bar", 13, 1, 14)))
+///         ])
+///     ))
+/// );
+/// # }
+/// ```
+pub fn intrinsic_unset(input: Span) -> Result<Span, Expression> {
+    let (next_input, operands) = unset_operands(input)?;
+
+    match into_intrinsic_operands(operands, is_unset_operand) {
+        Some(expressions) => Ok((next_input, into_unset(expressions))),
+
+        None => Err(Error::Failure(Context::Code(
+            input,
+            ErrorKind::Custom(IntrinsicError::InvalidUnsetOperand as u32),
+        ))),
+    }
+}
 
-        # fn main() {
-        assert_eq!(
-            intrinsic_unset(Span::new(b\"unset($foo, $bar)\")),
-            Ok((
-                Span::new_at(b\"\", 17, 1, 18),
-                Expression::Unset(smallvec![
-                    Variable(Span::new_at(b\"foo\", 7, 1, 8)),
-                    Variable(Span::new_at(b\"bar\", 13, 1, 14))
-                ])
-            ))
-        );
-        # }
-        ```
-    "],
-    pub intrinsic_unset<Span, Expression>,
+named!(
+    unset_operands<Span, Vec<Option<Expression>>>,
     do_parse!(
         accumulator: map_res!(
             preceded!(
                 keyword!(tokens::UNSET),
                 preceded!(
                     first!(tag!(tokens::LEFT_PARENTHESIS)),
-                    first!(variable)
+                    first!(intrinsic_operand)
                 )
             ),
-            into_smallvector_mapper
+            into_vector_mapper
         ) >>
         result: terminated!(
             fold_into_vector_many0!(
                 preceded!(
                     first!(tag!(tokens::COMMA)),
-                    first!(variable)
+                    first!(intrinsic_operand)
                 ),
                 accumulator
             ),
             first!(tag!(tokens::RIGHT_PARENTHESIS))
         ) >>
-        (into_unset(result))
+        (result)
     )
 );
 
-#[inline]
-fn into_smallvector_mapper(variable: Variable) -> StdResult<SmallVec<[Variable; 1]>, ()> {
-    Ok(smallvec![variable])
+named_attr!(
+    #[doc="
+        Recognize an operand of `isset` or `unset`. A parenthesized
+        operand, e.g. `($a)`, is the result of an expression, not a
+        variable, and it is represented by `None`.
+    "],
+    intrinsic_operand<Span, Option<Expression>>,
+    alt!(
+        terminated!(
+            delimited!(
+                tag!(tokens::LEFT_PARENTHESIS),
+                first!(expression),
+                first!(tag!(tokens::RIGHT_PARENTHESIS))
+            ),
+            peek!(
+                first!(
+                    alt!(
+                        tag!(tokens::COMMA)
+                      | tag!(tokens::RIGHT_PARENTHESIS)
+                    )
+                )
+            )
+        ) => { |_| None }
+      | expression => { Some }
+    )
+);
+
+/// Unwrap the operands of `isset` or `unset`, if they are all valid.
+fn into_intrinsic_operands<'a>(
+    operands: Vec<Option<Expression<'a>>>,
+    is_operand: fn(&Expression) -> bool,
+) -> Option<Vec<Expression<'a>>> {
+    let mut expressions = Vec::with_capacity(operands.len());
+
+    for operand in operands {
+        let expression = operand?;
+
+        if !is_operand(&expression) {
+            return None;
+        }
+
+        expressions.push(expression);
+    }
+
+    Some(expressions)
 }
 
 #[inline]
-fn into_unset(variables: SmallVec<[Variable; 1]>) -> Expression {
-    Expression::Unset(variables)
+fn into_unset(expressions: Vec<Expression>) -> Expression {
+    Expression::Unset(expressions)
+}
+
+fn is_unset_operand(expression: &Expression) -> bool {
+    match *expression {
+        Expression::Variable(_) | Expression::StaticPropertyAccess(..) => true,
+
+        Expression::ArrayAccess(ref array, ref index) => {
+            index.is_some() && !dereferencable_expression_is_nullsafe(array)
+        }
+
+        Expression::PropertyAccess(ref object, _) => !dereferencable_expression_is_nullsafe(object),
+
+        _ => false,
+    }
+}
+
+fn dereferencable_expression_is_nullsafe(expression: &DereferencableExpression) -> bool {
+    match *expression {
+        DereferencableExpression::Expression(ref expression) => is_nullsafe(expression),
+        _ => false,
+    }
+}
+
+fn is_nullsafe(expression: &Expression) -> bool {
+    match *expression {
+        Expression::NullsafeMethodCall(..) | Expression::NullsafePropertyAccess(..) => true,
+
+        Expression::ArrayAccess(ref subject, _)
+        | Expression::MethodCall(ref subject, _, _)
+        | Expression::PropertyAccess(ref subject, _) => {
+            dereferencable_expression_is_nullsafe(subject)
+        }
+
+        Expression::Call(ref callee, _) => is_nullsafe(callee),
+
+        _ => false,
+    }
 }
 
 named_attr!(
@@ -914,74 +1770,154 @@ fn exit_mapper<'a, 'b>(
     }
 }
 
-named_attr!(
-    #[doc="
-        Recognize an exit.
-
-        # Examples
-
-        ```
-        # extern crate smallvec;
-        # #[macro_use]
-        # extern crate tagua_parser;
-        use tagua_parser::Result;
-        use tagua_parser::ast::{Expression, Variable};
-        use tagua_parser::rules::expressions::primaries::intrinsic_isset;
-        use tagua_parser::tokens::{
-            Span,
-            Token
-        };
+/// Recognize an isset.
+///
+/// An operand is a variable, an array element, or a property,
+/// possibly accessed with the nullsafe operator, e.g. `isset($a['k'],
+/// $o?->p, A::$s)`. Any other expression, e.g. `1 + 2` or `($a)`, is
+/// rejected with `IntrinsicError::InvalidIssetOperand`, as a failure
+/// (see `IntrinsicError`).
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tagua_parser;
+/// use tagua_parser::Result;
+/// use tagua_parser::ast::{Expression, Variable};
+/// use tagua_parser::rules::expressions::primaries::intrinsic_isset;
+/// use tagua_parser::tokens::Span;
+///
+/// # fn main() {
+/// assert_eq!(
+///     intrinsic_isset(Span::new(b"isset($foo, $bar)")),
+///     Ok((
+///         Span::new_at(b"", 17, 1, 18),
+///         Expression::Isset(vec![
+///             Expression::Variable(Variable(Span::new_at(b"foo", 7, 1, 8))),
+///             Expression::Variable(Variable(Span::new_at(b"bar", 13, 1, 14)))
+///         ])
+///     ))
+/// );
+/// # }
+/// ```
+pub fn intrinsic_isset(input: Span) -> Result<Span, Expression> {
+    let (next_input, operands) = isset_operands(input)?;
+
+    match into_intrinsic_operands(operands, is_isset_operand) {
+        Some(expressions) => Ok((next_input, into_isset(expressions))),
+
+        None => Err(Error::Failure(Context::Code(
+            input,
+            ErrorKind::Custom(IntrinsicError::InvalidIssetOperand as u32),
+        ))),
+    }
+}
 
-        # fn main() {
-        assert_eq!(
-            intrinsic_isset(Span::new(b\"isset($foo, $bar)\")),
-            Ok((
-                Span::new_at(b\"\", 17, 1, 18),
-                Expression::Isset(smallvec![
-                    Variable(Span::new_at(b\"foo\", 7, 1, 8)),
-                    Variable(Span::new_at(b\"bar\", 13, 1, 14))
-                ])
-            ))
-        );
-        # }
-        ```
-    "],
-    pub intrinsic_isset<Span, Expression>,
+named!(
+    isset_operands<Span, Vec<Option<Expression>>>,
     do_parse!(
         accumulator: map_res!(
             preceded!(
                 keyword!(tokens::ISSET),
                 preceded!(
                     first!(tag!(tokens::LEFT_PARENTHESIS)),
-                    first!(variable)
+                    first!(intrinsic_operand)
                 )
             ),
-            into_smallvector_mapper
+            into_vector_mapper
         ) >>
         result: terminated!(
             fold_into_vector_many0!(
                 preceded!(
                     first!(tag!(tokens::COMMA)),
-                    first!(variable)
+                    first!(intrinsic_operand)
                 ),
                 accumulator
             ),
             first!(tag!(tokens::RIGHT_PARENTHESIS))
         ) >>
-        (into_isset(result))
+        (result)
     )
 );
 
 #[inline]
-fn into_isset(variables: SmallVec<[Variable; 1]>) -> Expression {
-    Expression::Isset(variables)
+fn into_isset(expressions: Vec<Expression>) -> Expression {
+    Expression::Isset(expressions)
 }
 
-named_attr!(
-    #[doc="
-        Recognize a print.
+fn is_isset_operand(expression: &Expression) -> bool {
+    match *expression {
+        Expression::NullsafePropertyAccess(..)
+        | Expression::PropertyAccess(..)
+        | Expression::StaticPropertyAccess(..)
+        | Expression::Variable(_) => true,
 
-        # Examples
+        Expression::ArrayAccess(_, ref index) => index.is_some(),
+
+        _ => false,
+    }
+}
+
+named_attr!(
+    #[doc="
+        Recognize an include or a require of a file.
+
+        The path is recognized as far as possible, e.g. `include $a or
+        $b` includes `$a or $b`.
+
+        # Examples
+
+        ```
+        use std::borrow::Cow;
+        use tagua_parser::Result;
+        use tagua_parser::ast::{Expression, ImportKind, Literal};
+        use tagua_parser::rules::expressions::primaries::intrinsic_import;
+        use tagua_parser::tokens::{
+            Span,
+            Token
+        };
+
+        # fn main() {
+        assert_eq!(
+            intrinsic_import(Span::new(b\"include_once 'a.php'\")),
+            Ok((
+                Span::new_at(b\"\", 20, 1, 21),
+                Expression::Import(
+                    ImportKind::IncludeOnce,
+                    Box::new(
+                        Expression::Literal(
+                            Literal::String(Token::new(Cow::from(&b\"a.php\"[..]), Span::new_at(b\"'a.php'\", 13, 1, 14)))
+                        )
+                    )
+                )
+            ))
+        );
+        # }
+        ```
+    "],
+    pub intrinsic_import<Span, Expression>,
+    do_parse!(
+        kind: alt!(
+            keyword!(tokens::INCLUDE_ONCE) => { |_| ImportKind::IncludeOnce }
+          | keyword!(tokens::INCLUDE)      => { |_| ImportKind::Include }
+          | keyword!(tokens::REQUIRE_ONCE) => { |_| ImportKind::RequireOnce }
+          | keyword!(tokens::REQUIRE)      => { |_| ImportKind::Require }
+        ) >>
+        path: first!(expression) >>
+        (into_import(kind, path))
+    )
+);
+
+#[inline]
+fn into_import(kind: ImportKind, path: Expression) -> Expression {
+    Expression::Import(kind, Box::new(path))
+}
+
+named_attr!(
+    #[doc="
+        Recognize a print.
+
+        # Examples
 
         ```
         use std::borrow::Cow;
@@ -1027,6 +1963,160 @@ fn print_mapper(expression: Expression) -> StdResult<Expression, ()> {
     Ok(Expression::Print(Box::new(expression)))
 }
 
+named_attr!(
+    #[doc="
+        Recognize a throw.
+
+        The thrown expression has the lowest precedence, e.g. `throw
+        $x ?? $y` throws `$x ?? $y`. A `throw` statement is an
+        expression statement.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{Arguments, Expression, Name, ScopeResolver};
+        use tagua_parser::rules::expressions::primaries::intrinsic_throw;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            intrinsic_throw(Span::new(b\"throw new E()\")),
+            Ok((
+                Span::new_at(b\"\", 13, 1, 14),
+                Expression::Throw(
+                    Box::new(
+                        Expression::New(
+                            ScopeResolver::ByName(Name::Unqualified(Span::new_at(b\"E\", 10, 1, 11))),
+                            Some(Arguments::List(vec![]))
+                        )
+                    )
+                )
+            ))
+        );
+        # }
+        ```
+    "],
+    pub intrinsic_throw<Span, Expression>,
+    map_res!(
+        preceded!(
+            keyword!(tokens::THROW),
+            first!(expression)
+        ),
+        throw_mapper
+    )
+);
+
+#[inline]
+fn throw_mapper(expression: Expression) -> StdResult<Expression, ()> {
+    Ok(Expression::Throw(Box::new(expression)))
+}
+
+named_attr!(
+    #[doc="
+        Recognize a yield, with an optional key and an optional value.
+
+        The value has a lower precedence than the assignment, but a
+        higher precedence than the `and`, `or` and `xor` logical
+        operators.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{Expression, Variable};
+        use tagua_parser::rules::expressions::primaries::intrinsic_yield;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            intrinsic_yield(Span::new(b\"yield $k => $v\")),
+            Ok((
+                Span::new_at(b\"\", 14, 1, 15),
+                Expression::Yield(
+                    Some(Box::new(Expression::Variable(Variable(Span::new_at(b\"k\", 7, 1, 8))))),
+                    Some(Box::new(Expression::Variable(Variable(Span::new_at(b\"v\", 13, 1, 14)))))
+                )
+            ))
+        );
+        # }
+        ```
+    "],
+    pub intrinsic_yield<Span, Expression>,
+    do_parse!(
+        keyword!(tokens::YIELD) >>
+        key_and_value: opt!(
+            complete!(
+                do_parse!(
+                    first_expression: first!(assignment) >>
+                    second_expression: opt!(
+                        complete!(
+                            preceded!(
+                                first!(tag!(tokens::YIELD_ARROW)),
+                                first!(assignment)
+                            )
+                        )
+                    ) >>
+                    (first_expression, second_expression)
+                )
+            )
+        ) >>
+        (into_yield(key_and_value))
+    )
+);
+
+#[inline]
+fn into_yield<'a>(
+    key_and_value: Option<(Expression<'a>, Option<Expression<'a>>)>,
+) -> Expression<'a> {
+    match key_and_value {
+        Some((key, Some(value))) => Expression::Yield(Some(Box::new(key)), Some(Box::new(value))),
+        Some((value, None)) => Expression::Yield(None, Some(Box::new(value))),
+        None => Expression::Yield(None, None),
+    }
+}
+
+named_attr!(
+    #[doc="
+        Recognize a yield from, i.e. a delegation to another generator.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{Expression, Variable};
+        use tagua_parser::rules::expressions::primaries::intrinsic_yield_from;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            intrinsic_yield_from(Span::new(b\"yield from $g\")),
+            Ok((
+                Span::new_at(b\"\", 13, 1, 14),
+                Expression::YieldFrom(
+                    Box::new(Expression::Variable(Variable(Span::new_at(b\"g\", 12, 1, 13))))
+                )
+            ))
+        );
+        # }
+        ```
+    "],
+    pub intrinsic_yield_from<Span, Expression>,
+    do_parse!(
+        keyword!(tokens::YIELD) >>
+        whitespace >>
+        keyword!(b"from") >>
+        not!(regex!(r"(?-u)^[a-zA-Z0-9_\x80-\xff]")) >>
+        expression: first!(assignment) >>
+        (into_yield_from(expression))
+    )
+);
+
+#[inline]
+fn into_yield_from(expression: Expression) -> Expression {
+    Expression::YieldFrom(Box::new(expression))
+}
+
 named_attr!(
     #[doc="
         Recognize an anonymous function.
@@ -1042,11 +2132,13 @@ named_attr!(
             AnonymousFunction,
             Arity,
             Expression,
+            FunctionKind,
             Name,
             Parameter,
             DeclarationScope,
             Statement,
             Ty,
+            Type,
             Variable
         };
         use tagua_parser::rules::expressions::primaries::anonymous_function;
@@ -1062,26 +2154,33 @@ named_attr!(
                 Span::new_at(b\"\", 61, 1, 62),
                 Expression::AnonymousFunction(
                     AnonymousFunction {
+                        attributes       : vec![],
                         declaration_scope: DeclarationScope::Dynamic,
                         inputs           : Arity::Finite(vec![
                             Parameter {
-                                ty   : Ty::Copy(None),
-                                name : Variable(Span::new_at(b\"x\", 12, 1, 13)),
-                                value: None
+                                attributes: vec![],
+                                promotion : None,
+                                ty        : Ty::Copy(None),
+                                name      : Variable(Span::new_at(b\"x\", 12, 1, 13)),
+                                value     : None
                             },
                             Parameter {
-                                ty   : Ty::Copy(Some(Name::FullyQualified(smallvec![Span::new_at(b\"I\", 16, 1, 17), Span::new_at(b\"J\", 18, 1, 19)]))),
-                                name : Variable(Span::new_at(b\"y\", 21, 1, 22)),
-                                value: None
+                                attributes: vec![],
+                                promotion : None,
+                                ty        : Ty::Copy(Some(Type::Name(Name::FullyQualified(smallvec![Span::new_at(b\"I\", 16, 1, 17), Span::new_at(b\"J\", 18, 1, 19)])))),
+                                name      : Variable(Span::new_at(b\"y\", 21, 1, 22)),
+                                value     : None
                             },
                             Parameter {
-                                ty   : Ty::Reference(Some(Name::FullyQualified(smallvec![Span::new_at(b\"int\", 24, 1, 25)]))),
-                                name : Variable(Span::new_at(b\"z\", 30, 1, 31)),
-                                value: None
+                                attributes: vec![],
+                                promotion : None,
+                                ty        : Ty::Reference(Some(Type::Name(Name::FullyQualified(smallvec![Span::new_at(b\"int\", 24, 1, 25)])))),
+                                name      : Variable(Span::new_at(b\"z\", 30, 1, 31)),
+                                value     : None
                             }
                         ]),
-                        output         : Ty::Reference(Some(Name::Unqualified(Span::new_at(b\"O\", 48, 1, 49)))),
-                        enclosing_scope: Some(vec![
+                        output           : Ty::Reference(Some(Type::Name(Name::Unqualified(Span::new_at(b\"O\", 48, 1, 49))))),
+                        enclosing_scope  : Some(vec![
                             Expression::Variable(Variable(Span::new_at(b\"a\", 39, 1, 40))),
                             Expression::Reference(
                                 Box::new(
@@ -1089,7 +2188,8 @@ named_attr!(
                                 )
                             )
                         ]),
-                        body: vec![Statement::Return]
+                        kind             : FunctionKind::Regular,
+                        body             : vec![Statement::Return(None)]
                     }
                 )
             ))
@@ -1099,23 +2199,17 @@ named_attr!(
     "],
     pub anonymous_function<Span, Expression>,
     do_parse!(
-        static_scope: opt!(keyword!(tokens::STATIC)) >>
+        attributes: attributes >>
+        static_scope: opt!(first!(keyword!(tokens::STATIC))) >>
         first!(keyword!(tokens::FUNCTION)) >>
         output_is_a_reference: opt!(first!(tag!(tokens::REFERENCE))) >>
-        inputs: first!(parameters) >>
+        inputs: first!(call!(checked_parameters, false)) >>
         enclosing_scope: opt!(first!(anonymous_function_use)) >>
-        output_type: opt!(
-            preceded!(
-                first!(tag!(tokens::FUNCTION_OUTPUT)),
-                alt!(
-                    first!(native_type)
-                  | first!(qualified_name)
-                )
-            )
-        ) >>
+        output: call!(output_type, output_is_a_reference.is_some()) >>
         body: first!(compound_statement) >>
         (
             into_anonymous_function(
+                attributes,
                 match static_scope {
                     Some(_) => {
                         DeclarationScope::Static
@@ -1125,9 +2219,8 @@ named_attr!(
                         DeclarationScope::Dynamic
                     }
                 },
-                output_is_a_reference.is_some(),
                 inputs,
-                output_type,
+                output,
                 enclosing_scope,
                 body
             )
@@ -1201,44 +2294,275 @@ fn into_anonymous_function_use_list_item(reference: bool, name: Variable) -> Exp
 
 #[inline]
 fn into_anonymous_function<'a>(
+    attributes: Vec<Attribute<'a>>,
     declaration_scope: DeclarationScope,
-    output_is_a_reference: bool,
     inputs: Arity<'a>,
-    output_type: Option<Name<'a>>,
+    output: Ty<'a>,
     enclosing_scope: Option<Vec<Expression<'a>>>,
     body: Vec<Statement<'a>>,
 ) -> Expression<'a> {
-    let output = if output_is_a_reference {
-        Ty::Reference(output_type)
-    } else {
-        Ty::Copy(output_type)
-    };
-
     Expression::AnonymousFunction(AnonymousFunction {
-        declaration_scope: declaration_scope,
-        inputs: inputs,
-        output: output,
-        enclosing_scope: enclosing_scope,
-        body: body,
+        attributes,
+        declaration_scope,
+        inputs,
+        output,
+        enclosing_scope,
+        kind: function_kind(&body),
+        body,
+    })
+}
+
+named_attr!(
+    #[doc="
+        Recognize an arrow function.
+
+        The body of an arrow function is an expression, which is
+        recognized as far as possible, e.g. `fn ($x) => $x + 1` and not
+        `(fn ($x) => $x) + 1`.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{
+            Arity,
+            ArrowFunction,
+            Attribute,
+            DeclarationScope,
+            Expression,
+            FunctionKind,
+            Name,
+            Ty,
+            Variable
+        };
+        use tagua_parser::rules::expressions::primaries::arrow_function;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            arrow_function(Span::new(b\"#[A] fn &() => $x\")),
+            Ok((
+                Span::new_at(b\"\", 17, 1, 18),
+                Expression::ArrowFunction(
+                    ArrowFunction {
+                        attributes       : vec![
+                            Attribute {
+                                name     : Name::Unqualified(Span::new_at(b\"A\", 2, 1, 3)),
+                                arguments: None
+                            }
+                        ],
+                        declaration_scope: DeclarationScope::Dynamic,
+                        inputs           : Arity::Constant,
+                        output           : Ty::Reference(None),
+                        kind             : FunctionKind::Regular,
+                        body             : Box::new(Expression::Variable(Variable(Span::new_at(b\"x\", 16, 1, 17))))
+                    }
+                )
+            ))
+        );
+        # }
+        ```
+    "],
+    pub arrow_function<Span, Expression>,
+    do_parse!(
+        attributes: attributes >>
+        static_scope: opt!(first!(keyword!(tokens::STATIC))) >>
+        first!(keyword!(tokens::FN)) >>
+        output_is_a_reference: opt!(first!(tag!(tokens::REFERENCE))) >>
+        inputs: first!(call!(checked_parameters, false)) >>
+        output: call!(output_type, output_is_a_reference.is_some()) >>
+        first!(tag!(tokens::FUNCTION_ARROW)) >>
+        body: first!(expression) >>
+        (
+            into_arrow_function(
+                attributes,
+                match static_scope {
+                    Some(_) => {
+                        DeclarationScope::Static
+                    },
+
+                    None => {
+                        DeclarationScope::Dynamic
+                    }
+                },
+                inputs,
+                output,
+                body
+            )
+        )
+    )
+);
+
+#[inline]
+fn into_arrow_function<'a>(
+    attributes: Vec<Attribute<'a>>,
+    declaration_scope: DeclarationScope,
+    inputs: Arity<'a>,
+    output: Ty<'a>,
+    body: Expression<'a>,
+) -> Expression<'a> {
+    Expression::ArrowFunction(ArrowFunction {
+        attributes,
+        declaration_scope,
+        inputs,
+        output,
+        kind: arrow_function_kind(&body),
+        body: Box::new(body),
     })
 }
 
+/// Recognize a match expression.
+///
+/// An arm has a list of conditions, or is the `default` arm. A match
+/// expression has at most one `default` arm, otherwise it fails with
+/// `MatchError::MultipleDefaultArms`. Trailing commas are allowed
+/// after the last condition of an arm, and after the last arm.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tagua_parser;
+/// use tagua_parser::Result;
+/// use tagua_parser::ast::{Expression, Literal, MatchArm, Name, Variable};
+/// use tagua_parser::rules::expressions::primaries::match_expression;
+/// use tagua_parser::tokens::{Span, Token};
+///
+/// # fn main() {
+/// assert_eq!(
+///     match_expression(Span::new(b"match ($x) { A, B, => 1, }")),
+///     Ok((
+///         Span::new_at(b"", 26, 1, 27),
+///         Expression::Match(
+///             Box::new(Expression::Variable(Variable(Span::new_at(b"x", 8, 1, 9)))),
+///             vec![
+///                 MatchArm {
+///                     conditions: Some(vec![
+///                         Expression::Name(Name::Unqualified(Span::new_at(b"A", 13, 1, 14))),
+///                         Expression::Name(Name::Unqualified(Span::new_at(b"B", 16, 1, 17)))
+///                     ]),
+///                     expression: Expression::Literal(Literal::Integer(Token::new(1i64, Span::new_at(b"1", 22, 1, 23)))),
+///                     span      : Span::new_at(b"A, B, => 1", 13, 1, 14)
+///                 }
+///             ]
+///         )
+///     ))
+/// );
+/// # }
+/// ```
+pub fn match_expression(input: Span) -> Result<Span, Expression> {
+    let (next_input, (subject, arms)) = match_subject_and_arms(input)?;
+
+    if arms.iter().filter(|arm| arm.conditions.is_none()).count() > 1 {
+        return Err(Error::Failure(Context::Code(
+            input,
+            ErrorKind::Custom(MatchError::MultipleDefaultArms as u32),
+        )));
+    }
+
+    Ok((next_input, Expression::Match(Box::new(subject), arms)))
+}
+
+named!(
+    match_subject_and_arms<Span, (Expression, Vec<MatchArm>)>,
+    do_parse!(
+        keyword!(tokens::MATCH) >>
+        first!(tag!(tokens::LEFT_PARENTHESIS)) >>
+        subject: first!(expression) >>
+        first!(tag!(tokens::RIGHT_PARENTHESIS)) >>
+        first!(tag!(tokens::LEFT_CURLY_BRACKET)) >>
+        arms: opt!(first!(match_arms)) >>
+        first!(tag!(tokens::RIGHT_CURLY_BRACKET)) >>
+        (subject, arms.unwrap_or_else(Vec::new))
+    )
+);
+
+named!(
+    match_arms<Span, Vec<MatchArm>>,
+    do_parse!(
+        accumulator: map!(match_arm, |arm| vec![arm]) >>
+        result: fold_into_vector_many0!(
+            preceded!(
+                first!(tag!(tokens::COMMA)),
+                first!(match_arm)
+            ),
+            accumulator
+        ) >>
+        opt!(complete!(first!(tag!(tokens::COMMA)))) >>
+        (result)
+    )
+);
+
+/// Recognize an arm of a match expression, and compute its span.
+fn match_arm(input: Span) -> Result<Span, MatchArm> {
+    let (next_input, (conditions, expression)) = match_arm_conditions_and_expression(input)?;
+    let span = input.slice(..next_input.offset - input.offset);
+
+    Ok((
+        next_input,
+        MatchArm {
+            conditions,
+            expression,
+            span,
+        },
+    ))
+}
+
+named!(
+    match_arm_conditions_and_expression<Span, (Option<Vec<Expression>>, Expression)>,
+    do_parse!(
+        conditions: alt_complete!(
+            terminated!(
+                keyword!(tokens::DEFAULT),
+                first!(tag!(tokens::MATCH_ARROW))
+            ) => { |_| None }
+          | terminated!(
+                match_conditions,
+                first!(tag!(tokens::MATCH_ARROW))
+            ) => { Some }
+        ) >>
+        expression: first!(expression) >>
+        (conditions, expression)
+    )
+);
+
+named!(
+    match_conditions<Span, Vec<Expression>>,
+    do_parse!(
+        accumulator: map!(expression, |condition| vec![condition]) >>
+        result: fold_into_vector_many0!(
+            preceded!(
+                first!(tag!(tokens::COMMA)),
+                first!(expression)
+            ),
+            accumulator
+        ) >>
+        opt!(complete!(first!(tag!(tokens::COMMA)))) >>
+        (result)
+    )
+);
+
 #[cfg(test)]
 mod tests {
     use super::super::super::super::ast::{
-        AnonymousFunction, Arity, DeclarationScope, DereferencableExpression, Expression, Literal,
-        Name, Parameter, RelativeScope, ScopeResolver, Statement, Ty, Variable,
+        AnonymousFunction, Argument, Arguments, Arity, ArrayItem, ArrowFunction, Attribute,
+        BinaryOperator, DeclarationScope, DereferencableExpression, Expression, FunctionKind,
+        ImportKind, Literal, MagicConstant, MatchArm, MemberName, NAryOperation, Name, Parameter,
+        RelativeScope, ScopeResolver, Statement, Ty, Type, Variable,
     };
     use super::super::super::super::internal::{Context, Error, ErrorKind};
     use super::super::super::super::tokens::{Span, Token};
+    use super::super::super::statements::function::FunctionError;
     use super::super::expression;
     use super::{
-        anonymous_function, array, class_constant_access, dereferencable_expression, intrinsic,
-        intrinsic_construct, intrinsic_echo, intrinsic_empty, intrinsic_eval, intrinsic_exit,
-        intrinsic_isset, intrinsic_list, intrinsic_operator, intrinsic_print, intrinsic_unset,
-        primary, relative_scope, scope_resolution_qualifier,
+        anonymous_function, arguments, array, arrow_function, class_constant_access, clone,
+        dereferencable_expression, intrinsic, intrinsic_construct, intrinsic_echo, intrinsic_empty,
+        intrinsic_eval, intrinsic_exit, intrinsic_import, intrinsic_isset, intrinsic_list,
+        intrinsic_operator, intrinsic_print, intrinsic_throw, intrinsic_unset, intrinsic_yield,
+        intrinsic_yield_from, match_expression, new, primary, relative_scope,
+        scope_resolution_qualifier, IntrinsicError, MatchError,
     };
     use std::borrow::Cow;
+    use std::time::{Duration, Instant};
 
     #[test]
     fn case_class_constant_access_relative_self() {
@@ -1326,15 +2650,140 @@ mod tests {
     }
 
     #[test]
-    fn case_scope_resolution_qualifier_by_relative() {
-        let input = Span::new(b"self");
+    fn case_magic_constant_class_name() {
+        let input = Span::new(b"Foo\\Bar::class");
         let output = Ok((
-            Span::new_at(b"", 4, 1, 5),
-            ScopeResolver::ByRelative(RelativeScope::ToSelf),
-        ));
-
-        assert_eq!(scope_resolution_qualifier(input), output);
-    }
+            Span::new_at(b"", 14, 1, 15),
+            Expression::MagicConstant(MagicConstant::ClassName(
+                ScopeResolver::ByName(Name::Qualified(smallvec![
+                    Span::new(b"Foo"),
+                    Span::new_at(b"Bar", 4, 1, 5)
+                ])),
+                Span::new_at(b"class", 9, 1, 10),
+            )),
+        ));
+
+        assert_eq!(class_constant_access(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_magic_constant_class_name_is_case_insensitive() {
+        let input = Span::new(b"self::CLASS");
+        let output = Ok((
+            Span::new_at(b"", 11, 1, 12),
+            Expression::MagicConstant(MagicConstant::ClassName(
+                ScopeResolver::ByRelative(RelativeScope::ToSelf),
+                Span::new_at(b"CLASS", 6, 1, 7),
+            )),
+        ));
+
+        assert_eq!(class_constant_access(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_magic_constant_class_name_of_an_object() {
+        let input = Span::new(b"$x::class");
+        let output = Ok((
+            Span::new_at(b"", 9, 1, 10),
+            Expression::MagicConstant(MagicConstant::ClassName(
+                ScopeResolver::ByExpression(DereferencableExpression::Variable(Variable(
+                    Span::new_at(b"x", 1, 1, 2),
+                ))),
+                Span::new_at(b"class", 4, 1, 5),
+            )),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_magic_constants() {
+        type Constructor = fn(Span<'static>) -> MagicConstant<'static>;
+
+        let constants: [(&[u8], Constructor); 8] = [
+            (b"__CLASS__", MagicConstant::Class),
+            (b"__DIR__", MagicConstant::Directory),
+            (b"__FILE__", MagicConstant::File),
+            (b"__FUNCTION__", MagicConstant::Function),
+            (b"__LINE__", MagicConstant::Line),
+            (b"__METHOD__", MagicConstant::Method),
+            (b"__NAMESPACE__", MagicConstant::Namespace),
+            (b"__TRAIT__", MagicConstant::Trait),
+        ];
+
+        for &(input, constant) in &constants {
+            let input = Span::new(input);
+            let output = Ok((
+                Span::new_at(
+                    b"",
+                    input.as_slice().len(),
+                    1,
+                    input.as_slice().len() as u32 + 1,
+                ),
+                Expression::MagicConstant(constant(input)),
+            ));
+
+            assert_eq!(primary(input), output);
+            assert_eq!(expression(input), output);
+        }
+    }
+
+    #[test]
+    fn case_magic_constant_is_case_insensitive() {
+        let input = Span::new(b"__line__");
+        let output = Ok((
+            Span::new_at(b"", 8, 1, 9),
+            Expression::MagicConstant(MagicConstant::Line(input)),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_magic_constant_must_be_unqualified() {
+        let input = Span::new(b"\\__LINE__");
+        let output = Ok((
+            Span::new_at(b"", 9, 1, 10),
+            Expression::Name(Name::FullyQualified(smallvec![Span::new_at(
+                b"__LINE__",
+                1,
+                1,
+                2
+            )])),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_magic_constant_prefix_is_a_name() {
+        let input = Span::new(b"__LINE__S");
+        let output = Ok((
+            Span::new_at(b"", 9, 1, 10),
+            Expression::Name(Name::Unqualified(input)),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_scope_resolution_qualifier_by_relative() {
+        let input = Span::new(b"self");
+        let output = Ok((
+            Span::new_at(b"", 4, 1, 5),
+            ScopeResolver::ByRelative(RelativeScope::ToSelf),
+        ));
+
+        assert_eq!(scope_resolution_qualifier(input), output);
+    }
 
     #[test]
     fn case_scope_resolution_qualifier_by_name() {
@@ -1381,71 +2830,863 @@ mod tests {
             )))),
         ));
 
-        assert_eq!(dereferencable_expression(input), output);
+        assert_eq!(dereferencable_expression(input), output);
+    }
+
+    #[test]
+    fn case_dereferencable_expression_array() {
+        let input = Span::new(b"['C', 'f']");
+        let output = Ok((
+            Span::new_at(b"", 10, 1, 11),
+            DereferencableExpression::Array(Box::new(Expression::Array(vec![
+                ArrayItem::Pair(
+                    None,
+                    Expression::Literal(Literal::String(Token::new(
+                        Cow::from(&b"C"[..]),
+                        Span::new_at(b"'C'", 1, 1, 2),
+                    ))),
+                ),
+                ArrayItem::Pair(
+                    None,
+                    Expression::Literal(Literal::String(Token::new(
+                        Cow::from(&b"f"[..]),
+                        Span::new_at(b"'f'", 6, 1, 7),
+                    ))),
+                ),
+            ]))),
+        ));
+
+        assert_eq!(dereferencable_expression(input), output);
+    }
+
+    #[test]
+    fn case_dereferencable_expression_string() {
+        let input = Span::new(b"'C'");
+        let output = Ok((
+            Span::new_at(b"", 3, 1, 4),
+            DereferencableExpression::String(Literal::String(Token::new(
+                Cow::from(&b"C"[..]),
+                Span::new_at(b"'C'", 0, 1, 1),
+            ))),
+        ));
+
+        assert_eq!(dereferencable_expression(input), output);
+    }
+
+    #[test]
+    fn case_dereferencable_expression_double_quoted_string() {
+        let input = Span::new(b"\"C\"");
+        let output = Ok((
+            Span::new_at(b"", 3, 1, 4),
+            DereferencableExpression::String(Literal::String(Token::new(
+                Cow::from(&b"C"[..]),
+                Span::new_at(b"\"C\"", 0, 1, 1),
+            ))),
+        ));
+
+        assert_eq!(dereferencable_expression(input), output);
+    }
+
+    #[test]
+    fn case_relative_scope_self() {
+        let input = Span::new(b"self");
+        let output = Ok((Span::new_at(b"", 4, 1, 5), RelativeScope::ToSelf));
+
+        assert_eq!(relative_scope(input), output);
+    }
+
+    #[test]
+    fn case_relative_scope_parent() {
+        let input = Span::new(b"parent");
+        let output = Ok((Span::new_at(b"", 6, 1, 7), RelativeScope::ToParent));
+
+        assert_eq!(relative_scope(input), output);
+    }
+
+    #[test]
+    fn case_relative_scope_static() {
+        let input = Span::new(b"static");
+        let output = Ok((Span::new_at(b"", 6, 1, 7), RelativeScope::ToStatic));
+
+        assert_eq!(relative_scope(input), output);
+    }
+
+    #[test]
+    fn case_call() {
+        let input = Span::new(b"foo(1, 2)");
+        let output = Ok((
+            Span::new_at(b"", 9, 1, 10),
+            Expression::Call(
+                Box::new(Expression::Name(Name::Unqualified(Span::new(b"foo")))),
+                Arguments::List(vec![
+                    Argument::Positional(Expression::Literal(Literal::Integer(Token::new(
+                        1i64,
+                        Span::new_at(b"1", 4, 1, 5),
+                    )))),
+                    Argument::Positional(Expression::Literal(Literal::Integer(Token::new(
+                        2i64,
+                        Span::new_at(b"2", 7, 1, 8),
+                    )))),
+                ]),
+            ),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_call_without_arguments() {
+        let input = Span::new(b"\\foo ( )");
+        let output = Ok((
+            Span::new_at(b"", 8, 1, 9),
+            Expression::Call(
+                Box::new(Expression::Name(Name::FullyQualified(smallvec![
+                    Span::new_at(b"foo", 1, 1, 2)
+                ]))),
+                Arguments::List(vec![]),
+            ),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_call_a_name_starting_with_a_keyword() {
+        let input = Span::new(b"newline()");
+        let output = Ok((
+            Span::new_at(b"", 9, 1, 10),
+            Expression::Call(
+                Box::new(Expression::Name(Name::Unqualified(Span::new(b"newline")))),
+                Arguments::List(vec![]),
+            ),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_call_a_variable() {
+        let input = Span::new(b"$foo($bar)");
+        let output = Ok((
+            Span::new_at(b"", 10, 1, 11),
+            Expression::Call(
+                Box::new(Expression::Variable(Variable(Span::new_at(
+                    b"foo", 1, 1, 2,
+                )))),
+                Arguments::List(vec![Argument::Positional(Expression::Variable(Variable(
+                    Span::new_at(b"bar", 6, 1, 7),
+                )))]),
+            ),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_call_chained() {
+        let input = Span::new(b"foo()()");
+        let output = Ok((
+            Span::new_at(b"", 7, 1, 8),
+            Expression::Call(
+                Box::new(Expression::Call(
+                    Box::new(Expression::Name(Name::Unqualified(Span::new(b"foo")))),
+                    Arguments::List(vec![]),
+                )),
+                Arguments::List(vec![]),
+            ),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_call_grouped_by_parenthesis() {
+        let input = Span::new(b"($foo)()");
+        let output = Ok((
+            Span::new_at(b"", 8, 1, 9),
+            Expression::Call(
+                Box::new(Expression::Variable(Variable(Span::new_at(
+                    b"foo", 2, 1, 3,
+                )))),
+                Arguments::List(vec![]),
+            ),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_call_with_unpacked_arguments() {
+        let input = Span::new(b"f($x, ... $y)");
+        let output = Ok((
+            Span::new_at(b"", 13, 1, 14),
+            Expression::Call(
+                Box::new(Expression::Name(Name::Unqualified(Span::new(b"f")))),
+                Arguments::List(vec![
+                    Argument::Positional(Expression::Variable(Variable(Span::new_at(
+                        b"x", 3, 1, 4,
+                    )))),
+                    Argument::Unpacked(Expression::Variable(Variable(Span::new_at(
+                        b"y", 11, 1, 12,
+                    )))),
+                ]),
+            ),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_call_with_named_arguments() {
+        let input = Span::new(b"f(array: $x, b : C::D)");
+        let output = Ok((
+            Span::new_at(b"", 22, 1, 23),
+            Expression::Call(
+                Box::new(Expression::Name(Name::Unqualified(Span::new(b"f")))),
+                Arguments::List(vec![
+                    Argument::Named(
+                        Span::new_at(b"array", 2, 1, 3),
+                        Expression::Variable(Variable(Span::new_at(b"x", 10, 1, 11))),
+                    ),
+                    Argument::Named(
+                        Span::new_at(b"b", 13, 1, 14),
+                        Expression::ClassConstantAccess(
+                            ScopeResolver::ByName(Name::Unqualified(Span::new_at(b"C", 17, 1, 18))),
+                            Span::new_at(b"D", 20, 1, 21),
+                        ),
+                    ),
+                ]),
+            ),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_call_with_a_class_constant_argument() {
+        let input = Span::new(b"f(C::D)");
+        let output = Ok((
+            Span::new_at(b"", 7, 1, 8),
+            Expression::Call(
+                Box::new(Expression::Name(Name::Unqualified(Span::new(b"f")))),
+                Arguments::List(vec![Argument::Positional(Expression::ClassConstantAccess(
+                    ScopeResolver::ByName(Name::Unqualified(Span::new_at(b"C", 2, 1, 3))),
+                    Span::new_at(b"D", 5, 1, 6),
+                ))]),
+            ),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_call_with_a_trailing_comma() {
+        let input = Span::new(b"f($x,)");
+        let output = Ok((
+            Span::new_at(b"", 6, 1, 7),
+            Expression::Call(
+                Box::new(Expression::Name(Name::Unqualified(Span::new(b"f")))),
+                Arguments::List(vec![Argument::Positional(Expression::Variable(Variable(
+                    Span::new_at(b"x", 3, 1, 4),
+                )))]),
+            ),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_call_first_class_callable() {
+        let input = Span::new(b"strlen( ... )");
+        let output = Ok((
+            Span::new_at(b"", 13, 1, 14),
+            Expression::Call(
+                Box::new(Expression::Name(Name::Unqualified(Span::new(b"strlen")))),
+                Arguments::FirstClassCallable,
+            ),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_arguments_vector_capacity() {
+        if let Ok((_, Arguments::List(arguments))) = arguments(Span::new(b"(1, 2, 3)")) {
+            assert_eq!(arguments.capacity(), arguments.len());
+            assert_eq!(arguments.len(), 3);
+        } else {
+            panic!("Expected a list of arguments.");
+        }
+    }
+
+    #[test]
+    fn case_invalid_arguments_empty_trailing_comma() {
+        let input = Span::new(b"(,)");
+
+        assert_eq!(
+            arguments(input),
+            Err(Error::Error(Context::Code(
+                Span::new_at(b",)", 1, 1, 2),
+                ErrorKind::Alt
+            )))
+        );
+    }
+
+    #[test]
+    fn case_invalid_arguments_trailing_commas() {
+        let input = Span::new(b"(1,,)");
+
+        assert_eq!(
+            arguments(input),
+            Err(Error::Error(Context::Code(
+                Span::new_at(b"1,,)", 1, 1, 2),
+                ErrorKind::Alt
+            )))
+        );
+    }
+
+    #[test]
+    fn case_array_access() {
+        let input = Span::new(b"$foo[1][]");
+        let output = Ok((
+            Span::new_at(b"", 9, 1, 10),
+            Expression::ArrayAccess(
+                DereferencableExpression::Expression(Box::new(Expression::ArrayAccess(
+                    DereferencableExpression::Variable(Variable(Span::new_at(b"foo", 1, 1, 2))),
+                    Some(Box::new(Expression::Literal(Literal::Integer(Token::new(
+                        1i64,
+                        Span::new_at(b"1", 5, 1, 6),
+                    ))))),
+                ))),
+                None,
+            ),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_array_access_on_a_constant() {
+        let input = Span::new(b"FOO[0]");
+        let output = Ok((
+            Span::new_at(b"", 6, 1, 7),
+            Expression::ArrayAccess(
+                DereferencableExpression::Expression(Box::new(Expression::Name(
+                    Name::Unqualified(Span::new(b"FOO")),
+                ))),
+                Some(Box::new(Expression::Literal(Literal::Integer(Token::new(
+                    0i64,
+                    Span::new_at(b"0", 4, 1, 5),
+                ))))),
+            ),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_property_access() {
+        let input = Span::new(b"$foo -> bar");
+        let output = Ok((
+            Span::new_at(b"", 11, 1, 12),
+            Expression::PropertyAccess(
+                DereferencableExpression::Variable(Variable(Span::new_at(b"foo", 1, 1, 2))),
+                MemberName::Name(Span::new_at(b"bar", 8, 1, 9)),
+            ),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_property_access_by_variable() {
+        let input = Span::new(b"$foo->$bar");
+        let output = Ok((
+            Span::new_at(b"", 10, 1, 11),
+            Expression::PropertyAccess(
+                DereferencableExpression::Variable(Variable(Span::new_at(b"foo", 1, 1, 2))),
+                MemberName::Expression(Box::new(Expression::Variable(Variable(Span::new_at(
+                    b"bar", 7, 1, 8,
+                ))))),
+            ),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_property_access_by_expression() {
+        let input = Span::new(b"$foo->{'bar'}");
+        let output = Ok((
+            Span::new_at(b"", 13, 1, 14),
+            Expression::PropertyAccess(
+                DereferencableExpression::Variable(Variable(Span::new_at(b"foo", 1, 1, 2))),
+                MemberName::Expression(Box::new(Expression::Literal(Literal::String(Token::new(
+                    Cow::from(&b"bar"[..]),
+                    Span::new_at(b"'bar'", 7, 1, 8),
+                ))))),
+            ),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_method_call() {
+        let input = Span::new(b"$obj->m(1)");
+        let output = Ok((
+            Span::new_at(b"", 10, 1, 11),
+            Expression::MethodCall(
+                DereferencableExpression::Variable(Variable(Span::new_at(b"obj", 1, 1, 2))),
+                MemberName::Name(Span::new_at(b"m", 6, 1, 7)),
+                Arguments::List(vec![Argument::Positional(Expression::Literal(
+                    Literal::Integer(Token::new(1i64, Span::new_at(b"1", 8, 1, 9))),
+                ))]),
+            ),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_method_call_chained() {
+        let input = Span::new(b"$a->b()->c[0]->d()");
+        let output = Ok((
+            Span::new_at(b"", 18, 1, 19),
+            Expression::MethodCall(
+                DereferencableExpression::Expression(Box::new(Expression::ArrayAccess(
+                    DereferencableExpression::Expression(Box::new(Expression::PropertyAccess(
+                        DereferencableExpression::Expression(Box::new(Expression::MethodCall(
+                            DereferencableExpression::Variable(Variable(Span::new_at(
+                                b"a", 1, 1, 2,
+                            ))),
+                            MemberName::Name(Span::new_at(b"b", 4, 1, 5)),
+                            Arguments::List(vec![]),
+                        ))),
+                        MemberName::Name(Span::new_at(b"c", 9, 1, 10)),
+                    ))),
+                    Some(Box::new(Expression::Literal(Literal::Integer(Token::new(
+                        0i64,
+                        Span::new_at(b"0", 11, 1, 12),
+                    ))))),
+                ))),
+                MemberName::Name(Span::new_at(b"d", 15, 1, 16)),
+                Arguments::List(vec![]),
+            ),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_static_method_call() {
+        let input = Span::new(b"A::b()");
+        let output = Ok((
+            Span::new_at(b"", 6, 1, 7),
+            Expression::StaticMethodCall(
+                ScopeResolver::ByName(Name::Unqualified(Span::new(b"A"))),
+                MemberName::Name(Span::new_at(b"b", 3, 1, 4)),
+                Arguments::List(vec![]),
+            ),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_static_method_call_relative_parent() {
+        let input = Span::new(b"parent::__construct($x)");
+        let output = Ok((
+            Span::new_at(b"", 23, 1, 24),
+            Expression::StaticMethodCall(
+                ScopeResolver::ByRelative(RelativeScope::ToParent),
+                MemberName::Name(Span::new_at(b"__construct", 8, 1, 9)),
+                Arguments::List(vec![Argument::Positional(Expression::Variable(Variable(
+                    Span::new_at(b"x", 21, 1, 22),
+                )))]),
+            ),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_static_method_call_by_variable() {
+        let input = Span::new(b"$a::$b()");
+        let output = Ok((
+            Span::new_at(b"", 8, 1, 9),
+            Expression::StaticMethodCall(
+                ScopeResolver::ByExpression(DereferencableExpression::Variable(Variable(
+                    Span::new_at(b"a", 1, 1, 2),
+                ))),
+                MemberName::Expression(Box::new(Expression::Variable(Variable(Span::new_at(
+                    b"b", 5, 1, 6,
+                ))))),
+                Arguments::List(vec![]),
+            ),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_static_method_call_by_expression() {
+        let input = Span::new(b"A::{'b'}()");
+        let output = Ok((
+            Span::new_at(b"", 10, 1, 11),
+            Expression::StaticMethodCall(
+                ScopeResolver::ByName(Name::Unqualified(Span::new(b"A"))),
+                MemberName::Expression(Box::new(Expression::Literal(Literal::String(Token::new(
+                    Cow::from(&b"b"[..]),
+                    Span::new_at(b"'b'", 4, 1, 5),
+                ))))),
+                Arguments::List(vec![]),
+            ),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_static_property_access() {
+        let input = Span::new(b"static::$foo");
+        let output = Ok((
+            Span::new_at(b"", 12, 1, 13),
+            Expression::StaticPropertyAccess(
+                ScopeResolver::ByRelative(RelativeScope::ToStatic),
+                Variable(Span::new_at(b"foo", 9, 1, 10)),
+            ),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_new() {
+        let input = Span::new(b"new Foo(1, 2)");
+        let output = Ok((
+            Span::new_at(b"", 13, 1, 14),
+            Expression::New(
+                ScopeResolver::ByName(Name::Unqualified(Span::new_at(b"Foo", 4, 1, 5))),
+                Some(Arguments::List(vec![
+                    Argument::Positional(Expression::Literal(Literal::Integer(Token::new(
+                        1i64,
+                        Span::new_at(b"1", 8, 1, 9),
+                    )))),
+                    Argument::Positional(Expression::Literal(Literal::Integer(Token::new(
+                        2i64,
+                        Span::new_at(b"2", 11, 1, 12),
+                    )))),
+                ])),
+            ),
+        ));
+
+        assert_eq!(new(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_new_without_arguments() {
+        let input = Span::new(b"new Foo\\Bar");
+        let output = Ok((
+            Span::new_at(b"", 11, 1, 12),
+            Expression::New(
+                ScopeResolver::ByName(Name::Qualified(smallvec![
+                    Span::new_at(b"Foo", 4, 1, 5),
+                    Span::new_at(b"Bar", 8, 1, 9)
+                ])),
+                None,
+            ),
+        ));
+
+        assert_eq!(new(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_new_relative_scope() {
+        let input = Span::new(b"new self");
+        let output = Ok((
+            Span::new_at(b"", 8, 1, 9),
+            Expression::New(ScopeResolver::ByRelative(RelativeScope::ToSelf), None),
+        ));
+
+        assert_eq!(new(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_new_a_name_starting_with_a_relative_scope() {
+        let input = Span::new(b"new selfish");
+        let output = Ok((
+            Span::new_at(b"", 11, 1, 12),
+            Expression::New(
+                ScopeResolver::ByName(Name::Unqualified(Span::new_at(b"selfish", 4, 1, 5))),
+                None,
+            ),
+        ));
+
+        assert_eq!(new(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_new_dereferencable_expression() {
+        let input = Span::new(b"new $a->b['c']($d)");
+        let output = Ok((
+            Span::new_at(b"", 18, 1, 19),
+            Expression::New(
+                ScopeResolver::ByExpression(DereferencableExpression::Expression(Box::new(
+                    Expression::ArrayAccess(
+                        DereferencableExpression::Expression(Box::new(Expression::PropertyAccess(
+                            DereferencableExpression::Variable(Variable(Span::new_at(
+                                b"a", 5, 1, 6,
+                            ))),
+                            MemberName::Name(Span::new_at(b"b", 8, 1, 9)),
+                        ))),
+                        Some(Box::new(Expression::Literal(Literal::String(Token::new(
+                            Cow::from(&b"c"[..]),
+                            Span::new_at(b"'c'", 10, 1, 11),
+                        ))))),
+                    ),
+                ))),
+                Some(Arguments::List(vec![Argument::Positional(
+                    Expression::Variable(Variable(Span::new_at(b"d", 16, 1, 17))),
+                )])),
+            ),
+        ));
+
+        assert_eq!(new(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_clone() {
+        let input = Span::new(b"clone $foo->bar()");
+        let output = Ok((
+            Span::new_at(b"", 17, 1, 18),
+            Expression::Clone(Box::new(Expression::MethodCall(
+                DereferencableExpression::Variable(Variable(Span::new_at(b"foo", 7, 1, 8))),
+                MemberName::Name(Span::new_at(b"bar", 12, 1, 13)),
+                Arguments::List(vec![]),
+            ))),
+        ));
+
+        assert_eq!(clone(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_nullsafe_property_access() {
+        let input = Span::new(b"$foo ?-> bar");
+        let output = Ok((
+            Span::new_at(b"", 12, 1, 13),
+            Expression::NullsafePropertyAccess(
+                DereferencableExpression::Variable(Variable(Span::new_at(b"foo", 1, 1, 2))),
+                MemberName::Name(Span::new_at(b"bar", 9, 1, 10)),
+            ),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_nullsafe_method_call_chained() {
+        let input = Span::new(b"$a?->b()?->c");
+        let output = Ok((
+            Span::new_at(b"", 12, 1, 13),
+            Expression::NullsafePropertyAccess(
+                DereferencableExpression::Expression(Box::new(Expression::NullsafeMethodCall(
+                    DereferencableExpression::Variable(Variable(Span::new_at(b"a", 1, 1, 2))),
+                    MemberName::Name(Span::new_at(b"b", 5, 1, 6)),
+                    Arguments::List(vec![]),
+                ))),
+                MemberName::Name(Span::new_at(b"c", 11, 1, 12)),
+            ),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_nullsafe_method_call_by_expression() {
+        let input = Span::new(b"$a?->{$b}(1)");
+        let output = Ok((
+            Span::new_at(b"", 12, 1, 13),
+            Expression::NullsafeMethodCall(
+                DereferencableExpression::Variable(Variable(Span::new_at(b"a", 1, 1, 2))),
+                MemberName::Expression(Box::new(Expression::Variable(Variable(Span::new_at(
+                    b"b", 7, 1, 8,
+                ))))),
+                Arguments::List(vec![Argument::Positional(Expression::Literal(
+                    Literal::Integer(Token::new(1i64, Span::new_at(b"1", 10, 1, 11))),
+                ))]),
+            ),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
     }
 
     #[test]
-    fn case_dereferencable_expression_array() {
-        let input = Span::new(b"['C', 'f']");
+    fn case_array_access_with_curly_braces() {
+        let input = Span::new(b"$foo{0}");
         let output = Ok((
-            Span::new_at(b"", 10, 1, 11),
-            DereferencableExpression::Array(Box::new(Expression::Array(vec![
-                (
-                    None,
-                    Expression::Literal(Literal::String(Token::new(
-                        Cow::from(&b"C"[..]),
-                        Span::new_at(b"'C'", 1, 1, 2),
-                    ))),
-                ),
-                (
-                    None,
-                    Expression::Literal(Literal::String(Token::new(
-                        Cow::from(&b"f"[..]),
-                        Span::new_at(b"'f'", 6, 1, 7),
-                    ))),
-                ),
-            ]))),
+            Span::new_at(b"", 7, 1, 8),
+            Expression::ArrayAccess(
+                DereferencableExpression::Variable(Variable(Span::new_at(b"foo", 1, 1, 2))),
+                Some(Box::new(Expression::Literal(Literal::Integer(Token::new(
+                    0i64,
+                    Span::new_at(b"0", 5, 1, 6),
+                ))))),
+            ),
         ));
 
-        assert_eq!(dereferencable_expression(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
     }
 
     #[test]
-    fn case_dereferencable_expression_string() {
-        let input = Span::new(b"'C'");
+    fn case_array_access_on_a_string() {
+        let input = Span::new(b"\"abc\"[0]");
         let output = Ok((
-            Span::new_at(b"", 3, 1, 4),
-            DereferencableExpression::String(Literal::String(Token::new(
-                Cow::from(&b"C"[..]),
-                Span::new_at(b"'C'", 0, 1, 1),
-            ))),
+            Span::new_at(b"", 8, 1, 9),
+            Expression::ArrayAccess(
+                DereferencableExpression::String(Literal::String(Token::new(
+                    Cow::from(&b"abc"[..]),
+                    Span::new_at(b"\"abc\"", 0, 1, 1),
+                ))),
+                Some(Box::new(Expression::Literal(Literal::Integer(Token::new(
+                    0i64,
+                    Span::new_at(b"0", 6, 1, 7),
+                ))))),
+            ),
         ));
 
-        assert_eq!(dereferencable_expression(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
     }
 
     #[test]
-    fn case_relative_scope_self() {
-        let input = Span::new(b"self");
-        let output = Ok((Span::new_at(b"", 4, 1, 5), RelativeScope::ToSelf));
+    fn case_array_access_on_an_array() {
+        let input = Span::new(b"[1, 2][0]");
+        let output = Ok((
+            Span::new_at(b"", 9, 1, 10),
+            Expression::ArrayAccess(
+                DereferencableExpression::Array(Box::new(Expression::Array(vec![
+                    ArrayItem::Pair(
+                        None,
+                        Expression::Literal(Literal::Integer(Token::new(
+                            1i64,
+                            Span::new_at(b"1", 1, 1, 2),
+                        ))),
+                    ),
+                    ArrayItem::Pair(
+                        None,
+                        Expression::Literal(Literal::Integer(Token::new(
+                            2i64,
+                            Span::new_at(b"2", 4, 1, 5),
+                        ))),
+                    ),
+                ]))),
+                Some(Box::new(Expression::Literal(Literal::Integer(Token::new(
+                    0i64,
+                    Span::new_at(b"0", 7, 1, 8),
+                ))))),
+            ),
+        ));
 
-        assert_eq!(relative_scope(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
     }
 
     #[test]
-    fn case_relative_scope_parent() {
-        let input = Span::new(b"parent");
-        let output = Ok((Span::new_at(b"", 6, 1, 7), RelativeScope::ToParent));
+    fn case_class_constant_access_on_a_string() {
+        let input = Span::new(b"'Foo'::BAR");
+        let output = Ok((
+            Span::new_at(b"", 10, 1, 11),
+            Expression::ClassConstantAccess(
+                ScopeResolver::ByExpression(DereferencableExpression::String(Literal::String(
+                    Token::new(Cow::from(&b"Foo"[..]), Span::new_at(b"'Foo'", 0, 1, 1)),
+                ))),
+                Span::new_at(b"BAR", 7, 1, 8),
+            ),
+        ));
 
-        assert_eq!(relative_scope(input), output);
+        assert_eq!(class_constant_access(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
     }
 
     #[test]
-    fn case_relative_scope_static() {
-        let input = Span::new(b"static");
-        let output = Ok((Span::new_at(b"", 6, 1, 7), RelativeScope::ToStatic));
+    fn case_chained_dereferences() {
+        let input = Span::new(b"$a::$b[0]{1}->c?->d()");
+        let output = Ok((
+            Span::new_at(b"", 21, 1, 22),
+            Expression::NullsafeMethodCall(
+                DereferencableExpression::Expression(Box::new(Expression::PropertyAccess(
+                    DereferencableExpression::Expression(Box::new(Expression::ArrayAccess(
+                        DereferencableExpression::Expression(Box::new(Expression::ArrayAccess(
+                            DereferencableExpression::Expression(Box::new(
+                                Expression::StaticPropertyAccess(
+                                    ScopeResolver::ByExpression(
+                                        DereferencableExpression::Variable(Variable(Span::new_at(
+                                            b"a", 1, 1, 2,
+                                        ))),
+                                    ),
+                                    Variable(Span::new_at(b"b", 5, 1, 6)),
+                                ),
+                            )),
+                            Some(Box::new(Expression::Literal(Literal::Integer(Token::new(
+                                0i64,
+                                Span::new_at(b"0", 7, 1, 8),
+                            ))))),
+                        ))),
+                        Some(Box::new(Expression::Literal(Literal::Integer(Token::new(
+                            1i64,
+                            Span::new_at(b"1", 10, 1, 11),
+                        ))))),
+                    ))),
+                    MemberName::Name(Span::new_at(b"c", 14, 1, 15)),
+                ))),
+                MemberName::Name(Span::new_at(b"d", 18, 1, 19)),
+                Arguments::List(vec![]),
+            ),
+        ));
 
-        assert_eq!(relative_scope(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
     }
 
     #[test]
@@ -1463,7 +3704,7 @@ mod tests {
         let input = Span::new(b"['foo']");
         let output = Ok((
             Span::new_at(b"", 7, 1, 8),
-            Expression::Array(vec![(
+            Expression::Array(vec![ArrayItem::Pair(
                 None,
                 Expression::Literal(Literal::String(Token::new(
                     Cow::from(&b"foo"[..]),
@@ -1482,11 +3723,11 @@ mod tests {
         let input = Span::new(b"[42 => 'foo']");
         let output = Ok((
             Span::new_at(b"", 13, 1, 14),
-            Expression::Array(vec![(
-                Some(Expression::Literal(Literal::Integer(Token::new(
+            Expression::Array(vec![ArrayItem::Pair(
+                Some(Box::new(Expression::Literal(Literal::Integer(Token::new(
                     42i64,
                     Span::new_at(b"42", 1, 1, 2),
-                )))),
+                ))))),
                 Expression::Literal(Literal::String(Token::new(
                     Cow::from(&b"foo"[..]),
                     Span::new_at(b"'foo'", 7, 1, 8),
@@ -1505,28 +3746,28 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 35, 1, 36),
             Expression::Array(vec![
-                (
+                ArrayItem::Pair(
                     None,
                     Expression::Literal(Literal::String(Token::new(
                         Cow::from(&b"foo"[..]),
                         Span::new_at(b"'foo'", 1, 1, 2),
                     ))),
                 ),
-                (
-                    Some(Expression::Literal(Literal::Integer(Token::new(
+                ArrayItem::Pair(
+                    Some(Box::new(Expression::Literal(Literal::Integer(Token::new(
                         42i64,
                         Span::new_at(b"42", 8, 1, 9),
-                    )))),
+                    ))))),
                     Expression::Literal(Literal::String(Token::new(
                         Cow::from(&b"bar"[..]),
                         Span::new_at(b"'bar'", 14, 1, 15),
                     ))),
                 ),
-                (
-                    Some(Expression::Literal(Literal::String(Token::new(
+                ArrayItem::Pair(
+                    Some(Box::new(Expression::Literal(Literal::String(Token::new(
                         Cow::from(&b"baz"[..]),
                         Span::new_at(b"'baz'", 21, 1, 22),
-                    )))),
+                    ))))),
                     Expression::Variable(Variable(Span::new_at(b"qux", 31, 1, 32))),
                 ),
             ]),
@@ -1553,21 +3794,21 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 20, 1, 21),
             Expression::Array(vec![
-                (
+                ArrayItem::Pair(
                     None,
                     Expression::Literal(Literal::Integer(Token::new(
                         1i64,
                         Span::new_at(b"1", 1, 1, 2),
                     ))),
                 ),
-                (
+                ArrayItem::Pair(
                     None,
                     Expression::Literal(Literal::Integer(Token::new(
                         2i64,
                         Span::new_at(b"2", 4, 1, 5),
                     ))),
                 ),
-                (
+                ArrayItem::Pair(
                     None,
                     Expression::Literal(Literal::Integer(Token::new(
                         3i64,
@@ -1588,39 +3829,39 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 57, 1, 58),
             Expression::Array(vec![
-                (
+                ArrayItem::Pair(
                     None,
                     Expression::Literal(Literal::String(Token::new(
                         Cow::from(&b"foo"[..]),
                         Span::new_at(b"'foo'", 1, 1, 2),
                     ))),
                 ),
-                (
-                    Some(Expression::Literal(Literal::Integer(Token::new(
+                ArrayItem::Pair(
+                    Some(Box::new(Expression::Literal(Literal::Integer(Token::new(
                         42i64,
                         Span::new_at(b"42", 8, 1, 9),
-                    )))),
+                    ))))),
                     Expression::Array(vec![
-                        (
-                            Some(Expression::Literal(Literal::Integer(Token::new(
+                        ArrayItem::Pair(
+                            Some(Box::new(Expression::Literal(Literal::Integer(Token::new(
                                 3i64,
                                 Span::new_at(b"3", 15, 1, 16),
-                            )))),
+                            ))))),
                             Expression::Literal(Literal::Integer(Token::new(
                                 5i64,
                                 Span::new_at(b"5", 20, 1, 21),
                             ))),
                         ),
-                        (
-                            Some(Expression::Literal(Literal::Integer(Token::new(
+                        ArrayItem::Pair(
+                            Some(Box::new(Expression::Literal(Literal::Integer(Token::new(
                                 7i64,
                                 Span::new_at(b"7", 23, 1, 24),
-                            )))),
-                            Expression::Array(vec![(
-                                Some(Expression::Literal(Literal::Integer(Token::new(
+                            ))))),
+                            Expression::Array(vec![ArrayItem::Pair(
+                                Some(Box::new(Expression::Literal(Literal::Integer(Token::new(
                                     11i64,
                                     Span::new_at(b"11", 29, 1, 30),
-                                )))),
+                                ))))),
                                 Expression::Literal(Literal::String(Token::new(
                                     Cow::from(&b"13"[..]),
                                     Span::new_at(b"'13'", 35, 1, 36),
@@ -1629,11 +3870,11 @@ mod tests {
                         ),
                     ]),
                 ),
-                (
-                    Some(Expression::Literal(Literal::String(Token::new(
+                ArrayItem::Pair(
+                    Some(Box::new(Expression::Literal(Literal::String(Token::new(
                         Cow::from(&b"baz"[..]),
                         Span::new_at(b"'baz'", 43, 1, 44),
-                    )))),
+                    ))))),
                     Expression::Variable(Variable(Span::new_at(b"qux", 53, 1, 54))),
                 ),
             ]),
@@ -1650,20 +3891,20 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 24, 1, 25),
             Expression::Array(vec![
-                (
-                    Some(Expression::Literal(Literal::Integer(Token::new(
+                ArrayItem::Pair(
+                    Some(Box::new(Expression::Literal(Literal::Integer(Token::new(
                         7i64,
                         Span::new_at(b"7", 1, 1, 2),
-                    )))),
+                    ))))),
                     Expression::Reference(Box::new(Expression::Variable(Variable(Span::new_at(
                         b"foo", 8, 1, 9,
                     ))))),
                 ),
-                (
-                    Some(Expression::Literal(Literal::Integer(Token::new(
+                ArrayItem::Pair(
+                    Some(Box::new(Expression::Literal(Literal::Integer(Token::new(
                         42i64,
                         Span::new_at(b"42", 13, 1, 14),
-                    )))),
+                    ))))),
                     Expression::Variable(Variable(Span::new_at(b"bar", 20, 1, 21))),
                 ),
             ]),
@@ -1674,6 +3915,34 @@ mod tests {
         assert_eq!(expression(input), output);
     }
 
+    #[test]
+    fn case_array_unpacked() {
+        let input = Span::new(b"[...$x, 'a' => 1, ...$y]");
+        let output = Ok((
+            Span::new_at(b"", 24, 1, 25),
+            Expression::Array(vec![
+                ArrayItem::Unpacked(Expression::Variable(Variable(Span::new_at(b"x", 5, 1, 6)))),
+                ArrayItem::Pair(
+                    Some(Box::new(Expression::Literal(Literal::String(Token::new(
+                        Cow::from(&b"a"[..]),
+                        Span::new_at(b"'a'", 8, 1, 9),
+                    ))))),
+                    Expression::Literal(Literal::Integer(Token::new(
+                        1i64,
+                        Span::new_at(b"1", 15, 1, 16),
+                    ))),
+                ),
+                ArrayItem::Unpacked(Expression::Variable(Variable(Span::new_at(
+                    b"y", 22, 1, 23,
+                )))),
+            ]),
+        ));
+
+        assert_eq!(array(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
     #[test]
     fn case_invalid_array_trailing_commas() {
         let input = Span::new(b"[1, 2, 3,,]");
@@ -1715,7 +3984,7 @@ mod tests {
         let input = Span::new(b"array('foo')");
         let output = Ok((
             Span::new_at(b"", 12, 1, 13),
-            Expression::Array(vec![(
+            Expression::Array(vec![ArrayItem::Pair(
                 None,
                 Expression::Literal(Literal::String(Token::new(
                     Cow::from(&b"foo"[..]),
@@ -1734,11 +4003,11 @@ mod tests {
         let input = Span::new(b"array(42 => 'foo')");
         let output = Ok((
             Span::new_at(b"", 18, 1, 19),
-            Expression::Array(vec![(
-                Some(Expression::Literal(Literal::Integer(Token::new(
+            Expression::Array(vec![ArrayItem::Pair(
+                Some(Box::new(Expression::Literal(Literal::Integer(Token::new(
                     42i64,
                     Span::new_at(b"42", 6, 1, 7),
-                )))),
+                ))))),
                 Expression::Literal(Literal::String(Token::new(
                     Cow::from(&b"foo"[..]),
                     Span::new_at(b"'foo'", 12, 1, 13),
@@ -1757,28 +4026,28 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 40, 1, 41),
             Expression::Array(vec![
-                (
+                ArrayItem::Pair(
                     None,
                     Expression::Literal(Literal::String(Token::new(
                         Cow::from(&b"foo"[..]),
                         Span::new_at(b"'foo'", 6, 1, 7),
                     ))),
                 ),
-                (
-                    Some(Expression::Literal(Literal::Integer(Token::new(
+                ArrayItem::Pair(
+                    Some(Box::new(Expression::Literal(Literal::Integer(Token::new(
                         42i64,
                         Span::new_at(b"42", 13, 1, 14),
-                    )))),
+                    ))))),
                     Expression::Literal(Literal::String(Token::new(
                         Cow::from(&b"bar"[..]),
                         Span::new_at(b"'bar'", 19, 1, 20),
                     ))),
                 ),
-                (
-                    Some(Expression::Literal(Literal::String(Token::new(
+                ArrayItem::Pair(
+                    Some(Box::new(Expression::Literal(Literal::String(Token::new(
                         Cow::from(&b"baz"[..]),
                         Span::new_at(b"'baz'", 26, 1, 27),
-                    )))),
+                    ))))),
                     Expression::Variable(Variable(Span::new_at(b"qux", 36, 1, 37))),
                 ),
             ]),
@@ -1795,21 +4064,21 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 25, 1, 26),
             Expression::Array(vec![
-                (
+                ArrayItem::Pair(
                     None,
                     Expression::Literal(Literal::Integer(Token::new(
                         1i64,
                         Span::new_at(b"1", 6, 1, 7),
                     ))),
                 ),
-                (
+                ArrayItem::Pair(
                     None,
                     Expression::Literal(Literal::Integer(Token::new(
                         2i64,
                         Span::new_at(b"2", 9, 1, 10),
                     ))),
                 ),
-                (
+                ArrayItem::Pair(
                     None,
                     Expression::Literal(Literal::Integer(Token::new(
                         3i64,
@@ -1831,39 +4100,39 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 72, 1, 73),
             Expression::Array(vec![
-                (
+                ArrayItem::Pair(
                     None,
                     Expression::Literal(Literal::String(Token::new(
                         Cow::from(&b"foo"[..]),
                         Span::new_at(b"'foo'", 6, 1, 7),
                     ))),
                 ),
-                (
-                    Some(Expression::Literal(Literal::Integer(Token::new(
+                ArrayItem::Pair(
+                    Some(Box::new(Expression::Literal(Literal::Integer(Token::new(
                         42i64,
                         Span::new_at(b"42", 13, 1, 14),
-                    )))),
+                    ))))),
                     Expression::Array(vec![
-                        (
-                            Some(Expression::Literal(Literal::Integer(Token::new(
+                        ArrayItem::Pair(
+                            Some(Box::new(Expression::Literal(Literal::Integer(Token::new(
                                 3i64,
                                 Span::new_at(b"3", 25, 1, 26),
-                            )))),
+                            ))))),
                             Expression::Literal(Literal::Integer(Token::new(
                                 5i64,
                                 Span::new_at(b"5", 30, 1, 31),
                             ))),
                         ),
-                        (
-                            Some(Expression::Literal(Literal::Integer(Token::new(
+                        ArrayItem::Pair(
+                            Some(Box::new(Expression::Literal(Literal::Integer(Token::new(
                                 7i64,
                                 Span::new_at(b"7", 33, 1, 34),
-                            )))),
-                            Expression::Array(vec![(
-                                Some(Expression::Literal(Literal::Integer(Token::new(
+                            ))))),
+                            Expression::Array(vec![ArrayItem::Pair(
+                                Some(Box::new(Expression::Literal(Literal::Integer(Token::new(
                                     11i64,
                                     Span::new_at(b"11", 44, 1, 45),
-                                )))),
+                                ))))),
                                 Expression::Literal(Literal::String(Token::new(
                                     Cow::from(&b"13"[..]),
                                     Span::new_at(b"'13'", 50, 1, 51),
@@ -1872,11 +4141,11 @@ mod tests {
                         ),
                     ]),
                 ),
-                (
-                    Some(Expression::Literal(Literal::String(Token::new(
+                ArrayItem::Pair(
+                    Some(Box::new(Expression::Literal(Literal::String(Token::new(
                         Cow::from(&b"baz"[..]),
                         Span::new_at(b"'baz'", 58, 1, 59),
-                    )))),
+                    ))))),
                     Expression::Variable(Variable(Span::new_at(b"qux", 68, 1, 69))),
                 ),
             ]),
@@ -1893,20 +4162,20 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 29, 1, 30),
             Expression::Array(vec![
-                (
-                    Some(Expression::Literal(Literal::Integer(Token::new(
+                ArrayItem::Pair(
+                    Some(Box::new(Expression::Literal(Literal::Integer(Token::new(
                         7i64,
                         Span::new_at(b"7", 6, 1, 7),
-                    )))),
+                    ))))),
                     Expression::Reference(Box::new(Expression::Variable(Variable(Span::new_at(
                         b"foo", 13, 1, 14,
                     ))))),
                 ),
-                (
-                    Some(Expression::Literal(Literal::Integer(Token::new(
+                ArrayItem::Pair(
+                    Some(Box::new(Expression::Literal(Literal::Integer(Token::new(
                         42i64,
                         Span::new_at(b"42", 18, 1, 19),
-                    )))),
+                    ))))),
                     Expression::Variable(Variable(Span::new_at(b"bar", 25, 1, 26))),
                 ),
             ]),
@@ -1959,6 +4228,42 @@ mod tests {
         assert_eq!(expression(input), output);
     }
 
+    #[test]
+    fn case_literal_binary_string() {
+        let input = Span::new(b"b\"xy\"");
+        let output = Ok((
+            Span::new_at(b"", 5, 1, 6),
+            Expression::Literal(Literal::String(Token::new(
+                Cow::from(&b"xy"[..]),
+                Span::new_at(b"\"xy\"", 1, 1, 2),
+            ))),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_literal_binary_string_dereferenced() {
+        let input = Span::new(b"B'xy'[0]");
+        let output = Ok((
+            Span::new_at(b"", 8, 1, 9),
+            Expression::ArrayAccess(
+                DereferencableExpression::String(Literal::String(Token::new(
+                    Cow::from(&b"xy"[..]),
+                    Span::new_at(b"'xy'", 1, 1, 2),
+                ))),
+                Some(Box::new(Expression::Literal(Literal::Integer(Token::new(
+                    0i64,
+                    Span::new_at(b"0", 6, 1, 7),
+                ))))),
+            ),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
     #[test]
     fn case_intrinsic_echo_one_expression() {
         let input = Span::new(b"echo /* baz */ 'foobar'");
@@ -2301,6 +4606,113 @@ mod tests {
         assert_eq!(expression(input), output);
     }
 
+    #[test]
+    fn case_intrinsic_list_short_syntax() {
+        let input = Span::new(b"[$x, [, $y], &$z]");
+        let output = Ok((
+            Span::new_at(b"", 17, 1, 18),
+            Expression::List(vec![
+                Some((
+                    None,
+                    Expression::Variable(Variable(Span::new_at(b"x", 2, 1, 3))),
+                )),
+                Some((
+                    None,
+                    Expression::List(vec![
+                        None,
+                        Some((
+                            None,
+                            Expression::Variable(Variable(Span::new_at(b"y", 9, 1, 10))),
+                        )),
+                    ]),
+                )),
+                Some((
+                    None,
+                    Expression::Reference(Box::new(Expression::Variable(Variable(Span::new_at(
+                        b"z", 15, 1, 16,
+                    ))))),
+                )),
+            ]),
+        ));
+
+        assert_eq!(intrinsic_list(input), output);
+    }
+
+    #[test]
+    fn case_intrinsic_list_short_syntax_with_a_skipped_slot_in_a_primary() {
+        let input = Span::new(b"[, $x]");
+        let output = Ok((
+            Span::new_at(b"", 6, 1, 7),
+            Expression::List(vec![
+                None,
+                Some((
+                    None,
+                    Expression::Variable(Variable(Span::new_at(b"x", 4, 1, 5))),
+                )),
+            ]),
+        ));
+
+        assert_eq!(primary(input), output);
+    }
+
+    #[test]
+    fn case_invalid_intrinsic_list_short_syntax_with_a_skipped_slot_and_a_literal() {
+        let input = Span::new(b"[, 1]");
+        let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
+
+        assert_eq!(primary(input), output);
+    }
+
+    #[test]
+    fn case_array_deeply_nested() {
+        let depth = 32;
+        let mut input = Vec::new();
+        input.extend(b"[1, ".iter().cycle().take(4 * depth));
+        input.extend(b"]".iter().cycle().take(depth));
+
+        let now = Instant::now();
+        let result = expression(Span::new(&input));
+
+        assert!(now.elapsed() < Duration::from_secs(1));
+
+        match result {
+            Ok((rest, Expression::Array(_))) => assert_eq!(rest.as_slice().len(), 0),
+            _ => panic!("Expected an array."),
+        }
+    }
+
+    #[test]
+    fn case_intrinsic_list_short_syntax_deeply_nested() {
+        let depth = 32;
+        let mut input = Vec::new();
+        input.extend(b"[$a, ".iter().cycle().take(5 * depth));
+        input.extend(b"]".iter().cycle().take(depth));
+        input.extend(b" = $x");
+
+        let now = Instant::now();
+        let result = expression(Span::new(&input));
+
+        assert!(now.elapsed() < Duration::from_secs(1));
+
+        match result {
+            Ok((rest, Expression::NAryOperation(_))) => assert_eq!(rest.as_slice().len(), 0),
+            _ => panic!("Expected an assignment."),
+        }
+    }
+
+    #[test]
+    fn case_invalid_intrinsic_list_mismatched_brackets() {
+        let input = Span::new(b"[$x)");
+
+        assert_eq!(
+            intrinsic_list(input),
+            Err(Error::Error(Context::Code(
+                Span::new_at(b")", 3, 1, 4),
+                ErrorKind::Tag
+            )))
+        );
+    }
+
     #[test]
     fn case_invalid_intrinsic_list_mixed_pairs() {
         let input = Span::new(b"list('foo' => $foo, $bar)");
@@ -2354,7 +4766,9 @@ mod tests {
         let input = Span::new(b"unset($foo)");
         let output = Ok((
             Span::new_at(b"", 11, 1, 12),
-            Expression::Unset(smallvec![Variable(Span::new_at(b"foo", 7, 1, 8))]),
+            Expression::Unset(vec![Expression::Variable(Variable(Span::new_at(
+                b"foo", 7, 1, 8,
+            )))]),
         ));
 
         assert_eq!(intrinsic_unset(input), output);
@@ -2369,10 +4783,53 @@ mod tests {
         let input = Span::new(b"unset($foo, $bar, $baz)");
         let output = Ok((
             Span::new_at(b"", 23, 1, 24),
-            Expression::Unset(smallvec![
-                Variable(Span::new_at(b"foo", 7, 1, 8)),
-                Variable(Span::new_at(b"bar", 13, 1, 14)),
-                Variable(Span::new_at(b"baz", 19, 1, 20))
+            Expression::Unset(vec![
+                Expression::Variable(Variable(Span::new_at(b"foo", 7, 1, 8))),
+                Expression::Variable(Variable(Span::new_at(b"bar", 13, 1, 14))),
+                Expression::Variable(Variable(Span::new_at(b"baz", 19, 1, 20))),
+            ]),
+        ));
+
+        assert_eq!(intrinsic_unset(input), output);
+        assert_eq!(intrinsic_construct(input), output);
+        assert_eq!(intrinsic(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_intrinsic_unset_vector_capacity() {
+        if let Ok((_, Expression::Unset(vector))) =
+            intrinsic_unset(Span::new(b"unset($foo, $bar, $baz)"))
+        {
+            assert_eq!(vector.capacity(), vector.len());
+            assert_eq!(vector.len(), 3);
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn case_intrinsic_unset_array_element_and_properties() {
+        let input = Span::new(b"unset($a['k'], $o->p, A::$s)");
+        let output = Ok((
+            Span::new_at(b"", 28, 1, 29),
+            Expression::Unset(vec![
+                Expression::ArrayAccess(
+                    DereferencableExpression::Variable(Variable(Span::new_at(b"a", 7, 1, 8))),
+                    Some(Box::new(Expression::Literal(Literal::String(Token::new(
+                        Cow::from(&b"k"[..]),
+                        Span::new_at(b"'k'", 9, 1, 10),
+                    ))))),
+                ),
+                Expression::PropertyAccess(
+                    DereferencableExpression::Variable(Variable(Span::new_at(b"o", 16, 1, 17))),
+                    MemberName::Name(Span::new_at(b"p", 19, 1, 20)),
+                ),
+                Expression::StaticPropertyAccess(
+                    ScopeResolver::ByName(Name::Unqualified(Span::new_at(b"A", 22, 1, 23))),
+                    Variable(Span::new_at(b"s", 26, 1, 27)),
+                ),
             ]),
         ));
 
@@ -2384,15 +4841,42 @@ mod tests {
     }
 
     #[test]
-    fn case_intrinsic_unset_vector_capacity() {
-        if let Ok((_, Expression::Unset(vector))) =
-            intrinsic_unset(Span::new(b"unset($foo, $bar, $baz)"))
-        {
-            assert_eq!(vector.capacity(), vector.len());
-            assert_eq!(vector.len(), 3);
-        } else {
-            assert!(false);
-        }
+    fn case_invalid_intrinsic_unset_not_a_variable() {
+        let input = Span::new(b"unset(f())");
+        let output = Err(Error::Failure(Context::Code(
+            input,
+            ErrorKind::Custom(IntrinsicError::InvalidUnsetOperand as u32),
+        )));
+
+        assert_eq!(intrinsic_unset(input), output);
+        assert_eq!(intrinsic_construct(input), output);
+        assert_eq!(intrinsic(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_intrinsic_unset_parenthesized_variable() {
+        let input = Span::new(b"unset($a, ($b))");
+        let output = Err(Error::Failure(Context::Code(
+            input,
+            ErrorKind::Custom(IntrinsicError::InvalidUnsetOperand as u32),
+        )));
+
+        assert_eq!(intrinsic_unset(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_intrinsic_unset_nullsafe_property() {
+        let input = Span::new(b"unset($o?->p['k'])");
+        let output = Err(Error::Failure(Context::Code(
+            input,
+            ErrorKind::Custom(IntrinsicError::InvalidUnsetOperand as u32),
+        )));
+
+        assert_eq!(intrinsic_unset(input), output);
+        assert_eq!(expression(input), output);
     }
 
     #[test]
@@ -2404,7 +4888,7 @@ mod tests {
             intrinsic_unset(input),
             Err(Error::Error(Context::Code(
                 Span::new_at(b")", 6, 1, 7),
-                ErrorKind::Tag
+                ErrorKind::Alt
             )))
         );
         assert_eq!(intrinsic_construct(input), output);
@@ -2660,59 +5144,424 @@ mod tests {
         let input = Span::new(b"isset($foo)");
         let output = Ok((
             Span::new_at(b"", 11, 1, 12),
-            Expression::Isset(smallvec![Variable(Span::new_at(b"foo", 7, 1, 8))]),
+            Expression::Isset(vec![Expression::Variable(Variable(Span::new_at(
+                b"foo", 7, 1, 8,
+            )))]),
+        ));
+
+        assert_eq!(intrinsic_isset(input), output);
+        assert_eq!(intrinsic_operator(input), output);
+        assert_eq!(intrinsic(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_intrinsic_isset_many_variables() {
+        let input = Span::new(b"isset($foo, $bar, $baz)");
+        let output = Ok((
+            Span::new_at(b"", 23, 1, 24),
+            Expression::Isset(vec![
+                Expression::Variable(Variable(Span::new_at(b"foo", 7, 1, 8))),
+                Expression::Variable(Variable(Span::new_at(b"bar", 13, 1, 14))),
+                Expression::Variable(Variable(Span::new_at(b"baz", 19, 1, 20))),
+            ]),
+        ));
+
+        assert_eq!(intrinsic_isset(input), output);
+        assert_eq!(intrinsic_operator(input), output);
+        assert_eq!(intrinsic(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_intrinsic_isset_vector_capacity() {
+        if let Ok((_, Expression::Isset(vector))) =
+            intrinsic_isset(Span::new(b"isset($foo, $bar, $baz)"))
+        {
+            assert_eq!(vector.capacity(), vector.len());
+            assert_eq!(vector.len(), 3);
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn case_intrinsic_isset_array_element_and_properties() {
+        let input = Span::new(b"isset($a['k'], $o?->p, A::$s)");
+        let output = Ok((
+            Span::new_at(b"", 29, 1, 30),
+            Expression::Isset(vec![
+                Expression::ArrayAccess(
+                    DereferencableExpression::Variable(Variable(Span::new_at(b"a", 7, 1, 8))),
+                    Some(Box::new(Expression::Literal(Literal::String(Token::new(
+                        Cow::from(&b"k"[..]),
+                        Span::new_at(b"'k'", 9, 1, 10),
+                    ))))),
+                ),
+                Expression::NullsafePropertyAccess(
+                    DereferencableExpression::Variable(Variable(Span::new_at(b"o", 16, 1, 17))),
+                    MemberName::Name(Span::new_at(b"p", 20, 1, 21)),
+                ),
+                Expression::StaticPropertyAccess(
+                    ScopeResolver::ByName(Name::Unqualified(Span::new_at(b"A", 23, 1, 24))),
+                    Variable(Span::new_at(b"s", 27, 1, 28)),
+                ),
+            ]),
+        ));
+
+        assert_eq!(intrinsic_isset(input), output);
+        assert_eq!(intrinsic_operator(input), output);
+        assert_eq!(intrinsic(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_intrinsic_isset_not_a_variable() {
+        let input = Span::new(b"isset($x, 1 + 2)");
+        let output = Err(Error::Failure(Context::Code(
+            input,
+            ErrorKind::Custom(IntrinsicError::InvalidIssetOperand as u32),
+        )));
+
+        assert_eq!(intrinsic_isset(input), output);
+        assert_eq!(intrinsic_operator(input), output);
+        assert_eq!(intrinsic(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_intrinsic_isset_parenthesized_variable() {
+        let input = Span::new(b"isset(($a))");
+        let output = Err(Error::Failure(Context::Code(
+            input,
+            ErrorKind::Custom(IntrinsicError::InvalidIssetOperand as u32),
+        )));
+
+        assert_eq!(intrinsic_isset(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_intrinsic_isset_parenthesized_array() {
+        let input = Span::new(b"isset(($a)['k'])");
+
+        assert!(intrinsic_isset(input).is_ok());
+    }
+
+    #[test]
+    fn case_invalid_intrinsic_isset_zero_variable() {
+        let input = Span::new(b"isset()");
+        let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
+
+        assert_eq!(
+            intrinsic_isset(input),
+            Err(Error::Error(Context::Code(
+                Span::new_at(b")", 6, 1, 7),
+                ErrorKind::Alt
+            )))
+        );
+        assert_eq!(intrinsic_operator(input), output);
+        assert_eq!(intrinsic(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_intrinsic_print() {
+        let input = Span::new(b"print /* baz */ 'foobar'");
+        let output = Ok((
+            Span::new_at(b"", 24, 1, 25),
+            Expression::Print(Box::new(Expression::Literal(Literal::String(Token::new(
+                Cow::from(&b"foobar"[..]),
+                Span::new_at(b"'foobar'", 16, 1, 17),
+            ))))),
+        ));
+
+        assert_eq!(intrinsic_print(input), output);
+        assert_eq!(intrinsic_operator(input), output);
+        assert_eq!(intrinsic(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_intrinsic_print_expression_missing() {
+        let input = Span::new(b"print;");
+        let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
+
+        assert_eq!(
+            intrinsic_print(input),
+            Err(Error::Error(Context::Code(
+                Span::new_at(b";", 5, 1, 6),
+                ErrorKind::Alt
+            )))
+        );
+        assert_eq!(intrinsic_operator(input), output);
+        assert_eq!(intrinsic(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_intrinsic_throw() {
+        let input = Span::new(b"throw $x ?? $y");
+        let output = Ok((
+            Span::new_at(b"", 14, 1, 15),
+            Expression::Throw(Box::new(Expression::NAryOperation(NAryOperation::Binary {
+                operator: BinaryOperator::Coalesce,
+                left_operand: Box::new(NAryOperation::Nullary(Box::new(Expression::Variable(
+                    Variable(Span::new_at(b"x", 7, 1, 8)),
+                )))),
+                right_operand: Box::new(NAryOperation::Nullary(Box::new(Expression::Variable(
+                    Variable(Span::new_at(b"y", 13, 1, 14)),
+                )))),
+            }))),
+        ));
+
+        assert_eq!(intrinsic_throw(input), output);
+        assert_eq!(intrinsic_operator(input), output);
+        assert_eq!(intrinsic(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_intrinsic_throw_as_an_operand() {
+        let input = Span::new(b"$x ?: throw new E");
+        let output = Ok((
+            Span::new_at(b"", 17, 1, 18),
+            Expression::NAryOperation(NAryOperation::Binary {
+                operator: BinaryOperator::Conditional,
+                left_operand: Box::new(NAryOperation::Nullary(Box::new(Expression::Variable(
+                    Variable(Span::new_at(b"x", 1, 1, 2)),
+                )))),
+                right_operand: Box::new(NAryOperation::Nullary(Box::new(Expression::Throw(
+                    Box::new(Expression::New(
+                        ScopeResolver::ByName(Name::Unqualified(Span::new_at(b"E", 16, 1, 17))),
+                        None,
+                    )),
+                )))),
+            }),
+        ));
+
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_intrinsic_throw_expression_missing() {
+        let input = Span::new(b"throw;");
+
+        assert_eq!(
+            intrinsic_throw(input),
+            Err(Error::Error(Context::Code(
+                Span::new_at(b";", 5, 1, 6),
+                ErrorKind::Alt
+            )))
+        );
+        assert_eq!(
+            expression(input),
+            Err(Error::Error(Context::Code(input, ErrorKind::Alt)))
+        );
+    }
+
+    #[test]
+    fn case_intrinsic_import() {
+        let kinds = [
+            (&b"include $x"[..], ImportKind::Include),
+            (&b"include_once $x"[..], ImportKind::IncludeOnce),
+            (&b"require $x"[..], ImportKind::Require),
+            (&b"require_once $x"[..], ImportKind::RequireOnce),
+        ];
+
+        for &(input, kind) in &kinds {
+            let length = input.len();
+            let input = Span::new(input);
+            let output = Ok((
+                Span::new_at(b"", length, 1, length as u32 + 1),
+                Expression::Import(
+                    kind,
+                    Box::new(Expression::Variable(Variable(Span::new_at(
+                        b"x",
+                        length - 1,
+                        1,
+                        length as u32,
+                    )))),
+                ),
+            ));
+
+            assert_eq!(intrinsic_import(input), output);
+            assert_eq!(intrinsic_operator(input), output);
+            assert_eq!(intrinsic(input), output);
+            assert_eq!(primary(input), output);
+            assert_eq!(expression(input), output);
+        }
+    }
+
+    #[test]
+    fn case_intrinsic_import_has_a_lower_precedence_than_logical_operators() {
+        let input = Span::new(b"include $x or $y");
+        let output = Ok((
+            Span::new_at(b"", 16, 1, 17),
+            Expression::Import(
+                ImportKind::Include,
+                Box::new(Expression::NAryOperation(NAryOperation::Binary {
+                    operator: BinaryOperator::LogicalOr,
+                    left_operand: Box::new(NAryOperation::Nullary(Box::new(Expression::Variable(
+                        Variable(Span::new_at(b"x", 9, 1, 10)),
+                    )))),
+                    right_operand: Box::new(NAryOperation::Nullary(Box::new(
+                        Expression::Variable(Variable(Span::new_at(b"y", 15, 1, 16))),
+                    ))),
+                })),
+            ),
+        ));
+
+        assert_eq!(intrinsic_import(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_intrinsic_import_path_missing() {
+        let input = Span::new(b"require;");
+
+        assert_eq!(
+            intrinsic_import(input),
+            Err(Error::Error(Context::Code(
+                Span::new_at(b";", 7, 1, 8),
+                ErrorKind::Alt
+            )))
+        );
+    }
+
+    #[test]
+    fn case_intrinsic_yield_without_value() {
+        let input = Span::new(b"yield;");
+        let output = Ok((Span::new_at(b";", 5, 1, 6), Expression::Yield(None, None)));
+
+        assert_eq!(intrinsic_yield(input), output);
+        assert_eq!(intrinsic_operator(input), output);
+        assert_eq!(intrinsic(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_intrinsic_yield_with_a_value() {
+        let input = Span::new(b"yield $x ?? $y");
+        let output = Ok((
+            Span::new_at(b"", 14, 1, 15),
+            Expression::Yield(
+                None,
+                Some(Box::new(Expression::NAryOperation(NAryOperation::Binary {
+                    operator: BinaryOperator::Coalesce,
+                    left_operand: Box::new(NAryOperation::Nullary(Box::new(Expression::Variable(
+                        Variable(Span::new_at(b"x", 7, 1, 8)),
+                    )))),
+                    right_operand: Box::new(NAryOperation::Nullary(Box::new(
+                        Expression::Variable(Variable(Span::new_at(b"y", 13, 1, 14))),
+                    ))),
+                }))),
+            ),
+        ));
+
+        assert_eq!(intrinsic_yield(input), output);
+        assert_eq!(intrinsic_operator(input), output);
+        assert_eq!(intrinsic(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_intrinsic_yield_with_a_key_and_a_value() {
+        let input = Span::new(b"yield 'k' => $v");
+        let output = Ok((
+            Span::new_at(b"", 15, 1, 16),
+            Expression::Yield(
+                Some(Box::new(Expression::Literal(Literal::String(Token::new(
+                    Cow::from(&b"k"[..]),
+                    Span::new_at(b"'k'", 6, 1, 7),
+                ))))),
+                Some(Box::new(Expression::Variable(Variable(Span::new_at(
+                    b"v", 14, 1, 15,
+                ))))),
+            ),
+        ));
+
+        assert_eq!(intrinsic_yield(input), output);
+        assert_eq!(intrinsic_operator(input), output);
+        assert_eq!(intrinsic(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_intrinsic_yield_has_a_higher_precedence_than_logical_operators() {
+        let input = Span::new(b"yield $x = 1 and $y");
+        let output = Ok((
+            Span::new_at(b"", 19, 1, 20),
+            Expression::NAryOperation(NAryOperation::Binary {
+                operator: BinaryOperator::LogicalAnd,
+                left_operand: Box::new(NAryOperation::Nullary(Box::new(Expression::Yield(
+                    None,
+                    Some(Box::new(Expression::NAryOperation(NAryOperation::Binary {
+                        operator: BinaryOperator::Assignment,
+                        left_operand: Box::new(NAryOperation::Nullary(Box::new(
+                            Expression::Variable(Variable(Span::new_at(b"x", 7, 1, 8))),
+                        ))),
+                        right_operand: Box::new(NAryOperation::Nullary(Box::new(
+                            Expression::Literal(Literal::Integer(Token::new(
+                                1i64,
+                                Span::new_at(b"1", 11, 1, 12),
+                            ))),
+                        ))),
+                    }))),
+                )))),
+                right_operand: Box::new(NAryOperation::Nullary(Box::new(Expression::Variable(
+                    Variable(Span::new_at(b"y", 18, 1, 19)),
+                )))),
+            }),
         ));
 
-        assert_eq!(intrinsic_isset(input), output);
-        assert_eq!(intrinsic_operator(input), output);
-        assert_eq!(intrinsic(input), output);
-        assert_eq!(primary(input), output);
         assert_eq!(expression(input), output);
     }
 
     #[test]
-    fn case_intrinsic_isset_many_variables() {
-        let input = Span::new(b"isset($foo, $bar, $baz)");
+    fn case_intrinsic_yield_as_an_assignment_operand() {
+        let input = Span::new(b"$x = yield;");
         let output = Ok((
-            Span::new_at(b"", 23, 1, 24),
-            Expression::Isset(smallvec![
-                Variable(Span::new_at(b"foo", 7, 1, 8)),
-                Variable(Span::new_at(b"bar", 13, 1, 14)),
-                Variable(Span::new_at(b"baz", 19, 1, 20))
-            ]),
+            Span::new_at(b";", 10, 1, 11),
+            Expression::NAryOperation(NAryOperation::Binary {
+                operator: BinaryOperator::Assignment,
+                left_operand: Box::new(NAryOperation::Nullary(Box::new(Expression::Variable(
+                    Variable(Span::new_at(b"x", 1, 1, 2)),
+                )))),
+                right_operand: Box::new(NAryOperation::Nullary(Box::new(Expression::Yield(
+                    None, None,
+                )))),
+            }),
         ));
 
-        assert_eq!(intrinsic_isset(input), output);
-        assert_eq!(intrinsic_operator(input), output);
-        assert_eq!(intrinsic(input), output);
-        assert_eq!(primary(input), output);
         assert_eq!(expression(input), output);
     }
 
     #[test]
-    fn case_intrinsic_isset_vector_capacity() {
-        if let Ok((_, Expression::Isset(vector))) =
-            intrinsic_isset(Span::new(b"isset($foo, $bar, $baz)"))
-        {
-            assert_eq!(vector.capacity(), vector.len());
-            assert_eq!(vector.len(), 3);
-        } else {
-            assert!(false);
-        }
-    }
-
-    #[test]
-    fn case_invalid_intrinsic_isset_zero_variable() {
-        let input = Span::new(b"isset()");
-        let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
+    fn case_intrinsic_yield_from() {
+        let input = Span::new(b"yield  \n from f()");
+        let output = Ok((
+            Span::new_at(b"", 17, 2, 10),
+            Expression::YieldFrom(Box::new(Expression::Call(
+                Box::new(Expression::Name(Name::Unqualified(Span::new_at(
+                    b"f", 14, 2, 7,
+                )))),
+                Arguments::List(vec![]),
+            ))),
+        ));
 
-        assert_eq!(
-            intrinsic_isset(input),
-            Err(Error::Error(Context::Code(
-                Span::new_at(b")", 6, 1, 7),
-                ErrorKind::Tag
-            )))
-        );
+        assert_eq!(intrinsic_yield_from(input), output);
         assert_eq!(intrinsic_operator(input), output);
         assert_eq!(intrinsic(input), output);
         assert_eq!(primary(input), output);
@@ -2720,17 +5569,22 @@ mod tests {
     }
 
     #[test]
-    fn case_intrinsic_print() {
-        let input = Span::new(b"print /* baz */ 'foobar'");
+    fn case_intrinsic_yield_of_a_name_starting_with_from() {
+        let input = Span::new(b"yield fromage()");
         let output = Ok((
-            Span::new_at(b"", 24, 1, 25),
-            Expression::Print(Box::new(Expression::Literal(Literal::String(Token::new(
-                Cow::from(&b"foobar"[..]),
-                Span::new_at(b"'foobar'", 16, 1, 17),
-            ))))),
+            Span::new_at(b"", 15, 1, 16),
+            Expression::Yield(
+                None,
+                Some(Box::new(Expression::Call(
+                    Box::new(Expression::Name(Name::Unqualified(Span::new_at(
+                        b"fromage", 6, 1, 7,
+                    )))),
+                    Arguments::List(vec![]),
+                ))),
+            ),
         ));
 
-        assert_eq!(intrinsic_print(input), output);
+        assert_eq!(intrinsic_yield(input), output);
         assert_eq!(intrinsic_operator(input), output);
         assert_eq!(intrinsic(input), output);
         assert_eq!(primary(input), output);
@@ -2738,21 +5592,16 @@ mod tests {
     }
 
     #[test]
-    fn case_invalid_intrinsic_print_expression_missing() {
-        let input = Span::new(b"print;");
-        let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
+    fn case_invalid_intrinsic_yield_from_expression_missing() {
+        let input = Span::new(b"yield from;");
 
         assert_eq!(
-            intrinsic_print(input),
+            intrinsic_yield_from(input),
             Err(Error::Error(Context::Code(
-                Span::new_at(b";", 5, 1, 6),
+                Span::new_at(b";", 10, 1, 11),
                 ErrorKind::Alt
             )))
         );
-        assert_eq!(intrinsic_operator(input), output);
-        assert_eq!(intrinsic(input), output);
-        assert_eq!(primary(input), output);
-        assert_eq!(expression(input), output);
     }
 
     #[test]
@@ -2779,24 +5628,88 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 46, 1, 47),
             Expression::AnonymousFunction(AnonymousFunction {
+                attributes: vec![],
                 declaration_scope: DeclarationScope::Dynamic,
                 inputs: Arity::Finite(vec![
                     Parameter {
-                        ty: Ty::Copy(Some(Name::Unqualified(Span::new_at(b"I", 10, 1, 11)))),
+                        attributes: vec![],
+                        promotion: None,
+                        ty: Ty::Copy(Some(Type::Name(Name::Unqualified(Span::new_at(
+                            b"I", 10, 1, 11,
+                        ))))),
                         name: Variable(Span::new_at(b"x", 13, 1, 14)),
                         value: None,
                     },
                     Parameter {
-                        ty: Ty::Reference(Some(Name::Unqualified(Span::new_at(b"J", 16, 1, 17)))),
+                        attributes: vec![],
+                        promotion: None,
+                        ty: Ty::Reference(Some(Type::Name(Name::Unqualified(Span::new_at(
+                            b"J", 16, 1, 17,
+                        ))))),
                         name: Variable(Span::new_at(b"y", 20, 1, 21)),
                         value: None,
                     },
                 ]),
-                output: Ty::Copy(Some(Name::Unqualified(Span::new_at(b"O", 33, 1, 34)))),
+                output: Ty::Copy(Some(Type::Name(Name::Unqualified(Span::new_at(
+                    b"O", 33, 1, 34,
+                ))))),
                 enclosing_scope: Some(vec![Expression::Variable(Variable(Span::new_at(
                     b"z", 29, 1, 30,
                 )))]),
-                body: vec![Statement::Return],
+                kind: FunctionKind::Regular,
+                body: vec![Statement::Return(None)],
+            }),
+        ));
+
+        assert_eq!(anonymous_function(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_anonymous_function_generator() {
+        let input = Span::new(b"function () { return yield; }");
+        let output = Ok((
+            Span::new_at(b"", 29, 1, 30),
+            Expression::AnonymousFunction(AnonymousFunction {
+                attributes: vec![],
+                declaration_scope: DeclarationScope::Dynamic,
+                inputs: Arity::Constant,
+                output: Ty::Copy(None),
+                enclosing_scope: None,
+                kind: FunctionKind::Generator,
+                body: vec![Statement::Return(Some(Expression::Yield(None, None)))],
+            }),
+        ));
+
+        assert_eq!(anonymous_function(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_anonymous_function_returning_a_generator() {
+        let input = Span::new(b"function () { return function () { yield; }; }");
+        let output = Ok((
+            Span::new_at(b"", 46, 1, 47),
+            Expression::AnonymousFunction(AnonymousFunction {
+                attributes: vec![],
+                declaration_scope: DeclarationScope::Dynamic,
+                inputs: Arity::Constant,
+                output: Ty::Copy(None),
+                enclosing_scope: None,
+                kind: FunctionKind::Regular,
+                body: vec![Statement::Return(Some(Expression::AnonymousFunction(
+                    AnonymousFunction {
+                        attributes: vec![],
+                        declaration_scope: DeclarationScope::Dynamic,
+                        inputs: Arity::Constant,
+                        output: Ty::Copy(None),
+                        enclosing_scope: None,
+                        kind: FunctionKind::Generator,
+                        body: vec![Statement::Expression(Expression::Yield(None, None))],
+                    },
+                )))],
             }),
         ));
 
@@ -2811,11 +5724,13 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 14, 1, 15),
             Expression::AnonymousFunction(AnonymousFunction {
+                attributes: vec![],
                 declaration_scope: DeclarationScope::Dynamic,
                 inputs: Arity::Constant,
                 output: Ty::Copy(None),
                 enclosing_scope: None,
-                body: vec![Statement::Return],
+                kind: FunctionKind::Regular,
+                body: vec![],
             }),
         ));
 
@@ -2830,15 +5745,19 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 16, 1, 17),
             Expression::AnonymousFunction(AnonymousFunction {
+                attributes: vec![],
                 declaration_scope: DeclarationScope::Dynamic,
                 inputs: Arity::Finite(vec![Parameter {
+                    attributes: vec![],
+                    promotion: None,
                     ty: Ty::Copy(None),
                     name: Variable(Span::new_at(b"x", 11, 1, 12)),
                     value: None,
                 }]),
                 output: Ty::Copy(None),
                 enclosing_scope: None,
-                body: vec![Statement::Return],
+                kind: FunctionKind::Regular,
+                body: vec![],
             }),
         ));
 
@@ -2853,15 +5772,19 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 17, 1, 18),
             Expression::AnonymousFunction(AnonymousFunction {
+                attributes: vec![],
                 declaration_scope: DeclarationScope::Dynamic,
                 inputs: Arity::Finite(vec![Parameter {
+                    attributes: vec![],
+                    promotion: None,
                     ty: Ty::Reference(None),
                     name: Variable(Span::new_at(b"x", 12, 1, 13)),
                     value: None,
                 }]),
                 output: Ty::Copy(None),
                 enclosing_scope: None,
-                body: vec![Statement::Return],
+                kind: FunctionKind::Regular,
+                body: vec![],
             }),
         ));
 
@@ -2876,19 +5799,23 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 22, 1, 23),
             Expression::AnonymousFunction(AnonymousFunction {
+                attributes: vec![],
                 declaration_scope: DeclarationScope::Dynamic,
                 inputs: Arity::Finite(vec![Parameter {
-                    ty: Ty::Copy(Some(Name::Qualified(smallvec![
+                    attributes: vec![],
+                    promotion: None,
+                    ty: Ty::Copy(Some(Type::Name(Name::Qualified(smallvec![
                         Span::new_at(b"A", 10, 1, 11),
                         Span::new_at(b"B", 12, 1, 13),
                         Span::new_at(b"C", 14, 1, 15)
-                    ]))),
+                    ])))),
                     name: Variable(Span::new_at(b"x", 17, 1, 18)),
                     value: None,
                 }]),
                 output: Ty::Copy(None),
                 enclosing_scope: None,
-                body: vec![Statement::Return],
+                kind: FunctionKind::Regular,
+                body: vec![],
             }),
         ));
 
@@ -2903,17 +5830,21 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 21, 1, 22),
             Expression::AnonymousFunction(AnonymousFunction {
+                attributes: vec![],
                 declaration_scope: DeclarationScope::Dynamic,
                 inputs: Arity::Finite(vec![Parameter {
-                    ty: Ty::Reference(Some(Name::FullyQualified(smallvec![Span::new_at(
-                        b"int", 10, 1, 11
-                    )]))),
+                    attributes: vec![],
+                    promotion: None,
+                    ty: Ty::Reference(Some(Type::Name(Name::FullyQualified(smallvec![
+                        Span::new_at(b"int", 10, 1, 11)
+                    ])))),
                     name: Variable(Span::new_at(b"x", 16, 1, 17)),
                     value: None,
                 }]),
                 output: Ty::Copy(None),
                 enclosing_scope: None,
-                body: vec![Statement::Return],
+                kind: FunctionKind::Regular,
+                body: vec![],
             }),
         ));
 
@@ -2928,39 +5859,49 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 40, 1, 41),
             Expression::AnonymousFunction(AnonymousFunction {
+                attributes: vec![],
                 declaration_scope: DeclarationScope::Dynamic,
                 inputs: Arity::Finite(vec![
                     Parameter {
+                        attributes: vec![],
+                        promotion: None,
                         ty: Ty::Copy(None),
                         name: Variable(Span::new_at(b"a", 11, 1, 12)),
                         value: None,
                     },
                     Parameter {
-                        ty: Ty::Copy(Some(Name::Qualified(smallvec![
+                        attributes: vec![],
+                        promotion: None,
+                        ty: Ty::Copy(Some(Type::Name(Name::Qualified(smallvec![
                             Span::new_at(b"I", 14, 1, 15),
                             Span::new_at(b"J", 16, 1, 17)
-                        ]))),
+                        ])))),
                         name: Variable(Span::new_at(b"b", 19, 1, 20)),
                         value: None,
                     },
                     Parameter {
-                        ty: Ty::Reference(Some(Name::FullyQualified(smallvec![Span::new_at(
-                            b"int", 22, 1, 23
-                        )]))),
+                        attributes: vec![],
+                        promotion: None,
+                        ty: Ty::Reference(Some(Type::Name(Name::FullyQualified(smallvec![
+                            Span::new_at(b"int", 22, 1, 23)
+                        ])))),
                         name: Variable(Span::new_at(b"c", 28, 1, 29)),
                         value: None,
                     },
                     Parameter {
-                        ty: Ty::Copy(Some(Name::FullyQualified(smallvec![Span::new_at(
-                            b"K", 32, 1, 33
-                        )]))),
+                        attributes: vec![],
+                        promotion: None,
+                        ty: Ty::Copy(Some(Type::Name(Name::FullyQualified(smallvec![
+                            Span::new_at(b"K", 32, 1, 33)
+                        ])))),
                         name: Variable(Span::new_at(b"d", 35, 1, 36)),
                         value: None,
                     },
                 ]),
                 output: Ty::Copy(None),
                 enclosing_scope: None,
-                body: vec![Statement::Return],
+                kind: FunctionKind::Regular,
+                body: vec![],
             }),
         ));
 
@@ -2975,13 +5916,15 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 18, 1, 19),
             Expression::AnonymousFunction(AnonymousFunction {
+                attributes: vec![],
                 declaration_scope: DeclarationScope::Dynamic,
                 inputs: Arity::Constant,
-                output: Ty::Copy(Some(Name::FullyQualified(smallvec![Span::new_at(
-                    b"O", 14, 1, 15
-                )]))),
+                output: Ty::Copy(Some(Type::Name(Name::FullyQualified(smallvec![
+                    Span::new_at(b"O", 14, 1, 15)
+                ])))),
                 enclosing_scope: None,
-                body: vec![Statement::Return],
+                kind: FunctionKind::Regular,
+                body: vec![],
             }),
         ));
 
@@ -2996,13 +5939,15 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 20, 1, 21),
             Expression::AnonymousFunction(AnonymousFunction {
+                attributes: vec![],
                 declaration_scope: DeclarationScope::Dynamic,
                 inputs: Arity::Constant,
-                output: Ty::Reference(Some(Name::FullyQualified(smallvec![Span::new_at(
-                    b"int", 14, 1, 15
-                )]))),
+                output: Ty::Reference(Some(Type::Name(Name::FullyQualified(smallvec![
+                    Span::new_at(b"int", 14, 1, 15)
+                ])))),
                 enclosing_scope: None,
-                body: vec![Statement::Return],
+                kind: FunctionKind::Regular,
+                body: vec![],
             }),
         ));
 
@@ -3017,11 +5962,13 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 21, 1, 22),
             Expression::AnonymousFunction(AnonymousFunction {
+                attributes: vec![],
                 declaration_scope: DeclarationScope::Dynamic,
                 inputs: Arity::Constant,
                 output: Ty::Copy(None),
                 enclosing_scope: Some(vec![]),
-                body: vec![Statement::Return],
+                kind: FunctionKind::Regular,
+                body: vec![],
             }),
         ));
 
@@ -3036,13 +5983,15 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 23, 1, 24),
             Expression::AnonymousFunction(AnonymousFunction {
+                attributes: vec![],
                 declaration_scope: DeclarationScope::Dynamic,
                 inputs: Arity::Constant,
                 output: Ty::Copy(None),
                 enclosing_scope: Some(vec![Expression::Variable(Variable(Span::new_at(
                     b"x", 18, 1, 19,
                 )))]),
-                body: vec![Statement::Return],
+                kind: FunctionKind::Regular,
+                body: vec![],
             }),
         ));
 
@@ -3057,13 +6006,15 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 24, 1, 25),
             Expression::AnonymousFunction(AnonymousFunction {
+                attributes: vec![],
                 declaration_scope: DeclarationScope::Dynamic,
                 inputs: Arity::Constant,
                 output: Ty::Copy(None),
                 enclosing_scope: Some(vec![Expression::Reference(Box::new(Expression::Variable(
                     Variable(Span::new_at(b"x", 19, 1, 20)),
                 )))]),
-                body: vec![Statement::Return],
+                kind: FunctionKind::Regular,
+                body: vec![],
             }),
         ));
 
@@ -3078,6 +6029,7 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 32, 1, 33),
             Expression::AnonymousFunction(AnonymousFunction {
+                attributes: vec![],
                 declaration_scope: DeclarationScope::Dynamic,
                 inputs: Arity::Constant,
                 output: Ty::Copy(None),
@@ -3088,7 +6040,8 @@ mod tests {
                     ))))),
                     Expression::Variable(Variable(Span::new_at(b"z", 27, 1, 28))),
                 ]),
-                body: vec![Statement::Return],
+                kind: FunctionKind::Regular,
+                body: vec![],
             }),
         ));
 
@@ -3103,11 +6056,37 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 21, 1, 22),
             Expression::AnonymousFunction(AnonymousFunction {
+                attributes: vec![],
+                declaration_scope: DeclarationScope::Static,
+                inputs: Arity::Constant,
+                output: Ty::Copy(None),
+                enclosing_scope: None,
+                kind: FunctionKind::Regular,
+                body: vec![],
+            }),
+        ));
+
+        assert_eq!(anonymous_function(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_anonymous_function_with_attributes() {
+        let input = Span::new(b"#[A] static function () {}");
+        let output = Ok((
+            Span::new_at(b"", 26, 1, 27),
+            Expression::AnonymousFunction(AnonymousFunction {
+                attributes: vec![Attribute {
+                    name: Name::Unqualified(Span::new_at(b"A", 2, 1, 3)),
+                    arguments: None,
+                }],
                 declaration_scope: DeclarationScope::Static,
                 inputs: Arity::Constant,
                 output: Ty::Copy(None),
                 enclosing_scope: None,
-                body: vec![Statement::Return],
+                kind: FunctionKind::Regular,
+                body: vec![],
             }),
         ));
 
@@ -3115,4 +6094,317 @@ mod tests {
         assert_eq!(primary(input), output);
         assert_eq!(expression(input), output);
     }
+
+    #[test]
+    fn case_invalid_anonymous_function_with_a_promoted_parameter() {
+        let input = Span::new(b"function (public $x) {}");
+
+        assert_eq!(
+            anonymous_function(input),
+            Err(Error::Failure(Context::Code(
+                Span::new_at(b"(public $x) {}", 9, 1, 10),
+                ErrorKind::Custom(FunctionError::PromotedParameterOutsideConstructor as u32)
+            )))
+        );
+    }
+
+    #[test]
+    fn case_arrow_function() {
+        let input = Span::new(b"fn ($x) => $x ?? $y");
+        let output = Ok((
+            Span::new_at(b"", 19, 1, 20),
+            Expression::ArrowFunction(ArrowFunction {
+                attributes: vec![],
+                declaration_scope: DeclarationScope::Dynamic,
+                inputs: Arity::Finite(vec![Parameter {
+                    attributes: vec![],
+                    promotion: None,
+                    ty: Ty::Copy(None),
+                    name: Variable(Span::new_at(b"x", 5, 1, 6)),
+                    value: None,
+                }]),
+                output: Ty::Copy(None),
+                kind: FunctionKind::Regular,
+                body: Box::new(Expression::NAryOperation(NAryOperation::Binary {
+                    operator: BinaryOperator::Coalesce,
+                    left_operand: Box::new(NAryOperation::Nullary(Box::new(Expression::Variable(
+                        Variable(Span::new_at(b"x", 12, 1, 13)),
+                    )))),
+                    right_operand: Box::new(NAryOperation::Nullary(Box::new(
+                        Expression::Variable(Variable(Span::new_at(b"y", 18, 1, 19))),
+                    ))),
+                })),
+            }),
+        ));
+
+        assert_eq!(arrow_function(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_arrow_function_static_by_reference() {
+        let input = Span::new(b"static fn&(array $xs): array => $xs");
+        let output = Ok((
+            Span::new_at(b"", 35, 1, 36),
+            Expression::ArrowFunction(ArrowFunction {
+                attributes: vec![],
+                declaration_scope: DeclarationScope::Static,
+                inputs: Arity::Finite(vec![Parameter {
+                    attributes: vec![],
+                    promotion: None,
+                    ty: Ty::Copy(Some(Type::Name(Name::FullyQualified(smallvec![
+                        Span::new_at(b"array", 11, 1, 12)
+                    ])))),
+                    name: Variable(Span::new_at(b"xs", 18, 1, 19)),
+                    value: None,
+                }]),
+                output: Ty::Reference(Some(Type::Name(Name::FullyQualified(smallvec![
+                    Span::new_at(b"array", 23, 1, 24)
+                ])))),
+                kind: FunctionKind::Regular,
+                body: Box::new(Expression::Variable(Variable(Span::new_at(
+                    b"xs", 33, 1, 34,
+                )))),
+            }),
+        ));
+
+        assert_eq!(arrow_function(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_arrow_function_generator() {
+        let input = Span::new(b"fn () => yield $x");
+        let output = Ok((
+            Span::new_at(b"", 17, 1, 18),
+            Expression::ArrowFunction(ArrowFunction {
+                attributes: vec![],
+                declaration_scope: DeclarationScope::Dynamic,
+                inputs: Arity::Constant,
+                output: Ty::Copy(None),
+                kind: FunctionKind::Generator,
+                body: Box::new(Expression::Yield(
+                    None,
+                    Some(Box::new(Expression::Variable(Variable(Span::new_at(
+                        b"x", 16, 1, 17,
+                    ))))),
+                )),
+            }),
+        ));
+
+        assert_eq!(arrow_function(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_arrow_function_as_an_operand() {
+        let input = Span::new(b"$f ?: fn () => $x ?? $y");
+        let output = Ok((
+            Span::new_at(b"", 23, 1, 24),
+            Expression::NAryOperation(NAryOperation::Binary {
+                operator: BinaryOperator::Conditional,
+                left_operand: Box::new(NAryOperation::Nullary(Box::new(Expression::Variable(
+                    Variable(Span::new_at(b"f", 1, 1, 2)),
+                )))),
+                right_operand: Box::new(NAryOperation::Nullary(Box::new(
+                    Expression::ArrowFunction(ArrowFunction {
+                        attributes: vec![],
+                        declaration_scope: DeclarationScope::Dynamic,
+                        inputs: Arity::Constant,
+                        output: Ty::Copy(None),
+                        kind: FunctionKind::Regular,
+                        body: Box::new(Expression::NAryOperation(NAryOperation::Binary {
+                            operator: BinaryOperator::Coalesce,
+                            left_operand: Box::new(NAryOperation::Nullary(Box::new(
+                                Expression::Variable(Variable(Span::new_at(b"x", 16, 1, 17))),
+                            ))),
+                            right_operand: Box::new(NAryOperation::Nullary(Box::new(
+                                Expression::Variable(Variable(Span::new_at(b"y", 22, 1, 23))),
+                            ))),
+                        })),
+                    }),
+                ))),
+            }),
+        ));
+
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_arrow_function_as_an_argument() {
+        let input = Span::new(b"f(fn ($x) => $x, $y)");
+        let output = Ok((
+            Span::new_at(b"", 20, 1, 21),
+            Expression::Call(
+                Box::new(Expression::Name(Name::Unqualified(Span::new_at(
+                    b"f", 0, 1, 1,
+                )))),
+                Arguments::List(vec![
+                    Argument::Positional(Expression::ArrowFunction(ArrowFunction {
+                        attributes: vec![],
+                        declaration_scope: DeclarationScope::Dynamic,
+                        inputs: Arity::Finite(vec![Parameter {
+                            attributes: vec![],
+                            promotion: None,
+                            ty: Ty::Copy(None),
+                            name: Variable(Span::new_at(b"x", 7, 1, 8)),
+                            value: None,
+                        }]),
+                        output: Ty::Copy(None),
+                        kind: FunctionKind::Regular,
+                        body: Box::new(Expression::Variable(Variable(Span::new_at(
+                            b"x", 14, 1, 15,
+                        )))),
+                    })),
+                    Argument::Positional(Expression::Variable(Variable(Span::new_at(
+                        b"y", 18, 1, 19,
+                    )))),
+                ]),
+            ),
+        ));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_arrow_function_with_a_promoted_parameter() {
+        let input = Span::new(b"fn (public $x) => $x");
+
+        assert_eq!(
+            arrow_function(input),
+            Err(Error::Failure(Context::Code(
+                Span::new_at(b"(public $x) => $x", 3, 1, 4),
+                ErrorKind::Custom(FunctionError::PromotedParameterOutsideConstructor as u32)
+            )))
+        );
+    }
+
+    #[test]
+    fn case_match() {
+        let input = Span::new(b"match ($x) { 1, 2 => 'a', default => 'b' }");
+        let output = Ok((
+            Span::new_at(b"", 42, 1, 43),
+            Expression::Match(
+                Box::new(Expression::Variable(Variable(Span::new_at(b"x", 8, 1, 9)))),
+                vec![
+                    MatchArm {
+                        conditions: Some(vec![
+                            Expression::Literal(Literal::Integer(Token::new(
+                                1i64,
+                                Span::new_at(b"1", 13, 1, 14),
+                            ))),
+                            Expression::Literal(Literal::Integer(Token::new(
+                                2i64,
+                                Span::new_at(b"2", 16, 1, 17),
+                            ))),
+                        ]),
+                        expression: Expression::Literal(Literal::String(Token::new(
+                            Cow::from(&b"a"[..]),
+                            Span::new_at(b"'a'", 21, 1, 22),
+                        ))),
+                        span: Span::new_at(b"1, 2 => 'a'", 13, 1, 14),
+                    },
+                    MatchArm {
+                        conditions: None,
+                        expression: Expression::Literal(Literal::String(Token::new(
+                            Cow::from(&b"b"[..]),
+                            Span::new_at(b"'b'", 37, 1, 38),
+                        ))),
+                        span: Span::new_at(b"default => 'b'", 26, 1, 27),
+                    },
+                ],
+            ),
+        ));
+
+        assert_eq!(match_expression(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_match_without_arms() {
+        let input = Span::new(b"match ($x) {}");
+        let output = Ok((
+            Span::new_at(b"", 13, 1, 14),
+            Expression::Match(
+                Box::new(Expression::Variable(Variable(Span::new_at(b"x", 8, 1, 9)))),
+                vec![],
+            ),
+        ));
+
+        assert_eq!(match_expression(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_match_arm_on_many_lines() {
+        let input = Span::new(b"match ($x) {\n    default => $y ?? $z,\n}");
+        let output = Ok((
+            Span::new_at(b"", 39, 3, 2),
+            Expression::Match(
+                Box::new(Expression::Variable(Variable(Span::new_at(b"x", 8, 1, 9)))),
+                vec![MatchArm {
+                    conditions: None,
+                    expression: Expression::NAryOperation(NAryOperation::Binary {
+                        operator: BinaryOperator::Coalesce,
+                        left_operand: Box::new(NAryOperation::Nullary(Box::new(
+                            Expression::Variable(Variable(Span::new_at(b"y", 29, 2, 17))),
+                        ))),
+                        right_operand: Box::new(NAryOperation::Nullary(Box::new(
+                            Expression::Variable(Variable(Span::new_at(b"z", 35, 2, 23))),
+                        ))),
+                    }),
+                    span: Span::new_at(b"default => $y ?? $z", 17, 2, 5),
+                }],
+            ),
+        ));
+
+        assert_eq!(match_expression(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_match_with_a_condition_starting_with_default() {
+        let input = Span::new(b"match ($x) { defaultValue => 1 }");
+        let output = Ok((
+            Span::new_at(b"", 32, 1, 33),
+            Expression::Match(
+                Box::new(Expression::Variable(Variable(Span::new_at(b"x", 8, 1, 9)))),
+                vec![MatchArm {
+                    conditions: Some(vec![Expression::Name(Name::Unqualified(Span::new_at(
+                        b"defaultValue",
+                        13,
+                        1,
+                        14,
+                    )))]),
+                    expression: Expression::Literal(Literal::Integer(Token::new(
+                        1i64,
+                        Span::new_at(b"1", 29, 1, 30),
+                    ))),
+                    span: Span::new_at(b"defaultValue => 1", 13, 1, 14),
+                }],
+            ),
+        ));
+
+        assert_eq!(match_expression(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_match_with_many_default_arms() {
+        let input = Span::new(b"match ($x) { default => 1, default => 2 }");
+        let output = Err(Error::Failure(Context::Code(
+            input,
+            ErrorKind::Custom(MatchError::MultipleDefaultArms as u32),
+        )));
+
+        assert_eq!(match_expression(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
 }
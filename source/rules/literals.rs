@@ -35,10 +35,14 @@
 //! the [Grammar chapter, Literals
 //! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#literals).
 
-use super::super::ast::Literal;
+use super::super::ast::{
+    DereferencableExpression, Expression, Literal, MemberName, StringFragment, Variable,
+};
 use super::super::internal::{Context, Error, ErrorKind, Result};
+use super::super::tokens;
 use super::super::tokens::{Span, Token};
-use super::tokens;
+use super::expressions::expression;
+use super::tokens::{name, variable};
 use nom::{InputLength, Slice};
 use std::borrow::Cow;
 use std::num::{ParseFloatError, ParseIntError};
@@ -347,6 +351,20 @@ pub enum StringError {
 
     /// The string delimiter identifier is syntactically invalid.
     InvalidDelimiterIdentifier,
+
+    /// An escape sequence is invalid, e.g. `\u{}` or `\u{110000}`.
+    InvalidEscapeSequence,
+
+    /// An embedded expression is syntactically invalid, e.g.
+    /// `"$foo[ 42]"` or `"{$foo"`.
+    InvalidEmbeddedExpression,
+
+    /// The indentation of a heredoc or a nowdoc mixes tabs and spaces.
+    InvalidMixedIndentation,
+
+    /// A line of a heredoc or a nowdoc body is less indented than the
+    /// closing identifier.
+    InvalidBodyIndentation,
 }
 
 named_attr!(
@@ -379,7 +397,9 @@ named_attr!(
     pub string<Span, Literal>,
     alt!(
         string_single_quoted
+      | string_double_quoted
       | string_nowdoc
+      | string_heredoc
     )
 );
 
@@ -465,14 +485,69 @@ pub fn string_single_quoted(span: Span) -> Result<Span, Literal> {
     )))
 }
 
+/// Recognize a double quoted string.
+///
+/// Escape sequences are interpreted, and variables or expressions
+/// can be embedded. A string without any embedded expression is a
+/// `Literal::String`, else it is a `Literal::InterpolatedString`.
+///
+/// See the `string` parser for more details.
+pub fn string_double_quoted(span: Span) -> Result<Span, Literal> {
+    let input = span.as_slice();
+    let input_length = span.input_len();
+
+    if input_length < 2 {
+        return Err(Error::Error(Context::Code(
+            span,
+            ErrorKind::Custom(StringError::TooShort as u32),
+        )));
+    }
+
+    if input[0] == b'b' || input[0] == b'B' {
+        if input_length < 3 {
+            return Err(Error::Error(Context::Code(
+                span,
+                ErrorKind::Custom(StringError::TooShort as u32),
+            )));
+        } else if input[1] != b'"' {
+            return Err(Error::Error(Context::Code(
+                span,
+                ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32),
+            )));
+        } else {
+            return string_double_quoted(span.slice(1..));
+        }
+    } else if input[0] != b'"' {
+        return Err(Error::Error(Context::Code(
+            span,
+            ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32),
+        )));
+    }
+
+    let (fragments, index, closing_length) =
+        string_fragments(span, 1, Some(b'"'), &[], |input, index| {
+            if input[index] == b'"' {
+                Some(1)
+            } else {
+                None
+            }
+        })?;
+    let end = index + closing_length;
+
+    Ok((
+        span.slice(end..),
+        into_string_literal(fragments, span.slice(..end)),
+    ))
+}
+
 const STRING_NOWDOC_OPENING: &'static [u8] = &[b'<', b'<', b'<'];
 
 fn string_nowdoc(span: Span) -> Result<Span, Literal> {
     let input = span.as_slice();
     let input_length = span.input_len();
 
-    // `<<<'A'\nA\n` is the shortest datum.
-    if input_length < 9 {
+    // `<<<'A'\nA` is the shortest datum.
+    if input_length < 8 {
         return Err(Error::Error(Context::Code(
             span,
             ErrorKind::Custom(StringError::TooShort as u32),
@@ -480,7 +555,7 @@ fn string_nowdoc(span: Span) -> Result<Span, Literal> {
     }
 
     if input[0] == b'b' || input[0] == b'B' {
-        if input_length < 10 {
+        if input_length < 9 {
             return Err(Error::Error(Context::Code(
                 span,
                 ErrorKind::Custom(StringError::TooShort as u32),
@@ -500,6 +575,103 @@ fn string_nowdoc(span: Span) -> Result<Span, Literal> {
         )));
     }
 
+    let (name, body_offset) = string_doc_opening(span, b'\'', true)?;
+    let (body_end, indentation, end) = string_doc_closing(span, body_offset, name)?;
+
+    if indentation.is_empty() || body_offset == body_end {
+        return Ok((
+            span.slice(end..),
+            Literal::String(Token::new(
+                Cow::Borrowed(&input[body_offset..body_end]),
+                span.slice(..end),
+            )),
+        ));
+    }
+
+    let mut output = Vec::new();
+    let mut index = body_offset;
+
+    while index < body_end {
+        index += string_doc_indentation(span, index, indentation)?;
+
+        let line_end = match input[index..body_end]
+            .iter()
+            .position(|item| *item == b'\n')
+        {
+            Some(position) => index + position + 1,
+            None => body_end,
+        };
+
+        output.extend(&input[index..line_end]);
+        index = line_end;
+    }
+
+    Ok((
+        span.slice(end..),
+        Literal::String(Token::new(Cow::Owned(output), span.slice(..end))),
+    ))
+}
+
+fn string_heredoc(span: Span) -> Result<Span, Literal> {
+    let input = span.as_slice();
+    let input_length = span.input_len();
+
+    // `<<<A\nA` is the shortest datum.
+    if input_length < 6 {
+        return Err(Error::Error(Context::Code(
+            span,
+            ErrorKind::Custom(StringError::TooShort as u32),
+        )));
+    }
+
+    if input[0] == b'b' || input[0] == b'B' {
+        if input_length < 7 {
+            return Err(Error::Error(Context::Code(
+                span,
+                ErrorKind::Custom(StringError::TooShort as u32),
+            )));
+        } else if !input[1..].starts_with(STRING_NOWDOC_OPENING) {
+            return Err(Error::Error(Context::Code(
+                span,
+                ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32),
+            )));
+        } else {
+            return string_heredoc(span.slice(1..));
+        }
+    } else if !input.starts_with(STRING_NOWDOC_OPENING) {
+        return Err(Error::Error(Context::Code(
+            span,
+            ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32),
+        )));
+    }
+
+    let (name, body_offset) = string_doc_opening(span, b'"', false)?;
+    let (body_end, indentation, end) = string_doc_closing(span, body_offset, name)?;
+    let (fragments, _, _) = string_fragments(span, body_offset, None, indentation, |_, index| {
+        if index == body_end {
+            Some(0)
+        } else {
+            None
+        }
+    })?;
+
+    Ok((
+        span.slice(end..),
+        into_string_literal(fragments, span.slice(..end)),
+    ))
+}
+
+/// Recognize the opening of a heredoc or of a nowdoc, i.e. `<<<`,
+/// the delimiter identifier, optionally enclosed by `quote`, and a
+/// newline.
+///
+/// The output is the delimiter identifier and the offset of the body.
+fn string_doc_opening(
+    span: Span,
+    quote: u8,
+    is_quote_mandatory: bool,
+) -> StdResult<(&[u8], usize), Error<Span>> {
+    let input = span.as_slice();
     let mut offset = 3;
 
     for item in input[offset..].iter() {
@@ -510,35 +682,34 @@ fn string_nowdoc(span: Span) -> Result<Span, Literal> {
         offset += 1;
     }
 
-    if input[offset] != b'\'' {
+    let is_quoted = input.get(offset) == Some(&quote);
+
+    if is_quoted {
+        offset += 1;
+    } else if is_quote_mandatory {
         return Err(Error::Error(Context::Code(
             span,
             ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32),
         )));
     }
 
-    offset += 1;
-
-    let name_span;
-    let next_span;
+    let name = match name(span.slice(offset..)) {
+        Ok((_, name)) => name.as_slice(),
 
-    if let Ok((i, n)) = tokens::name(span.slice(offset..)) {
-        name_span = n;
-        next_span = i;
-    } else {
-        return Err(Error::Error(Context::Code(
-            span.slice(offset..),
-            ErrorKind::Custom(StringError::InvalidDelimiterIdentifier as u32),
-        )));
-    }
+        Err(_) => {
+            return Err(Error::Error(Context::Code(
+                span.slice(offset..),
+                ErrorKind::Custom(StringError::InvalidDelimiterIdentifier as u32),
+            )));
+        }
+    };
 
-    let name = name_span.as_slice();
-    let name_length = name_span.input_len();
-    let next_input = next_span.as_slice();
-    let next_input_length = next_span.input_len();
+    let mut body_offset = offset + name.len();
 
-    if next_input_length < 3 + name_length || next_input[0] != b'\'' || next_input[1] != b'\n' {
-        if next_input[1] != b'\r' || next_input[2] != b'\n' {
+    if is_quoted {
+        if input.get(body_offset) == Some(&quote) {
+            body_offset += 1;
+        } else {
             return Err(Error::Error(Context::Code(
                 span.slice(offset..),
                 ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32),
@@ -546,78 +717,260 @@ fn string_nowdoc(span: Span) -> Result<Span, Literal> {
         }
     }
 
-    let mut span_offset = offset + name_length;
-
-    if next_input[1] == b'\r' {
-        offset = 2;
+    if input[body_offset..].starts_with(b"\n") {
+        body_offset += 1;
+    } else if input[body_offset..].starts_with(b"\r\n") {
+        body_offset += 2;
     } else {
-        offset = 1;
+        return Err(Error::Error(Context::Code(
+            span.slice(offset..),
+            ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32),
+        )));
     }
 
-    span_offset += offset;
+    Ok((name, body_offset))
+}
 
-    for (index, item) in next_input[offset..].iter().enumerate() {
-        if *item == b'\n' {
-            if !next_input[offset + index + 1..].starts_with(name) {
-                continue;
+/// Recognize the closing delimiter identifier of a heredoc or of a
+/// nowdoc, starting from the body at `offset`.
+///
+/// The closing identifier is the first line starting with the
+/// delimiter identifier, possibly indented, and not followed by a
+/// character that could continue the identifier, e.g. `FOO;`, `FOO,`,
+/// `FOO)`. The newline preceding the closing identifier is not part of
+/// the body.
+///
+/// The output is the offset of the end of the body, the indentation of
+/// the closing identifier, and the offset right after the latter.
+fn string_doc_closing<'a>(
+    span: Span<'a>,
+    offset: usize,
+    name: &[u8],
+) -> StdResult<(usize, &'a [u8], usize), Error<Span<'a>>> {
+    let input = span.as_slice();
+    let mut line_offset = offset;
+
+    loop {
+        let indentation_length = input[line_offset..]
+            .iter()
+            .take_while(|item| **item == b' ' || **item == b'\t')
+            .count();
+        let name_offset = line_offset + indentation_length;
+        let end = name_offset + name.len();
+
+        if input[name_offset..].starts_with(name)
+            && !input
+                .get(end)
+                .map_or(false, |item| is_name_character(*item))
+        {
+            let indentation = &input[line_offset..name_offset];
+
+            if indentation.iter().any(|item| *item != indentation[0]) {
+                return Err(Error::Error(Context::Code(
+                    span.slice(line_offset..),
+                    ErrorKind::Custom(StringError::InvalidMixedIndentation as u32),
+                )));
             }
 
-            let mut lookahead_offset = offset + index + name_length + 1;
+            let body_end = if line_offset == offset {
+                offset
+            } else if line_offset >= offset + 2 && input[line_offset - 2] == b'\r' {
+                line_offset - 2
+            } else {
+                line_offset - 1
+            };
+
+            return Ok((body_end, indentation, end));
+        }
+
+        match input[line_offset..].iter().position(|item| *item == b'\n') {
+            Some(position) => line_offset += position + 1,
 
-            if lookahead_offset >= next_input_length {
+            None => {
                 return Err(Error::Error(Context::Code(
-                    next_span.slice(lookahead_offset - name_length..),
+                    span,
                     ErrorKind::Custom(StringError::InvalidClosingCharacter as u32),
                 )));
             }
+        }
+    }
+}
 
-            let mut semicolon_offset_adjustment = 0;
-
-            if next_input[lookahead_offset] == b';' {
-                lookahead_offset += 1;
-                semicolon_offset_adjustment = 1;
+/// Compute the length of the indentation to remove from the line
+/// starting at `offset` in a heredoc or a nowdoc body, given the
+/// `indentation` of the closing identifier.
+///
+/// A line must be at least as indented as the closing identifier, with
+/// the same characters, unless it is empty.
+fn string_doc_indentation<'a>(
+    span: Span<'a>,
+    offset: usize,
+    indentation: &[u8],
+) -> StdResult<usize, Error<Span<'a>>> {
+    let input = span.as_slice();
+    let length = input[offset..]
+        .iter()
+        .zip(indentation)
+        .take_while(|&(item, expected)| item == expected)
+        .count();
+
+    if length < indentation.len() {
+        match input.get(offset + length) {
+            Some(&b' ') | Some(&b'\t') => {
+                return Err(Error::Error(Context::Code(
+                    span.slice(offset..),
+                    ErrorKind::Custom(StringError::InvalidMixedIndentation as u32),
+                )));
             }
 
-            if lookahead_offset >= next_input_length {
+            Some(&b'\n') | Some(&b'\r') | None => {}
+
+            _ => {
                 return Err(Error::Error(Context::Code(
-                    next_span.slice(lookahead_offset - name_length - semicolon_offset_adjustment..),
-                    ErrorKind::Custom(StringError::InvalidClosingCharacter as u32),
+                    span.slice(offset..),
+                    ErrorKind::Custom(StringError::InvalidBodyIndentation as u32),
                 )));
             }
+        }
+    }
+
+    Ok(length)
+}
+
+#[inline]
+fn is_name_character(item: u8) -> bool {
+    item.is_ascii_alphanumeric() || item == b'_' || item >= 0x80
+}
+
+/// Scan the body of a double quoted string or of a heredoc.
+///
+/// The body starts at `offset` in `span`. The `closing` function
+/// computes the length of the closing sequence if there is one at
+/// the given index of the input, and the scan stops there. Escape
+/// sequences are interpreted, and the escaped `quote` character, if
+/// any, is recognized as such. The `indentation` is removed from the
+/// start of each line, see the `string_doc_indentation` function.
+///
+/// The output is the list of fragments, the index where the closing
+/// sequence starts, and the length of the latter.
+fn string_fragments<'a, F>(
+    span: Span<'a>,
+    offset: usize,
+    quote: Option<u8>,
+    indentation: &[u8],
+    closing: F,
+) -> StdResult<(Vec<StringFragment<'a>>, usize, usize), Error<Span<'a>>>
+where
+    F: Fn(&[u8], usize) -> Option<usize>,
+{
+    let input = span.as_slice();
+    let input_length = span.input_len();
+    let mut fragments = Vec::new();
+    let mut output: Option<Vec<u8>> = None;
+    let mut fragment_offset = offset;
+    let mut copy_offset = offset;
+    let mut index = offset;
+
+    while index < input_length {
+        if let Some(closing_length) = closing(input, index) {
+            if fragment_offset < index {
+                fragments.push(into_literal_fragment(
+                    span,
+                    output.take(),
+                    fragment_offset,
+                    copy_offset,
+                    index,
+                ));
+            }
+
+            return Ok((fragments, index, closing_length));
+        }
+
+        if !indentation.is_empty() && (index == offset || input[index - 1] == b'\n') {
+            let indentation_length = string_doc_indentation(span, index, indentation)?;
+
+            if indentation_length > 0 {
+                let data = output.get_or_insert_with(Vec::new);
+                data.extend(&input[copy_offset..index]);
+
+                index += indentation_length;
+                copy_offset = index;
 
-            let mut ending_offset = 0;
+                continue;
+            }
+        }
 
-            if next_input[lookahead_offset] == b'\r' {
-                if lookahead_offset + 1 >= next_input_length {
-                    return Err(Error::Error(Context::Code(
-                        next_span
-                            .slice(lookahead_offset - name_length - semicolon_offset_adjustment..),
-                        ErrorKind::Custom(StringError::InvalidClosingCharacter as u32),
-                    )));
+        match input[index] {
+            b'\\' => {
+                let mut escaped = Vec::new();
+                let escape_length =
+                    escape(&input[index + 1..], quote, &mut escaped).map_err(|_| {
+                        Error::Error(Context::Code(
+                            span.slice(index..),
+                            ErrorKind::Custom(StringError::InvalidEscapeSequence as u32),
+                        ))
+                    })?;
+
+                if escape_length == 0 {
+                    index += 1;
+
+                    continue;
                 }
 
-                ending_offset = 1;
-                lookahead_offset += 1;
+                let data = output.get_or_insert_with(Vec::new);
+                data.extend(&input[copy_offset..index]);
+                data.extend(escaped);
+
+                index += 1 + escape_length;
+                copy_offset = index;
             }
 
-            if next_input[lookahead_offset] == b'\n' {
-                if index == 0 {
-                    return Ok((
-                        next_span.slice(lookahead_offset + 1..),
-                        Literal::String(Token::new(
-                            Cow::Borrowed(&b""[..]),
-                            span.slice(..span_offset + lookahead_offset - ending_offset),
-                        )),
-                    ));
+            b'$' | b'{' => {
+                let embedding = if input[index] == b'$' {
+                    embedded_variable(span.slice(index..))
+                } else {
+                    embedded_expression(span.slice(index..))
+                };
+
+                match embedding {
+                    Ok(Some((next_span, expression))) => {
+                        if fragment_offset < index {
+                            fragments.push(into_literal_fragment(
+                                span,
+                                output.take(),
+                                fragment_offset,
+                                copy_offset,
+                                index,
+                            ));
+                        }
+
+                        let next_index = input_length - next_span.input_len();
+
+                        fragments.push(StringFragment::Expression(Token::new(
+                            Box::new(expression),
+                            span.slice(index..next_index),
+                        )));
+
+                        index = next_index;
+                        fragment_offset = index;
+                        copy_offset = index;
+                    }
+
+                    Ok(None) => {
+                        index += 1;
+                    }
+
+                    Err(_) => {
+                        return Err(Error::Error(Context::Code(
+                            span.slice(index..),
+                            ErrorKind::Custom(StringError::InvalidEmbeddedExpression as u32),
+                        )));
+                    }
                 }
+            }
 
-                return Ok((
-                    next_span.slice(lookahead_offset + 1..),
-                    Literal::String(Token::new(
-                        Cow::Borrowed(&next_input[offset + 1..offset - ending_offset + index]),
-                        span.slice(..span_offset + lookahead_offset - ending_offset),
-                    )),
-                ));
+            _ => {
+                index += 1;
             }
         }
     }
@@ -628,102 +981,412 @@ fn string_nowdoc(span: Span) -> Result<Span, Literal> {
     )))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::super::super::ast::Literal;
-    use super::super::super::internal::{Context, Error, ErrorKind};
-    use super::super::super::tokens::{Span, Token};
-    use super::{
-        binary, decimal, exponential, hexadecimal, integer, literal, octal, string, string_nowdoc,
-        string_single_quoted, StringError,
-    };
-    use nom::Slice;
-    use std::borrow::Cow;
+/// Build a literal fragment ranging from `from` to `to`. If some
+/// escape sequences have been interpreted, `output` contains the
+/// value up to `copy_from`.
+fn into_literal_fragment<'a>(
+    span: Span<'a>,
+    output: Option<Vec<u8>>,
+    from: usize,
+    copy_from: usize,
+    to: usize,
+) -> StringFragment<'a> {
+    let input = span.as_slice();
+    let value = match output {
+        Some(mut data) => {
+            data.extend(&input[copy_from..to]);
 
-    #[test]
-    fn case_binary_lowercase_b() {
-        let input = Span::new(b"0b101010");
-        let output = Ok((
-            Span::new_at(b"", 8, 1, 9),
-            Literal::Integer(Token::new(42i64, input)),
-        ));
+            Cow::Owned(data)
+        }
 
-        assert_eq!(binary(input), output);
-        assert_eq!(integer(input), output);
-        assert_eq!(literal(input), output);
-    }
+        None => Cow::Borrowed(&input[from..to]),
+    };
 
-    #[test]
-    fn case_binary_uppercase_b() {
-        let input = Span::new(b"0B101010");
-        let output = Ok((
-            Span::new_at(b"", 8, 1, 9),
-            Literal::Integer(Token::new(42i64, input)),
-        ));
+    StringFragment::Literal(Token::new(value, span.slice(from..to)))
+}
 
-        assert_eq!(binary(input), output);
-        assert_eq!(integer(input), output);
-        assert_eq!(literal(input), output);
-    }
+/// Turn a list of fragments into a string literal if there is no
+/// embedded expression, or into an interpolated string literal.
+fn into_string_literal<'a>(mut fragments: Vec<StringFragment<'a>>, span: Span<'a>) -> Literal<'a> {
+    match fragments.len() {
+        0 => Literal::String(Token::new(Cow::Borrowed(&b""[..]), span)),
 
-    #[test]
-    fn case_binary_maximum_integer_value() {
-        let input = Span::new(b"0b111111111111111111111111111111111111111111111111111111111111111");
-        let output = Ok((
-            Span::new_at(b"", 65, 1, 66),
-            Literal::Integer(Token::new(::std::i64::MAX, input)),
-        ));
+        1 => match fragments.pop() {
+            Some(StringFragment::Literal(Token { value, .. })) => {
+                Literal::String(Token::new(value, span))
+            }
 
-        assert_eq!(binary(input), output);
-        assert_eq!(integer(input), output);
-        assert_eq!(literal(input), output);
-    }
+            Some(fragment) => Literal::InterpolatedString(Token::new(vec![fragment], span)),
 
-    #[test]
-    fn case_invalid_binary_overflow() {
-        let input =
-            Span::new(b"0b1000000000000000000000000000000000000000000000000000000000000000");
-        let output = Ok((
-            Span::new_at(
-                b"b1000000000000000000000000000000000000000000000000000000000000000",
-                1,
-                1,
-                2,
-            ),
-            Literal::Integer(Token::new(0i64, Span::new(b"0"))),
-        ));
+            None => unreachable!(),
+        },
 
-        assert_eq!(
-            binary(input),
-            Err(Error::Error(Context::Code(input, ErrorKind::MapRes)))
-        );
-        assert_eq!(integer(input), output);
-        assert_eq!(literal(input), output);
+        _ => Literal::InterpolatedString(Token::new(fragments, span)),
     }
+}
 
-    #[test]
-    fn case_invalid_binary_no_number() {
-        let input = Span::new(b"0b");
-        let output = Ok((
-            Span::new_at(b"b", 1, 1, 2),
-            Literal::Integer(Token::new(0i64, Span::new(b"0"))),
-        ));
+/// Interpret the escape sequence starting at `input`, right after the
+/// backslash, and write its value into `output`.
+///
+/// The output is the length of the escape sequence, or 0 if it is not
+/// an escape sequence, in which case the backslash is kept verbatim.
+fn escape(input: &[u8], quote: Option<u8>, output: &mut Vec<u8>) -> StdResult<usize, ()> {
+    let first = match input.first() {
+        Some(first) => *first,
+        None => return Ok(0),
+    };
 
-        assert_eq!(
-            binary(input),
-            Err(Error::Error(Context::Code(input, ErrorKind::RegexpFind)))
-        );
-        assert_eq!(integer(input), output);
-        assert_eq!(literal(input), output);
-    }
+    match first {
+        b'n' => output.push(b'\n'),
+        b't' => output.push(b'\t'),
+        b'r' => output.push(b'\r'),
+        b'v' => output.push(0x0b),
+        b'e' => output.push(0x1b),
+        b'f' => output.push(0x0c),
+        b'\\' => output.push(b'\\'),
+        b'$' => output.push(b'$'),
+
+        b'0'..=b'7' => {
+            let length = input
+                .iter()
+                .take(3)
+                .take_while(|item| **item >= b'0' && **item <= b'7')
+                .count();
+            let value =
+                u32::from_str_radix(unsafe { str::from_utf8_unchecked(&input[..length]) }, 8)
+                    .map_err(|_| ())?;
+
+            output.push((value & 0xff) as u8);
+
+            return Ok(length);
+        }
 
-    #[test]
-    fn case_invalid_binary_not_starting_by_zero_b() {
-        let input = Span::new(b"1");
-        let output = Ok((
-            Span::new_at(b"", 1, 1, 2),
-            Literal::Integer(Token::new(1i64, input)),
-        ));
+        b'x' => {
+            let length = input[1..]
+                .iter()
+                .take(2)
+                .take_while(|item| (**item as char).is_digit(16))
+                .count();
+
+            if length == 0 {
+                return Ok(0);
+            }
+
+            let value =
+                u8::from_str_radix(unsafe { str::from_utf8_unchecked(&input[1..=length]) }, 16)
+                    .map_err(|_| ())?;
+
+            output.push(value);
+
+            return Ok(1 + length);
+        }
+
+        b'u' => {
+            if input.get(1) != Some(&b'{') {
+                return Ok(0);
+            }
+
+            let length = input[2..].iter().take_while(|item| **item != b'}').count();
+
+            if 2 + length >= input.len() || length == 0 {
+                return Err(());
+            }
+
+            let hexadecimal = &input[2..2 + length];
+
+            if !hexadecimal.iter().all(|item| (*item as char).is_digit(16)) {
+                return Err(());
+            }
+
+            let code_point =
+                u32::from_str_radix(unsafe { str::from_utf8_unchecked(hexadecimal) }, 16)
+                    .map_err(|_| ())?;
+
+            encode_utf8(code_point, output)?;
+
+            return Ok(length + 3);
+        }
+
+        _ => {
+            if Some(first) == quote {
+                output.push(first);
+            } else {
+                return Ok(0);
+            }
+        }
+    }
+
+    Ok(1)
+}
+
+/// Encode a code point as UTF-8 into `output`. Surrogates are encoded
+/// like any other code point.
+fn encode_utf8(code_point: u32, output: &mut Vec<u8>) -> StdResult<(), ()> {
+    if code_point < 0x80 {
+        output.push(code_point as u8);
+    } else if code_point < 0x800 {
+        output.push(0xc0 | (code_point >> 6) as u8);
+        output.push(0x80 | (code_point & 0x3f) as u8);
+    } else if code_point < 0x10000 {
+        output.push(0xe0 | (code_point >> 12) as u8);
+        output.push(0x80 | ((code_point >> 6) & 0x3f) as u8);
+        output.push(0x80 | (code_point & 0x3f) as u8);
+    } else if code_point <= 0x0010_ffff {
+        output.push(0xf0 | (code_point >> 18) as u8);
+        output.push(0x80 | ((code_point >> 12) & 0x3f) as u8);
+        output.push(0x80 | ((code_point >> 6) & 0x3f) as u8);
+        output.push(0x80 | (code_point & 0x3f) as u8);
+    } else {
+        return Err(());
+    }
+
+    Ok(())
+}
+
+/// Recognize a variable embedded in a string with the simple syntax,
+/// e.g. `$foo`, `$foo[42]`, `$foo[bar]`, `$foo[$bar]`, or
+/// `$foo->bar`, or with the `${foo}` syntax.
+///
+/// The output is `None` if `$` does not start an embedded variable.
+fn embedded_variable(span: Span) -> StdResult<Option<(Span, Expression)>, ()> {
+    if let Ok((next_span, expression)) = embedded_variable_name(span) {
+        return Ok(Some((next_span, expression)));
+    } else if span.as_slice().starts_with(b"${") {
+        return Err(());
+    }
+
+    let (next_span, variable) = match variable(span) {
+        Ok(result) => result,
+        Err(_) => return Ok(None),
+    };
+
+    let next_input = next_span.as_slice();
+
+    if next_input.starts_with(tokens::LEFT_SQUARE_BRACKET) {
+        return match terminated!(
+            next_span.slice(1..),
+            embedded_offset,
+            tag!(tokens::RIGHT_SQUARE_BRACKET)
+        ) {
+            Ok((next_span, offset)) => Ok(Some((
+                next_span,
+                Expression::ArrayAccess(
+                    DereferencableExpression::Variable(variable),
+                    Some(Box::new(offset)),
+                ),
+            ))),
+
+            Err(_) => Err(()),
+        };
+    }
+
+    if next_input.starts_with(tokens::DYNAMIC_CALL) {
+        if let Ok((next_span, name)) = name(next_span.slice(2..)) {
+            return Ok(Some((
+                next_span,
+                Expression::PropertyAccess(
+                    DereferencableExpression::Variable(variable),
+                    MemberName::Name(name),
+                ),
+            )));
+        }
+    }
+
+    Ok(Some((next_span, Expression::Variable(variable))))
+}
+
+/// Recognize an expression embedded in a string with the `{$…}`
+/// syntax.
+///
+/// The output is `None` if `{` does not start an embedded expression.
+fn embedded_expression(span: Span) -> StdResult<Option<(Span, Expression)>, ()> {
+    if !span.as_slice().starts_with(b"{$") {
+        return Ok(None);
+    }
+
+    match delimited!(
+        span,
+        tag!(tokens::LEFT_CURLY_BRACKET),
+        expression,
+        first!(tag!(tokens::RIGHT_CURLY_BRACKET))
+    ) {
+        Ok(result) => Ok(Some(result)),
+        Err(_) => Err(()),
+    }
+}
+
+named!(
+    embedded_variable_name<Span, Expression>,
+    do_parse!(
+        tag!(tokens::VARIABLE) >>
+        tag!(tokens::LEFT_CURLY_BRACKET) >>
+        name: name >>
+        offset: opt!(
+            complete!(
+                delimited!(
+                    tag!(tokens::LEFT_SQUARE_BRACKET),
+                    first!(expression),
+                    first!(tag!(tokens::RIGHT_SQUARE_BRACKET))
+                )
+            )
+        ) >>
+        first!(tag!(tokens::RIGHT_CURLY_BRACKET)) >>
+        (into_embedded_variable_name(name, offset))
+    )
+);
+
+#[inline]
+fn into_embedded_variable_name<'a>(
+    name: Span<'a>,
+    offset: Option<Expression<'a>>,
+) -> Expression<'a> {
+    match offset {
+        Some(offset) => Expression::ArrayAccess(
+            DereferencableExpression::Variable(Variable(name)),
+            Some(Box::new(offset)),
+        ),
+
+        None => Expression::Variable(Variable(name)),
+    }
+}
+
+named!(
+    embedded_offset<Span, Expression>,
+    alt!(
+        variable                => { embedded_offset_variable_mapper }
+      | name                    => { embedded_offset_name_mapper }
+      | regex!(r"^-?[0-9]+")    => { embedded_offset_number_mapper }
+    )
+);
+
+#[inline]
+fn embedded_offset_variable_mapper(variable: Variable) -> Expression {
+    Expression::Variable(variable)
+}
+
+#[inline]
+fn embedded_offset_name_mapper(span: Span) -> Expression {
+    Expression::Literal(Literal::String(Token::new(
+        Cow::Borrowed(span.as_slice()),
+        span,
+    )))
+}
+
+/// A number in an offset is an integer if it is written as a canonical
+/// decimal integer, else it is a string, e.g. `$foo[42]` vs.
+/// `$foo[042]`.
+fn embedded_offset_number_mapper(span: Span) -> Expression {
+    let input = span.as_slice();
+    let digits = if input[0] == b'-' { &input[1..] } else { input };
+    let is_canonical = digits == b"0" && input[0] != b'-' || digits[0] != b'0';
+
+    if is_canonical {
+        if let Ok(integer) = i64::from_str(unsafe { str::from_utf8_unchecked(input) }) {
+            return Expression::Literal(Literal::Integer(Token::new(integer, span)));
+        }
+    }
+
+    Expression::Literal(Literal::String(Token::new(Cow::Borrowed(input), span)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::ast::{
+        DereferencableExpression, Expression, Literal, MemberName, StringFragment, Variable,
+    };
+    use super::super::super::internal::{Context, Error, ErrorKind};
+    use super::super::super::tokens::{Span, Token};
+    use super::{
+        binary, decimal, exponential, hexadecimal, integer, literal, octal, string,
+        string_double_quoted, string_heredoc, string_nowdoc, string_single_quoted, StringError,
+    };
+    use nom::Slice;
+    use std::borrow::Cow;
+
+    #[test]
+    fn case_binary_lowercase_b() {
+        let input = Span::new(b"0b101010");
+        let output = Ok((
+            Span::new_at(b"", 8, 1, 9),
+            Literal::Integer(Token::new(42i64, input)),
+        ));
+
+        assert_eq!(binary(input), output);
+        assert_eq!(integer(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_binary_uppercase_b() {
+        let input = Span::new(b"0B101010");
+        let output = Ok((
+            Span::new_at(b"", 8, 1, 9),
+            Literal::Integer(Token::new(42i64, input)),
+        ));
+
+        assert_eq!(binary(input), output);
+        assert_eq!(integer(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_binary_maximum_integer_value() {
+        let input = Span::new(b"0b111111111111111111111111111111111111111111111111111111111111111");
+        let output = Ok((
+            Span::new_at(b"", 65, 1, 66),
+            Literal::Integer(Token::new(::std::i64::MAX, input)),
+        ));
+
+        assert_eq!(binary(input), output);
+        assert_eq!(integer(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_binary_overflow() {
+        let input =
+            Span::new(b"0b1000000000000000000000000000000000000000000000000000000000000000");
+        let output = Ok((
+            Span::new_at(
+                b"b1000000000000000000000000000000000000000000000000000000000000000",
+                1,
+                1,
+                2,
+            ),
+            Literal::Integer(Token::new(0i64, Span::new(b"0"))),
+        ));
+
+        assert_eq!(
+            binary(input),
+            Err(Error::Error(Context::Code(input, ErrorKind::MapRes)))
+        );
+        assert_eq!(integer(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_binary_no_number() {
+        let input = Span::new(b"0b");
+        let output = Ok((
+            Span::new_at(b"b", 1, 1, 2),
+            Literal::Integer(Token::new(0i64, Span::new(b"0"))),
+        ));
+
+        assert_eq!(
+            binary(input),
+            Err(Error::Error(Context::Code(input, ErrorKind::RegexpFind)))
+        );
+        assert_eq!(integer(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_binary_not_starting_by_zero_b() {
+        let input = Span::new(b"1");
+        let output = Ok((
+            Span::new_at(b"", 1, 1, 2),
+            Literal::Integer(Token::new(1i64, input)),
+        ));
 
         assert_eq!(
             binary(input),
@@ -861,7 +1524,9 @@ mod tests {
             let input  = input * 2 + 1;
             let string = input.to_string();
 
-            match decimal(Span::new(string.as_bytes())) {
+            let result = decimal(Span::new(string.as_bytes()));
+
+            match result {
                 Ok((_, Literal::Integer(Token { value: output, .. }))) => {
                     input == output as u32
                 },
@@ -1549,114 +2214,920 @@ mod tests {
     }
 
     #[test]
-    fn case_string_nowdoc() {
-        let input = Span::new(b"<<<'FOO'\nhello \n  world \nFOO;\ntail");
+    fn case_string_double_quoted() {
+        let input = Span::new(b"\"foobar\"tail");
         let output = Ok((
-            Span::new_at(b"tail", 30, 5, 1),
+            Span::new_at(b"tail", 8, 1, 9),
             Literal::String(Token::new(
-                Cow::from(&b"hello \n  world "[..]),
-                Span::new(b"<<<'FOO'\nhello \n  world \nFOO;\n"),
+                Cow::from(&b"foobar"[..]),
+                Span::new(b"\"foobar\""),
             )),
         ));
 
-        assert_eq!(string_nowdoc(input), output);
+        assert_eq!(string_double_quoted(input), output);
         assert_eq!(string(input), output);
         assert_eq!(literal(input), output);
     }
 
     #[test]
-    fn case_string_nowdoc_without_copy() {
-        let input = Span::new(b"<<<'FOO'\nhello \n  world \nFOO;\ntail");
+    fn case_string_double_quoted_without_copy() {
+        let input = Span::new(b"\"foobar\"tail");
 
-        match string_nowdoc(input) {
+        match string_double_quoted(input) {
             Ok((
                 Span { .. },
                 Literal::String(Token {
                     value: Cow::Borrowed(..),
                     ..
                 }),
-            )) => {
-                assert!(true);
-            }
+            )) => {}
 
-            _ => {
-                assert!(false);
-            }
+            _ => panic!("Expected a borrowed string."),
         }
     }
 
     #[test]
-    fn case_string_nowdoc_crlf() {
-        let input = Span::new(b"<<<'FOO'\r\nhello \r\n  world \r\nFOO;\r\ntail");
+    fn case_string_double_quoted_empty() {
+        let input = Span::new(b"\"\"tail");
         let output = Ok((
-            Span::new_at(b"tail", 34, 5, 1),
+            Span::new_at(b"tail", 2, 1, 3),
+            Literal::String(Token::new(Cow::from(&b""[..]), Span::new(b"\"\""))),
+        ));
+
+        assert_eq!(string_double_quoted(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_double_quoted_escaped_characters() {
+        let input = Span::new(b"\"a\\nb\\tc\\rd\\ve\\ef\\fg\\\\h\\$i\\\"j\"tail");
+        let output = Ok((
+            Span::new_at(b"tail", 30, 1, 31),
             Literal::String(Token::new(
-                Cow::from(&b"hello \r\n  world "[..]),
-                Span::new(b"<<<'FOO'\r\nhello \r\n  world \r\nFOO;\r\n"),
+                Cow::from(&b"a\nb\tc\rd\x0be\x1bf\x0cg\\h$i\"j"[..]),
+                Span::new(b"\"a\\nb\\tc\\rd\\ve\\ef\\fg\\\\h\\$i\\\"j\""),
             )),
         ));
 
-        assert_eq!(string_nowdoc(input), output);
+        assert_eq!(string_double_quoted(input), output);
         assert_eq!(string(input), output);
         assert_eq!(literal(input), output);
     }
 
     #[test]
-    fn case_string_nowdoc_without_semi_colon() {
-        let input = Span::new(b"<<<'FOO'\nhello \n  world \nFOO\ntail");
+    fn case_string_double_quoted_escaped_characters_with_copy() {
+        let input = Span::new(b"\"foo\\nbar\"tail");
+
+        match string_double_quoted(input) {
+            Ok((
+                Span { .. },
+                Literal::String(Token {
+                    value: Cow::Owned(..),
+                    ..
+                }),
+            )) => {}
+
+            _ => panic!("Expected an owned string."),
+        }
+    }
+
+    #[test]
+    fn case_string_double_quoted_escaped_octal() {
+        let input = Span::new(b"\"\\101\\1012\\0\\400\\8\"tail");
         let output = Ok((
-            Span::new_at(b"tail", 29, 5, 1),
+            Span::new_at(b"tail", 19, 1, 20),
             Literal::String(Token::new(
-                Cow::from(&b"hello \n  world "[..]),
-                Span::new(b"<<<'FOO'\nhello \n  world \nFOO\n"),
+                Cow::from(&b"AA2\x00\x00\\8"[..]),
+                Span::new(b"\"\\101\\1012\\0\\400\\8\""),
             )),
         ));
 
-        assert_eq!(string_nowdoc(input), output);
+        assert_eq!(string_double_quoted(input), output);
         assert_eq!(string(input), output);
         assert_eq!(literal(input), output);
     }
 
     #[test]
-    fn case_string_nowdoc_without_semi_colon_crlf() {
-        let input = Span::new(b"<<<'FOO'\r\nhello \r\n  world \r\nFOO\r\ntail");
+    fn case_string_double_quoted_escaped_hexadecimal() {
+        let input = Span::new(b"\"\\x41\\x4g\\xz\\x414\"tail");
         let output = Ok((
-            Span::new_at(b"tail", 33, 5, 1),
+            Span::new_at(b"tail", 18, 1, 19),
             Literal::String(Token::new(
-                Cow::from(&b"hello \r\n  world "[..]),
-                Span::new(b"<<<'FOO'\r\nhello \r\n  world \r\nFOO\r\n"),
+                Cow::from(&b"A\x04g\\xzA4"[..]),
+                Span::new(b"\"\\x41\\x4g\\xz\\x414\""),
             )),
         ));
 
-        assert_eq!(string_nowdoc(input), output);
+        assert_eq!(string_double_quoted(input), output);
         assert_eq!(string(input), output);
         assert_eq!(literal(input), output);
     }
 
     #[test]
-    fn case_string_nowdoc_empty() {
-        let input = Span::new(b"<<<'FOO'\nFOO\ntail");
+    fn case_string_double_quoted_escaped_unicode() {
+        let input = Span::new(b"\"\\u{1F600}\\u{41}\\u{00e9}\\u\"tail");
         let output = Ok((
-            Span::new_at(b"tail", 13, 3, 1),
+            Span::new_at(b"tail", 27, 1, 28),
             Literal::String(Token::new(
-                Cow::from(&b""[..]),
-                Span::new(b"<<<'FOO'\nFOO\n"),
+                Cow::from(&b"\xf0\x9f\x98\x80A\xc3\xa9\\u"[..]),
+                Span::new(b"\"\\u{1F600}\\u{41}\\u{00e9}\\u\""),
             )),
         ));
 
-        assert_eq!(string_nowdoc(input), output);
+        assert_eq!(string_double_quoted(input), output);
         assert_eq!(string(input), output);
         assert_eq!(literal(input), output);
     }
 
     #[test]
-    fn case_string_nowdoc_empty_crlf() {
-        let input = Span::new(b"<<<'FOO'\r\nFOO\r\ntail");
+    fn case_string_double_quoted_escaped_any_without_copy() {
+        let input = Span::new(b"\"foo\\abar\\'baz\"tail");
+
+        match string_double_quoted(input) {
+            Ok((
+                Span { .. },
+                Literal::String(Token {
+                    value: Cow::Borrowed(value),
+                    ..
+                }),
+            )) => {
+                assert_eq!(value, &b"foo\\abar\\'baz"[..]);
+            }
+
+            _ => panic!("Expected a borrowed string."),
+        }
+    }
+
+    #[test]
+    fn case_string_double_quoted_with_literal_dollars_and_braces() {
+        let input = Span::new(b"\"$ $1 {foo} { $foo\"tail");
+        let output = Ok((
+            Span::new_at(b"tail", 19, 1, 20),
+            Literal::InterpolatedString(Token::new(
+                vec![
+                    StringFragment::Literal(Token::new(
+                        Cow::from(&b"$ $1 {foo} { "[..]),
+                        Span::new_at(b"$ $1 {foo} { ", 1, 1, 2),
+                    )),
+                    StringFragment::Expression(Token::new(
+                        Box::new(Expression::Variable(Variable(Span::new_at(
+                            b"foo", 15, 1, 16,
+                        )))),
+                        Span::new_at(b"$foo", 14, 1, 15),
+                    )),
+                ],
+                Span::new(b"\"$ $1 {foo} { $foo\""),
+            )),
+        ));
+
+        assert_eq!(string_double_quoted(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_double_quoted_with_escaped_embedded_variables() {
+        let input = Span::new(b"\"\\$foo {\\$foo}\"tail");
         let output = Ok((
-            Span::new_at(b"tail", 15, 3, 1),
+            Span::new_at(b"tail", 15, 1, 16),
             Literal::String(Token::new(
-                Cow::from(&b""[..]),
-                Span::new(b"<<<'FOO'\r\nFOO\r\n"),
+                Cow::from(&b"$foo {$foo}"[..]),
+                Span::new(b"\"\\$foo {\\$foo}\""),
+            )),
+        ));
+
+        assert_eq!(string_double_quoted(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_double_quoted_with_embedded_variable() {
+        let input = Span::new(b"\"foo $bar baz\\n\"tail");
+        let output = Ok((
+            Span::new_at(b"tail", 16, 1, 17),
+            Literal::InterpolatedString(Token::new(
+                vec![
+                    StringFragment::Literal(Token::new(
+                        Cow::from(&b"foo "[..]),
+                        Span::new_at(b"foo ", 1, 1, 2),
+                    )),
+                    StringFragment::Expression(Token::new(
+                        Box::new(Expression::Variable(Variable(Span::new_at(
+                            b"bar", 6, 1, 7,
+                        )))),
+                        Span::new_at(b"$bar", 5, 1, 6),
+                    )),
+                    StringFragment::Literal(Token::new(
+                        Cow::from(&b" baz\n"[..]),
+                        Span::new_at(b" baz\\n", 9, 1, 10),
+                    )),
+                ],
+                Span::new(b"\"foo $bar baz\\n\""),
+            )),
+        ));
+
+        assert_eq!(string_double_quoted(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_double_quoted_with_embedded_variables_only() {
+        let input = Span::new(b"\"$foo$bar\"tail");
+        let output = Ok((
+            Span::new_at(b"tail", 10, 1, 11),
+            Literal::InterpolatedString(Token::new(
+                vec![
+                    StringFragment::Expression(Token::new(
+                        Box::new(Expression::Variable(Variable(Span::new_at(
+                            b"foo", 2, 1, 3,
+                        )))),
+                        Span::new_at(b"$foo", 1, 1, 2),
+                    )),
+                    StringFragment::Expression(Token::new(
+                        Box::new(Expression::Variable(Variable(Span::new_at(
+                            b"bar", 6, 1, 7,
+                        )))),
+                        Span::new_at(b"$bar", 5, 1, 6),
+                    )),
+                ],
+                Span::new(b"\"$foo$bar\""),
+            )),
+        ));
+
+        assert_eq!(string_double_quoted(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_double_quoted_with_embedded_array_access() {
+        let input = Span::new(b"\"$a[42]$a[-7]$a[042]$a[b]$a[$b]\"tail");
+        let output = Ok((
+            Span::new_at(b"tail", 32, 1, 33),
+            Literal::InterpolatedString(Token::new(
+                vec![
+                    StringFragment::Expression(Token::new(
+                        Box::new(Expression::ArrayAccess(
+                            DereferencableExpression::Variable(Variable(Span::new_at(
+                                b"a", 2, 1, 3,
+                            ))),
+                            Some(Box::new(Expression::Literal(Literal::Integer(Token::new(
+                                42,
+                                Span::new_at(b"42", 4, 1, 5),
+                            ))))),
+                        )),
+                        Span::new_at(b"$a[42]", 1, 1, 2),
+                    )),
+                    StringFragment::Expression(Token::new(
+                        Box::new(Expression::ArrayAccess(
+                            DereferencableExpression::Variable(Variable(Span::new_at(
+                                b"a", 8, 1, 9,
+                            ))),
+                            Some(Box::new(Expression::Literal(Literal::Integer(Token::new(
+                                -7,
+                                Span::new_at(b"-7", 10, 1, 11),
+                            ))))),
+                        )),
+                        Span::new_at(b"$a[-7]", 7, 1, 8),
+                    )),
+                    StringFragment::Expression(Token::new(
+                        Box::new(Expression::ArrayAccess(
+                            DereferencableExpression::Variable(Variable(Span::new_at(
+                                b"a", 14, 1, 15,
+                            ))),
+                            Some(Box::new(Expression::Literal(Literal::String(Token::new(
+                                Cow::from(&b"042"[..]),
+                                Span::new_at(b"042", 16, 1, 17),
+                            ))))),
+                        )),
+                        Span::new_at(b"$a[042]", 13, 1, 14),
+                    )),
+                    StringFragment::Expression(Token::new(
+                        Box::new(Expression::ArrayAccess(
+                            DereferencableExpression::Variable(Variable(Span::new_at(
+                                b"a", 21, 1, 22,
+                            ))),
+                            Some(Box::new(Expression::Literal(Literal::String(Token::new(
+                                Cow::from(&b"b"[..]),
+                                Span::new_at(b"b", 23, 1, 24),
+                            ))))),
+                        )),
+                        Span::new_at(b"$a[b]", 20, 1, 21),
+                    )),
+                    StringFragment::Expression(Token::new(
+                        Box::new(Expression::ArrayAccess(
+                            DereferencableExpression::Variable(Variable(Span::new_at(
+                                b"a", 26, 1, 27,
+                            ))),
+                            Some(Box::new(Expression::Variable(Variable(Span::new_at(
+                                b"b", 29, 1, 30,
+                            ))))),
+                        )),
+                        Span::new_at(b"$a[$b]", 25, 1, 26),
+                    )),
+                ],
+                Span::new(b"\"$a[42]$a[-7]$a[042]$a[b]$a[$b]\""),
+            )),
+        ));
+
+        assert_eq!(string_double_quoted(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_double_quoted_with_embedded_property_access() {
+        let input = Span::new(b"\"$foo->bar->baz $foo->\"tail");
+        let output = Ok((
+            Span::new_at(b"tail", 23, 1, 24),
+            Literal::InterpolatedString(Token::new(
+                vec![
+                    StringFragment::Expression(Token::new(
+                        Box::new(Expression::PropertyAccess(
+                            DereferencableExpression::Variable(Variable(Span::new_at(
+                                b"foo", 2, 1, 3,
+                            ))),
+                            MemberName::Name(Span::new_at(b"bar", 7, 1, 8)),
+                        )),
+                        Span::new_at(b"$foo->bar", 1, 1, 2),
+                    )),
+                    StringFragment::Literal(Token::new(
+                        Cow::from(&b"->baz "[..]),
+                        Span::new_at(b"->baz ", 10, 1, 11),
+                    )),
+                    StringFragment::Expression(Token::new(
+                        Box::new(Expression::Variable(Variable(Span::new_at(
+                            b"foo", 17, 1, 18,
+                        )))),
+                        Span::new_at(b"$foo", 16, 1, 17),
+                    )),
+                    StringFragment::Literal(Token::new(
+                        Cow::from(&b"->"[..]),
+                        Span::new_at(b"->", 20, 1, 21),
+                    )),
+                ],
+                Span::new(b"\"$foo->bar->baz $foo->\""),
+            )),
+        ));
+
+        assert_eq!(string_double_quoted(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_double_quoted_with_embedded_expression() {
+        let input = Span::new(b"\"a{$foo }b\"tail");
+        let output = Ok((
+            Span::new_at(b"tail", 11, 1, 12),
+            Literal::InterpolatedString(Token::new(
+                vec![
+                    StringFragment::Literal(Token::new(
+                        Cow::from(&b"a"[..]),
+                        Span::new_at(b"a", 1, 1, 2),
+                    )),
+                    StringFragment::Expression(Token::new(
+                        Box::new(Expression::Variable(Variable(Span::new_at(
+                            b"foo", 4, 1, 5,
+                        )))),
+                        Span::new_at(b"{$foo }", 2, 1, 3),
+                    )),
+                    StringFragment::Literal(Token::new(
+                        Cow::from(&b"b"[..]),
+                        Span::new_at(b"b", 9, 1, 10),
+                    )),
+                ],
+                Span::new(b"\"a{$foo }b\""),
+            )),
+        ));
+
+        assert_eq!(string_double_quoted(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_double_quoted_with_embedded_variable_name() {
+        let input = Span::new(b"\"${foo}${foo[42]}\"tail");
+        let output = Ok((
+            Span::new_at(b"tail", 18, 1, 19),
+            Literal::InterpolatedString(Token::new(
+                vec![
+                    StringFragment::Expression(Token::new(
+                        Box::new(Expression::Variable(Variable(Span::new_at(
+                            b"foo", 3, 1, 4,
+                        )))),
+                        Span::new_at(b"${foo}", 1, 1, 2),
+                    )),
+                    StringFragment::Expression(Token::new(
+                        Box::new(Expression::ArrayAccess(
+                            DereferencableExpression::Variable(Variable(Span::new_at(
+                                b"foo", 9, 1, 10,
+                            ))),
+                            Some(Box::new(Expression::Literal(Literal::Integer(Token::new(
+                                42,
+                                Span::new_at(b"42", 13, 1, 14),
+                            ))))),
+                        )),
+                        Span::new_at(b"${foo[42]}", 7, 1, 8),
+                    )),
+                ],
+                Span::new(b"\"${foo}${foo[42]}\""),
+            )),
+        ));
+
+        assert_eq!(string_double_quoted(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_binary_double_quoted() {
+        let input = Span::new(b"b\"foo\\nbar\"");
+        let output = Ok((
+            Span::new_at(b"", 11, 1, 12),
+            Literal::String(Token::new(
+                Cow::from(&b"foo\nbar"[..]),
+                Span::new_at(b"\"foo\\nbar\"", 1, 1, 2),
+            )),
+        ));
+
+        assert_eq!(string_double_quoted(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_binary_uppercase_double_quoted() {
+        let input = Span::new(b"B\"foobar\"");
+        let output = Ok((
+            Span::new_at(b"", 9, 1, 10),
+            Literal::String(Token::new(
+                Cow::from(&b"foobar"[..]),
+                Span::new_at(b"\"foobar\"", 1, 1, 2),
+            )),
+        ));
+
+        assert_eq!(string_double_quoted(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_string_double_quoted_too_short() {
+        let input = Span::new(b"\"");
+        let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
+
+        assert_eq!(
+            string_double_quoted(input),
+            Err(Error::Error(Context::Code(
+                input,
+                ErrorKind::Custom(StringError::TooShort as u32)
+            )))
+        );
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_string_double_quoted_opening_character() {
+        let input = Span::new(b"foobar\"");
+        let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
+
+        assert_eq!(
+            string_double_quoted(input),
+            Err(Error::Error(Context::Code(
+                input,
+                ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32)
+            )))
+        );
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_string_double_quoted_closing_character() {
+        let input = Span::new(b"\"foobar");
+        let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
+
+        assert_eq!(
+            string_double_quoted(input),
+            Err(Error::Error(Context::Code(
+                input,
+                ErrorKind::Custom(StringError::InvalidClosingCharacter as u32)
+            )))
+        );
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_string_double_quoted_closing_character_is_escaped() {
+        let input = Span::new(b"\"foobar\\\"");
+        let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
+
+        assert_eq!(
+            string_double_quoted(input),
+            Err(Error::Error(Context::Code(
+                input,
+                ErrorKind::Custom(StringError::InvalidClosingCharacter as u32)
+            )))
+        );
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_string_double_quoted_escaped_unicode_empty() {
+        let input = Span::new(b"\"foo\\u{}\"");
+        let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
+
+        assert_eq!(
+            string_double_quoted(input),
+            Err(Error::Error(Context::Code(
+                input.slice(4..),
+                ErrorKind::Custom(StringError::InvalidEscapeSequence as u32)
+            )))
+        );
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_string_double_quoted_escaped_unicode_out_of_range() {
+        let input = Span::new(b"\"\\u{110000}\"");
+        let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
+
+        assert_eq!(
+            string_double_quoted(input),
+            Err(Error::Error(Context::Code(
+                input.slice(1..),
+                ErrorKind::Custom(StringError::InvalidEscapeSequence as u32)
+            )))
+        );
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_string_double_quoted_escaped_unicode_not_closed() {
+        let input = Span::new(b"\"\\u{41\"");
+        let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
+
+        assert_eq!(
+            string_double_quoted(input),
+            Err(Error::Error(Context::Code(
+                input.slice(1..),
+                ErrorKind::Custom(StringError::InvalidEscapeSequence as u32)
+            )))
+        );
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_string_double_quoted_embedded_array_access() {
+        let input = Span::new(b"\"foo $bar[ 42]\"");
+        let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
+
+        assert_eq!(
+            string_double_quoted(input),
+            Err(Error::Error(Context::Code(
+                input.slice(5..),
+                ErrorKind::Custom(StringError::InvalidEmbeddedExpression as u32)
+            )))
+        );
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_string_double_quoted_embedded_expression_not_closed() {
+        let input = Span::new(b"\"{$foo\"");
+        let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
+
+        assert_eq!(
+            string_double_quoted(input),
+            Err(Error::Error(Context::Code(
+                input.slice(1..),
+                ErrorKind::Custom(StringError::InvalidEmbeddedExpression as u32)
+            )))
+        );
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_string_double_quoted_embedded_variable_name() {
+        let input = Span::new(b"\"${42}\"");
+        let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
+
+        assert_eq!(
+            string_double_quoted(input),
+            Err(Error::Error(Context::Code(
+                input.slice(1..),
+                ErrorKind::Custom(StringError::InvalidEmbeddedExpression as u32)
+            )))
+        );
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_string_binary_double_quoted_opening_character() {
+        let input = Span::new(b"bb\"");
+        let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
+
+        assert_eq!(
+            string_double_quoted(input),
+            Err(Error::Error(Context::Code(
+                input,
+                ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32)
+            )))
+        );
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_nowdoc() {
+        let input = Span::new(b"<<<'FOO'\nhello \n  world \nFOO;\ntail");
+        let output = Ok((
+            Span::new_at(b";\ntail", 28, 4, 4),
+            Literal::String(Token::new(
+                Cow::from(&b"hello \n  world "[..]),
+                Span::new(b"<<<'FOO'\nhello \n  world \nFOO"),
+            )),
+        ));
+
+        assert_eq!(string_nowdoc(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_nowdoc_without_copy() {
+        let input = Span::new(b"<<<'FOO'\nhello \n  world \nFOO;\ntail");
+
+        match string_nowdoc(input) {
+            Ok((
+                Span { .. },
+                Literal::String(Token {
+                    value: Cow::Borrowed(..),
+                    ..
+                }),
+            )) => {
+                assert!(true);
+            }
+
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn case_string_nowdoc_crlf() {
+        let input = Span::new(b"<<<'FOO'\r\nhello \r\n  world \r\nFOO;\r\ntail");
+        let output = Ok((
+            Span::new_at(b";\r\ntail", 31, 4, 4),
+            Literal::String(Token::new(
+                Cow::from(&b"hello \r\n  world "[..]),
+                Span::new(b"<<<'FOO'\r\nhello \r\n  world \r\nFOO"),
+            )),
+        ));
+
+        assert_eq!(string_nowdoc(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_nowdoc_without_semi_colon() {
+        let input = Span::new(b"<<<'FOO'\nhello \n  world \nFOO\ntail");
+        let output = Ok((
+            Span::new_at(b"\ntail", 28, 4, 4),
+            Literal::String(Token::new(
+                Cow::from(&b"hello \n  world "[..]),
+                Span::new(b"<<<'FOO'\nhello \n  world \nFOO"),
+            )),
+        ));
+
+        assert_eq!(string_nowdoc(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_nowdoc_without_semi_colon_crlf() {
+        let input = Span::new(b"<<<'FOO'\r\nhello \r\n  world \r\nFOO\r\ntail");
+        let output = Ok((
+            Span::new_at(b"\r\ntail", 31, 4, 4),
+            Literal::String(Token::new(
+                Cow::from(&b"hello \r\n  world "[..]),
+                Span::new(b"<<<'FOO'\r\nhello \r\n  world \r\nFOO"),
+            )),
+        ));
+
+        assert_eq!(string_nowdoc(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_nowdoc_empty() {
+        let input = Span::new(b"<<<'FOO'\nFOO\ntail");
+        let output = Ok((
+            Span::new_at(b"\ntail", 12, 2, 4),
+            Literal::String(Token::new(Cow::from(&b""[..]), Span::new(b"<<<'FOO'\nFOO"))),
+        ));
+
+        assert_eq!(string_nowdoc(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_nowdoc_empty_crlf() {
+        let input = Span::new(b"<<<'FOO'\r\nFOO\r\ntail");
+        let output = Ok((
+            Span::new_at(b"\r\ntail", 13, 2, 4),
+            Literal::String(Token::new(
+                Cow::from(&b""[..]),
+                Span::new(b"<<<'FOO'\r\nFOO"),
+            )),
+        ));
+
+        assert_eq!(string_nowdoc(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_nowdoc_with_whitespaces_before_identifier() {
+        let input = Span::new(b"<<<   \t  'FOO'\nhello \n  world \nFOO\ntail");
+        let output = Ok((
+            Span::new_at(b"\ntail", 34, 4, 4),
+            Literal::String(Token::new(
+                Cow::from(&b"hello \n  world "[..]),
+                Span::new(b"<<<   \t  'FOO'\nhello \n  world \nFOO"),
+            )),
+        ));
+
+        assert_eq!(string_nowdoc(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_nowdoc_with_whitespaces_before_identifier_crlf() {
+        let input = Span::new(b"<<<   \t  'FOO'\r\nhello \r\n  world \r\nFOO\r\ntail");
+        let output = Ok((
+            Span::new_at(b"\r\ntail", 37, 4, 4),
+            Literal::String(Token::new(
+                Cow::from(&b"hello \r\n  world "[..]),
+                Span::new(b"<<<   \t  'FOO'\r\nhello \r\n  world \r\nFOO"),
+            )),
+        ));
+
+        assert_eq!(string_nowdoc(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_binary_nowdoc() {
+        let input = Span::new(b"b<<<'FOO'\nhello \n  world \nFOO\n");
+        let output = Ok((
+            Span::new_at(b"\n", 29, 4, 4),
+            Literal::String(Token::new(
+                Cow::from(&b"hello \n  world "[..]),
+                input.slice(1..29),
+            )),
+        ));
+
+        assert_eq!(string_nowdoc(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_binary_nowdoc_crlf() {
+        let input = Span::new(b"b<<<'FOO'\r\nhello \r\n  world \r\nFOO\r\n");
+        let output = Ok((
+            Span::new_at(b"\r\n", 32, 4, 4),
+            Literal::String(Token::new(
+                Cow::from(&b"hello \r\n  world "[..]),
+                input.slice(1..32),
+            )),
+        ));
+
+        assert_eq!(string_nowdoc(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_binary_uppercase_nowdoc() {
+        let input = Span::new(b"B<<<'FOO'\nhello \n  world \nFOO\n");
+        let output = Ok((
+            Span::new_at(b"\n", 29, 4, 4),
+            Literal::String(Token::new(
+                Cow::from(&b"hello \n  world "[..]),
+                input.slice(1..29),
+            )),
+        ));
+
+        assert_eq!(string_nowdoc(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_binary_uppercase_nowdoc_crlf() {
+        let input = Span::new(b"B<<<'FOO'\r\nhello \r\n  world \r\nFOO\r\n");
+        let output = Ok((
+            Span::new_at(b"\r\n", 32, 4, 4),
+            Literal::String(Token::new(
+                Cow::from(&b"hello \r\n  world "[..]),
+                input.slice(1..32),
+            )),
+        ));
+
+        assert_eq!(string_nowdoc(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_nowdoc_closing_identifier_at_the_end() {
+        let input = Span::new(b"<<<'FOO'\nhello \n  world \nFOO");
+        let output = Ok((
+            Span::new_at(b"", 28, 4, 4),
+            Literal::String(Token::new(Cow::from(&b"hello \n  world "[..]), input)),
+        ));
+
+        assert_eq!(string_nowdoc(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_nowdoc_closing_identifier_at_the_end_crlf() {
+        let input = Span::new(b"<<<'FOO'\r\nhello \r\n  world \r\nFOO");
+        let output = Ok((
+            Span::new_at(b"", 31, 4, 4),
+            Literal::String(Token::new(Cow::from(&b"hello \r\n  world "[..]), input)),
+        ));
+
+        assert_eq!(string_nowdoc(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_nowdoc_closing_identifier_followed_by_a_semi_colon_at_the_end() {
+        let input = Span::new(b"<<<'FOO'\nhello \n  world \nFOO;");
+        let output = Ok((
+            Span::new_at(b";", 28, 4, 4),
+            Literal::String(Token::new(
+                Cow::from(&b"hello \n  world "[..]),
+                input.slice(..28),
+            )),
+        ));
+
+        assert_eq!(string_nowdoc(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_nowdoc_closing_identifier_followed_by_a_semi_colon_at_the_end_crlf() {
+        let input = Span::new(b"<<<'FOO'\r\nhello \r\n  world \r\nFOO;");
+        let output = Ok((
+            Span::new_at(b";", 31, 4, 4),
+            Literal::String(Token::new(
+                Cow::from(&b"hello \r\n  world "[..]),
+                input.slice(..31),
+            )),
+        ));
+
+        assert_eq!(string_nowdoc(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_nowdoc_closing_identifier_followed_by_a_carriage_return() {
+        let input = Span::new(b"<<<'FOO'\r\nhello \r\n  world \r\nFOO\r");
+        let output = Ok((
+            Span::new_at(b"\r", 31, 4, 4),
+            Literal::String(Token::new(
+                Cow::from(&b"hello \r\n  world "[..]),
+                input.slice(..31),
             )),
         ));
 
@@ -1666,14 +3137,11 @@ mod tests {
     }
 
     #[test]
-    fn case_string_nowdoc_with_whitespaces_before_identifier() {
-        let input = Span::new(b"<<<   \t  'FOO'\nhello \n  world \nFOO\ntail");
+    fn case_string_nowdoc_closing_identifier_followed_by_a_comma() {
+        let input = Span::new(b"<<<'FOO'\nhello\nFOO, 42)");
         let output = Ok((
-            Span::new_at(b"tail", 35, 5, 1),
-            Literal::String(Token::new(
-                Cow::from(&b"hello \n  world "[..]),
-                Span::new(b"<<<   \t  'FOO'\nhello \n  world \nFOO\n"),
-            )),
+            Span::new_at(b", 42)", 18, 3, 4),
+            Literal::String(Token::new(Cow::from(&b"hello"[..]), input.slice(..18))),
         ));
 
         assert_eq!(string_nowdoc(input), output);
@@ -1682,14 +3150,11 @@ mod tests {
     }
 
     #[test]
-    fn case_string_nowdoc_with_whitespaces_before_identifier_crlf() {
-        let input = Span::new(b"<<<   \t  'FOO'\r\nhello \r\n  world \r\nFOO\r\ntail");
+    fn case_string_nowdoc_closing_identifier_followed_by_a_parenthesis() {
+        let input = Span::new(b"<<<'FOO'\nhello\nFOO)");
         let output = Ok((
-            Span::new_at(b"tail", 39, 5, 1),
-            Literal::String(Token::new(
-                Cow::from(&b"hello \r\n  world "[..]),
-                Span::new(b"<<<   \t  'FOO'\r\nhello \r\n  world \r\nFOO\r\n"),
-            )),
+            Span::new_at(b")", 18, 3, 4),
+            Literal::String(Token::new(Cow::from(&b"hello"[..]), input.slice(..18))),
         ));
 
         assert_eq!(string_nowdoc(input), output);
@@ -1698,13 +3163,13 @@ mod tests {
     }
 
     #[test]
-    fn case_string_binary_nowdoc() {
-        let input = Span::new(b"b<<<'FOO'\nhello \n  world \nFOO\n");
+    fn case_string_nowdoc_closing_identifier_prefix_in_the_body() {
+        let input = Span::new(b"<<<'FOO'\nFOOBAR\nFOO_\nFOO\n");
         let output = Ok((
-            Span::new_at(b"", 30, 5, 1),
+            Span::new_at(b"\n", 24, 4, 4),
             Literal::String(Token::new(
-                Cow::from(&b"hello \n  world "[..]),
-                input.slice(1..),
+                Cow::from(&b"FOOBAR\nFOO_"[..]),
+                input.slice(..24),
             )),
         ));
 
@@ -1714,13 +3179,13 @@ mod tests {
     }
 
     #[test]
-    fn case_string_binary_nowdoc_crlf() {
-        let input = Span::new(b"b<<<'FOO'\r\nhello \r\n  world \r\nFOO\r\n");
+    fn case_string_nowdoc_indented() {
+        let input = Span::new(b"<<<'FOO'\n    hello\n      world\n\n  \n    FOO;");
         let output = Ok((
-            Span::new_at(b"", 34, 5, 1),
+            Span::new_at(b";", 42, 6, 8),
             Literal::String(Token::new(
-                Cow::from(&b"hello \r\n  world "[..]),
-                input.slice(1..),
+                Cow::from(&b"hello\n  world\n\n"[..]),
+                input.slice(..42),
             )),
         ));
 
@@ -1730,14 +3195,11 @@ mod tests {
     }
 
     #[test]
-    fn case_string_binary_uppercase_nowdoc() {
-        let input = Span::new(b"B<<<'FOO'\nhello \n  world \nFOO\n");
+    fn case_string_nowdoc_indented_with_tabs_crlf() {
+        let input = Span::new(b"<<<'FOO'\r\n\t\thello\r\n\t\t\tworld\r\n\t\tFOO");
         let output = Ok((
-            Span::new_at(b"", 30, 5, 1),
-            Literal::String(Token::new(
-                Cow::from(&b"hello \n  world "[..]),
-                input.slice(1..),
-            )),
+            Span::new_at(b"", 34, 4, 6),
+            Literal::String(Token::new(Cow::from(&b"hello\r\n\tworld"[..]), input)),
         ));
 
         assert_eq!(string_nowdoc(input), output);
@@ -1746,14 +3208,11 @@ mod tests {
     }
 
     #[test]
-    fn case_string_binary_uppercase_nowdoc_crlf() {
-        let input = Span::new(b"B<<<'FOO'\r\nhello \r\n  world \r\nFOO\r\n");
+    fn case_string_nowdoc_indented_empty() {
+        let input = Span::new(b"<<<'FOO'\n  FOO");
         let output = Ok((
-            Span::new_at(b"", 34, 5, 1),
-            Literal::String(Token::new(
-                Cow::from(&b"hello \r\n  world "[..]),
-                input.slice(1..),
-            )),
+            Span::new_at(b"", 14, 2, 6),
+            Literal::String(Token::new(Cow::from(&b""[..]), input)),
         ));
 
         assert_eq!(string_nowdoc(input), output);
@@ -1763,7 +3222,7 @@ mod tests {
 
     #[test]
     fn case_invalid_string_nowdoc_too_short() {
-        let input = Span::new(b"<<<'A'\nA");
+        let input = Span::new(b"<<<'A'\n");
         let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
 
         assert_eq!(
@@ -1890,14 +3349,14 @@ mod tests {
     }
 
     #[test]
-    fn case_invalid_string_nowdoc_closing_character_no_semi_colon_no_newline() {
-        let input = Span::new(b"<<<'FOO'\nhello \n  world \nFOO");
+    fn case_invalid_string_nowdoc_closing_character_crlf() {
+        let input = Span::new(b"<<<'FOO'\r\nhello \r\n  world \r\nFO;\r\n");
         let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
 
         assert_eq!(
             string_nowdoc(input),
             Err(Error::Error(Context::Code(
-                Span::new_at(b"FOO", 25, 4, 1),
+                input,
                 ErrorKind::Custom(StringError::InvalidClosingCharacter as u32)
             )))
         );
@@ -1906,14 +3365,14 @@ mod tests {
     }
 
     #[test]
-    fn case_invalid_string_nowdoc_closing_character_no_semi_colon_no_newline_crlf() {
-        let input = Span::new(b"<<<'FOO'\r\nhello \r\n  world \r\nFOO");
+    fn case_invalid_string_nowdoc_closing_identifier_followed_by_an_identifier_character() {
+        let input = Span::new(b"<<<'FOO'\nhello\nFOOBAR");
         let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
 
         assert_eq!(
             string_nowdoc(input),
             Err(Error::Error(Context::Code(
-                Span::new_at(b"FOO", 28, 4, 1),
+                input,
                 ErrorKind::Custom(StringError::InvalidClosingCharacter as u32)
             )))
         );
@@ -1922,15 +3381,15 @@ mod tests {
     }
 
     #[test]
-    fn case_invalid_string_nowdoc_closing_character_no_newline() {
-        let input = Span::new(b"<<<'FOO'\nhello \n  world \nFOO;");
+    fn case_invalid_string_nowdoc_closing_identifier_with_mixed_indentation() {
+        let input = Span::new(b"<<<'FOO'\n \thello\n \tFOO\n");
         let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
 
         assert_eq!(
             string_nowdoc(input),
             Err(Error::Error(Context::Code(
-                Span::new_at(b"FOO;", 25, 4, 1),
-                ErrorKind::Custom(StringError::InvalidClosingCharacter as u32)
+                input.slice(17..),
+                ErrorKind::Custom(StringError::InvalidMixedIndentation as u32)
             )))
         );
         assert_eq!(string(input), output);
@@ -1938,15 +3397,15 @@ mod tests {
     }
 
     #[test]
-    fn case_invalid_string_nowdoc_closing_character_no_newline_crlf() {
-        let input = Span::new(b"<<<'FOO'\r\nhello \r\n  world \r\nFOO;");
+    fn case_invalid_string_nowdoc_body_with_mixed_indentation() {
+        let input = Span::new(b"<<<'FOO'\n  hello\n\tworld\n  FOO\n");
         let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
 
         assert_eq!(
             string_nowdoc(input),
             Err(Error::Error(Context::Code(
-                Span::new_at(b"FOO;", 28, 4, 1),
-                ErrorKind::Custom(StringError::InvalidClosingCharacter as u32)
+                input.slice(17..),
+                ErrorKind::Custom(StringError::InvalidMixedIndentation as u32)
             )))
         );
         assert_eq!(string(input), output);
@@ -1954,15 +3413,15 @@ mod tests {
     }
 
     #[test]
-    fn case_invalid_string_nowdoc_closing_character_missing_lf_in_crlf() {
-        let input = Span::new(b"<<<'FOO'\r\nhello \r\n  world \r\nFOO\r");
+    fn case_invalid_string_nowdoc_body_indentation() {
+        let input = Span::new(b"<<<'FOO'\n  hello\n world\n  FOO\n");
         let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
 
         assert_eq!(
             string_nowdoc(input),
             Err(Error::Error(Context::Code(
-                Span::new_at(b"FOO\r", 28, 4, 1),
-                ErrorKind::Custom(StringError::InvalidClosingCharacter as u32)
+                input.slice(17..),
+                ErrorKind::Custom(StringError::InvalidBodyIndentation as u32)
             )))
         );
         assert_eq!(string(input), output);
@@ -1971,7 +3430,7 @@ mod tests {
 
     #[test]
     fn case_invalid_string_binary_nowdoc_too_short() {
-        let input = Span::new(b"b<<<'A'\nA");
+        let input = Span::new(b"b<<<'A'\n");
         let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
 
         assert_eq!(
@@ -2035,7 +3494,7 @@ mod tests {
 
     #[test]
     fn case_invalid_string_binary_uppercase_nowdoc_too_short() {
-        let input = Span::new(b"B<<<'A'\nA");
+        let input = Span::new(b"B<<<'A'\n");
         let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
 
         assert_eq!(
@@ -2096,4 +3555,342 @@ mod tests {
         assert_eq!(string(input), output);
         assert_eq!(literal(input), output);
     }
+
+    #[test]
+    fn case_string_heredoc() {
+        let input = Span::new(b"<<<FOO\nhello \n  world \nFOO;\ntail");
+        let output = Ok((
+            Span::new_at(b";\ntail", 26, 4, 4),
+            Literal::String(Token::new(
+                Cow::from(&b"hello \n  world "[..]),
+                Span::new(b"<<<FOO\nhello \n  world \nFOO"),
+            )),
+        ));
+
+        assert_eq!(string_heredoc(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_heredoc_without_copy() {
+        let input = Span::new(b"<<<FOO\nhello \n  world \nFOO;\ntail");
+
+        match string_heredoc(input) {
+            Ok((
+                Span { .. },
+                Literal::String(Token {
+                    value: Cow::Borrowed(..),
+                    ..
+                }),
+            )) => {}
+
+            _ => panic!("Expected a borrowed string."),
+        }
+    }
+
+    #[test]
+    fn case_string_heredoc_quoted_identifier() {
+        let input = Span::new(b"<<<\"FOO\"\nhello \nFOO\ntail");
+        let output = Ok((
+            Span::new_at(b"\ntail", 19, 3, 4),
+            Literal::String(Token::new(
+                Cow::from(&b"hello "[..]),
+                Span::new(b"<<<\"FOO\"\nhello \nFOO"),
+            )),
+        ));
+
+        assert_eq!(string_heredoc(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_heredoc_crlf() {
+        let input = Span::new(b"<<<FOO\r\nhello \r\n  world \r\nFOO;\r\ntail");
+        let output = Ok((
+            Span::new_at(b";\r\ntail", 29, 4, 4),
+            Literal::String(Token::new(
+                Cow::from(&b"hello \r\n  world "[..]),
+                Span::new(b"<<<FOO\r\nhello \r\n  world \r\nFOO"),
+            )),
+        ));
+
+        assert_eq!(string_heredoc(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_heredoc_empty() {
+        let input = Span::new(b"<<<FOO\nFOO\ntail");
+        let output = Ok((
+            Span::new_at(b"\ntail", 10, 2, 4),
+            Literal::String(Token::new(Cow::from(&b""[..]), Span::new(b"<<<FOO\nFOO"))),
+        ));
+
+        assert_eq!(string_heredoc(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_heredoc_with_whitespaces_before_identifier() {
+        let input = Span::new(b"<<<   \t  FOO\nhello\nFOO\ntail");
+        let output = Ok((
+            Span::new_at(b"\ntail", 22, 3, 4),
+            Literal::String(Token::new(
+                Cow::from(&b"hello"[..]),
+                Span::new(b"<<<   \t  FOO\nhello\nFOO"),
+            )),
+        ));
+
+        assert_eq!(string_heredoc(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_heredoc_escaped_characters() {
+        let input = Span::new(b"<<<FOO\n\"\\t\\\"\\x41\\$\"\nFOO\ntail");
+        let output = Ok((
+            Span::new_at(b"\ntail", 23, 3, 4),
+            Literal::String(Token::new(
+                Cow::from(&b"\"\t\\\"A$\""[..]),
+                Span::new(b"<<<FOO\n\"\\t\\\"\\x41\\$\"\nFOO"),
+            )),
+        ));
+
+        assert_eq!(string_heredoc(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_heredoc_with_embedded_expressions() {
+        let input = Span::new(b"<<<FOO\nhello $name,\n{$foo}\nFOO\ntail");
+        let output = Ok((
+            Span::new_at(b"\ntail", 30, 4, 4),
+            Literal::InterpolatedString(Token::new(
+                vec![
+                    StringFragment::Literal(Token::new(
+                        Cow::from(&b"hello "[..]),
+                        Span::new_at(b"hello ", 7, 2, 1),
+                    )),
+                    StringFragment::Expression(Token::new(
+                        Box::new(Expression::Variable(Variable(Span::new_at(
+                            b"name", 14, 2, 8,
+                        )))),
+                        Span::new_at(b"$name", 13, 2, 7),
+                    )),
+                    StringFragment::Literal(Token::new(
+                        Cow::from(&b",\n"[..]),
+                        Span::new_at(b",\n", 18, 2, 12),
+                    )),
+                    StringFragment::Expression(Token::new(
+                        Box::new(Expression::Variable(Variable(Span::new_at(
+                            b"foo", 22, 3, 3,
+                        )))),
+                        Span::new_at(b"{$foo}", 20, 3, 1),
+                    )),
+                ],
+                Span::new(b"<<<FOO\nhello $name,\n{$foo}\nFOO"),
+            )),
+        ));
+
+        assert_eq!(string_heredoc(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_binary_heredoc() {
+        let input = Span::new(b"b<<<FOO\nhello\nFOO\ntail");
+        let output = Ok((
+            Span::new_at(b"\ntail", 17, 3, 4),
+            Literal::String(Token::new(
+                Cow::from(&b"hello"[..]),
+                Span::new_at(b"<<<FOO\nhello\nFOO", 1, 1, 2),
+            )),
+        ));
+
+        assert_eq!(string_heredoc(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_string_heredoc_too_short() {
+        let input = Span::new(b"<<<A\n");
+        let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
+
+        assert_eq!(
+            string_heredoc(input),
+            Err(Error::Error(Context::Code(
+                input,
+                ErrorKind::Custom(StringError::TooShort as u32)
+            )))
+        );
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_string_heredoc_opening_character() {
+        let input = Span::new(b"<<FOO\nhello\nFOO\n");
+        let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
+
+        assert_eq!(
+            string_heredoc(input),
+            Err(Error::Error(Context::Code(
+                input,
+                ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32)
+            )))
+        );
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_string_heredoc_opening_character_missing_second_quote() {
+        let input = Span::new(b"<<<\"FOO\nhello\nFOO\n");
+        let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
+
+        assert_eq!(
+            string_heredoc(input),
+            Err(Error::Error(Context::Code(
+                input.slice(4..),
+                ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32)
+            )))
+        );
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_string_heredoc_invalid_identifier() {
+        let input = Span::new(b"<<<42\nhello\n42\n");
+        let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
+
+        assert_eq!(
+            string_heredoc(input),
+            Err(Error::Error(Context::Code(
+                input.slice(3..),
+                ErrorKind::Custom(StringError::InvalidDelimiterIdentifier as u32)
+            )))
+        );
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_string_heredoc_opening_character_missing_newline() {
+        let input = Span::new(b"<<<FOO hello\nFOO\n");
+        let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
+
+        assert_eq!(
+            string_heredoc(input),
+            Err(Error::Error(Context::Code(
+                input.slice(3..),
+                ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32)
+            )))
+        );
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_string_heredoc_closing_character() {
+        let input = Span::new(b"<<<FOO\nhello\nFO;\n");
+        let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
+
+        assert_eq!(
+            string_heredoc(input),
+            Err(Error::Error(Context::Code(
+                input,
+                ErrorKind::Custom(StringError::InvalidClosingCharacter as u32)
+            )))
+        );
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_string_heredoc_embedded_expression() {
+        let input = Span::new(b"<<<FOO\n{$foo\nFOO\n");
+        let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
+
+        assert_eq!(
+            string_heredoc(input),
+            Err(Error::Error(Context::Code(
+                input.slice(7..),
+                ErrorKind::Custom(StringError::InvalidEmbeddedExpression as u32)
+            )))
+        );
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_heredoc_indented() {
+        let input = Span::new(b"<<<FOO\n  hello $name\n    world\n  FOO, 42");
+        let output = Ok((
+            Span::new_at(b", 42", 36, 4, 6),
+            Literal::InterpolatedString(Token::new(
+                vec![
+                    StringFragment::Literal(Token::new(
+                        Cow::from(&b"hello "[..]),
+                        Span::new_at(b"  hello ", 7, 2, 1),
+                    )),
+                    StringFragment::Expression(Token::new(
+                        Box::new(Expression::Variable(Variable(Span::new_at(
+                            b"name", 16, 2, 10,
+                        )))),
+                        Span::new_at(b"$name", 15, 2, 9),
+                    )),
+                    StringFragment::Literal(Token::new(
+                        Cow::from(&b"\n  world"[..]),
+                        Span::new_at(b"\n    world", 20, 2, 14),
+                    )),
+                ],
+                input.slice(..36),
+            )),
+        ));
+
+        assert_eq!(string_heredoc(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_string_heredoc_closing_identifier_with_mixed_indentation() {
+        let input = Span::new(b"<<<FOO\n\t a\n\t FOO\n");
+        let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
+
+        assert_eq!(
+            string_heredoc(input),
+            Err(Error::Error(Context::Code(
+                input.slice(11..),
+                ErrorKind::Custom(StringError::InvalidMixedIndentation as u32)
+            )))
+        );
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_string_heredoc_body_indentation() {
+        let input = Span::new(b"<<<FOO\n  a\n$foo\n  FOO\n");
+        let output = Err(Error::Error(Context::Code(input, ErrorKind::Alt)));
+
+        assert_eq!(
+            string_heredoc(input),
+            Err(Error::Error(Context::Code(
+                input.slice(11..),
+                ErrorKind::Custom(StringError::InvalidBodyIndentation as u32)
+            )))
+        );
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
 }
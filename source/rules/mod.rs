@@ -33,6 +33,7 @@
 //!
 //! The grammar is splitted into group of rules for the sake of clarity.
 
+pub mod attributes;
 pub mod comments;
 pub mod expressions;
 pub mod literals;
@@ -41,8 +42,22 @@ pub mod statements;
 pub mod tokens;
 pub mod whitespaces;
 
-use super::ast::Expression;
+use super::ast::{Expression, Program, Statement};
+use super::internal::{Context, Error, ErrorKind};
 use super::tokens::Span;
+use nom::InputLength;
+use std::result::Result as StdResult;
+
+/// An error raised when a datum cannot be parsed as a whole program.
+#[derive(Debug, PartialEq)]
+pub struct ProgramError<'a> {
+    /// The span where the parser has stopped.
+    pub span: Span<'a>,
+
+    /// The kind of the error, as reported by the failing rule. Custom
+    /// kinds hold the error enumeration of the rule, like `StringError`.
+    pub kind: ErrorKind,
+}
 
 /// The `root` parser is the axiom of the grammar, i.e. the entry
 /// point of all the parsers.
@@ -75,11 +90,153 @@ pub fn root(input: Span) -> Expression {
     }
 }
 
+named!(
+    statement_list<Span, Vec<Statement>>,
+    do_parse!(
+        head: call!(statements::leading_inline_html) >>
+        list: fold_many0!(
+            first!(complete!(statements::statement)),
+            fold_statement(Vec::new(), head),
+            fold_statement
+        ) >>
+        (list)
+    )
+);
+
+/// Fold a statement into a list of statements, except empty inline
+/// HTML, e.g. between `?>` and `<?php`, that carries nothing.
+#[inline]
+fn fold_statement<'a>(
+    mut accumulator: Vec<Statement<'a>>,
+    statement: Statement<'a>,
+) -> Vec<Statement<'a>> {
+    if let Statement::InlineHtml(ref span) = statement {
+        if span.input_len() == 0 {
+            return accumulator;
+        }
+    }
+
+    accumulator.push(statement);
+
+    accumulator
+}
+
+/// The `program` parser is the axiom of the grammar for a whole file,
+/// i.e. a datum made of top-level statements.
+///
+/// A file starts with inline HTML, until the first open tag (`<?php` or
+/// `<?=`). Contrary to other rules, the entire input must be consumed.
+/// If the parser stops before the end of the input, the span and the
+/// kind of the error are returned instead of the program. Once a
+/// statement is recognized up to its end, e.g. up to its terminator, a
+/// syntax error inside it is reported where the statement stops.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tagua_parser;
+/// use tagua_parser::ast::{
+///     Arity,
+///     Function,
+///     FunctionKind,
+///     Program,
+///     Statement,
+///     Ty
+/// };
+/// use tagua_parser::internal::ErrorKind;
+/// use tagua_parser::rules::{
+///     ProgramError,
+///     program
+/// };
+/// use tagua_parser::tokens::Span;
+///
+/// # fn main() {
+/// assert_eq!(
+///     program(Span::new(b"<?php function f() {}\n")),
+///     Ok(
+///         Program {
+///             statements: vec![
+///                 Statement::Function(
+///                     Function {
+///                         attributes: vec![],
+///                         name      : Span::new_at(b"f", 15, 1, 16),
+///                         inputs    : Arity::Constant,
+///                         output    : Ty::Copy(None),
+///                         kind      : FunctionKind::Regular,
+///                         body      : vec![]
+///                     }
+///                 )
+///             ]
+///         }
+///     )
+/// );
+///
+/// assert_eq!(
+///     program(Span::new(b"<?php function f() {} !")),
+///     Err(
+///         ProgramError {
+///             span: Span::new_at(b"!", 22, 1, 23),
+///             kind: ErrorKind::Alt
+///         }
+///     )
+/// );
+/// # }
+/// ```
+pub fn program(input: Span) -> StdResult<Program, ProgramError> {
+    let (input, statements) = match statement_list(input) {
+        Ok(result) => result,
+        Err(error) => return Err(into_program_error(error, input)),
+    };
+    let input = match skip::skip(input) {
+        Ok((input, _)) => input,
+        Err(error) => return Err(into_program_error(error, input)),
+    };
+
+    if input.input_len() == 0 {
+        return Ok(Program { statements });
+    }
+
+    // The list of statements has stopped before the end of the
+    // input. Run the statement rule again to report its error.
+    match statements::statement(input) {
+        Err(error) => Err(into_program_error(error, input)),
+        Ok(_) => Err(ProgramError {
+            span: input,
+            kind: ErrorKind::Eof,
+        }),
+    }
+}
+
+#[inline]
+fn into_program_error<'a>(error: Error<Span<'a>>, input: Span<'a>) -> ProgramError<'a> {
+    match error {
+        Error::Error(context) | Error::Failure(context) => match context {
+            Context::Code(span, kind) => ProgramError { span, kind },
+
+            Context::List(mut errors) => {
+                let (span, kind) = errors.remove(0);
+
+                ProgramError { span, kind }
+            }
+        },
+
+        Error::Incomplete(_) => ProgramError {
+            span: input,
+            kind: ErrorKind::Complete,
+        },
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::super::ast::{Expression, Literal};
+    use super::super::ast::{
+        Arity, BinaryOperator, ControlSyntax, Expression, Function, FunctionKind, Literal,
+        NAryOperation, Program, Statement, Ty, Variable,
+    };
+    use super::super::internal::ErrorKind;
     use super::super::tokens::{Span, Token};
-    use super::root;
+    use super::statements::function::TypeError;
+    use super::{program, root, ProgramError};
     use std::borrow::Cow;
 
     #[test]
@@ -98,4 +255,229 @@ mod tests {
     fn case_root_panic() {
         root(Span::new(b"!"));
     }
+
+    #[test]
+    fn case_program_empty() {
+        let input = Span::new(b"");
+        let output = Ok(Program { statements: vec![] });
+
+        assert_eq!(program(input), output);
+    }
+
+    #[test]
+    fn case_program_only_inline_html() {
+        let input = Span::new(b"  /* foo */\n<?xml ?>\n");
+        let output = Ok(Program {
+            statements: vec![Statement::InlineHtml(input)],
+        });
+
+        assert_eq!(program(input), output);
+    }
+
+    #[test]
+    fn case_program_only_whitespaces_and_comments() {
+        let input = Span::new(b"<?php  /* foo */\n// bar\n");
+        let output = Ok(Program { statements: vec![] });
+
+        assert_eq!(program(input), output);
+    }
+
+    #[test]
+    fn case_program_many_statements() {
+        let input = Span::new(b"<?php\nfunction f() {}\nfunction g() { return; }");
+        let output = Ok(Program {
+            statements: vec![
+                Statement::Function(Function {
+                    attributes: vec![],
+                    name: Span::new_at(b"f", 15, 2, 10),
+                    inputs: Arity::Constant,
+                    output: Ty::Copy(None),
+                    kind: FunctionKind::Regular,
+                    body: vec![],
+                }),
+                Statement::Function(Function {
+                    attributes: vec![],
+                    name: Span::new_at(b"g", 31, 3, 10),
+                    inputs: Arity::Constant,
+                    output: Ty::Copy(None),
+                    kind: FunctionKind::Regular,
+                    body: vec![Statement::Return(None)],
+                }),
+            ],
+        });
+
+        assert_eq!(program(input), output);
+    }
+
+    #[test]
+    fn case_program_interleaved_with_inline_html() {
+        let input = Span::new(
+            b"<p>\n<?php function f() {} ?>\n<b><?= $x ?></b>\n<?php function g() {} ?>\n",
+        );
+        let output = Ok(Program {
+            statements: vec![
+                Statement::InlineHtml(Span::new(b"<p>\n")),
+                Statement::Function(Function {
+                    attributes: vec![],
+                    name: Span::new_at(b"f", 19, 2, 16),
+                    inputs: Arity::Constant,
+                    output: Ty::Copy(None),
+                    kind: FunctionKind::Regular,
+                    body: vec![],
+                }),
+                Statement::InlineHtml(Span::new_at(b"<b>", 29, 3, 1)),
+                Statement::Expression(Expression::Echo(vec![Expression::Variable(Variable(
+                    Span::new_at(b"x", 37, 3, 9),
+                ))])),
+                Statement::InlineHtml(Span::new_at(b"</b>\n", 41, 3, 13)),
+                Statement::Function(Function {
+                    attributes: vec![],
+                    name: Span::new_at(b"g", 61, 4, 16),
+                    inputs: Arity::Constant,
+                    output: Ty::Copy(None),
+                    kind: FunctionKind::Regular,
+                    body: vec![],
+                }),
+            ],
+        });
+
+        assert_eq!(program(input), output);
+    }
+
+    #[test]
+    fn case_program_close_tag_in_a_single_line_comment() {
+        let input = Span::new(b"<?php // foo ?>bar");
+        let output = Ok(Program {
+            statements: vec![Statement::InlineHtml(Span::new_at(b"bar", 15, 1, 16))],
+        });
+
+        assert_eq!(program(input), output);
+    }
+
+    #[test]
+    fn case_program_with_control_structures() {
+        let input = Span::new(b"<?php\nwhile ($x) {\n    if ($y) break;\n}\n?>done");
+        let output = Ok(Program {
+            statements: vec![
+                Statement::While {
+                    condition: Expression::Variable(Variable(Span::new_at(b"x", 14, 2, 9))),
+                    body: vec![Statement::If {
+                        condition: Expression::Variable(Variable(Span::new_at(b"y", 28, 3, 10))),
+                        body: vec![Statement::Break(None)],
+                        else_ifs: vec![],
+                        else_body: None,
+                        syntax: ControlSyntax::Standard,
+                    }],
+                    syntax: ControlSyntax::Standard,
+                },
+                Statement::InlineHtml(Span::new_at(b"done", 42, 5, 3)),
+            ],
+        });
+
+        assert_eq!(program(input), output);
+    }
+
+    #[test]
+    fn case_program_template_with_alternative_syntax() {
+        let input =
+            Span::new(b"<?php foreach ($xs as $x): ?><li><?= $x ?></li><?php endforeach; ?>\n");
+        let output = Ok(Program {
+            statements: vec![Statement::Foreach {
+                subject: Expression::Variable(Variable(Span::new_at(b"xs", 16, 1, 17))),
+                key: None,
+                value: Box::new(Expression::Variable(Variable(Span::new_at(
+                    b"x", 23, 1, 24,
+                )))),
+                body: vec![
+                    Statement::InlineHtml(Span::new_at(b"<li>", 29, 1, 30)),
+                    Statement::Expression(Expression::Echo(vec![Expression::Variable(Variable(
+                        Span::new_at(b"x", 37, 1, 38),
+                    ))])),
+                    Statement::InlineHtml(Span::new_at(b"</li>", 42, 1, 43)),
+                ],
+                syntax: ControlSyntax::Alternative,
+            }],
+        });
+
+        assert_eq!(program(input), output);
+    }
+
+    #[test]
+    fn case_program_binary_strings() {
+        let input = Span::new(b"<?php $x = b\"xy\"; $y = B'z';");
+        let output = Ok(Program {
+            statements: vec![
+                Statement::Expression(Expression::NAryOperation(NAryOperation::Binary {
+                    operator: BinaryOperator::Assignment,
+                    left_operand: Box::new(NAryOperation::Nullary(Box::new(Expression::Variable(
+                        Variable(Span::new_at(b"x", 7, 1, 8)),
+                    )))),
+                    right_operand: Box::new(NAryOperation::Nullary(Box::new(Expression::Literal(
+                        Literal::String(Token::new(
+                            Cow::from(&b"xy"[..]),
+                            Span::new_at(b"\"xy\"", 12, 1, 13),
+                        )),
+                    )))),
+                })),
+                Statement::Expression(Expression::NAryOperation(NAryOperation::Binary {
+                    operator: BinaryOperator::Assignment,
+                    left_operand: Box::new(NAryOperation::Nullary(Box::new(Expression::Variable(
+                        Variable(Span::new_at(b"y", 19, 1, 20)),
+                    )))),
+                    right_operand: Box::new(NAryOperation::Nullary(Box::new(Expression::Literal(
+                        Literal::String(Token::new(
+                            Cow::from(&b"z"[..]),
+                            Span::new_at(b"'z'", 24, 1, 25),
+                        )),
+                    )))),
+                })),
+            ],
+        });
+
+        assert_eq!(program(input), output);
+    }
+
+    #[test]
+    fn case_invalid_program_unconsumed_input() {
+        let input = Span::new(b"<?php function f() {}\n!");
+        let output = Err(ProgramError {
+            span: Span::new_at(b"!", 22, 2, 1),
+            kind: ErrorKind::Alt,
+        });
+
+        assert_eq!(program(input), output);
+    }
+
+    #[test]
+    fn case_invalid_program_incomplete_statement() {
+        let input = Span::new(b"<?php function f() {");
+        let output = Err(ProgramError {
+            span: Span::new_at(b"", 20, 1, 21),
+            kind: ErrorKind::Eof,
+        });
+
+        assert_eq!(program(input), output);
+    }
+
+    #[test]
+    fn case_invalid_program_after_several_statements() {
+        let input = Span::new(b"<?php\n$x = 1;\n$y = 2;\nif ($x) {\n    $z = ;\n}\n");
+        let output = Err(ProgramError {
+            span: Span::new_at(b"= ;\n}\n", 39, 5, 8),
+            kind: ErrorKind::Alt,
+        });
+
+        assert_eq!(program(input), output);
+    }
+
+    #[test]
+    fn case_invalid_program_type() {
+        let input = Span::new(b"<?php function f(?mixed $x) {}");
+        let output = Err(ProgramError {
+            span: Span::new_at(b"(?mixed $x) {}", 16, 1, 17),
+            kind: ErrorKind::Custom(TypeError::InvalidNullableType as u32),
+        });
+
+        assert_eq!(program(input), output);
+    }
 }
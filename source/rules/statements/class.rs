@@ -0,0 +1,2291 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2017, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of class, interface, trait and enumeration declaration rules.
+//!
+//! The class, interface and trait declarations are provided by the PHP
+//! Language Specification in the [Grammar chapter, Classes
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#classes),
+//! [Interfaces
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#interfaces),
+//! and [Traits
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#traits).
+//! Enumerations have been introduced in PHP 8.1, after the
+//! specification.
+
+use super::super::super::ast::{
+    Attribute, Class, ClassMember, Enum, EnumCase, Expression, FunctionKind, Interface, Modifier,
+    Name, Statement, Trait, TraitAdaptation, Ty, Variable, Visibility,
+};
+use super::super::super::internal::{Context, Error, ErrorKind, Result};
+use super::super::super::tokens;
+use super::super::super::tokens::Span;
+use super::super::attributes::attributes;
+use super::super::expressions::constant::constant_expression;
+use super::super::skip::skip;
+use super::super::tokens::{name, qualified_name, variable};
+use super::compound_statement;
+use super::function::{
+    check_type, checked_parameters, function_kind, native_type, output_type, type_expression,
+};
+use std::result::Result as StdResult;
+
+/// Class errors.
+///
+/// They are failures: once the keyword of a declaration or the
+/// keywords of a member are recognized, nothing else can be parsed
+/// from the same input.
+#[derive(Debug, PartialEq)]
+pub enum ClassError {
+    /// A modifier is declared more than once.
+    DuplicatedModifier,
+
+    /// A member has more than one visibility.
+    MultipleVisibilities,
+
+    /// A class or a member is declared both `abstract` and `final`.
+    AbstractAndFinal,
+
+    /// A modifier is not allowed on this kind of member, e.g. a
+    /// `static` constant, a `readonly` method, or `var` combined with
+    /// another modifier.
+    InvalidModifier,
+
+    /// A property is declared without any visibility nor modifier.
+    MissingPropertyModifier,
+
+    /// A `readonly` property has no type.
+    UntypedReadonlyProperty,
+
+    /// An abstract method has a body.
+    AbstractMethodWithBody,
+
+    /// A non-abstract method has no body.
+    MissingMethodBody,
+
+    /// An interface member is not a constant nor a method.
+    InvalidInterfaceMember,
+
+    /// An interface member is not public.
+    NonPublicInterfaceMember,
+
+    /// An interface method has a body.
+    InterfaceMethodWithBody,
+
+    /// The backing type of an enumeration is not `int` nor `string`.
+    InvalidEnumBackingType,
+
+    /// A case of a backed enumeration has no value.
+    MissingEnumCaseValue,
+
+    /// A case of a pure enumeration has a value.
+    UnexpectedEnumCaseValue,
+
+    /// An enumeration member is a property.
+    InvalidEnumMember,
+
+    /// A use of traits has attributes.
+    InvalidAttributeTarget,
+}
+
+named_attr!(
+    #[doc="
+        Recognize a class declaration.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{
+            Arity,
+            Class,
+            ClassMember,
+            FunctionKind,
+            Modifier,
+            Name,
+            Statement,
+            Ty,
+            Visibility
+        };
+        use tagua_parser::rules::statements::class::class;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            class(Span::new(b\"final class C extends D { public function f() {} }\")),
+            Ok((
+                Span::new_at(b\"\", 50, 1, 51),
+                Statement::Class(
+                    Class {
+                        attributes: vec![],
+                        modifiers : vec![Modifier::Final],
+                        name      : Span::new_at(b\"C\", 12, 1, 13),
+                        extends   : Some(Name::Unqualified(Span::new_at(b\"D\", 22, 1, 23))),
+                        implements: vec![],
+                        members   : vec![
+                            ClassMember::Method {
+                                attributes: vec![],
+                                visibility: Visibility::Public,
+                                modifiers : vec![],
+                                name      : Span::new_at(b\"f\", 42, 1, 43),
+                                inputs    : Arity::Constant,
+                                output    : Box::new(Ty::Copy(None)),
+                                kind      : FunctionKind::Regular,
+                                body      : Some(vec![])
+                            }
+                        ]
+                    }
+                )
+            ))
+        );
+        # }
+        ```
+    "],
+    pub class<Span, Statement>,
+    do_parse!(
+        attributes: attributes >>
+        modifiers: class_modifiers >>
+        first!(statement_keyword!(tokens::CLASS)) >>
+        name: first!(name) >>
+        extends: opt!(
+            preceded!(
+                first!(statement_keyword!(tokens::EXTENDS)),
+                first!(qualified_name)
+            )
+        ) >>
+        implements: opt!(
+            preceded!(
+                first!(statement_keyword!(tokens::IMPLEMENTS)),
+                first!(names)
+            )
+        ) >>
+        members: call!(members, class_member) >>
+        (
+            Statement::Class(
+                Class {
+                    attributes: attributes,
+                    modifiers : modifiers,
+                    name      : name,
+                    extends   : extends,
+                    implements: implements.unwrap_or_else(Vec::new),
+                    members   : members
+                }
+            )
+        )
+    )
+);
+
+/// Recognize the modifiers of a class, i.e. `abstract`, `final` and
+/// `readonly`, in any order.
+fn class_modifiers(input: Span) -> Result<Span, Vec<Modifier>> {
+    let (next_input, modifiers) =
+        fold_into_vector_many0!(input, complete!(first!(class_modifier)), Vec::new())?;
+
+    match check_modifiers(&modifiers) {
+        Ok(()) => Ok((next_input, modifiers)),
+        Err(error) => Err(Error::Failure(Context::Code(
+            input,
+            ErrorKind::Custom(error as u32),
+        ))),
+    }
+}
+
+named!(
+    class_modifier<Span, Modifier>,
+    alt_complete!(
+        statement_keyword!(tokens::ABSTRACT) => { |_| Modifier::Abstract }
+      | statement_keyword!(tokens::FINAL) => { |_| Modifier::Final }
+      | statement_keyword!(tokens::READONLY) => { |_| Modifier::Readonly }
+    )
+);
+
+named!(
+    names<Span, Vec<Name>>,
+    do_parse!(
+        accumulator: map!(first!(qualified_name), |name| vec![name]) >>
+        result: fold_into_vector_many0!(
+            preceded!(
+                first!(tag!(tokens::COMMA)),
+                first!(qualified_name)
+            ),
+            accumulator
+        ) >>
+        (result)
+    )
+);
+
+/// Recognize the members of a class, an interface or a trait,
+/// delimited by curly braces. An invalid member makes the whole body
+/// invalid, with the error of the member.
+fn members<'a, F>(input: Span<'a>, member: F) -> Result<Span<'a>, Vec<ClassMember<'a>>>
+where
+    F: Fn(Span<'a>) -> Result<Span<'a>, ClassMember<'a>>,
+{
+    let (mut input, _) = first!(input, tag!(tokens::LEFT_CURLY_BRACKET))?;
+    let mut members = Vec::new();
+
+    loop {
+        if let Ok((next_input, _)) = first!(input, tag!(tokens::RIGHT_CURLY_BRACKET)) {
+            return Ok((next_input, members));
+        }
+
+        let (next_input, member) = first!(input, &member)?;
+
+        members.push(member);
+        input = next_input;
+    }
+}
+
+named_attr!(
+    #[doc="
+        Recognize an interface declaration.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{ClassMember, Expression, Interface, Literal, Statement, Visibility};
+        use tagua_parser::rules::statements::class::interface;
+        use tagua_parser::tokens::{Span, Token};
+
+        # fn main() {
+        assert_eq!(
+            interface(Span::new(b\"interface I { const A = 1; }\")),
+            Ok((
+                Span::new_at(b\"\", 28, 1, 29),
+                Statement::Interface(
+                    Interface {
+                        attributes: vec![],
+                        name      : Span::new_at(b\"I\", 10, 1, 11),
+                        extends   : vec![],
+                        members   : vec![
+                            ClassMember::Constant {
+                                attributes: vec![],
+                                visibility: Visibility::Public,
+                                modifiers : vec![],
+                                constants : vec![
+                                    (
+                                        Span::new_at(b\"A\", 20, 1, 21),
+                                        Expression::Literal(Literal::Integer(Token::new(1i64, Span::new_at(b\"1\", 24, 1, 25))))
+                                    )
+                                ]
+                            }
+                        ]
+                    }
+                )
+            ))
+        );
+        # }
+        ```
+    "],
+    pub interface<Span, Statement>,
+    do_parse!(
+        attributes: attributes >>
+        first!(statement_keyword!(tokens::INTERFACE)) >>
+        name: first!(name) >>
+        extends: opt!(
+            preceded!(
+                first!(statement_keyword!(tokens::EXTENDS)),
+                first!(names)
+            )
+        ) >>
+        members: call!(members, interface_member) >>
+        (
+            Statement::Interface(
+                Interface {
+                    attributes: attributes,
+                    name      : name,
+                    extends   : extends.unwrap_or_else(Vec::new),
+                    members   : members
+                }
+            )
+        )
+    )
+);
+
+named_attr!(
+    #[doc="
+        Recognize a trait declaration.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{
+            Arity, ClassMember, FunctionKind, Statement, Trait, Ty, Visibility
+        };
+        use tagua_parser::rules::statements::class::trait_declaration;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            trait_declaration(Span::new(b\"trait T { function f() {} }\")),
+            Ok((
+                Span::new_at(b\"\", 27, 1, 28),
+                Statement::Trait(
+                    Trait {
+                        attributes: vec![],
+                        name      : Span::new_at(b\"T\", 6, 1, 7),
+                        members   : vec![
+                            ClassMember::Method {
+                                attributes: vec![],
+                                visibility: Visibility::Public,
+                                modifiers : vec![],
+                                name      : Span::new_at(b\"f\", 19, 1, 20),
+                                inputs    : Arity::Constant,
+                                output    : Box::new(Ty::Copy(None)),
+                                kind      : FunctionKind::Regular,
+                                body      : Some(vec![])
+                            }
+                        ]
+                    }
+                )
+            ))
+        );
+        # }
+        ```
+    "],
+    pub trait_declaration<Span, Statement>,
+    do_parse!(
+        attributes: attributes >>
+        first!(statement_keyword!(tokens::TRAIT)) >>
+        name: first!(name) >>
+        members: call!(members, class_member) >>
+        (
+            Statement::Trait(
+                Trait {
+                    attributes: attributes,
+                    name      : name,
+                    members   : members
+                }
+            )
+        )
+    )
+);
+
+named_attr!(
+    #[doc="
+        Recognize an enumeration declaration.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{
+            Arity, ClassMember, Enum, EnumCase, FunctionKind, Statement, Ty, Visibility
+        };
+        use tagua_parser::rules::statements::class::enum_declaration;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            enum_declaration(Span::new(b\"enum E { case A; case B; public function f() {} }\")),
+            Ok((
+                Span::new_at(b\"\", 49, 1, 50),
+                Statement::Enum(
+                    Enum {
+                        attributes  : vec![],
+                        name        : Span::new_at(b\"E\", 5, 1, 6),
+                        backing_type: None,
+                        implements  : vec![],
+                        cases       : vec![
+                            EnumCase {
+                                attributes: vec![],
+                                name      : Span::new_at(b\"A\", 14, 1, 15),
+                                value     : None
+                            },
+                            EnumCase {
+                                attributes: vec![],
+                                name      : Span::new_at(b\"B\", 22, 1, 23),
+                                value     : None
+                            }
+                        ],
+                        members     : vec![
+                            ClassMember::Method {
+                                attributes: vec![],
+                                visibility: Visibility::Public,
+                                modifiers : vec![],
+                                name      : Span::new_at(b\"f\", 41, 1, 42),
+                                inputs    : Arity::Constant,
+                                output    : Box::new(Ty::Copy(None)),
+                                kind      : FunctionKind::Regular,
+                                body      : Some(vec![])
+                            }
+                        ]
+                    }
+                )
+            ))
+        );
+        # }
+        ```
+    "],
+    pub enum_declaration<Span, Statement>,
+    do_parse!(
+        attributes: attributes >>
+        first!(statement_keyword!(tokens::ENUM)) >>
+        name: first!(name) >>
+        backing_type: enum_backing_type >>
+        implements: opt!(
+            preceded!(
+                first!(statement_keyword!(tokens::IMPLEMENTS)),
+                first!(names)
+            )
+        ) >>
+        body: call!(enum_body, backing_type.is_some()) >>
+        ({
+            let (cases, members) = body;
+
+            Statement::Enum(
+                Enum {
+                    attributes  : attributes,
+                    name        : name,
+                    backing_type: backing_type,
+                    implements  : implements.unwrap_or_else(Vec::new),
+                    cases       : cases,
+                    members     : members
+                }
+            )
+        })
+    )
+);
+
+/// Recognize the optional backing type of an enumeration, i.e. `:
+/// int` or `: string`.
+fn enum_backing_type(input: Span) -> Result<Span, Option<Name>> {
+    let (next_input, backing_type) = opt!(
+        input,
+        preceded!(
+            first!(tag!(tokens::ENUM_BACKING_TYPE)),
+            alt_complete!(first!(native_type) | first!(qualified_name))
+        )
+    )?;
+
+    let is_valid = match backing_type {
+        Some(Name::FullyQualified(ref names)) => {
+            names.len() == 1
+                && (names[0].as_slice().eq_ignore_ascii_case(tokens::INT)
+                    || names[0].as_slice().eq_ignore_ascii_case(tokens::STRING))
+        }
+
+        Some(_) => false,
+
+        None => true,
+    };
+
+    if !is_valid {
+        return Err(Error::Failure(Context::Code(
+            input,
+            ErrorKind::Custom(ClassError::InvalidEnumBackingType as u32),
+        )));
+    }
+
+    Ok((next_input, backing_type))
+}
+
+/// Recognize the body of an enumeration, i.e. a list of cases and
+/// members delimited by curly braces. The cases of a backed
+/// enumeration must have a value, and the cases of a pure enumeration
+/// must not.
+fn enum_body<'a>(
+    input: Span<'a>,
+    is_backed: bool,
+) -> Result<Span<'a>, (Vec<EnumCase<'a>>, Vec<ClassMember<'a>>)> {
+    let (mut input, _) = first!(input, tag!(tokens::LEFT_CURLY_BRACKET))?;
+    let mut cases = Vec::new();
+    let mut members = Vec::new();
+
+    loop {
+        let (member_input, _) = skip(input)?;
+
+        if let Ok((next_input, _)) = tag!(member_input, tokens::RIGHT_CURLY_BRACKET) {
+            return Ok((next_input, (cases, members)));
+        }
+
+        if let Ok((next_input, case)) = enum_case(member_input) {
+            if is_backed != case.value.is_some() {
+                let error = if is_backed {
+                    ClassError::MissingEnumCaseValue
+                } else {
+                    ClassError::UnexpectedEnumCaseValue
+                };
+
+                return Err(Error::Failure(Context::Code(
+                    member_input,
+                    ErrorKind::Custom(error as u32),
+                )));
+            }
+
+            cases.push(case);
+            input = next_input;
+
+            continue;
+        }
+
+        let (next_input, member) = validated_member(member_input, into_enum_member)?;
+
+        members.push(member);
+        input = next_input;
+    }
+}
+
+named!(
+    enum_case<Span, EnumCase>,
+    do_parse!(
+        attributes: attributes >>
+        first!(statement_keyword!(tokens::CASE)) >>
+        name: first!(name) >>
+        value: opt!(
+            preceded!(
+                first!(tag!(tokens::ASSIGN)),
+                first!(constant_expression)
+            )
+        ) >>
+        first!(tag!(tokens::SEMICOLON)) >>
+        (
+            EnumCase {
+                attributes: attributes,
+                name      : name,
+                value     : value
+            }
+        )
+    )
+);
+
+/// A keyword preceding a class member.
+enum MemberKeyword {
+    /// An explicit visibility.
+    Visibility(Visibility),
+
+    /// The `var` keyword.
+    Var,
+
+    /// A modifier.
+    Modifier(Modifier),
+}
+
+/// Recognize a class member, i.e. a list of constants, a list of
+/// properties, a method, or a use of traits.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tagua_parser;
+/// use tagua_parser::Result;
+/// use tagua_parser::ast::{ClassMember, Modifier, Ty, Variable, Visibility};
+/// use tagua_parser::rules::statements::class::class_member;
+/// use tagua_parser::tokens::Span;
+///
+/// # fn main() {
+/// assert_eq!(
+///     class_member(Span::new(b"private static $x;")),
+///     Ok((
+///         Span::new_at(b"", 18, 1, 19),
+///         ClassMember::Property {
+///             attributes: vec![],
+///             visibility: Visibility::Private,
+///             modifiers : vec![Modifier::Static],
+///             ty        : Box::new(Ty::Copy(None)),
+///             properties: vec![(Variable(Span::new_at(b"x", 16, 1, 17)), None)]
+///         }
+///     ))
+/// );
+/// # }
+/// ```
+pub fn class_member(input: Span) -> Result<Span, ClassMember> {
+    validated_member(input, into_class_member)
+}
+
+/// Recognize an interface member, i.e. a list of public constants, or
+/// a public method without a body.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tagua_parser;
+/// use tagua_parser::Result;
+/// use tagua_parser::ast::{Arity, ClassMember, FunctionKind, Modifier, Ty, Visibility};
+/// use tagua_parser::rules::statements::class::interface_member;
+/// use tagua_parser::tokens::Span;
+///
+/// # fn main() {
+/// assert_eq!(
+///     interface_member(Span::new(b"public static function f();")),
+///     Ok((
+///         Span::new_at(b"", 27, 1, 28),
+///         ClassMember::Method {
+///             attributes: vec![],
+///             visibility: Visibility::Public,
+///             modifiers : vec![Modifier::Static],
+///             name      : Span::new_at(b"f", 23, 1, 24),
+///             inputs    : Arity::Constant,
+///             output    : Box::new(Ty::Copy(None)),
+///             kind      : FunctionKind::Regular,
+///             body      : None
+///         }
+///     ))
+/// );
+/// # }
+/// ```
+pub fn interface_member(input: Span) -> Result<Span, ClassMember> {
+    validated_member(input, into_interface_member)
+}
+
+/// Recognize a member with its attributes and its keywords, and
+/// validate them.
+fn validated_member<'a>(
+    input: Span<'a>,
+    validate: fn(Vec<MemberKeyword>, ClassMember<'a>) -> StdResult<ClassMember<'a>, ClassError>,
+) -> Result<Span<'a>, ClassMember<'a>> {
+    let (next_input, member_attributes) = attributes(input)?;
+    let (next_input, keywords) =
+        fold_into_vector_many0!(next_input, complete!(first!(member_keyword)), Vec::new())?;
+    let (next_input, member) = first!(next_input, member)?;
+
+    match validate(keywords, member).and_then(|member| with_attributes(member, member_attributes)) {
+        Ok(member) => Ok((next_input, member)),
+        Err(error) => Err(Error::Failure(Context::Code(
+            input,
+            ErrorKind::Custom(error as u32),
+        ))),
+    }
+}
+
+/// Attach attributes to a validated member. A use of traits cannot
+/// have attributes.
+fn with_attributes<'a>(
+    mut member: ClassMember<'a>,
+    member_attributes: Vec<Attribute<'a>>,
+) -> StdResult<ClassMember<'a>, ClassError> {
+    match member {
+        ClassMember::Constant {
+            ref mut attributes, ..
+        }
+        | ClassMember::Property {
+            ref mut attributes, ..
+        }
+        | ClassMember::Method {
+            ref mut attributes, ..
+        } => {
+            *attributes = member_attributes;
+        }
+
+        ClassMember::TraitUse { .. } => {
+            if !member_attributes.is_empty() {
+                return Err(ClassError::InvalidAttributeTarget);
+            }
+        }
+    }
+
+    Ok(member)
+}
+
+named!(
+    member_keyword<Span, MemberKeyword>,
+    alt_complete!(
+        visibility => { MemberKeyword::Visibility }
+      | statement_keyword!(tokens::VAR) => { |_| MemberKeyword::Var }
+      | statement_keyword!(tokens::ABSTRACT) => { |_| MemberKeyword::Modifier(Modifier::Abstract) }
+      | statement_keyword!(tokens::FINAL) => { |_| MemberKeyword::Modifier(Modifier::Final) }
+      | statement_keyword!(tokens::READONLY) => { |_| MemberKeyword::Modifier(Modifier::Readonly) }
+      | statement_keyword!(tokens::STATIC) => { |_| MemberKeyword::Modifier(Modifier::Static) }
+    )
+);
+
+named!(
+    member<Span, ClassMember>,
+    alt_complete!(
+        constants
+      | method
+      | trait_use
+      | properties
+    )
+);
+
+named!(
+    constants<Span, ClassMember>,
+    do_parse!(
+        statement_keyword!(tokens::CONST) >>
+        accumulator: map!(first!(constant), |constant| vec![constant]) >>
+        result: fold_into_vector_many0!(
+            preceded!(
+                first!(tag!(tokens::COMMA)),
+                first!(constant)
+            ),
+            accumulator
+        ) >>
+        first!(tag!(tokens::SEMICOLON)) >>
+        (
+            ClassMember::Constant {
+                attributes: vec![],
+                visibility: Visibility::Public,
+                modifiers : vec![],
+                constants : result
+            }
+        )
+    )
+);
+
+named!(
+    constant<Span, (Span, Expression)>,
+    do_parse!(
+        name: name >>
+        first!(tag!(tokens::ASSIGN)) >>
+        value: first!(constant_expression) >>
+        (name, value)
+    )
+);
+
+named!(
+    method<Span, ClassMember>,
+    do_parse!(
+        statement_keyword!(tokens::FUNCTION) >>
+        output_is_a_reference: opt!(first!(tag!(tokens::REFERENCE))) >>
+        name: first!(name) >>
+        inputs: first!(
+            call!(
+                checked_parameters,
+                name.as_slice().eq_ignore_ascii_case(tokens::CONSTRUCT)
+            )
+        ) >>
+        output: call!(output_type, output_is_a_reference.is_some()) >>
+        body: alt_complete!(
+            first!(compound_statement) => { Some }
+          | first!(tag!(tokens::SEMICOLON)) => { |_| None }
+        ) >>
+        (
+            ClassMember::Method {
+                attributes: vec![],
+                visibility: Visibility::Public,
+                modifiers : vec![],
+                name      : name,
+                inputs    : inputs,
+                output    : Box::new(output),
+                kind      : body.as_ref().map_or(FunctionKind::Regular, |body| function_kind(body)),
+                body      : body
+            }
+        )
+    )
+);
+
+named!(
+    properties<Span, ClassMember>,
+    do_parse!(
+        ty: property_type >>
+        accumulator: map!(first!(property), |property| vec![property]) >>
+        result: fold_into_vector_many0!(
+            preceded!(
+                first!(tag!(tokens::COMMA)),
+                first!(property)
+            ),
+            accumulator
+        ) >>
+        first!(tag!(tokens::SEMICOLON)) >>
+        (
+            ClassMember::Property {
+                attributes: vec![],
+                visibility: Visibility::Public,
+                modifiers : vec![],
+                ty        : Box::new(ty),
+                properties: result
+            }
+        )
+    )
+);
+
+/// Recognize the optional type of a property, and check it. An
+/// invalid type is a failure, like in `parameters`.
+fn property_type(input: Span) -> Result<Span, Ty> {
+    let (next_input, ty) = opt!(input, first!(type_expression))?;
+    let ty = Ty::Copy(ty);
+
+    match check_type(&ty) {
+        Ok(()) => Ok((next_input, ty)),
+        Err(error) => Err(Error::Failure(Context::Code(
+            input,
+            ErrorKind::Custom(error as u32),
+        ))),
+    }
+}
+
+named!(
+    property<Span, (Variable, Option<Expression>)>,
+    do_parse!(
+        name: variable >>
+        default_value: opt!(
+            preceded!(
+                first!(tag!(tokens::ASSIGN)),
+                first!(constant_expression)
+            )
+        ) >>
+        (name, default_value)
+    )
+);
+
+named!(
+    trait_use<Span, ClassMember>,
+    do_parse!(
+        statement_keyword!(tokens::USE) >>
+        traits: first!(names) >>
+        adaptations: alt_complete!(
+            first!(tag!(tokens::SEMICOLON)) => { |_| Vec::new() }
+          | delimited!(
+                first!(tag!(tokens::LEFT_CURLY_BRACKET)),
+                fold_into_vector_many0!(
+                    terminated!(
+                        first!(trait_adaptation),
+                        first!(tag!(tokens::SEMICOLON))
+                    ),
+                    Vec::new()
+                ),
+                first!(tag!(tokens::RIGHT_CURLY_BRACKET))
+            )
+        ) >>
+        (
+            ClassMember::TraitUse {
+                traits     : traits,
+                adaptations: adaptations
+            }
+        )
+    )
+);
+
+named!(
+    trait_adaptation<Span, TraitAdaptation>,
+    alt_complete!(
+        do_parse!(
+            trait_name: qualified_name >>
+            first!(tag!(tokens::STATIC_CALL)) >>
+            method: first!(name) >>
+            first!(statement_keyword!(tokens::INSTEADOF)) >>
+            insteadof: first!(names) >>
+            (
+                TraitAdaptation::Insteadof {
+                    trait_name: trait_name,
+                    method    : method,
+                    insteadof : insteadof
+                }
+            )
+        )
+      | do_parse!(
+            trait_name: opt!(
+                complete!(
+                    terminated!(
+                        qualified_name,
+                        first!(tag!(tokens::STATIC_CALL))
+                    )
+                )
+            ) >>
+            method: first!(name) >>
+            first!(statement_keyword!(tokens::AS)) >>
+            alias: alt_complete!(
+                do_parse!(
+                    visibility: first!(visibility) >>
+                    alias: opt!(complete!(first!(name))) >>
+                    (Some(visibility), alias)
+                )
+              | first!(name) => { |alias| (None, Some(alias)) }
+            ) >>
+            (
+                TraitAdaptation::Alias {
+                    trait_name: trait_name,
+                    method    : method,
+                    visibility: alias.0,
+                    alias     : alias.1
+                }
+            )
+        )
+    )
+);
+
+named_attr!(
+    #[doc="
+        Recognize a visibility, i.e. `public`, `protected` or `private`.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::Visibility;
+        use tagua_parser::rules::statements::class::visibility;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            visibility(Span::new(b\"protected\")),
+            Ok((Span::new_at(b\"\", 9, 1, 10), Visibility::Protected))
+        );
+        # }
+        ```
+    "],
+    pub visibility<Span, Visibility>,
+    alt_complete!(
+        statement_keyword!(tokens::PUBLIC) => { |_| Visibility::Public }
+      | statement_keyword!(tokens::PROTECTED) => { |_| Visibility::Protected }
+      | statement_keyword!(tokens::PRIVATE) => { |_| Visibility::Private }
+    )
+);
+
+/// Check that a list of modifiers has no duplicates, and is not both
+/// abstract and final.
+fn check_modifiers(modifiers: &[Modifier]) -> StdResult<(), ClassError> {
+    for (index, modifier) in modifiers.iter().enumerate() {
+        if modifiers[index + 1..].contains(modifier) {
+            return Err(ClassError::DuplicatedModifier);
+        }
+    }
+
+    if modifiers.contains(&Modifier::Abstract) && modifiers.contains(&Modifier::Final) {
+        return Err(ClassError::AbstractAndFinal);
+    }
+
+    Ok(())
+}
+
+/// Split the keywords of a member into its visibility, whether it is
+/// declared with `var`, and its modifiers.
+fn split_member_keywords(
+    keywords: Vec<MemberKeyword>,
+) -> StdResult<(Option<Visibility>, bool, Vec<Modifier>), ClassError> {
+    let mut visibility = None;
+    let mut is_var = false;
+    let mut modifiers = Vec::new();
+
+    for keyword in keywords {
+        match keyword {
+            MemberKeyword::Visibility(member_visibility) => {
+                if visibility.is_some() {
+                    return Err(ClassError::MultipleVisibilities);
+                }
+
+                visibility = Some(member_visibility);
+            }
+
+            MemberKeyword::Var => {
+                is_var = true;
+            }
+
+            MemberKeyword::Modifier(modifier) => {
+                modifiers.push(modifier);
+            }
+        }
+    }
+
+    check_modifiers(&modifiers)?;
+
+    Ok((visibility, is_var, modifiers))
+}
+
+fn into_class_member<'a>(
+    keywords: Vec<MemberKeyword>,
+    member: ClassMember<'a>,
+) -> StdResult<ClassMember<'a>, ClassError> {
+    let has_keywords = !keywords.is_empty();
+    let number_of_keywords = keywords.len();
+    let (visibility, is_var, modifiers) = split_member_keywords(keywords)?;
+
+    if is_var {
+        match member {
+            ClassMember::Property { .. } if number_of_keywords == 1 => {}
+            _ => {
+                return Err(ClassError::InvalidModifier);
+            }
+        }
+    }
+
+    let visibility = visibility.unwrap_or(Visibility::Public);
+
+    match member {
+        ClassMember::Constant {
+            attributes,
+            constants,
+            ..
+        } => {
+            if modifiers
+                .iter()
+                .any(|modifier| *modifier != Modifier::Final)
+            {
+                return Err(ClassError::InvalidModifier);
+            }
+
+            Ok(ClassMember::Constant {
+                attributes,
+                visibility,
+                modifiers,
+                constants,
+            })
+        }
+
+        ClassMember::Property {
+            attributes,
+            ty,
+            properties,
+            ..
+        } => {
+            if !has_keywords {
+                return Err(ClassError::MissingPropertyModifier);
+            }
+
+            if modifiers
+                .iter()
+                .any(|modifier| *modifier == Modifier::Abstract || *modifier == Modifier::Final)
+                || (modifiers.contains(&Modifier::Readonly)
+                    && modifiers.contains(&Modifier::Static))
+            {
+                return Err(ClassError::InvalidModifier);
+            }
+
+            if modifiers.contains(&Modifier::Readonly) && *ty == Ty::Copy(None) {
+                return Err(ClassError::UntypedReadonlyProperty);
+            }
+
+            Ok(ClassMember::Property {
+                attributes,
+                visibility,
+                modifiers,
+                ty,
+                properties,
+            })
+        }
+
+        ClassMember::Method {
+            attributes,
+            name,
+            inputs,
+            output,
+            kind,
+            body,
+            ..
+        } => {
+            if modifiers.contains(&Modifier::Readonly) {
+                return Err(ClassError::InvalidModifier);
+            }
+
+            match (modifiers.contains(&Modifier::Abstract), &body) {
+                (true, &Some(_)) => {
+                    return Err(ClassError::AbstractMethodWithBody);
+                }
+
+                (false, &None) => {
+                    return Err(ClassError::MissingMethodBody);
+                }
+
+                _ => {}
+            }
+
+            Ok(ClassMember::Method {
+                attributes,
+                visibility,
+                modifiers,
+                name,
+                inputs,
+                output,
+                kind,
+                body,
+            })
+        }
+
+        ClassMember::TraitUse {
+            traits,
+            adaptations,
+        } => {
+            if has_keywords {
+                return Err(ClassError::InvalidModifier);
+            }
+
+            Ok(ClassMember::TraitUse {
+                traits,
+                adaptations,
+            })
+        }
+    }
+}
+
+fn into_enum_member<'a>(
+    keywords: Vec<MemberKeyword>,
+    member: ClassMember<'a>,
+) -> StdResult<ClassMember<'a>, ClassError> {
+    if let ClassMember::Property { .. } = member {
+        return Err(ClassError::InvalidEnumMember);
+    }
+
+    let member = into_class_member(keywords, member)?;
+
+    if let ClassMember::Method { ref modifiers, .. } = member {
+        if modifiers.contains(&Modifier::Abstract) {
+            return Err(ClassError::InvalidModifier);
+        }
+    }
+
+    Ok(member)
+}
+
+fn into_interface_member<'a>(
+    keywords: Vec<MemberKeyword>,
+    member: ClassMember<'a>,
+) -> StdResult<ClassMember<'a>, ClassError> {
+    let (visibility, is_var, modifiers) = split_member_keywords(keywords)?;
+
+    if is_var {
+        return Err(ClassError::InvalidModifier);
+    }
+
+    match visibility {
+        None | Some(Visibility::Public) => {}
+        _ => {
+            return Err(ClassError::NonPublicInterfaceMember);
+        }
+    }
+
+    match member {
+        ClassMember::Constant {
+            attributes,
+            constants,
+            ..
+        } => {
+            if modifiers
+                .iter()
+                .any(|modifier| *modifier != Modifier::Final)
+            {
+                return Err(ClassError::InvalidModifier);
+            }
+
+            Ok(ClassMember::Constant {
+                attributes,
+                visibility: Visibility::Public,
+                modifiers,
+                constants,
+            })
+        }
+
+        ClassMember::Method {
+            attributes,
+            name,
+            inputs,
+            output,
+            kind,
+            body,
+            ..
+        } => {
+            if modifiers
+                .iter()
+                .any(|modifier| *modifier != Modifier::Static)
+            {
+                return Err(ClassError::InvalidModifier);
+            }
+
+            if body.is_some() {
+                return Err(ClassError::InterfaceMethodWithBody);
+            }
+
+            Ok(ClassMember::Method {
+                attributes,
+                visibility: Visibility::Public,
+                modifiers,
+                name,
+                inputs,
+                output,
+                kind,
+                body,
+            })
+        }
+
+        _ => Err(ClassError::InvalidInterfaceMember),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::super::ast::{
+        Arity, Attribute, BinaryOperator, Class, ClassMember, Enum, EnumCase, Expression,
+        FunctionKind, Interface, Literal, MagicConstant, Modifier, NAryOperation, Name, Parameter,
+        Promotion, RelativeScope, ScopeResolver, Statement, Trait, TraitAdaptation, Ty, Type,
+        UnaryOperator, Variable, Visibility,
+    };
+    use super::super::super::super::internal::{Context, Error, ErrorKind};
+    use super::super::super::super::tokens::{Span, Token};
+    use super::super::function::{FunctionError, TypeError};
+    use super::super::statement;
+    use super::{
+        class, class_member, enum_declaration, interface, interface_member, trait_declaration,
+        ClassError,
+    };
+    use std::borrow::Cow;
+
+    #[test]
+    fn case_class_empty() {
+        let input = Span::new(b"class C {}");
+        let output = Ok((
+            Span::new_at(b"", 10, 1, 11),
+            Statement::Class(Class {
+                attributes: vec![],
+                modifiers: vec![],
+                name: Span::new_at(b"C", 6, 1, 7),
+                extends: None,
+                implements: vec![],
+                members: vec![],
+            }),
+        ));
+
+        assert_eq!(class(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_class_with_modifiers_extends_and_implements() {
+        let input = Span::new(b"final READONLY class C extends \\A\\B implements I, J\\K {}");
+        let output = Ok((
+            Span::new_at(b"", 56, 1, 57),
+            Statement::Class(Class {
+                attributes: vec![],
+                modifiers: vec![Modifier::Final, Modifier::Readonly],
+                name: Span::new_at(b"C", 21, 1, 22),
+                extends: Some(Name::FullyQualified(smallvec![
+                    Span::new_at(b"A", 32, 1, 33),
+                    Span::new_at(b"B", 34, 1, 35)
+                ])),
+                implements: vec![
+                    Name::Unqualified(Span::new_at(b"I", 47, 1, 48)),
+                    Name::Qualified(smallvec![
+                        Span::new_at(b"J", 50, 1, 51),
+                        Span::new_at(b"K", 52, 1, 53)
+                    ]),
+                ],
+                members: vec![],
+            }),
+        ));
+
+        assert_eq!(class(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_class_constants() {
+        let input = Span::new(b"class C { const A = 1; final protected const B = 2, C = 3; }");
+        let output = Ok((
+            Span::new_at(b"", 60, 1, 61),
+            Statement::Class(Class {
+                attributes: vec![],
+                modifiers: vec![],
+                name: Span::new_at(b"C", 6, 1, 7),
+                extends: None,
+                implements: vec![],
+                members: vec![
+                    ClassMember::Constant {
+                        attributes: vec![],
+                        visibility: Visibility::Public,
+                        modifiers: vec![],
+                        constants: vec![(
+                            Span::new_at(b"A", 16, 1, 17),
+                            Expression::Literal(Literal::Integer(Token::new(
+                                1i64,
+                                Span::new_at(b"1", 20, 1, 21),
+                            ))),
+                        )],
+                    },
+                    ClassMember::Constant {
+                        attributes: vec![],
+                        visibility: Visibility::Protected,
+                        modifiers: vec![Modifier::Final],
+                        constants: vec![
+                            (
+                                Span::new_at(b"B", 45, 1, 46),
+                                Expression::Literal(Literal::Integer(Token::new(
+                                    2i64,
+                                    Span::new_at(b"2", 49, 1, 50),
+                                ))),
+                            ),
+                            (
+                                Span::new_at(b"C", 52, 1, 53),
+                                Expression::Literal(Literal::Integer(Token::new(
+                                    3i64,
+                                    Span::new_at(b"3", 56, 1, 57),
+                                ))),
+                            ),
+                        ],
+                    },
+                ],
+            }),
+        ));
+
+        assert_eq!(class(input), output);
+    }
+
+    #[test]
+    fn case_class_member_constructor_with_promoted_parameters() {
+        let input = Span::new(b"public function __construct(private readonly int $x) {}");
+        let output = Ok((
+            Span::new_at(b"", 55, 1, 56),
+            ClassMember::Method {
+                attributes: vec![],
+                visibility: Visibility::Public,
+                modifiers: vec![],
+                name: Span::new_at(b"__construct", 16, 1, 17),
+                inputs: Arity::Finite(vec![Parameter {
+                    attributes: vec![],
+                    promotion: Some(Promotion {
+                        visibility: Visibility::Private,
+                        modifiers: vec![Modifier::Readonly],
+                    }),
+                    ty: Ty::Copy(Some(Type::Name(Name::FullyQualified(smallvec![
+                        Span::new_at(b"int", 45, 1, 46)
+                    ])))),
+                    name: Variable(Span::new_at(b"x", 50, 1, 51)),
+                    value: None,
+                }]),
+                output: Box::new(Ty::Copy(None)),
+                kind: FunctionKind::Regular,
+                body: Some(vec![]),
+            },
+        ));
+
+        assert_eq!(class_member(input), output);
+    }
+
+    #[test]
+    fn case_class_member_generator_method() {
+        let input = Span::new(b"public function f() { yield; }");
+        let output = Ok((
+            Span::new_at(b"", 30, 1, 31),
+            ClassMember::Method {
+                attributes: vec![],
+                visibility: Visibility::Public,
+                modifiers: vec![],
+                name: Span::new_at(b"f", 16, 1, 17),
+                inputs: Arity::Constant,
+                output: Box::new(Ty::Copy(None)),
+                kind: FunctionKind::Generator,
+                body: Some(vec![Statement::Expression(Expression::Yield(None, None))]),
+            },
+        ));
+
+        assert_eq!(class_member(input), output);
+    }
+
+    #[test]
+    fn case_class_member_method_with_a_generator_closure() {
+        let input = Span::new(b"public function f() { return fn () => yield; }");
+
+        match class_member(input) {
+            Ok((_, ClassMember::Method { kind, .. })) => assert_eq!(kind, FunctionKind::Regular),
+            _ => panic!("Expected a method."),
+        }
+    }
+
+    #[test]
+    fn case_invalid_class_member_method_with_promoted_parameters() {
+        let input = Span::new(b"public function f(public $x) {}");
+
+        assert_eq!(
+            class_member(input),
+            Err(Error::Failure(Context::Code(
+                Span::new_at(b"(public $x) {}", 17, 1, 18),
+                ErrorKind::Custom(FunctionError::PromotedParameterOutsideConstructor as u32)
+            )))
+        );
+    }
+
+    #[test]
+    fn case_class_member_property_with_a_union_type() {
+        let input = Span::new(b"private int|string $x;");
+        let output = Ok((
+            Span::new_at(b"", 22, 1, 23),
+            ClassMember::Property {
+                attributes: vec![],
+                visibility: Visibility::Private,
+                modifiers: vec![],
+                ty: Box::new(Ty::Copy(Some(Type::Union(vec![
+                    Type::Name(Name::FullyQualified(smallvec![Span::new_at(
+                        b"int", 8, 1, 9
+                    )])),
+                    Type::Name(Name::FullyQualified(smallvec![Span::new_at(
+                        b"string", 12, 1, 13
+                    )])),
+                ])))),
+                properties: vec![(Variable(Span::new_at(b"x", 20, 1, 21)), None)],
+            },
+        ));
+
+        assert_eq!(class_member(input), output);
+    }
+
+    #[test]
+    fn case_invalid_class_member_property_with_a_nullable_mixed_type() {
+        let input = Span::new(b"private ?mixed $x;");
+
+        assert_eq!(
+            class_member(input),
+            Err(Error::Failure(Context::Code(
+                Span::new_at(b"?mixed $x;", 8, 1, 9),
+                ErrorKind::Custom(TypeError::InvalidNullableType as u32)
+            )))
+        );
+    }
+
+    #[test]
+    fn case_class_properties() {
+        let input =
+            Span::new(b"class C { var $a; public readonly int $b; static ?\\D $c = 1, $d; }");
+        let output = Ok((
+            Span::new_at(b"", 66, 1, 67),
+            Statement::Class(Class {
+                attributes: vec![],
+                modifiers: vec![],
+                name: Span::new_at(b"C", 6, 1, 7),
+                extends: None,
+                implements: vec![],
+                members: vec![
+                    ClassMember::Property {
+                        attributes: vec![],
+                        visibility: Visibility::Public,
+                        modifiers: vec![],
+                        ty: Box::new(Ty::Copy(None)),
+                        properties: vec![(Variable(Span::new_at(b"a", 15, 1, 16)), None)],
+                    },
+                    ClassMember::Property {
+                        attributes: vec![],
+                        visibility: Visibility::Public,
+                        modifiers: vec![Modifier::Readonly],
+                        ty: Box::new(Ty::Copy(Some(Type::Name(Name::FullyQualified(smallvec![
+                            Span::new_at(b"int", 34, 1, 35)
+                        ]))))),
+                        properties: vec![(Variable(Span::new_at(b"b", 39, 1, 40)), None)],
+                    },
+                    ClassMember::Property {
+                        attributes: vec![],
+                        visibility: Visibility::Public,
+                        modifiers: vec![Modifier::Static],
+                        ty: Box::new(Ty::Copy(Some(Type::Nullable(Name::FullyQualified(
+                            smallvec![Span::new_at(b"D", 51, 1, 52)],
+                        ))))),
+                        properties: vec![
+                            (
+                                Variable(Span::new_at(b"c", 54, 1, 55)),
+                                Some(Expression::Literal(Literal::Integer(Token::new(
+                                    1i64,
+                                    Span::new_at(b"1", 58, 1, 59),
+                                )))),
+                            ),
+                            (Variable(Span::new_at(b"d", 62, 1, 63)), None),
+                        ],
+                    },
+                ],
+            }),
+        ));
+
+        assert_eq!(class(input), output);
+    }
+
+    #[test]
+    fn case_class_properties_with_constant_expressions() {
+        let input =
+            Span::new(b"class C { public ?Foo $x = null; public $a = true; public $b = -1; }");
+        let output = Ok((
+            Span::new_at(b"", 68, 1, 69),
+            Statement::Class(Class {
+                attributes: vec![],
+                modifiers: vec![],
+                name: Span::new_at(b"C", 6, 1, 7),
+                extends: None,
+                implements: vec![],
+                members: vec![
+                    ClassMember::Property {
+                        attributes: vec![],
+                        visibility: Visibility::Public,
+                        modifiers: vec![],
+                        ty: Box::new(Ty::Copy(Some(Type::Nullable(Name::Unqualified(
+                            Span::new_at(b"Foo", 18, 1, 19),
+                        ))))),
+                        properties: vec![(
+                            Variable(Span::new_at(b"x", 23, 1, 24)),
+                            Some(Expression::Name(Name::Unqualified(Span::new_at(
+                                b"null", 27, 1, 28,
+                            )))),
+                        )],
+                    },
+                    ClassMember::Property {
+                        attributes: vec![],
+                        visibility: Visibility::Public,
+                        modifiers: vec![],
+                        ty: Box::new(Ty::Copy(None)),
+                        properties: vec![(
+                            Variable(Span::new_at(b"a", 41, 1, 42)),
+                            Some(Expression::Name(Name::Unqualified(Span::new_at(
+                                b"true", 45, 1, 46,
+                            )))),
+                        )],
+                    },
+                    ClassMember::Property {
+                        attributes: vec![],
+                        visibility: Visibility::Public,
+                        modifiers: vec![],
+                        ty: Box::new(Ty::Copy(None)),
+                        properties: vec![(
+                            Variable(Span::new_at(b"b", 59, 1, 60)),
+                            Some(Expression::NAryOperation(NAryOperation::Unary {
+                                operator: UnaryOperator::Minus,
+                                operand: Box::new(NAryOperation::Nullary(Box::new(
+                                    Expression::Literal(Literal::Integer(Token::new(
+                                        1i64,
+                                        Span::new_at(b"1", 64, 1, 65),
+                                    ))),
+                                ))),
+                            })),
+                        )],
+                    },
+                ],
+            }),
+        ));
+
+        assert_eq!(class(input), output);
+    }
+
+    #[test]
+    fn case_class_constants_with_constant_expressions() {
+        let input =
+            Span::new(b"class C { const A = self::B; const B = 1 + 1; const EOL = PHP_EOL; }");
+        let output = Ok((
+            Span::new_at(b"", 68, 1, 69),
+            Statement::Class(Class {
+                attributes: vec![],
+                modifiers: vec![],
+                name: Span::new_at(b"C", 6, 1, 7),
+                extends: None,
+                implements: vec![],
+                members: vec![
+                    ClassMember::Constant {
+                        attributes: vec![],
+                        visibility: Visibility::Public,
+                        modifiers: vec![],
+                        constants: vec![(
+                            Span::new_at(b"A", 16, 1, 17),
+                            Expression::ClassConstantAccess(
+                                ScopeResolver::ByRelative(RelativeScope::ToSelf),
+                                Span::new_at(b"B", 26, 1, 27),
+                            ),
+                        )],
+                    },
+                    ClassMember::Constant {
+                        attributes: vec![],
+                        visibility: Visibility::Public,
+                        modifiers: vec![],
+                        constants: vec![(
+                            Span::new_at(b"B", 35, 1, 36),
+                            Expression::NAryOperation(NAryOperation::Binary {
+                                operator: BinaryOperator::Plus,
+                                left_operand: Box::new(NAryOperation::Nullary(Box::new(
+                                    Expression::Literal(Literal::Integer(Token::new(
+                                        1i64,
+                                        Span::new_at(b"1", 39, 1, 40),
+                                    ))),
+                                ))),
+                                right_operand: Box::new(NAryOperation::Nullary(Box::new(
+                                    Expression::Literal(Literal::Integer(Token::new(
+                                        1i64,
+                                        Span::new_at(b"1", 43, 1, 44),
+                                    ))),
+                                ))),
+                            }),
+                        )],
+                    },
+                    ClassMember::Constant {
+                        attributes: vec![],
+                        visibility: Visibility::Public,
+                        modifiers: vec![],
+                        constants: vec![(
+                            Span::new_at(b"EOL", 52, 1, 53),
+                            Expression::Name(Name::Unqualified(Span::new_at(
+                                b"PHP_EOL", 58, 1, 59,
+                            ))),
+                        )],
+                    },
+                ],
+            }),
+        ));
+
+        assert_eq!(class(input), output);
+    }
+
+    #[test]
+    fn case_invalid_class_constant_with_a_non_constant_expression() {
+        assert!(class(Span::new(b"class C { const A = $x; }")).is_err());
+        assert!(class(Span::new(b"class C { const A = f(); }")).is_err());
+        assert!(class(Span::new(b"class C { const A = static::B; }")).is_err());
+        assert!(class(Span::new(b"class C { public $a = $b = 1; }")).is_err());
+    }
+
+    #[test]
+    fn case_class_methods() {
+        let input = Span::new(b"abstract class C { abstract protected function &f(int $x): ?int; final public static function g() { return; } }");
+        let output = Ok((
+            Span::new_at(b"", 111, 1, 112),
+            Statement::Class(Class {
+                attributes: vec![],
+                modifiers: vec![Modifier::Abstract],
+                name: Span::new_at(b"C", 15, 1, 16),
+                extends: None,
+                implements: vec![],
+                members: vec![
+                    ClassMember::Method {
+                        attributes: vec![],
+                        visibility: Visibility::Protected,
+                        modifiers: vec![Modifier::Abstract],
+                        name: Span::new_at(b"f", 48, 1, 49),
+                        inputs: Arity::Finite(vec![Parameter {
+                            attributes: vec![],
+                            promotion: None,
+                            ty: Ty::Copy(Some(Type::Name(Name::FullyQualified(smallvec![
+                                Span::new_at(b"int", 50, 1, 51)
+                            ])))),
+                            name: Variable(Span::new_at(b"x", 55, 1, 56)),
+                            value: None,
+                        }]),
+                        output: Box::new(Ty::Reference(Some(Type::Nullable(
+                            Name::FullyQualified(smallvec![Span::new_at(b"int", 60, 1, 61)]),
+                        )))),
+                        kind: FunctionKind::Regular,
+                        body: None,
+                    },
+                    ClassMember::Method {
+                        attributes: vec![],
+                        visibility: Visibility::Public,
+                        modifiers: vec![Modifier::Final, Modifier::Static],
+                        name: Span::new_at(b"g", 94, 1, 95),
+                        inputs: Arity::Constant,
+                        output: Box::new(Ty::Copy(None)),
+                        kind: FunctionKind::Regular,
+                        body: Some(vec![Statement::Return(None)]),
+                    },
+                ],
+            }),
+        ));
+
+        assert_eq!(class(input), output);
+    }
+
+    #[test]
+    fn case_invalid_class_keyword_without_a_boundary() {
+        let input = Span::new(b"classC {}");
+
+        assert!(class(input).is_err());
+    }
+
+    #[test]
+    fn case_invalid_class_duplicated_modifier() {
+        let input = Span::new(b"final final class C {}");
+
+        assert_eq!(
+            class(input),
+            Err(Error::Failure(Context::Code(
+                input,
+                ErrorKind::Custom(ClassError::DuplicatedModifier as u32)
+            )))
+        );
+    }
+
+    #[test]
+    fn case_invalid_class_abstract_and_final() {
+        let input = Span::new(b"abstract final class C {}");
+
+        assert_eq!(
+            class(input),
+            Err(Error::Failure(Context::Code(
+                input,
+                ErrorKind::Custom(ClassError::AbstractAndFinal as u32)
+            )))
+        );
+    }
+
+    #[test]
+    fn case_invalid_class_with_an_invalid_member() {
+        let input = Span::new(b"class C { function f(); }");
+
+        assert_eq!(
+            class(input),
+            Err(Error::Failure(Context::Code(
+                Span::new_at(b"function f(); }", 10, 1, 11),
+                ErrorKind::Custom(ClassError::MissingMethodBody as u32)
+            )))
+        );
+    }
+
+    #[test]
+    fn case_invalid_class_not_closed() {
+        let input = Span::new(b"class C { const A = 1;");
+
+        assert!(class(input).is_err());
+    }
+
+    #[test]
+    fn case_class_trait_uses() {
+        let input = Span::new(b"class C { use A; use \\B, C { f as g; h as private; B::i as protected; C::i insteadof \\B, D; } }");
+        let output = Ok((
+            Span::new_at(b"", 95, 1, 96),
+            Statement::Class(Class {
+                attributes: vec![],
+                modifiers: vec![],
+                name: Span::new_at(b"C", 6, 1, 7),
+                extends: None,
+                implements: vec![],
+                members: vec![
+                    ClassMember::TraitUse {
+                        traits: vec![Name::Unqualified(Span::new_at(b"A", 14, 1, 15))],
+                        adaptations: vec![],
+                    },
+                    ClassMember::TraitUse {
+                        traits: vec![
+                            Name::FullyQualified(smallvec![Span::new_at(b"B", 22, 1, 23)]),
+                            Name::Unqualified(Span::new_at(b"C", 25, 1, 26)),
+                        ],
+                        adaptations: vec![
+                            TraitAdaptation::Alias {
+                                trait_name: None,
+                                method: Span::new_at(b"f", 29, 1, 30),
+                                visibility: None,
+                                alias: Some(Span::new_at(b"g", 34, 1, 35)),
+                            },
+                            TraitAdaptation::Alias {
+                                trait_name: None,
+                                method: Span::new_at(b"h", 37, 1, 38),
+                                visibility: Some(Visibility::Private),
+                                alias: None,
+                            },
+                            TraitAdaptation::Alias {
+                                trait_name: Some(Name::Unqualified(Span::new_at(b"B", 51, 1, 52))),
+                                method: Span::new_at(b"i", 54, 1, 55),
+                                visibility: Some(Visibility::Protected),
+                                alias: None,
+                            },
+                            TraitAdaptation::Insteadof {
+                                trait_name: Name::Unqualified(Span::new_at(b"C", 70, 1, 71)),
+                                method: Span::new_at(b"i", 73, 1, 74),
+                                insteadof: vec![
+                                    Name::FullyQualified(smallvec![Span::new_at(b"B", 86, 1, 87)]),
+                                    Name::Unqualified(Span::new_at(b"D", 89, 1, 90)),
+                                ],
+                            },
+                        ],
+                    },
+                ],
+            }),
+        ));
+
+        assert_eq!(class(input), output);
+    }
+
+    #[test]
+    fn case_invalid_trait_use_adaptation_without_visibility_nor_alias() {
+        let input = Span::new(b"class C { use A { f as; } }");
+
+        assert!(class(input).is_err());
+    }
+
+    #[test]
+    fn case_invalid_trait_use_insteadof_without_trait_name() {
+        let input = Span::new(b"class C { use A { f insteadof B; } }");
+
+        assert!(class(input).is_err());
+    }
+
+    #[test]
+    fn case_interface() {
+        let input = Span::new(
+            b"interface I extends \\J, K\\L { const A = 1; public static function f(): int; }",
+        );
+        let output = Ok((
+            Span::new_at(b"", 77, 1, 78),
+            Statement::Interface(Interface {
+                attributes: vec![],
+                name: Span::new_at(b"I", 10, 1, 11),
+                extends: vec![
+                    Name::FullyQualified(smallvec![Span::new_at(b"J", 21, 1, 22)]),
+                    Name::Qualified(smallvec![
+                        Span::new_at(b"K", 24, 1, 25),
+                        Span::new_at(b"L", 26, 1, 27)
+                    ]),
+                ],
+                members: vec![
+                    ClassMember::Constant {
+                        attributes: vec![],
+                        visibility: Visibility::Public,
+                        modifiers: vec![],
+                        constants: vec![(
+                            Span::new_at(b"A", 36, 1, 37),
+                            Expression::Literal(Literal::Integer(Token::new(
+                                1i64,
+                                Span::new_at(b"1", 40, 1, 41),
+                            ))),
+                        )],
+                    },
+                    ClassMember::Method {
+                        attributes: vec![],
+                        visibility: Visibility::Public,
+                        modifiers: vec![Modifier::Static],
+                        name: Span::new_at(b"f", 66, 1, 67),
+                        inputs: Arity::Constant,
+                        output: Box::new(Ty::Copy(Some(Type::Name(Name::FullyQualified(
+                            smallvec![Span::new_at(b"int", 71, 1, 72)],
+                        ))))),
+                        kind: FunctionKind::Regular,
+                        body: None,
+                    },
+                ],
+            }),
+        ));
+
+        assert_eq!(interface(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_interface_with_an_invalid_member() {
+        let input = Span::new(b"interface I { public $x; }");
+
+        assert_eq!(
+            interface(input),
+            Err(Error::Failure(Context::Code(
+                Span::new_at(b"public $x; }", 14, 1, 15),
+                ErrorKind::Custom(ClassError::InvalidInterfaceMember as u32)
+            )))
+        );
+    }
+
+    #[test]
+    fn case_trait() {
+        let input = Span::new(b"trait T { use U; protected $x; abstract function f(); }");
+        let output = Ok((
+            Span::new_at(b"", 55, 1, 56),
+            Statement::Trait(Trait {
+                attributes: vec![],
+                name: Span::new_at(b"T", 6, 1, 7),
+                members: vec![
+                    ClassMember::TraitUse {
+                        traits: vec![Name::Unqualified(Span::new_at(b"U", 14, 1, 15))],
+                        adaptations: vec![],
+                    },
+                    ClassMember::Property {
+                        attributes: vec![],
+                        visibility: Visibility::Protected,
+                        modifiers: vec![],
+                        ty: Box::new(Ty::Copy(None)),
+                        properties: vec![(Variable(Span::new_at(b"x", 28, 1, 29)), None)],
+                    },
+                    ClassMember::Method {
+                        attributes: vec![],
+                        visibility: Visibility::Public,
+                        modifiers: vec![Modifier::Abstract],
+                        name: Span::new_at(b"f", 49, 1, 50),
+                        inputs: Arity::Constant,
+                        output: Box::new(Ty::Copy(None)),
+                        kind: FunctionKind::Regular,
+                        body: None,
+                    },
+                ],
+            }),
+        ));
+
+        assert_eq!(trait_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_enum_pure() {
+        let input = Span::new(b"enum E { case A; CASE B; }");
+        let output = Ok((
+            Span::new_at(b"", 26, 1, 27),
+            Statement::Enum(Enum {
+                attributes: vec![],
+                name: Span::new_at(b"E", 5, 1, 6),
+                backing_type: None,
+                implements: vec![],
+                cases: vec![
+                    EnumCase {
+                        attributes: vec![],
+                        name: Span::new_at(b"A", 14, 1, 15),
+                        value: None,
+                    },
+                    EnumCase {
+                        attributes: vec![],
+                        name: Span::new_at(b"B", 22, 1, 23),
+                        value: None,
+                    },
+                ],
+                members: vec![],
+            }),
+        ));
+
+        assert_eq!(enum_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_enum_backed_with_members() {
+        let input = Span::new(b"enum Suit: string implements I, J { case Hearts = 'H'; const Wild = 'W'; use T; public static function f(): int {} }");
+        let output = Ok((
+            Span::new_at(b"", 116, 1, 117),
+            Statement::Enum(Enum {
+                attributes: vec![],
+                name: Span::new_at(b"Suit", 5, 1, 6),
+                backing_type: Some(Name::FullyQualified(smallvec![Span::new_at(
+                    b"string", 11, 1, 12
+                )])),
+                implements: vec![
+                    Name::Unqualified(Span::new_at(b"I", 29, 1, 30)),
+                    Name::Unqualified(Span::new_at(b"J", 32, 1, 33)),
+                ],
+                cases: vec![EnumCase {
+                    attributes: vec![],
+                    name: Span::new_at(b"Hearts", 41, 1, 42),
+                    value: Some(Expression::Literal(Literal::String(Token::new(
+                        Cow::from(&b"H"[..]),
+                        Span::new_at(b"'H'", 50, 1, 51),
+                    )))),
+                }],
+                members: vec![
+                    ClassMember::Constant {
+                        attributes: vec![],
+                        visibility: Visibility::Public,
+                        modifiers: vec![],
+                        constants: vec![(
+                            Span::new_at(b"Wild", 61, 1, 62),
+                            Expression::Literal(Literal::String(Token::new(
+                                Cow::from(&b"W"[..]),
+                                Span::new_at(b"'W'", 68, 1, 69),
+                            ))),
+                        )],
+                    },
+                    ClassMember::TraitUse {
+                        traits: vec![Name::Unqualified(Span::new_at(b"T", 77, 1, 78))],
+                        adaptations: vec![],
+                    },
+                    ClassMember::Method {
+                        attributes: vec![],
+                        visibility: Visibility::Public,
+                        modifiers: vec![Modifier::Static],
+                        name: Span::new_at(b"f", 103, 1, 104),
+                        inputs: Arity::Constant,
+                        output: Box::new(Ty::Copy(Some(Type::Name(Name::FullyQualified(
+                            smallvec![Span::new_at(b"int", 108, 1, 109)],
+                        ))))),
+                        kind: FunctionKind::Regular,
+                        body: Some(vec![]),
+                    },
+                ],
+            }),
+        ));
+
+        assert_eq!(enum_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_enum_with_constant_expressions() {
+        let input = Span::new(
+            b"enum Suit: int { case A = 1 << 2; const Wild = self::Spades; const X = __CLASS__; }",
+        );
+        let output = Ok((
+            Span::new_at(b"", 83, 1, 84),
+            Statement::Enum(Enum {
+                attributes: vec![],
+                name: Span::new_at(b"Suit", 5, 1, 6),
+                backing_type: Some(Name::FullyQualified(smallvec![Span::new_at(
+                    b"int", 11, 1, 12
+                )])),
+                implements: vec![],
+                cases: vec![EnumCase {
+                    attributes: vec![],
+                    name: Span::new_at(b"A", 22, 1, 23),
+                    value: Some(Expression::NAryOperation(NAryOperation::Binary {
+                        operator: BinaryOperator::BitwiseShiftLeft,
+                        left_operand: Box::new(NAryOperation::Nullary(Box::new(
+                            Expression::Literal(Literal::Integer(Token::new(
+                                1i64,
+                                Span::new_at(b"1", 26, 1, 27),
+                            ))),
+                        ))),
+                        right_operand: Box::new(NAryOperation::Nullary(Box::new(
+                            Expression::Literal(Literal::Integer(Token::new(
+                                2i64,
+                                Span::new_at(b"2", 31, 1, 32),
+                            ))),
+                        ))),
+                    })),
+                }],
+                members: vec![
+                    ClassMember::Constant {
+                        attributes: vec![],
+                        visibility: Visibility::Public,
+                        modifiers: vec![],
+                        constants: vec![(
+                            Span::new_at(b"Wild", 40, 1, 41),
+                            Expression::ClassConstantAccess(
+                                ScopeResolver::ByRelative(RelativeScope::ToSelf),
+                                Span::new_at(b"Spades", 53, 1, 54),
+                            ),
+                        )],
+                    },
+                    ClassMember::Constant {
+                        attributes: vec![],
+                        visibility: Visibility::Public,
+                        modifiers: vec![],
+                        constants: vec![(
+                            Span::new_at(b"X", 67, 1, 68),
+                            Expression::MagicConstant(MagicConstant::Class(Span::new_at(
+                                b"__CLASS__",
+                                71,
+                                1,
+                                72,
+                            ))),
+                        )],
+                    },
+                ],
+            }),
+        ));
+
+        assert_eq!(enum_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_enum_backing_type() {
+        let input = Span::new(b"enum E: float {}");
+
+        assert_eq!(
+            enum_declaration(input),
+            Err(Error::Failure(Context::Code(
+                Span::new_at(b": float {}", 6, 1, 7),
+                ErrorKind::Custom(ClassError::InvalidEnumBackingType as u32)
+            )))
+        );
+    }
+
+    #[test]
+    fn case_invalid_enum_backed_case_without_value() {
+        let input = Span::new(b"enum E: int { case A = 1; case B; }");
+
+        assert_eq!(
+            enum_declaration(input),
+            Err(Error::Failure(Context::Code(
+                Span::new_at(b"case B; }", 26, 1, 27),
+                ErrorKind::Custom(ClassError::MissingEnumCaseValue as u32)
+            )))
+        );
+    }
+
+    #[test]
+    fn case_invalid_enum_pure_case_with_value() {
+        let input = Span::new(b"enum E { case A = 1; }");
+
+        assert_eq!(
+            enum_declaration(input),
+            Err(Error::Failure(Context::Code(
+                Span::new_at(b"case A = 1; }", 9, 1, 10),
+                ErrorKind::Custom(ClassError::UnexpectedEnumCaseValue as u32)
+            )))
+        );
+    }
+
+    #[test]
+    fn case_invalid_enum_case_with_a_non_constant_value() {
+        let input = Span::new(b"enum E: int { case A = $x; }");
+
+        assert!(enum_declaration(input).is_err());
+    }
+
+    #[test]
+    fn case_invalid_enum_with_a_property() {
+        let input = Span::new(b"enum E { public $x; }");
+
+        assert_eq!(
+            enum_declaration(input),
+            Err(Error::Failure(Context::Code(
+                Span::new_at(b"public $x; }", 9, 1, 10),
+                ErrorKind::Custom(ClassError::InvalidEnumMember as u32)
+            )))
+        );
+    }
+
+    #[test]
+    fn case_invalid_enum_with_an_abstract_method() {
+        let input = Span::new(b"enum E { abstract function f(); }");
+
+        assert_eq!(
+            enum_declaration(input),
+            Err(Error::Failure(Context::Code(
+                Span::new_at(b"abstract function f(); }", 9, 1, 10),
+                ErrorKind::Custom(ClassError::InvalidModifier as u32)
+            )))
+        );
+    }
+
+    #[test]
+    fn case_class_with_attributes() {
+        let input = Span::new(
+            b"#[A] final class C { #[B] const X = 1; #[C] public function f(#[D] $x) {} }",
+        );
+        let output = Ok((
+            Span::new_at(b"", 75, 1, 76),
+            Statement::Class(Class {
+                attributes: vec![Attribute {
+                    name: Name::Unqualified(Span::new_at(b"A", 2, 1, 3)),
+                    arguments: None,
+                }],
+                modifiers: vec![Modifier::Final],
+                name: Span::new_at(b"C", 17, 1, 18),
+                extends: None,
+                implements: vec![],
+                members: vec![
+                    ClassMember::Constant {
+                        attributes: vec![Attribute {
+                            name: Name::Unqualified(Span::new_at(b"B", 23, 1, 24)),
+                            arguments: None,
+                        }],
+                        visibility: Visibility::Public,
+                        modifiers: vec![],
+                        constants: vec![(
+                            Span::new_at(b"X", 32, 1, 33),
+                            Expression::Literal(Literal::Integer(Token::new(
+                                1i64,
+                                Span::new_at(b"1", 36, 1, 37),
+                            ))),
+                        )],
+                    },
+                    ClassMember::Method {
+                        attributes: vec![Attribute {
+                            name: Name::Unqualified(Span::new_at(b"C", 41, 1, 42)),
+                            arguments: None,
+                        }],
+                        visibility: Visibility::Public,
+                        modifiers: vec![],
+                        name: Span::new_at(b"f", 60, 1, 61),
+                        inputs: Arity::Finite(vec![Parameter {
+                            attributes: vec![Attribute {
+                                name: Name::Unqualified(Span::new_at(b"D", 64, 1, 65)),
+                                arguments: None,
+                            }],
+                            promotion: None,
+                            ty: Ty::Copy(None),
+                            name: Variable(Span::new_at(b"x", 68, 1, 69)),
+                            value: None,
+                        }]),
+                        output: Box::new(Ty::Copy(None)),
+                        kind: FunctionKind::Regular,
+                        body: Some(vec![]),
+                    },
+                ],
+            }),
+        ));
+
+        assert_eq!(class(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_enum_case_with_attributes() {
+        let input = Span::new(b"enum E { #[A] case B; }");
+        let output = Ok((
+            Span::new_at(b"", 23, 1, 24),
+            Statement::Enum(Enum {
+                attributes: vec![],
+                name: Span::new_at(b"E", 5, 1, 6),
+                backing_type: None,
+                implements: vec![],
+                cases: vec![EnumCase {
+                    attributes: vec![Attribute {
+                        name: Name::Unqualified(Span::new_at(b"A", 11, 1, 12)),
+                        arguments: None,
+                    }],
+                    name: Span::new_at(b"B", 19, 1, 20),
+                    value: None,
+                }],
+                members: vec![],
+            }),
+        ));
+
+        assert_eq!(enum_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    macro_rules! test_invalid_class_member {
+        ($test:ident: $input:expr => $error:ident) => {
+            #[test]
+            fn $test() {
+                let input = Span::new($input);
+
+                assert_eq!(
+                    class_member(input),
+                    Err(Error::Failure(Context::Code(
+                        input,
+                        ErrorKind::Custom(ClassError::$error as u32)
+                    )))
+                );
+            }
+        };
+    }
+
+    test_invalid_class_member!(case_invalid_class_member_duplicated_modifier: b"static static $x;" => DuplicatedModifier);
+    test_invalid_class_member!(case_invalid_class_member_multiple_visibilities: b"public private $x;" => MultipleVisibilities);
+    test_invalid_class_member!(case_invalid_class_member_abstract_and_final: b"abstract final function f();" => AbstractAndFinal);
+    test_invalid_class_member!(case_invalid_class_member_static_constant: b"static const A = 1;" => InvalidModifier);
+    test_invalid_class_member!(case_invalid_class_member_readonly_method: b"readonly function f() {}" => InvalidModifier);
+    test_invalid_class_member!(case_invalid_class_member_var_with_a_modifier: b"var static $x;" => InvalidModifier);
+    test_invalid_class_member!(case_invalid_class_member_var_method: b"var function f() {}" => InvalidModifier);
+    test_invalid_class_member!(case_invalid_class_member_static_readonly_property: b"static readonly int $x;" => InvalidModifier);
+    test_invalid_class_member!(case_invalid_class_member_property_without_modifier: b"$x;" => MissingPropertyModifier);
+    test_invalid_class_member!(case_invalid_class_member_untyped_readonly_property: b"public readonly $x;" => UntypedReadonlyProperty);
+    test_invalid_class_member!(case_invalid_class_member_abstract_method_with_body: b"abstract function f() {}" => AbstractMethodWithBody);
+    test_invalid_class_member!(case_invalid_class_member_method_without_body: b"public function f();" => MissingMethodBody);
+    test_invalid_class_member!(case_invalid_class_member_trait_use_with_a_modifier: b"public use A;" => InvalidModifier);
+    test_invalid_class_member!(case_invalid_class_member_trait_use_with_attributes: b"#[A] use B;" => InvalidAttributeTarget);
+
+    macro_rules! test_invalid_interface_member {
+        ($test:ident: $input:expr => $error:ident) => {
+            #[test]
+            fn $test() {
+                let input = Span::new($input);
+
+                assert_eq!(
+                    interface_member(input),
+                    Err(Error::Failure(Context::Code(
+                        input,
+                        ErrorKind::Custom(ClassError::$error as u32)
+                    )))
+                );
+            }
+        };
+    }
+
+    test_invalid_interface_member!(case_invalid_interface_member_property: b"public $x;" => InvalidInterfaceMember);
+    test_invalid_interface_member!(case_invalid_interface_member_trait_use: b"use A;" => InvalidInterfaceMember);
+    test_invalid_interface_member!(case_invalid_interface_member_protected_method: b"protected function f();" => NonPublicInterfaceMember);
+    test_invalid_interface_member!(case_invalid_interface_member_private_constant: b"private const A = 1;" => NonPublicInterfaceMember);
+    test_invalid_interface_member!(case_invalid_interface_member_abstract_method: b"abstract function f();" => InvalidModifier);
+    test_invalid_interface_member!(case_invalid_interface_member_method_with_body: b"function f() {}" => InterfaceMethodWithBody);
+}
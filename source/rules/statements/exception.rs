@@ -0,0 +1,439 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2017, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of exception handling statement rules.
+//!
+//! The `try` statement is provided by the PHP Language Specification in
+//! the [Grammar chapter, The try Statement
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#the-try-statement).
+//! Note that `throw` is an expression, see the
+//! `rules::expressions::primaries::intrinsic_throw` parser.
+
+use super::super::super::ast::{CatchClause, FinallyClause, Name, Statement, Variable};
+use super::super::super::internal::Result;
+use super::super::super::tokens;
+use super::super::super::tokens::Span;
+use super::super::tokens::{qualified_name, variable};
+use super::compound_statement;
+use nom::Slice;
+
+named_attr!(
+    #[doc="
+        Recognize a `try` statement, i.e. a `try` clause followed by
+        `catch` clauses, and an optional `finally` clause. At least one
+        `catch` or `finally` clause is required.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{CatchClause, FinallyClause, Name, Statement, Variable};
+        use tagua_parser::rules::statements::exception::try_statement;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            try_statement(Span::new(b\"try {} catch (E $e) {} finally {}\")),
+            Ok((
+                Span::new_at(b\"\", 33, 1, 34),
+                Statement::Try {
+                    span   : Span::new(b\"try {}\"),
+                    body   : vec![],
+                    catches: vec![
+                        CatchClause {
+                            span    : Span::new_at(b\"catch (E $e) {}\", 7, 1, 8),
+                            types   : vec![Name::Unqualified(Span::new_at(b\"E\", 14, 1, 15))],
+                            variable: Some(Variable(Span::new_at(b\"e\", 17, 1, 18))),
+                            body    : vec![]
+                        }
+                    ],
+                    finally: Some(
+                        FinallyClause {
+                            span: Span::new_at(b\"finally {}\", 23, 1, 24),
+                            body: vec![]
+                        }
+                    )
+                }
+            ))
+        );
+        # }
+        ```
+    "],
+    pub try_statement<Span, Statement>,
+    do_parse!(
+        clause: call!(spanned, try_clause) >>
+        handlers: first!(
+            alt_complete!(
+                do_parse!(
+                    head: catch_clause >>
+                    catches: fold_into_vector_many0!(
+                        complete!(first!(catch_clause)),
+                        vec![head]
+                    ) >>
+                    finally: opt!(complete!(first!(finally_clause))) >>
+                    ((catches, finally))
+                )
+              | finally_clause => { |finally| (Vec::new(), Some(finally)) }
+            )
+        ) >>
+        ({
+            let (span, body) = clause;
+            let (catches, finally) = handlers;
+
+            Statement::Try {
+                span,
+                body,
+                catches,
+                finally
+            }
+        })
+    )
+);
+
+named!(
+    try_clause<Span, Vec<Statement>>,
+    preceded!(
+        statement_keyword!(tokens::TRY),
+        first!(compound_statement)
+    )
+);
+
+named_attr!(
+    #[doc="
+        Recognize a `catch` clause.
+
+        A clause can catch several types, separated by a vertical bar,
+        e.g. `catch (A | B $e)`. The variable is optional, e.g. `catch
+        (E)`.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{CatchClause, Name};
+        use tagua_parser::rules::statements::exception::catch_clause;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            catch_clause(Span::new(b\"catch (A | B) {}\")),
+            Ok((
+                Span::new_at(b\"\", 16, 1, 17),
+                CatchClause {
+                    span    : Span::new(b\"catch (A | B) {}\"),
+                    types   : vec![
+                        Name::Unqualified(Span::new_at(b\"A\", 7, 1, 8)),
+                        Name::Unqualified(Span::new_at(b\"B\", 11, 1, 12))
+                    ],
+                    variable: None,
+                    body    : vec![]
+                }
+            ))
+        );
+        # }
+        ```
+    "],
+    pub catch_clause<Span, CatchClause>,
+    map!(
+        call!(spanned, catch_clause_content),
+        |(span, (types, variable, body))| {
+            CatchClause {
+                span,
+                types,
+                variable,
+                body
+            }
+        }
+    )
+);
+
+named!(
+    catch_clause_content<Span, (Vec<Name>, Option<Variable>, Vec<Statement>)>,
+    do_parse!(
+        statement_keyword!(tokens::CATCH) >>
+        first!(tag!(tokens::LEFT_PARENTHESIS)) >>
+        head: first!(qualified_name) >>
+        types: fold_into_vector_many0!(
+            preceded!(
+                first!(tag!(tokens::BITWISE_OR)),
+                first!(qualified_name)
+            ),
+            vec![head]
+        ) >>
+        variable: opt!(complete!(first!(variable))) >>
+        first!(tag!(tokens::RIGHT_PARENTHESIS)) >>
+        body: first!(compound_statement) >>
+        ((types, variable, body))
+    )
+);
+
+named_attr!(
+    #[doc="
+        Recognize a `finally` clause.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::FinallyClause;
+        use tagua_parser::rules::statements::exception::finally_clause;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            finally_clause(Span::new(b\"finally {}\")),
+            Ok((
+                Span::new_at(b\"\", 10, 1, 11),
+                FinallyClause {
+                    span: Span::new(b\"finally {}\"),
+                    body: vec![]
+                }
+            ))
+        );
+        # }
+        ```
+    "],
+    pub finally_clause<Span, FinallyClause>,
+    map!(
+        call!(spanned, finally_clause_content),
+        |(span, body)| FinallyClause { span, body }
+    )
+);
+
+named!(
+    finally_clause_content<Span, Vec<Statement>>,
+    preceded!(
+        statement_keyword!(tokens::FINALLY),
+        first!(compound_statement)
+    )
+);
+
+/// Run a parser, and return the span of the consumed input along with
+/// the output of the parser.
+fn spanned<'a, O, F>(input: Span<'a>, parser: F) -> Result<Span<'a>, (Span<'a>, O)>
+where
+    F: Fn(Span<'a>) -> Result<Span<'a>, O>,
+{
+    let (next_input, output) = parser(input)?;
+
+    Ok((
+        next_input,
+        (input.slice(..next_input.offset - input.offset), output),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::super::ast::{
+        CatchClause, Expression, FinallyClause, Name, Statement, Variable,
+    };
+    use super::super::super::super::internal::{Context, Error, ErrorKind};
+    use super::super::super::super::tokens::Span;
+    use super::super::statement;
+    use super::{catch_clause, finally_clause, try_statement};
+
+    #[test]
+    fn case_try_catch() {
+        let input = Span::new(b"try { $x; } catch (E $e) { $e; }");
+        let output = Ok((
+            Span::new_at(b"", 32, 1, 33),
+            Statement::Try {
+                span: Span::new(b"try { $x; }"),
+                body: vec![Statement::Expression(Expression::Variable(Variable(
+                    Span::new_at(b"x", 7, 1, 8),
+                )))],
+                catches: vec![CatchClause {
+                    span: Span::new_at(b"catch (E $e) { $e; }", 12, 1, 13),
+                    types: vec![Name::Unqualified(Span::new_at(b"E", 19, 1, 20))],
+                    variable: Some(Variable(Span::new_at(b"e", 22, 1, 23))),
+                    body: vec![Statement::Expression(Expression::Variable(Variable(
+                        Span::new_at(b"e", 28, 1, 29),
+                    )))],
+                }],
+                finally: None,
+            },
+        ));
+
+        assert_eq!(try_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_try_with_many_catches_and_finally() {
+        let input = Span::new(b"TRY {}\ncatch (\\A\\B | C) {}\ncatch(D $d){}\nfinally {}");
+        let output = Ok((
+            Span::new_at(b"", 51, 4, 11),
+            Statement::Try {
+                span: Span::new(b"TRY {}"),
+                body: vec![],
+                catches: vec![
+                    CatchClause {
+                        span: Span::new_at(b"catch (\\A\\B | C) {}", 7, 2, 1),
+                        types: vec![
+                            Name::FullyQualified(smallvec![
+                                Span::new_at(b"A", 15, 2, 9),
+                                Span::new_at(b"B", 17, 2, 11)
+                            ]),
+                            Name::Unqualified(Span::new_at(b"C", 21, 2, 15)),
+                        ],
+                        variable: None,
+                        body: vec![],
+                    },
+                    CatchClause {
+                        span: Span::new_at(b"catch(D $d){}", 27, 3, 1),
+                        types: vec![Name::Unqualified(Span::new_at(b"D", 33, 3, 7))],
+                        variable: Some(Variable(Span::new_at(b"d", 36, 3, 10))),
+                        body: vec![],
+                    },
+                ],
+                finally: Some(FinallyClause {
+                    span: Span::new_at(b"finally {}", 41, 4, 1),
+                    body: vec![],
+                }),
+            },
+        ));
+
+        assert_eq!(try_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_try_finally() {
+        let input = Span::new(b"try {} finally { return; }");
+        let output = Ok((
+            Span::new_at(b"", 26, 1, 27),
+            Statement::Try {
+                span: Span::new(b"try {}"),
+                body: vec![],
+                catches: vec![],
+                finally: Some(FinallyClause {
+                    span: Span::new_at(b"finally { return; }", 7, 1, 8),
+                    body: vec![Statement::Return(None)],
+                }),
+            },
+        ));
+
+        assert_eq!(try_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_try_without_catch_nor_finally() {
+        let input = Span::new(b"try {}");
+
+        assert_eq!(
+            try_statement(input),
+            Err(Error::Error(Context::Code(
+                Span::new_at(b"", 6, 1, 7),
+                ErrorKind::Alt
+            )))
+        );
+    }
+
+    #[test]
+    fn case_invalid_try_with_a_statement_body() {
+        let input = Span::new(b"try $x; finally {}");
+
+        assert!(try_statement(input).is_err());
+    }
+
+    #[test]
+    fn case_invalid_try_with_finally_before_catch() {
+        let input = Span::new(b"try {} finally {} catch (E $e) {}");
+
+        assert_eq!(
+            try_statement(input),
+            Ok((
+                Span::new_at(b" catch (E $e) {}", 17, 1, 18),
+                Statement::Try {
+                    span: Span::new(b"try {}"),
+                    body: vec![],
+                    catches: vec![],
+                    finally: Some(FinallyClause {
+                        span: Span::new_at(b"finally {}", 7, 1, 8),
+                        body: vec![],
+                    }),
+                },
+            ))
+        );
+    }
+
+    #[test]
+    fn case_catch_clause_without_variable() {
+        let input = Span::new(b"catch (E) {}");
+        let output = Ok((
+            Span::new_at(b"", 12, 1, 13),
+            CatchClause {
+                span: input,
+                types: vec![Name::Unqualified(Span::new_at(b"E", 7, 1, 8))],
+                variable: None,
+                body: vec![],
+            },
+        ));
+
+        assert_eq!(catch_clause(input), output);
+    }
+
+    #[test]
+    fn case_invalid_catch_clause_without_type() {
+        let input = Span::new(b"catch ($e) {}");
+
+        assert!(catch_clause(input).is_err());
+    }
+
+    #[test]
+    fn case_invalid_catch_clause_with_a_trailing_separator() {
+        let input = Span::new(b"catch (A | $e) {}");
+
+        assert!(catch_clause(input).is_err());
+    }
+
+    #[test]
+    fn case_finally_clause() {
+        let input = Span::new(b"finally { ; }");
+        let output = Ok((
+            Span::new_at(b"", 13, 1, 14),
+            FinallyClause {
+                span: input,
+                body: vec![Statement::Empty],
+            },
+        ));
+
+        assert_eq!(finally_clause(input), output);
+    }
+
+    #[test]
+    fn case_invalid_finally_clause_not_a_keyword() {
+        let input = Span::new(b"finallyx {}");
+
+        assert!(finally_clause(input).is_err());
+    }
+}
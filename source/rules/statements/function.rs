@@ -36,13 +36,17 @@
 //! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#function-definition).
 
 use super::super::super::ast::{
-    Arity, Expression, Function, Name, Parameter, Statement, Ty, Variable,
+    Arity, Attribute, Expression, Function, FunctionKind, Modifier, Name, Parameter, Promotion,
+    Statement, Ty, Type, Variable, Visibility,
 };
-use super::super::super::internal::{Context, Error, ErrorKind};
+use super::super::super::internal::{Context, Error, ErrorKind, Result};
 use super::super::super::tokens;
 use super::super::super::tokens::Span;
+use super::super::super::visitor::{walk_expression, walk_statement, walk_statements, Visitor};
+use super::super::attributes::attributes;
 use super::super::expressions::constant::constant_expression;
 use super::super::tokens::{name, qualified_name, variable};
+use super::class::visibility;
 use super::compound_statement;
 use std::result::Result as StdResult;
 
@@ -54,6 +58,27 @@ pub enum FunctionError {
 
     /// A function has multiple parameters declared with the same name.
     MultipleParametersWithSameName,
+
+    /// A parameter is promoted to a property, but the function is not
+    /// a constructor.
+    PromotedParameterOutsideConstructor,
+
+    /// A variadic parameter is promoted to a property.
+    VariadicPromotedParameter,
+}
+
+/// Type errors.
+pub enum TypeError {
+    /// A type that cannot be nullable is nullable, e.g. `?mixed`,
+    /// `?null`, `?never` or `?void`.
+    InvalidNullableType,
+
+    /// A type that must be used alone, i.e. `mixed`, `never` or
+    /// `void`, is part of a union or an intersection, e.g. `void|int`.
+    StandaloneTypeInComposite,
+
+    /// A native type is part of an intersection, e.g. `int&A`.
+    InvalidIntersectionMember,
 }
 
 named_attr!(
@@ -83,10 +108,12 @@ named_attr!(
         use tagua_parser::ast::{
             Arity,
             Function,
+            FunctionKind,
             Name,
             Parameter,
             Statement,
             Ty,
+            Type,
             Variable
         };
         use tagua_parser::rules::statements::function::function;
@@ -102,26 +129,34 @@ named_attr!(
                 Span::new_at(b\"\", 48, 1, 49),
                 Statement::Function(
                     Function {
-                        name  : Span::new_at(b\"f\", 10, 1, 11),
-                        inputs: Arity::Finite(vec![
+                        attributes: vec![],
+                        name      : Span::new_at(b\"f\", 10, 1, 11),
+                        inputs    : Arity::Finite(vec![
                             Parameter {
-                                ty   : Ty::Copy(None),
-                                name : Variable(Span::new_at(b\"x\", 13, 1, 14)),
-                                value: None
+                                attributes: vec![],
+                                promotion : None,
+                                ty        : Ty::Copy(None),
+                                name      : Variable(Span::new_at(b\"x\", 13, 1, 14)),
+                                value     : None
                             },
                             Parameter {
-                                ty   : Ty::Copy(Some(Name::FullyQualified(smallvec![Span::new_at(b\"I\", 17, 1, 18), Span::new_at(b\"J\", 19, 1, 20)]))),
-                                name : Variable(Span::new_at(b\"y\", 22, 1, 23)),
-                                value: None
+                                attributes: vec![],
+                                promotion : None,
+                                ty        : Ty::Copy(Some(Type::Name(Name::FullyQualified(smallvec![Span::new_at(b\"I\", 17, 1, 18), Span::new_at(b\"J\", 19, 1, 20)])))),
+                                name      : Variable(Span::new_at(b\"y\", 22, 1, 23)),
+                                value     : None
                             },
                             Parameter {
-                                ty   : Ty::Reference(Some(Name::FullyQualified(smallvec![Span::new_at(b\"int\", 25, 1, 26)]))),
-                                name : Variable(Span::new_at(b\"z\", 31, 1, 32)),
-                                value: None
+                                attributes: vec![],
+                                promotion : None,
+                                ty        : Ty::Reference(Some(Type::Name(Name::FullyQualified(smallvec![Span::new_at(b\"int\", 25, 1, 26)])))),
+                                name      : Variable(Span::new_at(b\"z\", 31, 1, 32)),
+                                value     : None
                             }
                         ]),
-                        output: Ty::Reference(Some(Name::Unqualified(Span::new_at(b\"O\", 35, 1, 36)))),
-                        body  : vec![Statement::Return]
+                        output    : Ty::Reference(Some(Type::Name(Name::Unqualified(Span::new_at(b\"O\", 35, 1, 36))))),
+                        kind      : FunctionKind::Regular,
+                        body      : vec![Statement::Return(None)]
                     }
                 )
             ))
@@ -141,10 +176,12 @@ named_attr!(
         use tagua_parser::ast::{
             Arity,
             Function,
+            FunctionKind,
             Name,
             Parameter,
             Statement,
             Ty,
+            Type,
             Variable
         };
         use tagua_parser::rules::statements::function::function;
@@ -160,21 +197,27 @@ named_attr!(
                 Span::new_at(b\"\", 37, 1, 38),
                 Statement::Function(
                     Function {
-                        name  : Span::new_at(b\"f\", 9, 1, 10),
-                        inputs: Arity::Infinite(vec![
+                        attributes: vec![],
+                        name      : Span::new_at(b\"f\", 9, 1, 10),
+                        inputs    : Arity::Infinite(vec![
                             Parameter {
-                                ty   : Ty::Copy(None),
-                                name : Variable(Span::new_at(b\"x\", 12, 1, 13)),
-                                value: None
+                                attributes: vec![],
+                                promotion : None,
+                                ty        : Ty::Copy(None),
+                                name      : Variable(Span::new_at(b\"x\", 12, 1, 13)),
+                                value     : None
                             },
                             Parameter {
-                                ty   : Ty::Copy(Some(Name::FullyQualified(smallvec![Span::new_at(b\"int\", 15, 1, 16)]))),
-                                name : Variable(Span::new_at(b\"y\", 23, 1, 24)),
-                                value: None
+                                attributes: vec![],
+                                promotion : None,
+                                ty        : Ty::Copy(Some(Type::Name(Name::FullyQualified(smallvec![Span::new_at(b\"int\", 15, 1, 16)])))),
+                                name      : Variable(Span::new_at(b\"y\", 23, 1, 24)),
+                                value     : None
                             }
                         ]),
-                        output: Ty::Copy(None),
-                        body  : vec![Statement::Return]
+                        output    : Ty::Copy(None),
+                        kind      : FunctionKind::Regular,
+                        body      : vec![Statement::Return(None)]
                     }
                 )
             ))
@@ -184,123 +227,137 @@ named_attr!(
     "],
     pub function<Span, Statement>,
     do_parse!(
+        attributes: attributes >>
         first!(keyword!(tokens::FUNCTION)) >>
         output_is_a_reference: opt!(first!(tag!(tokens::REFERENCE))) >>
         name: first!(name) >>
-        inputs: first!(parameters) >>
-        output: opt!(
-            do_parse!(
-                first!(tag!(tokens::FUNCTION_OUTPUT)) >>
-                output_is_nullable: opt!(first!(tag!(tokens::NULLABLE))) >>
-                output_type_name: alt!(
-                    first!(native_type)
-                  | first!(qualified_name)
-                ) >>
-                (
-                    into_type(
-                        Some(output_type_name),
-                        output_is_nullable.is_some(),
-                        output_is_a_reference.is_some()
-                    )
-                )
-            )
-        ) >>
+        inputs: first!(call!(checked_parameters, false)) >>
+        output: call!(output_type, output_is_a_reference.is_some()) >>
         body: first!(compound_statement) >>
         (
-            into_function(
-                name,
-                inputs,
-                output.unwrap_or_else(
-                    || {
-                        if output_is_a_reference.is_some() {
-                            Ty::Reference(None)
-                        } else {
-                            Ty::Copy(None)
-                        }
-                    }
-                ),
-                body
-            )
+            into_function(attributes, name, inputs, output, body)
         )
     )
 );
 
-named_attr!(
-    #[doc="
-        Recognize a list of function parameters.
+/// Recognize the output type of a function or of a method, i.e. an
+/// optional `: T`, where `T` is a type expression. Whether the output
+/// is a reference is known from the `&` before the function name, so
+/// it must be given. An invalid type is a failure, like in `parameters`.
+pub fn output_type(input: Span, output_is_a_reference: bool) -> Result<Span, Ty> {
+    let (next_input, output) = opt!(input, first!(tag!(tokens::FUNCTION_OUTPUT)))?;
 
-        # Examples
+    let (next_input, output) = match output {
+        Some(_) => {
+            let (next_input, output) = first!(next_input, type_expression)?;
 
-        ```
-        # extern crate smallvec;
-        # #[macro_use]
-        # extern crate tagua_parser;
-        use tagua_parser::Result;
-        use tagua_parser::ast::{
-            Arity,
-            Name,
-            Parameter,
-            Ty,
-            Variable
-        };
-        use tagua_parser::rules::statements::function::parameters;
-        use tagua_parser::tokens::{
-            Span,
-            Token
-        };
+            (next_input, Some(output))
+        }
 
-        # fn main() {
-        assert_eq!(
-            parameters(Span::new(b\"($x, \\\\I\\\\J $y, int &$z)\")),
-            Ok((
-                Span::new_at(b\"\", 22, 1, 23),
-                Arity::Finite(vec![
-                    Parameter {
-                        ty   : Ty::Copy(None),
-                        name : Variable(Span::new_at(b\"x\", 2, 1, 3)),
-                        value: None
-                    },
-                    Parameter {
-                        ty   : Ty::Copy(Some(Name::FullyQualified(smallvec![Span::new_at(b\"I\", 6, 1, 7), Span::new_at(b\"J\", 8, 1, 9)]))),
-                        name : Variable(Span::new_at(b\"y\", 11, 1, 12)),
-                        value: None
-                    },
-                    Parameter {
-                        ty   : Ty::Reference(Some(Name::FullyQualified(smallvec![Span::new_at(b\"int\", 14, 1, 15)]))),
-                        name : Variable(Span::new_at(b\"z\", 20, 1, 21)),
-                        value: None
-                    }
-                ])
-            ))
-        );
-        # }
-        ```
-    "],
-    pub parameters<Span, Arity>,
-    map_res_and_input!(
-        terminated!(
-            preceded!(
-                tag!(tokens::LEFT_PARENTHESIS),
-                opt!(
-                    do_parse!(
-                        accumulator: map_res!(
-                            first!(parameter),
-                            into_vector_mapper
-                        ) >>
-                        result: fold_into_vector_many0!(
-                            preceded!(
-                                first!(tag!(tokens::COMMA)),
-                                first!(parameter)
-                            ),
-                            accumulator
-                        ) >>
-                        (result)
-                    )
+        None => (next_input, None),
+    };
+
+    let output = into_type(output, output_is_a_reference);
+
+    match check_type(&output) {
+        Ok(()) => Ok((next_input, output)),
+        Err(error) => Err(Error::Failure(Context::Code(
+            input,
+            ErrorKind::Custom(error as u32),
+        ))),
+    }
+}
+
+/// Recognize a list of function parameters.
+///
+/// The parameters are checked, e.g. their types with `check_type`, or
+/// the position of the variadic parameter. Once the list is recognized,
+/// an invalid list is a failure, so that it is not discarded by an
+/// alternative, e.g. by the expression statement.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate smallvec;
+/// # #[macro_use]
+/// # extern crate tagua_parser;
+/// use tagua_parser::Result;
+/// use tagua_parser::ast::{
+///     Arity,
+///     Name,
+///     Parameter,
+///     Ty,
+///     Type,
+///     Variable
+/// };
+/// use tagua_parser::rules::statements::function::parameters;
+/// use tagua_parser::tokens::{
+///     Span,
+///     Token
+/// };
+///
+/// # fn main() {
+/// assert_eq!(
+///     parameters(Span::new(b"($x, \\I\\J $y, int &$z)")),
+///     Ok((
+///         Span::new_at(b"", 22, 1, 23),
+///         Arity::Finite(vec![
+///             Parameter {
+///                 attributes: vec![],
+///                 promotion : None,
+///                 ty        : Ty::Copy(None),
+///                 name      : Variable(Span::new_at(b"x", 2, 1, 3)),
+///                 value     : None
+///             },
+///             Parameter {
+///                 attributes: vec![],
+///                 promotion : None,
+///                 ty        : Ty::Copy(Some(Type::Name(Name::FullyQualified(smallvec![Span::new_at(b"I", 6, 1, 7), Span::new_at(b"J", 8, 1, 9)])))),
+///                 name      : Variable(Span::new_at(b"y", 11, 1, 12)),
+///                 value     : None
+///             },
+///             Parameter {
+///                 attributes: vec![],
+///                 promotion : None,
+///                 ty        : Ty::Reference(Some(Type::Name(Name::FullyQualified(smallvec![Span::new_at(b"int", 14, 1, 15)])))),
+///                 name      : Variable(Span::new_at(b"z", 20, 1, 21)),
+///                 value     : None
+///             }
+///         ])
+///     ))
+/// );
+/// # }
+/// ```
+pub fn parameters(input: Span) -> Result<Span, Arity> {
+    let (next_input, pairs) = parameter_list(input)?;
+    let arity = parameters_mapper(pairs, input)?;
+
+    Ok((next_input, arity))
+}
+
+named!(
+    parameter_list<Span, Option<Vec<(Parameter, bool)>>>,
+    terminated!(
+        preceded!(
+            tag!(tokens::LEFT_PARENTHESIS),
+            opt!(
+                do_parse!(
+                    accumulator: map_res!(
+                        first!(parameter),
+                        into_vector_mapper
+                    ) >>
+                    result: fold_into_vector_many0!(
+                        preceded!(
+                            first!(tag!(tokens::COMMA)),
+                            first!(parameter)
+                        ),
+                        accumulator
+                    ) >>
+                    (result)
                 )
-            ),
-            first!(tag!(tokens::RIGHT_PARENTHESIS))
+            )
         ),
-        parameters_mapper
+        first!(tag!(tokens::RIGHT_PARENTHESIS))
     )
 );
 
@@ -317,12 +374,28 @@ fn parameters_mapper<'a, 'b>(
         }
     };
 
+    for &(ref parameter, is_variadic) in &pairs {
+        if let Err(error) = check_type(&parameter.ty) {
+            return Err(Error::Failure(Context::Code(
+                input,
+                ErrorKind::Custom(error as u32),
+            )));
+        }
+
+        if is_variadic && parameter.promotion.is_some() {
+            return Err(Error::Failure(Context::Code(
+                input,
+                ErrorKind::Custom(FunctionError::VariadicPromotedParameter as u32),
+            )));
+        }
+    }
+
     let last_pair = pairs.pop();
     let mut parameters = Vec::new();
 
     for (parameter, is_variadic) in pairs {
         if is_variadic {
-            return Err(Error::Error(Context::Code(
+            return Err(Error::Failure(Context::Code(
                 input,
                 ErrorKind::Custom(FunctionError::InvalidVariadicParameterPosition as u32),
             )));
@@ -332,7 +405,7 @@ fn parameters_mapper<'a, 'b>(
             .iter()
             .any(|p: &Parameter<'a>| p.name == parameter.name)
         {
-            return Err(Error::Error(Context::Code(
+            return Err(Error::Failure(Context::Code(
                 input,
                 ErrorKind::Custom(FunctionError::MultipleParametersWithSameName as u32),
             )));
@@ -347,7 +420,7 @@ fn parameters_mapper<'a, 'b>(
                 .iter()
                 .any(|p: &Parameter<'a>| p.name == last_parameter.name)
             {
-                return Err(Error::Error(Context::Code(
+                return Err(Error::Failure(Context::Code(
                     input,
                     ErrorKind::Custom(FunctionError::MultipleParametersWithSameName as u32),
                 )));
@@ -369,16 +442,9 @@ fn parameters_mapper<'a, 'b>(
 named!(
     parameter<Span, (Parameter, bool)>,
     do_parse!(
-        type_pair: opt!(
-            do_parse!(
-                is_nullable: opt!(tag!(tokens::NULLABLE)) >>
-                type_name: alt!(
-                    first!(native_type)
-                  | first!(qualified_name)
-                ) >>
-                (Some(type_name), is_nullable)
-            )
-        ) >>
+        attributes: attributes >>
+        promotion: opt!(first!(promotion)) >>
+        ty: opt!(first!(type_expression)) >>
         is_a_reference: opt!(first!(tag!(tokens::REFERENCE))) >>
         is_variadic: opt!(first!(tag!(tokens::ELLIPSIS))) >>
         name: first!(variable) >>
@@ -388,47 +454,102 @@ named!(
                 first!(constant_expression)
             )
         ) >>
-        ({
-            let (type_name, is_nullable) = type_pair.unwrap_or_else(|| (None, None));
-
+        (
             into_parameter(
-                into_type(
-                    type_name,
-                    is_nullable.is_some(),
-                    is_a_reference.is_some()
-                ),
+                attributes,
+                promotion,
+                into_type(ty, is_a_reference.is_some()),
                 is_variadic.is_some(),
                 name,
                 default_value
             )
-        })
+        )
+    )
+);
+
+named!(
+    promotion<Span, Promotion>,
+    alt_complete!(
+        do_parse!(
+            visibility: visibility >>
+            is_readonly: opt!(complete!(first!(statement_keyword!(tokens::READONLY)))) >>
+            (into_promotion(visibility, is_readonly.is_some()))
+        )
+      | do_parse!(
+            statement_keyword!(tokens::READONLY) >>
+            visibility: opt!(complete!(first!(visibility))) >>
+            (into_promotion(visibility.unwrap_or(Visibility::Public), true))
+        )
     )
 );
 
 #[inline]
-fn into_vector_mapper<T>(item: T) -> StdResult<Vec<T>, ()> {
-    Ok(vec![item])
+fn into_promotion(visibility: Visibility, is_readonly: bool) -> Promotion {
+    Promotion {
+        visibility,
+        modifiers: if is_readonly {
+            vec![Modifier::Readonly]
+        } else {
+            vec![]
+        },
+    }
 }
 
-#[inline]
-fn into_type<'a>(ty: Option<Name<'a>>, is_nullable: bool, is_a_reference: bool) -> Ty<'a> {
-    match (ty, is_nullable, is_a_reference) {
-        (Some(ty), true, false) => Ty::NullableCopy(ty),
+/// Recognize a list of function parameters, and check that the
+/// parameters are promoted to properties only if the function is a
+/// constructor. Like in `parameters`, an invalid list is a failure.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tagua_parser;
+/// use tagua_parser::rules::statements::function::checked_parameters;
+/// use tagua_parser::tokens::Span;
+///
+/// # fn main() {
+/// assert!(checked_parameters(Span::new(b"(private $x)"), true).is_ok());
+/// assert!(checked_parameters(Span::new(b"(private $x)"), false).is_err());
+/// # }
+/// ```
+pub fn checked_parameters(input: Span, is_a_constructor: bool) -> Result<Span, Arity> {
+    let (next_input, arity) = parameters(input)?;
+
+    let is_promoted = match arity {
+        Arity::Constant => false,
+
+        Arity::Finite(ref parameters) | Arity::Infinite(ref parameters) => parameters
+            .iter()
+            .any(|parameter| parameter.promotion.is_some()),
+    };
 
-        (Some(ty), true, true) => Ty::NullableReference(ty),
+    if is_promoted && !is_a_constructor {
+        return Err(Error::Failure(Context::Code(
+            input,
+            ErrorKind::Custom(FunctionError::PromotedParameterOutsideConstructor as u32),
+        )));
+    }
 
-        (ty, false, false) => Ty::Copy(ty),
+    Ok((next_input, arity))
+}
 
-        (ty, false, true) => Ty::Reference(ty),
+#[inline]
+fn into_vector_mapper<T>(item: T) -> StdResult<Vec<T>, ()> {
+    Ok(vec![item])
+}
 
-        _ => {
-            unreachable!();
-        }
+#[inline]
+fn into_type<'a>(ty: Option<Type<'a>>, is_a_reference: bool) -> Ty<'a> {
+    if is_a_reference {
+        Ty::Reference(ty)
+    } else {
+        Ty::Copy(ty)
     }
 }
 
 #[inline]
 fn into_parameter<'a>(
+    attributes: Vec<Attribute<'a>>,
+    promotion: Option<Promotion>,
     ty: Ty<'a>,
     is_variadic: bool,
     name: Variable<'a>,
@@ -436,8 +557,10 @@ fn into_parameter<'a>(
 ) -> (Parameter<'a>, bool) {
     (
         Parameter {
-            ty: ty,
-            name: name,
+            attributes,
+            promotion,
+            ty,
+            name,
             value: default_value,
         },
         is_variadic,
@@ -476,47 +599,419 @@ named_attr!(
     pub native_type<Span, Name>,
     map_res!(
         alt_complete!(
-            tag!(tokens::ARRAY)
-          | tag!(tokens::BOOL)
-          | tag!(tokens::CALLABLE)
-          | tag!(tokens::FLOAT)
-          | tag!(tokens::INT)
-          | tag!(tokens::ITERABLE)
-          | tag!(tokens::STRING)
+            statement_keyword!(tokens::ARRAY)
+          | statement_keyword!(tokens::BOOL)
+          | statement_keyword!(tokens::CALLABLE)
+          | statement_keyword!(tokens::FALSE)
+          | statement_keyword!(tokens::FLOAT)
+          | statement_keyword!(tokens::INT)
+          | statement_keyword!(tokens::ITERABLE)
+          | statement_keyword!(tokens::MIXED)
+          | statement_keyword!(tokens::NEVER)
+          | statement_keyword!(tokens::NULL)
+          | statement_keyword!(tokens::OBJECT)
+          | statement_keyword!(tokens::STRING)
+          | statement_keyword!(tokens::TRUE)
+          | statement_keyword!(tokens::VOID)
         ),
         native_type_mapper
     )
 );
 
 #[inline]
-fn native_type_mapper(native_type_name: Span) -> Result<Name, ()> {
+fn native_type_mapper(native_type_name: Span) -> StdResult<Name, ()> {
     Ok(Name::FullyQualified(smallvec![native_type_name]))
 }
 
+named_attr!(
+    #[doc="
+        Recognize a type expression, i.e. a single type, a nullable
+        type, a union of types, or an intersection of types. A member
+        of a union can be an intersection delimited by parenthesis,
+        e.g. `(A&B)|null`.
+
+        The type expression is not checked, see `check_type`.
+
+        # Examples
+
+        ```
+        # extern crate smallvec;
+        # #[macro_use]
+        # extern crate tagua_parser;
+        use tagua_parser::Result;
+        use tagua_parser::ast::{Name, Type};
+        use tagua_parser::rules::statements::function::type_expression;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            type_expression(Span::new(b\"int|\\\\A\\\\B|null\")),
+            Ok((
+                Span::new_at(b\"\", 13, 1, 14),
+                Type::Union(vec![
+                    Type::Name(Name::FullyQualified(smallvec![Span::new_at(b\"int\", 0, 1, 1)])),
+                    Type::Name(Name::FullyQualified(smallvec![Span::new_at(b\"A\", 5, 1, 6), Span::new_at(b\"B\", 7, 1, 8)])),
+                    Type::Name(Name::FullyQualified(smallvec![Span::new_at(b\"null\", 9, 1, 10)]))
+                ])
+            ))
+        );
+        # }
+        ```
+    "],
+    pub type_expression<Span, Type>,
+    alt_complete!(
+        preceded!(
+            tag!(tokens::NULLABLE),
+            first!(single_type)
+        ) => { Type::Nullable }
+      | do_parse!(
+            head: parenthesized_intersection >>
+            tail: many1!(
+                complete!(
+                    preceded!(
+                        first!(tag!(tokens::TYPE_UNION)),
+                        first!(union_member)
+                    )
+                )
+            ) >>
+            (into_union(head, tail))
+        )
+      | intersection
+      | do_parse!(
+            head: single_type >>
+            tail: fold_into_vector_many0!(
+                preceded!(
+                    first!(tag!(tokens::TYPE_UNION)),
+                    first!(union_member)
+                ),
+                Vec::new()
+            ) >>
+            (
+                if tail.is_empty() {
+                    Type::Name(head)
+                } else {
+                    into_union(Type::Name(head), tail)
+                }
+            )
+        )
+    )
+);
+
+named!(
+    single_type<Span, Name>,
+    alt_complete!(
+        native_type
+      | statement_keyword!(tokens::STATIC) => { Name::Unqualified }
+      | qualified_name
+    )
+);
+
+named!(
+    union_member<Span, Type>,
+    alt_complete!(
+        parenthesized_intersection
+      | single_type => { Type::Name }
+    )
+);
+
+named!(
+    parenthesized_intersection<Span, Type>,
+    delimited!(
+        tag!(tokens::LEFT_PARENTHESIS),
+        first!(intersection),
+        first!(tag!(tokens::RIGHT_PARENTHESIS))
+    )
+);
+
+named!(
+    intersection<Span, Type>,
+    do_parse!(
+        head: single_type >>
+        tail: many1!(
+            complete!(
+                preceded!(
+                    intersection_separator,
+                    first!(single_type)
+                )
+            )
+        ) >>
+        ({
+            let mut names = vec![head];
+            names.extend(tail);
+
+            Type::Intersection(names)
+        })
+    )
+);
+
+// The `&` of an intersection must not be confused with the `&` of a
+// parameter passed by reference, e.g. `A &$x` or `A &...$x`.
+named!(
+    intersection_separator<Span, Span>,
+    terminated!(
+        first!(tag!(tokens::TYPE_INTERSECTION)),
+        not!(
+            complete!(
+                first!(
+                    alt!(
+                        tag!(tokens::VARIABLE)
+                      | tag!(tokens::ELLIPSIS)
+                    )
+                )
+            )
+        )
+    )
+);
+
+#[inline]
+fn into_union<'a>(head: Type<'a>, tail: Vec<Type<'a>>) -> Type<'a> {
+    let mut types = vec![head];
+    types.extend(tail);
+
+    Type::Union(types)
+}
+
+/// Check a type declaration: A type that cannot be nullable must not
+/// be nullable, a standalone type must not be part of a union or an
+/// intersection, and an intersection must contain class types only.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate smallvec;
+/// # #[macro_use]
+/// # extern crate tagua_parser;
+/// use tagua_parser::ast::{Name, Ty, Type};
+/// use tagua_parser::rules::statements::function::check_type;
+/// use tagua_parser::tokens::Span;
+///
+/// # fn main() {
+/// let ty = Ty::Copy(Some(Type::Nullable(Name::FullyQualified(smallvec![Span::new(b"mixed")]))));
+///
+/// assert!(check_type(&ty).is_err());
+/// # }
+/// ```
+pub fn check_type(ty: &Ty) -> StdResult<(), TypeError> {
+    match *ty {
+        Ty::Copy(Some(ref ty)) | Ty::Reference(Some(ref ty)) => check_type_expression(ty),
+
+        Ty::Copy(None) | Ty::Reference(None) => Ok(()),
+    }
+}
+
+fn check_type_expression(ty: &Type) -> StdResult<(), TypeError> {
+    match *ty {
+        Type::Name(_) => Ok(()),
+
+        Type::Nullable(ref name) => {
+            if is_native_type(
+                name,
+                &[tokens::MIXED, tokens::NEVER, tokens::NULL, tokens::VOID],
+            ) {
+                Err(TypeError::InvalidNullableType)
+            } else {
+                Ok(())
+            }
+        }
+
+        Type::Union(ref types) => {
+            for ty in types {
+                if let Type::Name(ref name) = *ty {
+                    if is_native_type(name, STANDALONE_TYPES) {
+                        return Err(TypeError::StandaloneTypeInComposite);
+                    }
+                }
+
+                check_type_expression(ty)?;
+            }
+
+            Ok(())
+        }
+
+        Type::Intersection(ref names) => {
+            for name in names {
+                if is_native_type(name, STANDALONE_TYPES) {
+                    return Err(TypeError::StandaloneTypeInComposite);
+                }
+
+                if is_native_type(name, NATIVE_TYPES) {
+                    return Err(TypeError::InvalidIntersectionMember);
+                }
+            }
+
+            Ok(())
+        }
+    }
+}
+
+/// Native types that must be used alone.
+const STANDALONE_TYPES: &[&[u8]] = &[tokens::MIXED, tokens::NEVER, tokens::VOID];
+
+/// All native types, see `native_type`.
+const NATIVE_TYPES: &[&[u8]] = &[
+    tokens::ARRAY,
+    tokens::BOOL,
+    tokens::CALLABLE,
+    tokens::FALSE,
+    tokens::FLOAT,
+    tokens::INT,
+    tokens::ITERABLE,
+    tokens::MIXED,
+    tokens::NEVER,
+    tokens::NULL,
+    tokens::OBJECT,
+    tokens::STRING,
+    tokens::TRUE,
+    tokens::VOID,
+];
+
+/// Check whether a name is one of the given native types. Native
+/// types are case-insensitive.
+fn is_native_type(name: &Name, native_types: &[&[u8]]) -> bool {
+    match *name {
+        Name::FullyQualified(ref names) => {
+            names.len() == 1
+                && native_types
+                    .iter()
+                    .any(|native_type| names[0].as_slice().eq_ignore_ascii_case(native_type))
+        }
+
+        _ => false,
+    }
+}
+
 #[inline]
 fn into_function<'a>(
+    attributes: Vec<Attribute<'a>>,
     name: Span<'a>,
     inputs: Arity<'a>,
     output: Ty<'a>,
     body: Vec<Statement<'a>>,
 ) -> Statement<'a> {
     Statement::Function(Function {
-        name: name,
-        inputs: inputs,
-        output: output,
-        body: body,
+        attributes,
+        name,
+        inputs,
+        output,
+        kind: function_kind(&body),
+        body,
     })
 }
 
+/// Compute the kind of a function from its body: A function is a
+/// generator if its body contains a `yield` or a `yield from`
+/// expression. The bodies of nested functions, anonymous functions,
+/// arrow functions and classes are not walked, since they have
+/// their own kind.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tagua_parser;
+/// use tagua_parser::ast::{Expression, FunctionKind, Statement};
+/// use tagua_parser::rules::statements::function::function_kind;
+///
+/// # fn main() {
+/// assert_eq!(
+///     function_kind(&[Statement::Expression(Expression::Yield(None, None))]),
+///     FunctionKind::Generator
+/// );
+/// assert_eq!(function_kind(&[Statement::Return(None)]), FunctionKind::Regular);
+/// # }
+/// ```
+pub fn function_kind(body: &[Statement]) -> FunctionKind {
+    let mut finder = YieldFinder { yields: false };
+    walk_statements(&mut finder, body);
+
+    finder.kind()
+}
+
+/// Compute the kind of an arrow function from its body, see
+/// `function_kind`.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tagua_parser;
+/// use tagua_parser::ast::{Expression, FunctionKind};
+/// use tagua_parser::rules::statements::function::arrow_function_kind;
+///
+/// # fn main() {
+/// assert_eq!(
+///     arrow_function_kind(&Expression::Yield(None, None)),
+///     FunctionKind::Generator
+/// );
+/// # }
+/// ```
+pub fn arrow_function_kind(body: &Expression) -> FunctionKind {
+    let mut finder = YieldFinder { yields: false };
+    finder.visit_expression(body);
+
+    finder.kind()
+}
+
+/// Find a `yield` or a `yield from` expression, outside of nested
+/// functions and classes.
+struct YieldFinder {
+    yields: bool,
+}
+
+impl YieldFinder {
+    fn kind(&self) -> FunctionKind {
+        if self.yields {
+            FunctionKind::Generator
+        } else {
+            FunctionKind::Regular
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for YieldFinder {
+    fn visit_statement(&mut self, statement: &Statement<'a>) {
+        match *statement {
+            Statement::Class(_)
+            | Statement::Enum(_)
+            | Statement::Function(_)
+            | Statement::Interface(_)
+            | Statement::Trait(_) => {}
+
+            _ => {
+                if !self.yields {
+                    walk_statement(self, statement);
+                }
+            }
+        }
+    }
+
+    fn visit_expression(&mut self, expression: &Expression<'a>) {
+        match *expression {
+            Expression::Yield(..) | Expression::YieldFrom(_) => self.yields = true,
+
+            Expression::AnonymousFunction(_) | Expression::ArrowFunction(_) => {}
+
+            _ => {
+                if !self.yields {
+                    walk_expression(self, expression);
+                }
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::super::super::ast::{
-        Arity, Expression, Function, Literal, Name, Parameter, Statement, Ty, Variable,
+        Argument, Arguments, Arity, ArrayItem, Attribute, ControlSyntax, Expression, Function,
+        FunctionKind, Literal, Modifier, Name, Parameter, Promotion, RelativeScope, ScopeResolver,
+        Statement, Ty, Type, Variable, Visibility,
     };
     use super::super::super::super::internal::{Context, Error, ErrorKind};
     use super::super::super::super::tokens::{Span, Token};
     use super::super::statement;
-    use super::{function, native_type, parameters};
+    use super::{
+        checked_parameters, function, native_type, parameters, type_expression, FunctionError,
+        TypeError,
+    };
     use std::borrow::Cow;
 
     #[test]
@@ -525,21 +1020,33 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 38, 1, 39),
             Statement::Function(Function {
+                attributes: vec![],
                 name: Span::new_at(b"f", 9, 1, 10),
                 inputs: Arity::Finite(vec![
                     Parameter {
-                        ty: Ty::Copy(Some(Name::Unqualified(Span::new_at(b"I", 11, 1, 12)))),
+                        attributes: vec![],
+                        promotion: None,
+                        ty: Ty::Copy(Some(Type::Name(Name::Unqualified(Span::new_at(
+                            b"I", 11, 1, 12,
+                        ))))),
                         name: Variable(Span::new_at(b"x", 14, 1, 15)),
                         value: None,
                     },
                     Parameter {
-                        ty: Ty::Reference(Some(Name::Unqualified(Span::new_at(b"J", 17, 1, 18)))),
+                        attributes: vec![],
+                        promotion: None,
+                        ty: Ty::Reference(Some(Type::Name(Name::Unqualified(Span::new_at(
+                            b"J", 17, 1, 18,
+                        ))))),
                         name: Variable(Span::new_at(b"y", 21, 1, 22)),
                         value: None,
                     },
                 ]),
-                output: Ty::Copy(Some(Name::Unqualified(Span::new_at(b"O", 25, 1, 26)))),
-                body: vec![Statement::Return],
+                output: Ty::Copy(Some(Type::Name(Name::Unqualified(Span::new_at(
+                    b"O", 25, 1, 26,
+                ))))),
+                kind: FunctionKind::Regular,
+                body: vec![Statement::Return(None)],
             }),
         ));
 
@@ -553,10 +1060,62 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 15, 1, 16),
             Statement::Function(Function {
+                attributes: vec![],
                 name: Span::new_at(b"f", 9, 1, 10),
                 inputs: Arity::Constant,
                 output: Ty::Copy(None),
-                body: vec![Statement::Return],
+                kind: FunctionKind::Regular,
+                body: vec![],
+            }),
+        ));
+
+        assert_eq!(function(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_function_generator() {
+        let input = Span::new(b"function f() { while ($x) { yield $x; } }");
+        let output = Ok((
+            Span::new_at(b"", 41, 1, 42),
+            Statement::Function(Function {
+                attributes: vec![],
+                name: Span::new_at(b"f", 9, 1, 10),
+                inputs: Arity::Constant,
+                output: Ty::Copy(None),
+                kind: FunctionKind::Generator,
+                body: vec![Statement::While {
+                    condition: Expression::Variable(Variable(Span::new_at(b"x", 23, 1, 24))),
+                    body: vec![Statement::Expression(Expression::Yield(
+                        None,
+                        Some(Box::new(Expression::Variable(Variable(Span::new_at(
+                            b"x", 35, 1, 36,
+                        ))))),
+                    ))],
+                    syntax: ControlSyntax::Standard,
+                }],
+            }),
+        ));
+
+        assert_eq!(function(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_function_with_attributes() {
+        let input = Span::new(b"#[A] function f() {}");
+        let output = Ok((
+            Span::new_at(b"", 20, 1, 21),
+            Statement::Function(Function {
+                attributes: vec![Attribute {
+                    name: Name::Unqualified(Span::new_at(b"A", 2, 1, 3)),
+                    arguments: None,
+                }],
+                name: Span::new_at(b"f", 14, 1, 15),
+                inputs: Arity::Constant,
+                output: Ty::Copy(None),
+                kind: FunctionKind::Regular,
+                body: vec![],
             }),
         ));
 
@@ -570,38 +1129,48 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 41, 1, 42),
             Statement::Function(Function {
+                attributes: vec![],
                 name: Span::new_at(b"f", 9, 1, 10),
                 inputs: Arity::Finite(vec![
                     Parameter {
+                        attributes: vec![],
+                        promotion: None,
                         ty: Ty::Copy(None),
                         name: Variable(Span::new_at(b"a", 12, 1, 13)),
                         value: None,
                     },
                     Parameter {
-                        ty: Ty::Copy(Some(Name::Qualified(smallvec![
+                        attributes: vec![],
+                        promotion: None,
+                        ty: Ty::Copy(Some(Type::Name(Name::Qualified(smallvec![
                             Span::new_at(b"I", 15, 1, 16),
                             Span::new_at(b"J", 17, 1, 18)
-                        ]))),
+                        ])))),
                         name: Variable(Span::new_at(b"b", 20, 1, 21)),
                         value: None,
                     },
                     Parameter {
-                        ty: Ty::Reference(Some(Name::FullyQualified(smallvec![Span::new_at(
-                            b"int", 23, 1, 24
-                        )]))),
+                        attributes: vec![],
+                        promotion: None,
+                        ty: Ty::Reference(Some(Type::Name(Name::FullyQualified(smallvec![
+                            Span::new_at(b"int", 23, 1, 24)
+                        ])))),
                         name: Variable(Span::new_at(b"c", 29, 1, 30)),
                         value: None,
                     },
                     Parameter {
-                        ty: Ty::Copy(Some(Name::FullyQualified(smallvec![Span::new_at(
-                            b"K", 33, 1, 34
-                        )]))),
+                        attributes: vec![],
+                        promotion: None,
+                        ty: Ty::Copy(Some(Type::Name(Name::FullyQualified(smallvec![
+                            Span::new_at(b"K", 33, 1, 34)
+                        ])))),
                         name: Variable(Span::new_at(b"d", 36, 1, 37)),
                         value: None,
                     },
                 ]),
                 output: Ty::Copy(None),
-                body: vec![Statement::Return],
+                kind: FunctionKind::Regular,
+                body: vec![],
             }),
         ));
 
@@ -615,31 +1184,39 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 37, 1, 38),
             Statement::Function(Function {
+                attributes: vec![],
                 name: Span::new_at(b"f", 9, 1, 10),
                 inputs: Arity::Infinite(vec![
                     Parameter {
+                        attributes: vec![],
+                        promotion: None,
                         ty: Ty::Copy(None),
                         name: Variable(Span::new_at(b"a", 12, 1, 13)),
                         value: None,
                     },
                     Parameter {
-                        ty: Ty::Copy(Some(Name::Qualified(smallvec![
+                        attributes: vec![],
+                        promotion: None,
+                        ty: Ty::Copy(Some(Type::Name(Name::Qualified(smallvec![
                             Span::new_at(b"I", 15, 1, 16),
                             Span::new_at(b"J", 17, 1, 18)
-                        ]))),
+                        ])))),
                         name: Variable(Span::new_at(b"b", 20, 1, 21)),
                         value: None,
                     },
                     Parameter {
-                        ty: Ty::Reference(Some(Name::FullyQualified(smallvec![Span::new_at(
-                            b"int", 23, 1, 24
-                        )]))),
+                        attributes: vec![],
+                        promotion: None,
+                        ty: Ty::Reference(Some(Type::Name(Name::FullyQualified(smallvec![
+                            Span::new_at(b"int", 23, 1, 24)
+                        ])))),
                         name: Variable(Span::new_at(b"c", 32, 1, 33)),
                         value: None,
                     },
                 ]),
                 output: Ty::Copy(None),
-                body: vec![Statement::Return],
+                kind: FunctionKind::Regular,
+                body: vec![],
             }),
         ));
 
@@ -653,10 +1230,12 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 15, 1, 16),
             Statement::Function(Function {
+                attributes: vec![],
                 name: Span::new_at(b"f", 9, 1, 10),
                 inputs: Arity::Constant,
                 output: Ty::Copy(None),
-                body: vec![Statement::Return],
+                kind: FunctionKind::Regular,
+                body: vec![],
             }),
         ));
 
@@ -670,12 +1249,14 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 19, 1, 20),
             Statement::Function(Function {
+                attributes: vec![],
                 name: Span::new_at(b"f", 9, 1, 10),
                 inputs: Arity::Constant,
-                output: Ty::Copy(Some(Name::FullyQualified(smallvec![Span::new_at(
-                    b"O", 15, 1, 16
-                )]))),
-                body: vec![Statement::Return],
+                output: Ty::Copy(Some(Type::Name(Name::FullyQualified(smallvec![
+                    Span::new_at(b"O", 15, 1, 16)
+                ])))),
+                kind: FunctionKind::Regular,
+                body: vec![],
             }),
         ));
 
@@ -689,12 +1270,14 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 20, 1, 21),
             Statement::Function(Function {
+                attributes: vec![],
                 name: Span::new_at(b"f", 9, 1, 10),
                 inputs: Arity::Constant,
-                output: Ty::NullableCopy(Name::FullyQualified(smallvec![Span::new_at(
-                    b"O", 16, 1, 17
-                )])),
-                body: vec![Statement::Return],
+                output: Ty::Copy(Some(Type::Nullable(Name::FullyQualified(smallvec![
+                    Span::new_at(b"O", 16, 1, 17)
+                ])))),
+                kind: FunctionKind::Regular,
+                body: vec![],
             }),
         ));
 
@@ -708,10 +1291,12 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 16, 1, 17),
             Statement::Function(Function {
+                attributes: vec![],
                 name: Span::new_at(b"f", 10, 1, 11),
                 inputs: Arity::Constant,
                 output: Ty::Reference(None),
-                body: vec![Statement::Return],
+                kind: FunctionKind::Regular,
+                body: vec![],
             }),
         ));
 
@@ -725,12 +1310,14 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 21, 1, 22),
             Statement::Function(Function {
+                attributes: vec![],
                 name: Span::new_at(b"f", 10, 1, 11),
                 inputs: Arity::Constant,
-                output: Ty::Reference(Some(Name::FullyQualified(smallvec![Span::new_at(
-                    b"int", 15, 1, 16
-                )]))),
-                body: vec![Statement::Return],
+                output: Ty::Reference(Some(Type::Name(Name::FullyQualified(smallvec![
+                    Span::new_at(b"int", 15, 1, 16)
+                ])))),
+                kind: FunctionKind::Regular,
+                body: vec![],
             }),
         ));
 
@@ -744,12 +1331,35 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 22, 1, 23),
             Statement::Function(Function {
+                attributes: vec![],
                 name: Span::new_at(b"f", 10, 1, 11),
                 inputs: Arity::Constant,
-                output: Ty::NullableReference(Name::FullyQualified(smallvec![Span::new_at(
-                    b"int", 16, 1, 17
-                )])),
-                body: vec![Statement::Return],
+                output: Ty::Reference(Some(Type::Nullable(Name::FullyQualified(smallvec![
+                    Span::new_at(b"int", 16, 1, 17)
+                ])))),
+                kind: FunctionKind::Regular,
+                body: vec![],
+            }),
+        ));
+
+        assert_eq!(function(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_function_output_by_static_copy() {
+        let input = Span::new(b"function f(): static {}");
+        let output = Ok((
+            Span::new_at(b"", 23, 1, 24),
+            Statement::Function(Function {
+                attributes: vec![],
+                name: Span::new_at(b"f", 9, 1, 10),
+                inputs: Arity::Constant,
+                output: Ty::Copy(Some(Type::Name(Name::Unqualified(Span::new_at(
+                    b"static", 14, 1, 15,
+                ))))),
+                kind: FunctionKind::Regular,
+                body: vec![],
             }),
         ));
 
@@ -757,21 +1367,79 @@ mod tests {
         assert_eq!(statement(input), output);
     }
 
+    #[test]
+    fn case_function_output_by_union_copy() {
+        let input = Span::new(b"function f(): (A&B)|null {}");
+        let output = Ok((
+            Span::new_at(b"", 27, 1, 28),
+            Statement::Function(Function {
+                attributes: vec![],
+                name: Span::new_at(b"f", 9, 1, 10),
+                inputs: Arity::Constant,
+                output: Ty::Copy(Some(Type::Union(vec![
+                    Type::Intersection(vec![
+                        Name::Unqualified(Span::new_at(b"A", 15, 1, 16)),
+                        Name::Unqualified(Span::new_at(b"B", 17, 1, 18)),
+                    ]),
+                    Type::Name(Name::FullyQualified(smallvec![Span::new_at(
+                        b"null", 20, 1, 21
+                    )])),
+                ]))),
+                kind: FunctionKind::Regular,
+                body: vec![],
+            }),
+        ));
+
+        assert_eq!(function(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_function_output_nullable_void() {
+        let input = Span::new(b"function f(): ?void {}");
+        let output = Err(Error::Failure(Context::Code(
+            Span::new_at(b": ?void {}", 12, 1, 13),
+            ErrorKind::Custom(TypeError::InvalidNullableType as u32),
+        )));
+
+        assert_eq!(function(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_function_with_a_nullable_mixed_parameter() {
+        let input = Span::new(b"function f(?mixed $x) {}");
+        let output = Err(Error::Failure(Context::Code(
+            Span::new_at(b"(?mixed $x) {}", 10, 1, 11),
+            ErrorKind::Custom(TypeError::InvalidNullableType as u32),
+        )));
+
+        assert_eq!(function(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_function_with_a_promoted_parameter() {
+        let input = Span::new(b"function f(public $x) {}");
+        let output = Err(Error::Failure(Context::Code(
+            Span::new_at(b"(public $x) {}", 10, 1, 11),
+            ErrorKind::Custom(FunctionError::PromotedParameterOutsideConstructor as u32),
+        )));
+
+        assert_eq!(function(input), output);
+        assert_eq!(statement(input), output);
+    }
+
     #[test]
     fn case_invalid_variadic_function_parameter_position() {
         let input = Span::new(b"function f(...$x, $y) {}");
+        let output = Err(Error::Failure(Context::Code(
+            Span::new_at(b"(...$x, $y) {}", 10, 1, 11),
+            ErrorKind::Custom(FunctionError::InvalidVariadicParameterPosition as u32),
+        )));
 
-        assert_eq!(
-            function(input),
-            Err(Error::Error(Context::Code(
-                Span::new_at(b"(...$x, $y) {}", 10, 1, 11),
-                ErrorKind::MapRes
-            )))
-        );
-        assert_eq!(
-            statement(input),
-            Err(Error::Error(Context::Code(input, ErrorKind::Alt)))
-        );
+        assert_eq!(function(input), output);
+        assert_eq!(statement(input), output);
     }
 
     #[test]
@@ -780,6 +1448,8 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 4, 1, 5),
             Arity::Finite(vec![Parameter {
+                attributes: vec![],
+                promotion: None,
                 ty: Ty::Copy(None),
                 name: Variable(Span::new_at(b"x", 2, 1, 3)),
                 value: None,
@@ -795,11 +1465,13 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 10, 1, 11),
             Arity::Finite(vec![Parameter {
-                ty: Ty::Copy(Some(Name::Qualified(smallvec![
+                attributes: vec![],
+                promotion: None,
+                ty: Ty::Copy(Some(Type::Name(Name::Qualified(smallvec![
                     Span::new_at(b"A", 1, 1, 2),
                     Span::new_at(b"B", 3, 1, 4),
                     Span::new_at(b"C", 5, 1, 6)
-                ]))),
+                ])))),
                 name: Variable(Span::new_at(b"x", 8, 1, 9)),
                 value: None,
             }]),
@@ -814,11 +1486,13 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 11, 1, 12),
             Arity::Finite(vec![Parameter {
-                ty: Ty::NullableCopy(Name::Qualified(smallvec![
+                attributes: vec![],
+                promotion: None,
+                ty: Ty::Copy(Some(Type::Nullable(Name::Qualified(smallvec![
                     Span::new_at(b"A", 2, 1, 3),
                     Span::new_at(b"B", 4, 1, 5),
                     Span::new_at(b"C", 6, 1, 7)
-                ])),
+                ])))),
                 name: Variable(Span::new_at(b"x", 9, 1, 10)),
                 value: None,
             }]),
@@ -833,6 +1507,8 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 5, 1, 6),
             Arity::Finite(vec![Parameter {
+                attributes: vec![],
+                promotion: None,
                 ty: Ty::Reference(None),
                 name: Variable(Span::new_at(b"x", 3, 1, 4)),
                 value: None,
@@ -848,9 +1524,11 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 9, 1, 10),
             Arity::Finite(vec![Parameter {
-                ty: Ty::Reference(Some(Name::FullyQualified(smallvec![Span::new_at(
-                    b"int", 1, 1, 2
-                )]))),
+                attributes: vec![],
+                promotion: None,
+                ty: Ty::Reference(Some(Type::Name(Name::FullyQualified(smallvec![
+                    Span::new_at(b"int", 1, 1, 2)
+                ])))),
                 name: Variable(Span::new_at(b"x", 7, 1, 8)),
                 value: None,
             }]),
@@ -865,9 +1543,11 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 10, 1, 11),
             Arity::Finite(vec![Parameter {
-                ty: Ty::NullableReference(Name::FullyQualified(smallvec![Span::new_at(
-                    b"int", 2, 1, 3
-                )])),
+                attributes: vec![],
+                promotion: None,
+                ty: Ty::Reference(Some(Type::Nullable(Name::FullyQualified(smallvec![
+                    Span::new_at(b"int", 2, 1, 3)
+                ])))),
                 name: Variable(Span::new_at(b"x", 8, 1, 9)),
                 value: None,
             }]),
@@ -882,6 +1562,8 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 7, 1, 8),
             Arity::Infinite(vec![Parameter {
+                attributes: vec![],
+                promotion: None,
                 ty: Ty::Copy(None),
                 name: Variable(Span::new_at(b"x", 5, 1, 6)),
                 value: None,
@@ -897,7 +1579,11 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 10, 1, 11),
             Arity::Infinite(vec![Parameter {
-                ty: Ty::Reference(Some(Name::Unqualified(Span::new_at(b"I", 1, 1, 2)))),
+                attributes: vec![],
+                promotion: None,
+                ty: Ty::Reference(Some(Type::Name(Name::Unqualified(Span::new_at(
+                    b"I", 1, 1, 2,
+                ))))),
                 name: Variable(Span::new_at(b"x", 8, 1, 9)),
                 value: None,
             }]),
@@ -912,6 +1598,8 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 9, 1, 10),
             Arity::Finite(vec![Parameter {
+                attributes: vec![],
+                promotion: None,
                 ty: Ty::Copy(None),
                 name: Variable(Span::new_at(b"x", 2, 1, 3)),
                 value: Some(Expression::Literal(Literal::Integer(Token::new(
@@ -930,9 +1618,11 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 16, 1, 17),
             Arity::Finite(vec![Parameter {
-                ty: Ty::Copy(Some(Name::FullyQualified(smallvec![Span::new_at(
-                    b"float", 1, 1, 2
-                )]))),
+                attributes: vec![],
+                promotion: None,
+                ty: Ty::Copy(Some(Type::Name(Name::FullyQualified(smallvec![
+                    Span::new_at(b"float", 1, 1, 2)
+                ])))),
                 name: Variable(Span::new_at(b"x", 8, 1, 9)),
                 value: Some(Expression::Literal(Literal::Real(Token::new(
                     4.2f64,
@@ -950,9 +1640,11 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 17, 1, 18),
             Arity::Finite(vec![Parameter {
-                ty: Ty::NullableCopy(Name::FullyQualified(smallvec![Span::new_at(
-                    b"float", 2, 1, 3
-                )])),
+                attributes: vec![],
+                promotion: None,
+                ty: Ty::Copy(Some(Type::Nullable(Name::FullyQualified(smallvec![
+                    Span::new_at(b"float", 2, 1, 3)
+                ])))),
                 name: Variable(Span::new_at(b"x", 9, 1, 10)),
                 value: Some(Expression::Literal(Literal::Real(Token::new(
                     4.2f64,
@@ -964,12 +1656,84 @@ mod tests {
         assert_eq!(parameters(input), output);
     }
 
+    #[test]
+    fn case_parameters_one_by_nullable_copy_and_a_null_default_value() {
+        let input = Span::new(b"(?int $a = null)");
+        let output = Ok((
+            Span::new_at(b"", 16, 1, 17),
+            Arity::Finite(vec![Parameter {
+                attributes: vec![],
+                promotion: None,
+                ty: Ty::Copy(Some(Type::Nullable(Name::FullyQualified(smallvec![
+                    Span::new_at(b"int", 2, 1, 3)
+                ])))),
+                name: Variable(Span::new_at(b"a", 7, 1, 8)),
+                value: Some(Expression::Name(Name::Unqualified(Span::new_at(
+                    b"null", 11, 1, 12,
+                )))),
+            }]),
+        ));
+
+        assert_eq!(parameters(input), output);
+    }
+
+    #[test]
+    fn case_parameters_one_by_none_copy_and_a_constant_default_value() {
+        let input = Span::new(b"($a = PHP_INT_MAX)");
+        let output = Ok((
+            Span::new_at(b"", 18, 1, 19),
+            Arity::Finite(vec![Parameter {
+                attributes: vec![],
+                promotion: None,
+                ty: Ty::Copy(None),
+                name: Variable(Span::new_at(b"a", 2, 1, 3)),
+                value: Some(Expression::Name(Name::Unqualified(Span::new_at(
+                    b"PHP_INT_MAX",
+                    6,
+                    1,
+                    7,
+                )))),
+            }]),
+        ));
+
+        assert_eq!(parameters(input), output);
+    }
+
+    #[test]
+    fn case_parameters_one_by_none_copy_and_a_class_constant_default_value() {
+        let input = Span::new(b"($a = self::X)");
+        let output = Ok((
+            Span::new_at(b"", 14, 1, 15),
+            Arity::Finite(vec![Parameter {
+                attributes: vec![],
+                promotion: None,
+                ty: Ty::Copy(None),
+                name: Variable(Span::new_at(b"a", 2, 1, 3)),
+                value: Some(Expression::ClassConstantAccess(
+                    ScopeResolver::ByRelative(RelativeScope::ToSelf),
+                    Span::new_at(b"X", 12, 1, 13),
+                )),
+            }]),
+        ));
+
+        assert_eq!(parameters(input), output);
+    }
+
+    #[test]
+    fn case_invalid_parameters_one_with_a_non_constant_default_value() {
+        let input = Span::new(b"($a = $b)");
+
+        assert!(parameters(input).is_err());
+    }
+
     #[test]
     fn case_parameters_one_by_none_reference_with_a_default_value() {
         let input = Span::new(b"(&$x = 'foo')");
         let output = Ok((
             Span::new_at(b"", 13, 1, 14),
             Arity::Finite(vec![Parameter {
+                attributes: vec![],
+                promotion: None,
                 ty: Ty::Reference(None),
                 name: Variable(Span::new_at(b"x", 3, 1, 4)),
                 value: Some(Expression::Literal(Literal::String(Token::new(
@@ -988,15 +1752,17 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 29, 1, 30),
             Arity::Finite(vec![Parameter {
-                ty: Ty::Reference(Some(Name::FullyQualified(smallvec![Span::new_at(
-                    b"array", 1, 1, 2
-                )]))),
+                attributes: vec![],
+                promotion: None,
+                ty: Ty::Reference(Some(Type::Name(Name::FullyQualified(smallvec![
+                    Span::new_at(b"array", 1, 1, 2)
+                ])))),
                 name: Variable(Span::new_at(b"x", 9, 1, 10)),
-                value: Some(Expression::Array(vec![(
-                    Some(Expression::Literal(Literal::String(Token::new(
+                value: Some(Expression::Array(vec![ArrayItem::Pair(
+                    Some(Box::new(Expression::Literal(Literal::String(Token::new(
                         Cow::from(&b"foo"[..]),
                         Span::new_at(b"'foo'", 14, 1, 15),
-                    )))),
+                    ))))),
                     Expression::Name(Name::Unqualified(Span::new_at(b"true", 23, 1, 24))),
                 )])),
             }]),
@@ -1011,9 +1777,11 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 21, 1, 22),
             Arity::Finite(vec![Parameter {
-                ty: Ty::NullableReference(Name::FullyQualified(smallvec![Span::new_at(
-                    b"string", 2, 1, 3
-                )])),
+                attributes: vec![],
+                promotion: None,
+                ty: Ty::Reference(Some(Type::Nullable(Name::FullyQualified(smallvec![
+                    Span::new_at(b"string", 2, 1, 3)
+                ])))),
                 name: Variable(Span::new_at(b"x", 11, 1, 12)),
                 value: Some(Expression::Literal(Literal::String(Token::new(
                     Cow::from(&b"foo"[..]),
@@ -1031,6 +1799,8 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 7, 1, 8),
             Arity::Infinite(vec![Parameter {
+                attributes: vec![],
+                promotion: None,
                 ty: Ty::Copy(None),
                 name: Variable(Span::new_at(b"x", 5, 1, 6)),
                 value: None,
@@ -1046,11 +1816,13 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 13, 1, 14),
             Arity::Infinite(vec![Parameter {
-                ty: Ty::Copy(Some(Name::Qualified(smallvec![
+                attributes: vec![],
+                promotion: None,
+                ty: Ty::Copy(Some(Type::Name(Name::Qualified(smallvec![
                     Span::new_at(b"A", 1, 1, 2),
                     Span::new_at(b"B", 3, 1, 4),
                     Span::new_at(b"C", 5, 1, 6)
-                ]))),
+                ])))),
                 name: Variable(Span::new_at(b"x", 11, 1, 12)),
                 value: None,
             }]),
@@ -1065,11 +1837,13 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 14, 1, 15),
             Arity::Infinite(vec![Parameter {
-                ty: Ty::NullableCopy(Name::Qualified(smallvec![
+                attributes: vec![],
+                promotion: None,
+                ty: Ty::Copy(Some(Type::Nullable(Name::Qualified(smallvec![
                     Span::new_at(b"A", 2, 1, 3),
                     Span::new_at(b"B", 4, 1, 5),
                     Span::new_at(b"C", 6, 1, 7)
-                ])),
+                ])))),
                 name: Variable(Span::new_at(b"x", 12, 1, 13)),
                 value: None,
             }]),
@@ -1084,6 +1858,8 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 8, 1, 9),
             Arity::Infinite(vec![Parameter {
+                attributes: vec![],
+                promotion: None,
                 ty: Ty::Reference(None),
                 name: Variable(Span::new_at(b"x", 6, 1, 7)),
                 value: None,
@@ -1099,9 +1875,11 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 12, 1, 13),
             Arity::Infinite(vec![Parameter {
-                ty: Ty::Reference(Some(Name::FullyQualified(smallvec![Span::new_at(
-                    b"int", 1, 1, 2
-                )]))),
+                attributes: vec![],
+                promotion: None,
+                ty: Ty::Reference(Some(Type::Name(Name::FullyQualified(smallvec![
+                    Span::new_at(b"int", 1, 1, 2)
+                ])))),
                 name: Variable(Span::new_at(b"x", 10, 1, 11)),
                 value: None,
             }]),
@@ -1116,9 +1894,11 @@ mod tests {
         let output = Ok((
             Span::new_at(b"", 13, 1, 14),
             Arity::Infinite(vec![Parameter {
-                ty: Ty::NullableReference(Name::FullyQualified(smallvec![Span::new_at(
-                    b"int", 2, 1, 3
-                )])),
+                attributes: vec![],
+                promotion: None,
+                ty: Ty::Reference(Some(Type::Nullable(Name::FullyQualified(smallvec![
+                    Span::new_at(b"int", 2, 1, 3)
+                ])))),
                 name: Variable(Span::new_at(b"x", 11, 1, 12)),
                 value: None,
             }]),
@@ -1134,22 +1914,28 @@ mod tests {
             Span::new_at(b"", 21, 1, 22),
             Arity::Finite(vec![
                 Parameter {
+                    attributes: vec![],
+                    promotion: None,
                     ty: Ty::Reference(None),
                     name: Variable(Span::new_at(b"x", 3, 1, 4)),
                     value: None,
                 },
                 Parameter {
-                    ty: Ty::Copy(Some(Name::FullyQualified(smallvec![Span::new_at(
-                        b"int", 6, 1, 7
-                    )]))),
+                    attributes: vec![],
+                    promotion: None,
+                    ty: Ty::Copy(Some(Type::Name(Name::FullyQualified(smallvec![
+                        Span::new_at(b"int", 6, 1, 7)
+                    ])))),
                     name: Variable(Span::new_at(b"y", 11, 1, 12)),
                     value: None,
                 },
                 Parameter {
-                    ty: Ty::Copy(Some(Name::Qualified(smallvec![
+                    attributes: vec![],
+                    promotion: None,
+                    ty: Ty::Copy(Some(Type::Name(Name::Qualified(smallvec![
                         Span::new_at(b"I", 14, 1, 15),
                         Span::new_at(b"J", 16, 1, 17)
-                    ]))),
+                    ])))),
                     name: Variable(Span::new_at(b"z", 19, 1, 20)),
                     value: None,
                 },
@@ -1166,22 +1952,28 @@ mod tests {
             Span::new_at(b"", 24, 1, 25),
             Arity::Infinite(vec![
                 Parameter {
+                    attributes: vec![],
+                    promotion: None,
                     ty: Ty::Reference(None),
                     name: Variable(Span::new_at(b"x", 3, 1, 4)),
                     value: None,
                 },
                 Parameter {
-                    ty: Ty::Copy(Some(Name::FullyQualified(smallvec![Span::new_at(
-                        b"int", 6, 1, 7
-                    )]))),
+                    attributes: vec![],
+                    promotion: None,
+                    ty: Ty::Copy(Some(Type::Name(Name::FullyQualified(smallvec![
+                        Span::new_at(b"int", 6, 1, 7)
+                    ])))),
                     name: Variable(Span::new_at(b"y", 11, 1, 12)),
                     value: None,
                 },
                 Parameter {
-                    ty: Ty::Copy(Some(Name::Qualified(smallvec![
+                    attributes: vec![],
+                    promotion: None,
+                    ty: Ty::Copy(Some(Type::Name(Name::Qualified(smallvec![
                         Span::new_at(b"I", 14, 1, 15),
                         Span::new_at(b"J", 16, 1, 17)
-                    ]))),
+                    ])))),
                     name: Variable(Span::new_at(b"z", 22, 1, 23)),
                     value: None,
                 },
@@ -1191,10 +1983,261 @@ mod tests {
         assert_eq!(parameters(input), output);
     }
 
+    #[test]
+    fn case_parameters_with_attributes() {
+        let input = Span::new(b"(#[A(1)] ?I $x, #[B] #[C] int &$y)");
+        let output = Ok((
+            Span::new_at(b"", 34, 1, 35),
+            Arity::Finite(vec![
+                Parameter {
+                    attributes: vec![Attribute {
+                        name: Name::Unqualified(Span::new_at(b"A", 3, 1, 4)),
+                        arguments: Some(Arguments::List(vec![Argument::Positional(
+                            Expression::Literal(Literal::Integer(Token::new(
+                                1i64,
+                                Span::new_at(b"1", 5, 1, 6),
+                            ))),
+                        )])),
+                    }],
+                    promotion: None,
+                    ty: Ty::Copy(Some(Type::Nullable(Name::Unqualified(Span::new_at(
+                        b"I", 10, 1, 11,
+                    ))))),
+                    name: Variable(Span::new_at(b"x", 13, 1, 14)),
+                    value: None,
+                },
+                Parameter {
+                    attributes: vec![
+                        Attribute {
+                            name: Name::Unqualified(Span::new_at(b"B", 18, 1, 19)),
+                            arguments: None,
+                        },
+                        Attribute {
+                            name: Name::Unqualified(Span::new_at(b"C", 23, 1, 24)),
+                            arguments: None,
+                        },
+                    ],
+                    promotion: None,
+                    ty: Ty::Reference(Some(Type::Name(Name::FullyQualified(smallvec![
+                        Span::new_at(b"int", 26, 1, 27)
+                    ])))),
+                    name: Variable(Span::new_at(b"y", 32, 1, 33)),
+                    value: None,
+                },
+            ]),
+        ));
+
+        assert_eq!(parameters(input), output);
+    }
+
+    #[test]
+    fn case_parameters_one_by_union_copy() {
+        let input = Span::new(b"(int|A\\B|null $x)");
+        let output = Ok((
+            Span::new_at(b"", 17, 1, 18),
+            Arity::Finite(vec![Parameter {
+                attributes: vec![],
+                promotion: None,
+                ty: Ty::Copy(Some(Type::Union(vec![
+                    Type::Name(Name::FullyQualified(smallvec![Span::new_at(
+                        b"int", 1, 1, 2
+                    )])),
+                    Type::Name(Name::Qualified(smallvec![
+                        Span::new_at(b"A", 5, 1, 6),
+                        Span::new_at(b"B", 7, 1, 8)
+                    ])),
+                    Type::Name(Name::FullyQualified(smallvec![Span::new_at(
+                        b"null", 9, 1, 10
+                    )])),
+                ]))),
+                name: Variable(Span::new_at(b"x", 15, 1, 16)),
+                value: None,
+            }]),
+        ));
+
+        assert_eq!(parameters(input), output);
+    }
+
+    #[test]
+    fn case_parameters_one_by_intersection_reference() {
+        let input = Span::new(b"(A&B &$x)");
+        let output = Ok((
+            Span::new_at(b"", 9, 1, 10),
+            Arity::Finite(vec![Parameter {
+                attributes: vec![],
+                promotion: None,
+                ty: Ty::Reference(Some(Type::Intersection(vec![
+                    Name::Unqualified(Span::new_at(b"A", 1, 1, 2)),
+                    Name::Unqualified(Span::new_at(b"B", 3, 1, 4)),
+                ]))),
+                name: Variable(Span::new_at(b"x", 7, 1, 8)),
+                value: None,
+            }]),
+        ));
+
+        assert_eq!(parameters(input), output);
+    }
+
+    #[test]
+    fn case_parameters_promoted() {
+        let input = Span::new(b"(public int $x, protected readonly ?A $y, readonly private $z)");
+        let output = Ok((
+            Span::new_at(b"", 62, 1, 63),
+            Arity::Finite(vec![
+                Parameter {
+                    attributes: vec![],
+                    promotion: Some(Promotion {
+                        visibility: Visibility::Public,
+                        modifiers: vec![],
+                    }),
+                    ty: Ty::Copy(Some(Type::Name(Name::FullyQualified(smallvec![
+                        Span::new_at(b"int", 8, 1, 9)
+                    ])))),
+                    name: Variable(Span::new_at(b"x", 13, 1, 14)),
+                    value: None,
+                },
+                Parameter {
+                    attributes: vec![],
+                    promotion: Some(Promotion {
+                        visibility: Visibility::Protected,
+                        modifiers: vec![Modifier::Readonly],
+                    }),
+                    ty: Ty::Copy(Some(Type::Nullable(Name::Unqualified(Span::new_at(
+                        b"A", 36, 1, 37,
+                    ))))),
+                    name: Variable(Span::new_at(b"y", 39, 1, 40)),
+                    value: None,
+                },
+                Parameter {
+                    attributes: vec![],
+                    promotion: Some(Promotion {
+                        visibility: Visibility::Private,
+                        modifiers: vec![Modifier::Readonly],
+                    }),
+                    ty: Ty::Copy(None),
+                    name: Variable(Span::new_at(b"z", 60, 1, 61)),
+                    value: None,
+                },
+            ]),
+        ));
+
+        assert_eq!(parameters(input), output);
+        assert_eq!(checked_parameters(input, true), output);
+    }
+
+    #[test]
+    fn case_parameters_promoted_with_default_values() {
+        let input = Span::new(b"(private ?int $a = null, public $b = self::X)");
+        let output = Ok((
+            Span::new_at(b"", 45, 1, 46),
+            Arity::Finite(vec![
+                Parameter {
+                    attributes: vec![],
+                    promotion: Some(Promotion {
+                        visibility: Visibility::Private,
+                        modifiers: vec![],
+                    }),
+                    ty: Ty::Copy(Some(Type::Nullable(Name::FullyQualified(smallvec![
+                        Span::new_at(b"int", 10, 1, 11)
+                    ])))),
+                    name: Variable(Span::new_at(b"a", 15, 1, 16)),
+                    value: Some(Expression::Name(Name::Unqualified(Span::new_at(
+                        b"null", 19, 1, 20,
+                    )))),
+                },
+                Parameter {
+                    attributes: vec![],
+                    promotion: Some(Promotion {
+                        visibility: Visibility::Public,
+                        modifiers: vec![],
+                    }),
+                    ty: Ty::Copy(None),
+                    name: Variable(Span::new_at(b"b", 33, 1, 34)),
+                    value: Some(Expression::ClassConstantAccess(
+                        ScopeResolver::ByRelative(RelativeScope::ToSelf),
+                        Span::new_at(b"X", 43, 1, 44),
+                    )),
+                },
+            ]),
+        ));
+
+        assert_eq!(parameters(input), output);
+        assert_eq!(checked_parameters(input, true), output);
+    }
+
+    #[test]
+    fn case_invalid_parameters_promoted_outside_a_constructor() {
+        let input = Span::new(b"(readonly int $x)");
+        let output = Err(Error::Failure(Context::Code(
+            input,
+            ErrorKind::Custom(FunctionError::PromotedParameterOutsideConstructor as u32),
+        )));
+
+        assert_eq!(checked_parameters(input, false), output);
+    }
+
+    #[test]
+    fn case_invalid_parameters_variadic_promoted() {
+        let input = Span::new(b"(public int ...$x)");
+        let output = Err(Error::Failure(Context::Code(
+            input,
+            ErrorKind::Custom(FunctionError::VariadicPromotedParameter as u32),
+        )));
+
+        assert_eq!(parameters(input), output);
+    }
+
+    #[test]
+    fn case_invalid_parameters_duplicated_promotion_modifier() {
+        let input = Span::new(b"(public public $x)");
+        let output = Err(Error::Error(Context::Code(
+            Span::new_at(b"public public $x)", 1, 1, 2),
+            ErrorKind::Tag,
+        )));
+
+        assert_eq!(parameters(input), output);
+    }
+
+    #[test]
+    fn case_invalid_parameters_nullable_mixed() {
+        let input = Span::new(b"(?mixed $x)");
+        let output = Err(Error::Failure(Context::Code(
+            input,
+            ErrorKind::Custom(TypeError::InvalidNullableType as u32),
+        )));
+
+        assert_eq!(parameters(input), output);
+    }
+
+    #[test]
+    fn case_invalid_parameters_standalone_type_in_union() {
+        let input = Span::new(b"(void|int $x)");
+        let output = Err(Error::Failure(Context::Code(
+            input,
+            ErrorKind::Custom(TypeError::StandaloneTypeInComposite as u32),
+        )));
+
+        assert_eq!(parameters(input), output);
+    }
+
+    #[test]
+    fn case_invalid_parameters_native_type_in_intersection() {
+        let input = Span::new(b"(int&A $x)");
+        let output = Err(Error::Failure(Context::Code(
+            input,
+            ErrorKind::Custom(TypeError::InvalidIntersectionMember as u32),
+        )));
+
+        assert_eq!(parameters(input), output);
+    }
+
     #[test]
     fn case_invalid_parameters_variadic_position() {
         let input = Span::new(b"(...$x, $y)");
-        let output = Err(Error::Error(Context::Code(input, ErrorKind::MapRes)));
+        let output = Err(Error::Failure(Context::Code(
+            input,
+            ErrorKind::Custom(FunctionError::InvalidVariadicParameterPosition as u32),
+        )));
 
         assert_eq!(parameters(input), output);
     }
@@ -1202,7 +2245,10 @@ mod tests {
     #[test]
     fn case_invalid_parameters_two_not_unique() {
         let input = Span::new(b"($x, $x)");
-        let output = Err(Error::Error(Context::Code(input, ErrorKind::MapRes)));
+        let output = Err(Error::Failure(Context::Code(
+            input,
+            ErrorKind::Custom(FunctionError::MultipleParametersWithSameName as u32),
+        )));
 
         assert_eq!(parameters(input), output);
     }
@@ -1210,7 +2256,10 @@ mod tests {
     #[test]
     fn case_invalid_parameters_many_not_unique() {
         let input = Span::new(b"($x, $y, $x, $z)");
-        let output = Err(Error::Error(Context::Code(input, ErrorKind::MapRes)));
+        let output = Err(Error::Failure(Context::Code(
+            input,
+            ErrorKind::Custom(FunctionError::MultipleParametersWithSameName as u32),
+        )));
 
         assert_eq!(parameters(input), output);
     }
@@ -1233,8 +2282,78 @@ mod tests {
     test_native_type!(case_native_type_array:    b"array");
     test_native_type!(case_native_type_bool:     b"bool");
     test_native_type!(case_native_type_callable: b"callable");
+    test_native_type!(case_native_type_false:    b"false");
     test_native_type!(case_native_type_float:    b"float");
     test_native_type!(case_native_type_int:      b"int");
     test_native_type!(case_native_type_iterable: b"iterable");
+    test_native_type!(case_native_type_mixed:    b"mixed");
+    test_native_type!(case_native_type_never:    b"never");
+    test_native_type!(case_native_type_null:     b"null");
+    test_native_type!(case_native_type_object:   b"object");
     test_native_type!(case_native_type_string:   b"string");
+    test_native_type!(case_native_type_true:     b"true");
+    test_native_type!(case_native_type_void:     b"void");
+
+    #[test]
+    fn case_native_type_not_a_prefix() {
+        let input = Span::new(b"integer");
+
+        assert!(native_type(input).is_err());
+    }
+
+    #[test]
+    fn case_type_expression_nullable() {
+        let input = Span::new(b"?A");
+        let output = Ok((
+            Span::new_at(b"", 2, 1, 3),
+            Type::Nullable(Name::Unqualified(Span::new_at(b"A", 1, 1, 2))),
+        ));
+
+        assert_eq!(type_expression(input), output);
+    }
+
+    #[test]
+    fn case_type_expression_union_of_relative_names() {
+        let input = Span::new(b"self|parent");
+        let output = Ok((
+            Span::new_at(b"", 11, 1, 12),
+            Type::Union(vec![
+                Type::Name(Name::Unqualified(Span::new_at(b"self", 0, 1, 1))),
+                Type::Name(Name::Unqualified(Span::new_at(b"parent", 5, 1, 6))),
+            ]),
+        ));
+
+        assert_eq!(type_expression(input), output);
+    }
+
+    #[test]
+    fn case_type_expression_union_with_intersection() {
+        let input = Span::new(b"A|(B&C)");
+        let output = Ok((
+            Span::new_at(b"", 7, 1, 8),
+            Type::Union(vec![
+                Type::Name(Name::Unqualified(Span::new_at(b"A", 0, 1, 1))),
+                Type::Intersection(vec![
+                    Name::Unqualified(Span::new_at(b"B", 3, 1, 4)),
+                    Name::Unqualified(Span::new_at(b"C", 5, 1, 6)),
+                ]),
+            ]),
+        ));
+
+        assert_eq!(type_expression(input), output);
+    }
+
+    #[test]
+    fn case_type_expression_intersection() {
+        let input = Span::new(b"A & B");
+        let output = Ok((
+            Span::new_at(b"", 5, 1, 6),
+            Type::Intersection(vec![
+                Name::Unqualified(Span::new_at(b"A", 0, 1, 1)),
+                Name::Unqualified(Span::new_at(b"B", 4, 1, 5)),
+            ]),
+        ));
+
+        assert_eq!(type_expression(input), output);
+    }
 }
@@ -0,0 +1,623 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2017, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of iteration statement rules.
+//!
+//! The list of all iteration statements is provided by the PHP Language
+//! Specification in the [Grammar chapter, Iteration Statements
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#iteration-statements).
+
+use super::super::super::ast::{Expression, Statement};
+use super::super::super::tokens;
+use super::super::super::tokens::Span;
+use super::super::expressions::expression;
+use super::super::expressions::primaries::into_destructuring_target;
+use super::selection::parenthesized_expression;
+use super::{control_structure_body, required_terminator, statement_body};
+
+named_attr!(
+    #[doc="
+        Recognize all kind of iteration statements.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{ControlSyntax, Expression, Statement, Variable};
+        use tagua_parser::rules::statements::iteration::iteration_statement;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            iteration_statement(Span::new(b\"while ($x);\")),
+            Ok((
+                Span::new_at(b\"\", 11, 1, 12),
+                Statement::While {
+                    condition: Expression::Variable(Variable(Span::new_at(b\"x\", 8, 1, 9))),
+                    body     : vec![Statement::Empty],
+                    syntax   : ControlSyntax::Standard
+                }
+            ))
+        );
+        # }
+        ```
+    "],
+    pub iteration_statement<Span, Statement>,
+    alt_complete!(
+        while_statement
+      | do_statement
+      | for_statement
+      | foreach_statement
+    )
+);
+
+named_attr!(
+    #[doc="
+        Recognize a `while` loop.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{ControlSyntax, Expression, Statement, Variable};
+        use tagua_parser::rules::statements::iteration::while_statement;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            while_statement(Span::new(b\"while ($x): $y; endwhile;\")),
+            Ok((
+                Span::new_at(b\"\", 25, 1, 26),
+                Statement::While {
+                    condition: Expression::Variable(Variable(Span::new_at(b\"x\", 8, 1, 9))),
+                    body     : vec![
+                        Statement::Expression(Expression::Variable(Variable(Span::new_at(b\"y\", 13, 1, 14))))
+                    ],
+                    syntax   : ControlSyntax::Alternative
+                }
+            ))
+        );
+        # }
+        ```
+    "],
+    pub while_statement<Span, Statement>,
+    do_parse!(
+        statement_keyword!(tokens::WHILE) >>
+        condition: first!(parenthesized_expression) >>
+        body: first!(call!(control_structure_body, tokens::ENDWHILE)) >>
+        ({
+            let (body, syntax) = body;
+
+            Statement::While {
+                condition,
+                body,
+                syntax
+            }
+        })
+    )
+);
+
+named_attr!(
+    #[doc="
+        Recognize a `do … while` loop.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{Expression, Statement, Variable};
+        use tagua_parser::rules::statements::iteration::do_statement;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            do_statement(Span::new(b\"do { $y; } while ($x);\")),
+            Ok((
+                Span::new_at(b\"\", 22, 1, 23),
+                Statement::DoWhile {
+                    body     : vec![
+                        Statement::Expression(Expression::Variable(Variable(Span::new_at(b\"y\", 6, 1, 7))))
+                    ],
+                    condition: Expression::Variable(Variable(Span::new_at(b\"x\", 19, 1, 20)))
+                }
+            ))
+        );
+        # }
+        ```
+    "],
+    pub do_statement<Span, Statement>,
+    do_parse!(
+        statement_keyword!(tokens::DO) >>
+        body: first!(statement_body) >>
+        first!(statement_keyword!(tokens::WHILE)) >>
+        condition: first!(parenthesized_expression) >>
+        first!(required_terminator) >>
+        (
+            Statement::DoWhile {
+                body,
+                condition
+            }
+        )
+    )
+);
+
+named_attr!(
+    #[doc="
+        Recognize a `for` loop.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{ControlSyntax, Expression, Statement, Variable};
+        use tagua_parser::rules::statements::iteration::for_statement;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            for_statement(Span::new(b\"for ($i, $j; ; $k) {}\")),
+            Ok((
+                Span::new_at(b\"\", 21, 1, 22),
+                Statement::For {
+                    initializers: vec![
+                        Expression::Variable(Variable(Span::new_at(b\"i\", 6, 1, 7))),
+                        Expression::Variable(Variable(Span::new_at(b\"j\", 10, 1, 11)))
+                    ],
+                    conditions  : vec![],
+                    steps       : vec![
+                        Expression::Variable(Variable(Span::new_at(b\"k\", 16, 1, 17)))
+                    ],
+                    body        : vec![],
+                    syntax      : ControlSyntax::Standard
+                }
+            ))
+        );
+        # }
+        ```
+    "],
+    pub for_statement<Span, Statement>,
+    do_parse!(
+        statement_keyword!(tokens::FOR) >>
+        first!(tag!(tokens::LEFT_PARENTHESIS)) >>
+        initializers: for_expressions >>
+        first!(tag!(tokens::SEMICOLON)) >>
+        conditions: for_expressions >>
+        first!(tag!(tokens::SEMICOLON)) >>
+        steps: for_expressions >>
+        first!(tag!(tokens::RIGHT_PARENTHESIS)) >>
+        body: first!(call!(control_structure_body, tokens::ENDFOR)) >>
+        ({
+            let (body, syntax) = body;
+
+            Statement::For {
+                initializers,
+                conditions,
+                steps,
+                body,
+                syntax
+            }
+        })
+    )
+);
+
+named!(
+    for_expressions<Span, Vec<Expression>>,
+    map!(
+        opt!(
+            complete!(
+                do_parse!(
+                    head: first!(expression) >>
+                    expressions: fold_into_vector_many0!(
+                        preceded!(
+                            first!(tag!(tokens::COMMA)),
+                            first!(expression)
+                        ),
+                        vec![head]
+                    ) >>
+                    (expressions)
+                )
+            )
+        ),
+        Option::unwrap_or_default
+    )
+);
+
+named_attr!(
+    #[doc="
+        Recognize a `foreach` loop.
+
+        The value can be assigned by reference, e.g. `&$v`, or it can
+        be destructured, e.g. `list($x, $y)` or `[$x, $y]`.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{ControlSyntax, Expression, Statement, Variable};
+        use tagua_parser::rules::statements::iteration::foreach_statement;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            foreach_statement(Span::new(b\"foreach ($x as $v);\")),
+            Ok((
+                Span::new_at(b\"\", 19, 1, 20),
+                Statement::Foreach {
+                    subject: Expression::Variable(Variable(Span::new_at(b\"x\", 10, 1, 11))),
+                    key    : None,
+                    value  : Box::new(Expression::Variable(Variable(Span::new_at(b\"v\", 16, 1, 17)))),
+                    body   : vec![Statement::Empty],
+                    syntax : ControlSyntax::Standard
+                }
+            ))
+        );
+        # }
+        ```
+    "],
+    pub foreach_statement<Span, Statement>,
+    do_parse!(
+        statement_keyword!(tokens::FOREACH) >>
+        first!(tag!(tokens::LEFT_PARENTHESIS)) >>
+        subject: first!(expression) >>
+        first!(statement_keyword!(tokens::AS)) >>
+        key: opt!(
+            complete!(
+                terminated!(
+                    first!(expression),
+                    first!(tag!(tokens::MAP))
+                )
+            )
+        ) >>
+        value: first!(foreach_value) >>
+        first!(tag!(tokens::RIGHT_PARENTHESIS)) >>
+        body: first!(call!(control_structure_body, tokens::ENDFOREACH)) >>
+        ({
+            let (body, syntax) = body;
+
+            Statement::Foreach {
+                subject,
+                key: key.map(Box::new),
+                value: Box::new(value),
+                body,
+                syntax
+            }
+        })
+    )
+);
+
+named!(
+    foreach_value<Span, Expression>,
+    alt_complete!(
+        preceded!(
+            tag!(tokens::REFERENCE),
+            first!(expression)
+        ) => { |value| Expression::Reference(Box::new(value)) }
+      | map_opt!(expression, into_foreach_value)
+    )
+);
+
+/// An array as the value of a `foreach` loop is a destructuring target.
+#[inline]
+fn into_foreach_value(value: Expression) -> Option<Expression> {
+    match value {
+        Expression::Array(_) => into_destructuring_target(value),
+        value => Some(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::super::ast::{ControlSyntax, Expression, Statement, Variable};
+    use super::super::super::super::tokens::Span;
+    use super::super::statement;
+    use super::{
+        do_statement, for_statement, foreach_statement, iteration_statement, while_statement,
+    };
+
+    #[test]
+    fn case_while() {
+        let input = Span::new(b"while ($x) { $y; }");
+        let output = Ok((
+            Span::new_at(b"", 18, 1, 19),
+            Statement::While {
+                condition: Expression::Variable(Variable(Span::new_at(b"x", 8, 1, 9))),
+                body: vec![Statement::Expression(Expression::Variable(Variable(
+                    Span::new_at(b"y", 14, 1, 15),
+                )))],
+                syntax: ControlSyntax::Standard,
+            },
+        ));
+
+        assert_eq!(while_statement(input), output);
+        assert_eq!(iteration_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_while_with_an_empty_body() {
+        let input = Span::new(b"WHILE($x);");
+        let output = Ok((
+            Span::new_at(b"", 10, 1, 11),
+            Statement::While {
+                condition: Expression::Variable(Variable(Span::new_at(b"x", 7, 1, 8))),
+                body: vec![Statement::Empty],
+                syntax: ControlSyntax::Standard,
+            },
+        ));
+
+        assert_eq!(while_statement(input), output);
+        assert_eq!(iteration_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_do_while() {
+        let input = Span::new(b"do $y; while ($x) ?>");
+        let output = Ok((
+            Span::new_at(b"?>", 18, 1, 19),
+            Statement::DoWhile {
+                body: vec![Statement::Expression(Expression::Variable(Variable(
+                    Span::new_at(b"y", 4, 1, 5),
+                )))],
+                condition: Expression::Variable(Variable(Span::new_at(b"x", 14, 1, 15))),
+            },
+        ));
+
+        assert_eq!(do_statement(input), output);
+        assert_eq!(iteration_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_for() {
+        let input = Span::new(b"for ($i; $j; $k, $l) {}");
+        let output = Ok((
+            Span::new_at(b"", 23, 1, 24),
+            Statement::For {
+                initializers: vec![Expression::Variable(Variable(Span::new_at(b"i", 6, 1, 7)))],
+                conditions: vec![Expression::Variable(Variable(Span::new_at(
+                    b"j", 10, 1, 11,
+                )))],
+                steps: vec![
+                    Expression::Variable(Variable(Span::new_at(b"k", 14, 1, 15))),
+                    Expression::Variable(Variable(Span::new_at(b"l", 18, 1, 19))),
+                ],
+                body: vec![],
+                syntax: ControlSyntax::Standard,
+            },
+        ));
+
+        assert_eq!(for_statement(input), output);
+        assert_eq!(iteration_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_for_without_expressions() {
+        let input = Span::new(b"for (;;);");
+        let output = Ok((
+            Span::new_at(b"", 9, 1, 10),
+            Statement::For {
+                initializers: vec![],
+                conditions: vec![],
+                steps: vec![],
+                body: vec![Statement::Empty],
+                syntax: ControlSyntax::Standard,
+            },
+        ));
+
+        assert_eq!(for_statement(input), output);
+        assert_eq!(iteration_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_foreach_with_a_key() {
+        let input = Span::new(b"foreach ($x as $k => $v) {}");
+        let output = Ok((
+            Span::new_at(b"", 27, 1, 28),
+            Statement::Foreach {
+                subject: Expression::Variable(Variable(Span::new_at(b"x", 10, 1, 11))),
+                key: Some(Box::new(Expression::Variable(Variable(Span::new_at(
+                    b"k", 16, 1, 17,
+                ))))),
+                value: Box::new(Expression::Variable(Variable(Span::new_at(
+                    b"v", 22, 1, 23,
+                )))),
+                body: vec![],
+                syntax: ControlSyntax::Standard,
+            },
+        ));
+
+        assert_eq!(foreach_statement(input), output);
+        assert_eq!(iteration_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_foreach_by_reference() {
+        let input = Span::new(b"foreach ($x as $k => &$v) {}");
+        let output = Ok((
+            Span::new_at(b"", 28, 1, 29),
+            Statement::Foreach {
+                subject: Expression::Variable(Variable(Span::new_at(b"x", 10, 1, 11))),
+                key: Some(Box::new(Expression::Variable(Variable(Span::new_at(
+                    b"k", 16, 1, 17,
+                ))))),
+                value: Box::new(Expression::Reference(Box::new(Expression::Variable(
+                    Variable(Span::new_at(b"v", 23, 1, 24)),
+                )))),
+                body: vec![],
+                syntax: ControlSyntax::Standard,
+            },
+        ));
+
+        assert_eq!(foreach_statement(input), output);
+        assert_eq!(iteration_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_foreach_with_a_list() {
+        let input = Span::new(b"foreach ($x as list($a, $b)) {}");
+        let output = Ok((
+            Span::new_at(b"", 31, 1, 32),
+            Statement::Foreach {
+                subject: Expression::Variable(Variable(Span::new_at(b"x", 10, 1, 11))),
+                key: None,
+                value: Box::new(Expression::List(vec![
+                    Some((
+                        None,
+                        Expression::Variable(Variable(Span::new_at(b"a", 21, 1, 22))),
+                    )),
+                    Some((
+                        None,
+                        Expression::Variable(Variable(Span::new_at(b"b", 25, 1, 26))),
+                    )),
+                ])),
+                body: vec![],
+                syntax: ControlSyntax::Standard,
+            },
+        ));
+
+        assert_eq!(foreach_statement(input), output);
+        assert_eq!(iteration_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_foreach_with_a_short_list() {
+        let input = Span::new(b"foreach ($x as $k => [$a, [$b]]) {}");
+        let output = Ok((
+            Span::new_at(b"", 35, 1, 36),
+            Statement::Foreach {
+                subject: Expression::Variable(Variable(Span::new_at(b"x", 10, 1, 11))),
+                key: Some(Box::new(Expression::Variable(Variable(Span::new_at(
+                    b"k", 16, 1, 17,
+                ))))),
+                value: Box::new(Expression::List(vec![
+                    Some((
+                        None,
+                        Expression::Variable(Variable(Span::new_at(b"a", 23, 1, 24))),
+                    )),
+                    Some((
+                        None,
+                        Expression::List(vec![Some((
+                            None,
+                            Expression::Variable(Variable(Span::new_at(b"b", 28, 1, 29))),
+                        ))]),
+                    )),
+                ])),
+                body: vec![],
+                syntax: ControlSyntax::Standard,
+            },
+        ));
+
+        assert_eq!(foreach_statement(input), output);
+        assert_eq!(iteration_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_foreach_without_as() {
+        let input = Span::new(b"foreach ($x) {}");
+
+        assert!(foreach_statement(input).is_err());
+    }
+
+    #[test]
+    fn case_while_alternative_syntax() {
+        let input = Span::new(b"while ($x): endwhile;");
+        let output = Ok((
+            Span::new_at(b"", 21, 1, 22),
+            Statement::While {
+                condition: Expression::Variable(Variable(Span::new_at(b"x", 8, 1, 9))),
+                body: vec![],
+                syntax: ControlSyntax::Alternative,
+            },
+        ));
+
+        assert_eq!(while_statement(input), output);
+        assert_eq!(iteration_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_for_alternative_syntax() {
+        let input = Span::new(b"for (;;): $x; endfor;");
+        let output = Ok((
+            Span::new_at(b"", 21, 1, 22),
+            Statement::For {
+                initializers: vec![],
+                conditions: vec![],
+                steps: vec![],
+                body: vec![Statement::Expression(Expression::Variable(Variable(
+                    Span::new_at(b"x", 11, 1, 12),
+                )))],
+                syntax: ControlSyntax::Alternative,
+            },
+        ));
+
+        assert_eq!(for_statement(input), output);
+        assert_eq!(iteration_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_foreach_alternative_syntax() {
+        let input = Span::new(b"foreach ($x as $v): $v; endforeach ?>");
+        let output = Ok((
+            Span::new_at(b"?>", 35, 1, 36),
+            Statement::Foreach {
+                subject: Expression::Variable(Variable(Span::new_at(b"x", 10, 1, 11))),
+                key: None,
+                value: Box::new(Expression::Variable(Variable(Span::new_at(
+                    b"v", 16, 1, 17,
+                )))),
+                body: vec![Statement::Expression(Expression::Variable(Variable(
+                    Span::new_at(b"v", 21, 1, 22),
+                )))],
+                syntax: ControlSyntax::Alternative,
+            },
+        ));
+
+        assert_eq!(foreach_statement(input), output);
+        assert_eq!(iteration_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_foreach_alternative_syntax_with_a_wrong_end_keyword() {
+        let input = Span::new(b"foreach ($x as $v): endwhile;");
+
+        assert!(foreach_statement(input).is_err());
+    }
+}
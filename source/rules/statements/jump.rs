@@ -0,0 +1,338 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2017, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of jump statement rules.
+//!
+//! The list of all jump statements is provided by the PHP Language
+//! Specification in the [Grammar chapter, Jump Statements
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#jump-statements).
+
+use super::super::super::ast::{Literal, Statement};
+use super::super::super::internal::{Context, Error, ErrorKind, Result};
+use super::super::super::tokens;
+use super::super::super::tokens::{Span, Token};
+use super::super::expressions::expression;
+use super::super::literals::integer;
+use super::required_terminator;
+
+/// Jump errors.
+pub enum JumpError {
+    /// The level of a `break` or a `continue` is zero, while it must
+    /// be strictly positive.
+    LevelIsZero,
+}
+
+named_attr!(
+    #[doc="
+        Recognize all kind of jump statements.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{Expression, Statement, Variable};
+        use tagua_parser::rules::statements::jump::jump_statement;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            jump_statement(Span::new(b\"return $x;\")),
+            Ok((
+                Span::new_at(b\"\", 10, 1, 11),
+                Statement::Return(Some(Expression::Variable(Variable(Span::new_at(b\"x\", 8, 1, 9)))))
+            ))
+        );
+        # }
+        ```
+    "],
+    pub jump_statement<Span, Statement>,
+    alt_complete!(
+        break_statement
+      | continue_statement
+      | return_statement
+    )
+);
+
+named_attr!(
+    #[doc="
+        Recognize a break, with an optional level.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::Statement;
+        use tagua_parser::rules::statements::jump::break_statement;
+        use tagua_parser::tokens::{
+            Span,
+            Token
+        };
+
+        # fn main() {
+        assert_eq!(
+            break_statement(Span::new(b\"break 2;\")),
+            Ok((
+                Span::new_at(b\"\", 8, 1, 9),
+                Statement::Break(Some(Token::new(2i64, Span::new_at(b\"2\", 6, 1, 7))))
+            ))
+        );
+        # }
+        ```
+    "],
+    pub break_statement<Span, Statement>,
+    do_parse!(
+        statement_keyword!(tokens::BREAK) >>
+        level: first!(level) >>
+        first!(required_terminator) >>
+        (Statement::Break(level))
+    )
+);
+
+named_attr!(
+    #[doc="
+        Recognize a continue, with an optional level.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::Statement;
+        use tagua_parser::rules::statements::jump::continue_statement;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            continue_statement(Span::new(b\"continue;\")),
+            Ok((
+                Span::new_at(b\"\", 9, 1, 10),
+                Statement::Continue(None)
+            ))
+        );
+        # }
+        ```
+    "],
+    pub continue_statement<Span, Statement>,
+    do_parse!(
+        statement_keyword!(tokens::CONTINUE) >>
+        level: first!(level) >>
+        first!(required_terminator) >>
+        (Statement::Continue(level))
+    )
+);
+
+/// Recognize the optional level of a `break` or a `continue`. The
+/// level must be strictly positive, a zero level is a failure.
+fn level(input: Span) -> Result<Span, Option<Token<i64>>> {
+    match integer(input) {
+        Ok((_, Literal::Integer(Token { value: 0, .. }))) => Err(Error::Failure(Context::Code(
+            input,
+            ErrorKind::Custom(JumpError::LevelIsZero as u32),
+        ))),
+
+        Ok((input, Literal::Integer(token))) => Ok((input, Some(token))),
+
+        _ => Ok((input, None)),
+    }
+}
+
+named_attr!(
+    #[doc="
+        Recognize a return, with an optional value.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::Statement;
+        use tagua_parser::rules::statements::jump::return_statement;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            return_statement(Span::new(b\"return;\")),
+            Ok((
+                Span::new_at(b\"\", 7, 1, 8),
+                Statement::Return(None)
+            ))
+        );
+        # }
+        ```
+    "],
+    pub return_statement<Span, Statement>,
+    do_parse!(
+        statement_keyword!(tokens::RETURN) >>
+        value: opt!(complete!(first!(expression))) >>
+        first!(required_terminator) >>
+        (Statement::Return(value))
+    )
+);
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::super::ast::{Expression, Statement, Variable};
+    use super::super::super::super::internal::{Context, Error, ErrorKind};
+    use super::super::super::super::tokens::{Span, Token};
+    use super::super::statement;
+    use super::{break_statement, continue_statement, jump_statement, return_statement, JumpError};
+
+    #[test]
+    fn case_break() {
+        let input = Span::new(b"break;");
+        let output = Ok((Span::new_at(b"", 6, 1, 7), Statement::Break(None)));
+
+        assert_eq!(break_statement(input), output);
+        assert_eq!(jump_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_break_with_a_level() {
+        let input = Span::new(b"break 2 ;");
+        let output = Ok((
+            Span::new_at(b"", 9, 1, 10),
+            Statement::Break(Some(Token::new(2i64, Span::new_at(b"2", 6, 1, 7)))),
+        ));
+
+        assert_eq!(break_statement(input), output);
+        assert_eq!(jump_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_break_before_a_close_tag() {
+        let input = Span::new(b"break ?>");
+        let output = Ok((Span::new_at(b"?>", 6, 1, 7), Statement::Break(None)));
+
+        assert_eq!(break_statement(input), output);
+        assert_eq!(jump_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_break_with_a_zero_level() {
+        let input = Span::new(b"break 0;");
+        let output = Err(Error::Failure(Context::Code(
+            Span::new_at(b"0;", 6, 1, 7),
+            ErrorKind::Custom(JumpError::LevelIsZero as u32),
+        )));
+
+        assert_eq!(break_statement(input), output);
+        assert_eq!(jump_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_break_without_terminator() {
+        let input = Span::new(b"break $x;");
+
+        assert_eq!(
+            break_statement(input),
+            Err(Error::Failure(Context::Code(
+                Span::new_at(b"$x;", 6, 1, 7),
+                ErrorKind::Alt
+            )))
+        );
+    }
+
+    #[test]
+    fn case_continue() {
+        let input = Span::new(b"continue;");
+        let output = Ok((Span::new_at(b"", 9, 1, 10), Statement::Continue(None)));
+
+        assert_eq!(continue_statement(input), output);
+        assert_eq!(jump_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_continue_with_a_level() {
+        let input = Span::new(b"CONTINUE 0x3;");
+        let output = Ok((
+            Span::new_at(b"", 13, 1, 14),
+            Statement::Continue(Some(Token::new(3i64, Span::new_at(b"0x3", 9, 1, 10)))),
+        ));
+
+        assert_eq!(continue_statement(input), output);
+        assert_eq!(jump_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_continue_with_a_zero_level() {
+        let input = Span::new(b"continue 0;");
+        let output = Err(Error::Failure(Context::Code(
+            Span::new_at(b"0;", 9, 1, 10),
+            ErrorKind::Custom(JumpError::LevelIsZero as u32),
+        )));
+
+        assert_eq!(continue_statement(input), output);
+        assert_eq!(jump_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_return() {
+        let input = Span::new(b"return;");
+        let output = Ok((Span::new_at(b"", 7, 1, 8), Statement::Return(None)));
+
+        assert_eq!(return_statement(input), output);
+        assert_eq!(jump_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_return_with_a_value() {
+        let input = Span::new(b"return($x);");
+        let output = Ok((
+            Span::new_at(b"", 11, 1, 12),
+            Statement::Return(Some(Expression::Variable(Variable(Span::new_at(
+                b"x", 8, 1, 9,
+            ))))),
+        ));
+
+        assert_eq!(return_statement(input), output);
+        assert_eq!(jump_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_return_is_not_a_prefix_of_a_name() {
+        let input = Span::new(b"returned;");
+
+        assert_eq!(
+            return_statement(input),
+            Err(Error::Error(Context::Code(
+                Span::new_at(b"ed;", 6, 1, 7),
+                ErrorKind::Not
+            )))
+        );
+    }
+}
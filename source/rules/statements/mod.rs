@@ -35,37 +35,982 @@
 //! in the [Grammar chapter, Statements
 //! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#statements).
 
+macro_rules! statement_keyword {
+    ($input:expr, $keyword:expr) => {
+        terminated!(
+            $input,
+            keyword!($keyword),
+            not!(regex!(r"(?-u)^[a-zA-Z0-9_\x80-\xff]"))
+        )
+    };
+}
+
+pub mod class;
+pub mod exception;
 pub mod function;
+pub mod iteration;
+pub mod jump;
+pub mod namespace;
+pub mod selection;
 
-use super::super::ast::Statement;
+use super::super::ast::{ControlSyntax, Expression, Statement, Variable};
+use super::super::internal::{Error, Result};
 use super::super::tokens;
 use super::super::tokens::Span;
+use super::expressions::constant::constant_expression;
+use super::expressions::expression;
+use super::tokens::{name, variable};
+use nom::{InputLength, Slice};
 
 named_attr!(
     #[doc="
-        Recognize a group of statements.
+        Recognize a group of statements, delimited by curly braces.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{Expression, Statement, Variable};
+        use tagua_parser::rules::statements::compound_statement;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            compound_statement(Span::new(b\"{ $x; return; }\")),
+            Ok((
+                Span::new_at(b\"\", 15, 1, 16),
+                vec![
+                    Statement::Expression(Expression::Variable(Variable(Span::new_at(b\"x\", 3, 1, 4)))),
+                    Statement::Return(None)
+                ]
+            ))
+        );
+        # }
+        ```
     "],
     pub compound_statement<Span, Vec<Statement>>,
-    map_res!(
-        terminated!(
-            preceded!(
-                tag!(tokens::LEFT_CURLY_BRACKET),
-                opt!(first!(tag!(b"return;")))
-            ),
-            first!(tag!(tokens::RIGHT_CURLY_BRACKET))
+    delimited!(
+        tag!(tokens::LEFT_CURLY_BRACKET),
+        fold_into_vector_many0!(
+            first!(statement),
+            Vec::new()
         ),
-        |_| -> Result<Vec<Statement>, ()> {
-            Ok(vec![Statement::Return])
-        }
+        first!(compound_statement_end)
     )
 );
 
-named!(
+/// Recognize the closing curly bracket of a compound statement. Once
+/// the opening one is recognized, a missing closing curly bracket is
+/// a failure, so that the error is reported where the statements
+/// stop, and not where the block starts.
+fn compound_statement_end(input: Span) -> Result<Span, Span> {
+    match tag!(input, tokens::RIGHT_CURLY_BRACKET) {
+        Err(Error::Error(context)) => Err(Error::Failure(context)),
+        result => result,
+    }
+}
+
+named_attr!(
     #[doc="
         Recognize a statement.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{ControlSyntax, Expression, Statement, Variable};
+        use tagua_parser::rules::statements::statement;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            statement(Span::new(b\"while ($x) { $y; }\")),
+            Ok((
+                Span::new_at(b\"\", 18, 1, 19),
+                Statement::While {
+                    condition: Expression::Variable(Variable(Span::new_at(b\"x\", 8, 1, 9))),
+                    body     : vec![
+                        Statement::Expression(Expression::Variable(Variable(Span::new_at(b\"y\", 14, 1, 15))))
+                    ],
+                    syntax   : ControlSyntax::Standard
+                }
+            ))
+        );
+        # }
+        ```
     "],
     pub statement<Span, Statement>,
-    alt!(
-        call!(function::function)
+    alt_complete!(
+        compound_statement => { Statement::Block }
+      | selection::selection_statement
+      | iteration::iteration_statement
+      | jump::jump_statement
+      | exception::try_statement
+      | function::function
+      | class::class
+      | class::interface
+      | class::trait_declaration
+      | class::enum_declaration
+      | declare_statement
+      | namespace::namespace_declaration
+      | namespace::use_declaration
+      | global_declaration
+      | static_declaration
+      | inline_html
+      | echo_tag
+      | tag!(tokens::SEMICOLON) => { |_| Statement::Empty }
+      | expression_statement
+    )
+);
+
+/// Recognize the body of a control structure, i.e. a statement. A
+/// group of statements is flattened, so that `while ($x) $y;` and
+/// `while ($x) { $y; }` have the same body.
+fn statement_body(input: Span) -> Result<Span, Vec<Statement>> {
+    match statement(input) {
+        Ok((input, Statement::Block(statements))) => Ok((input, statements)),
+        Ok((input, statement)) => Ok((input, vec![statement])),
+        Err(error) => Err(error),
+    }
+}
+
+/// Recognize the body of a control structure, either in the standard
+/// syntax, or in the alternative syntax, i.e. statements between a
+/// colon and the given end keyword, e.g. `while ($x): $y; endwhile;`.
+fn control_structure_body<'a>(
+    input: Span<'a>,
+    end_keyword: &'static [u8],
+) -> Result<Span<'a>, (Vec<Statement<'a>>, ControlSyntax)> {
+    alt_complete!(
+        input,
+        statement_body => { |body| (body, ControlSyntax::Standard) }
+      | do_parse!(
+            body: alternative_block >>
+            first!(statement_keyword!(end_keyword)) >>
+            first!(required_terminator) >>
+            (body)
+        ) => { |body| (body, ControlSyntax::Alternative) }
+    )
+}
+
+named!(
+    alternative_block<Span, Vec<Statement>>,
+    preceded!(
+        tag!(tokens::ALTERNATIVE_BLOCK_OPEN),
+        fold_into_vector_many0!(
+            first!(statement),
+            Vec::new()
+        )
+    )
+);
+
+named_attr!(
+    #[doc="
+        Recognize an expression used as a statement.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{Expression, Statement, Variable};
+        use tagua_parser::rules::statements::expression_statement;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            expression_statement(Span::new(b\"echo $x;\")),
+            Ok((
+                Span::new_at(b\"\", 8, 1, 9),
+                Statement::Expression(Expression::Echo(vec![Expression::Variable(Variable(Span::new_at(b\"x\", 6, 1, 7)))]))
+            ))
+        );
+        # }
+        ```
+    "],
+    pub expression_statement<Span, Statement>,
+    do_parse!(
+        expression: expression >>
+        first!(required_terminator) >>
+        (Statement::Expression(expression))
+    )
+);
+
+named_attr!(
+    #[doc="
+        Recognize a `declare` statement.
+
+        The value of a directive is a constant expression, but the name
+        of a directive is not checked by the parser.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{ControlSyntax, Expression, Literal, Statement};
+        use tagua_parser::rules::statements::declare_statement;
+        use tagua_parser::tokens::{
+            Span,
+            Token
+        };
+
+        # fn main() {
+        assert_eq!(
+            declare_statement(Span::new(b\"declare(ticks=1): enddeclare;\")),
+            Ok((
+                Span::new_at(b\"\", 29, 1, 30),
+                Statement::Declare {
+                    directives: vec![
+                        (
+                            Span::new_at(b\"ticks\", 8, 1, 9),
+                            Expression::Literal(Literal::Integer(Token::new(1i64, Span::new_at(b\"1\", 14, 1, 15))))
+                        )
+                    ],
+                    body      : vec![],
+                    syntax    : ControlSyntax::Alternative
+                }
+            ))
+        );
+        # }
+        ```
+    "],
+    pub declare_statement<Span, Statement>,
+    do_parse!(
+        statement_keyword!(tokens::DECLARE) >>
+        first!(tag!(tokens::LEFT_PARENTHESIS)) >>
+        head: first!(declare_directive) >>
+        directives: fold_into_vector_many0!(
+            preceded!(
+                first!(tag!(tokens::COMMA)),
+                first!(declare_directive)
+            ),
+            vec![head]
+        ) >>
+        first!(tag!(tokens::RIGHT_PARENTHESIS)) >>
+        body: first!(call!(control_structure_body, tokens::ENDDECLARE)) >>
+        ({
+            let (body, syntax) = body;
+
+            Statement::Declare {
+                directives,
+                body,
+                syntax
+            }
+        })
+    )
+);
+
+named!(
+    declare_directive<Span, (Span, Expression)>,
+    do_parse!(
+        name: name >>
+        first!(tag!(tokens::ASSIGN)) >>
+        value: first!(constant_expression) >>
+        ((name, value))
+    )
+);
+
+named_attr!(
+    #[doc="
+        Recognize a global declaration.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{Statement, Variable};
+        use tagua_parser::rules::statements::global_declaration;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            global_declaration(Span::new(b\"global $x, $y;\")),
+            Ok((
+                Span::new_at(b\"\", 14, 1, 15),
+                Statement::Global(vec![
+                    Variable(Span::new_at(b\"x\", 8, 1, 9)),
+                    Variable(Span::new_at(b\"y\", 12, 1, 13))
+                ])
+            ))
+        );
+        # }
+        ```
+    "],
+    pub global_declaration<Span, Statement>,
+    do_parse!(
+        statement_keyword!(tokens::GLOBAL) >>
+        head: first!(variable) >>
+        variables: fold_into_vector_many0!(
+            preceded!(
+                first!(tag!(tokens::COMMA)),
+                first!(variable)
+            ),
+            vec![head]
+        ) >>
+        first!(required_terminator) >>
+        (Statement::Global(variables))
     )
 );
+
+named_attr!(
+    #[doc="
+        Recognize a static variables declaration, in a function.
+
+        The initial value is expected to be a constant expression, but
+        this is not checked by the parser.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{Expression, Literal, Statement, Variable};
+        use tagua_parser::rules::statements::static_declaration;
+        use tagua_parser::tokens::{
+            Span,
+            Token
+        };
+
+        # fn main() {
+        assert_eq!(
+            static_declaration(Span::new(b\"static $x = 42, $y;\")),
+            Ok((
+                Span::new_at(b\"\", 19, 1, 20),
+                Statement::Static(vec![
+                    (
+                        Variable(Span::new_at(b\"x\", 8, 1, 9)),
+                        Some(Expression::Literal(Literal::Integer(Token::new(42i64, Span::new_at(b\"42\", 12, 1, 13)))))
+                    ),
+                    (
+                        Variable(Span::new_at(b\"y\", 17, 1, 18)),
+                        None
+                    )
+                ])
+            ))
+        );
+        # }
+        ```
+    "],
+    pub static_declaration<Span, Statement>,
+    do_parse!(
+        statement_keyword!(tokens::STATIC) >>
+        head: first!(static_variable) >>
+        variables: fold_into_vector_many0!(
+            preceded!(
+                first!(tag!(tokens::COMMA)),
+                first!(static_variable)
+            ),
+            vec![head]
+        ) >>
+        first!(required_terminator) >>
+        (Statement::Static(variables))
+    )
+);
+
+named!(
+    static_variable<Span, (Variable, Option<Expression>)>,
+    do_parse!(
+        variable: variable >>
+        value: opt!(
+            complete!(
+                preceded!(
+                    first!(tag!(tokens::ASSIGN)),
+                    first!(expression)
+                )
+            )
+        ) >>
+        ((variable, value))
+    )
+);
+
+named_attr!(
+    #[doc="
+        Recognize the end of a statement.
+
+        A statement ends with a semicolon, or with a close tag (`?>`)
+        which acts as an implicit semicolon. In the latter case, the
+        close tag is not consumed because it starts an inline HTML
+        statement.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::rules::statements::terminator;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            terminator(Span::new(b\"?>\")),
+            Ok((
+                Span::new(b\"?>\"),
+                Span::new(b\"?>\")
+            ))
+        );
+        # }
+        ```
+    "],
+    pub terminator<Span, Span>,
+    alt_complete!(
+        tag!(tokens::SEMICOLON)
+      | peek!(tag!(tokens::CLOSE_TAG))
+    )
+);
+
+/// Recognize the end of a statement that has been recognized up to
+/// its terminator. A missing terminator is a failure, like a missing
+/// closing curly bracket in `compound_statement`.
+fn required_terminator(input: Span) -> Result<Span, Span> {
+    match terminator(input) {
+        Err(Error::Error(context)) => Err(Error::Failure(context)),
+        result => result,
+    }
+}
+
+named_attr!(
+    #[doc="
+        Recognize an open tag, i.e. `<?php` followed by a whitespace or
+        the end of the input. The open tag is case-insensitive.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::rules::statements::open_tag;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            open_tag(Span::new(b\"<?PHP\\necho\")),
+            Ok((
+                Span::new_at(b\"echo\", 6, 2, 1),
+                Span::new(b\"<?php\")
+            ))
+        );
+        # }
+        ```
+    "],
+    pub open_tag<Span, Span>,
+    terminated!(
+        keyword!(tokens::OPEN_TAG),
+        alt_complete!(
+            tag!(b"\r\n")
+          | tag!(b"\n")
+          | tag!(b"\r")
+          | tag!(b" ")
+          | tag!(b"\t")
+          | eof!()
+        )
+    )
+);
+
+named_attr!(
+    #[doc="
+        Recognize the inline HTML starting a file, i.e. everything
+        before the first open tag, which is consumed too.
+
+        Since a file does not necessarily start with inline HTML,
+        the resulting span can be empty.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::Statement;
+        use tagua_parser::rules::statements::leading_inline_html;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            leading_inline_html(Span::new(b\"<b>Hello</b>\\n<?php echo\")),
+            Ok((
+                Span::new_at(b\"echo\", 19, 2, 7),
+                Statement::InlineHtml(Span::new(b\"<b>Hello</b>\\n\"))
+            ))
+        );
+        # }
+        ```
+    "],
+    pub leading_inline_html<Span, Statement>,
+    map!(
+        terminated!(
+            html,
+            opt!(complete!(open_tag))
+        ),
+        Statement::InlineHtml
+    )
+);
+
+named_attr!(
+    #[doc="
+        Recognize inline HTML, i.e. everything between a close tag
+        (`?>`) and the next open tag (`<?php` or `<?=`), or the end of
+        the input.
+
+        One single newline directly following the close tag is
+        consumed but it is not part of the inline HTML. The `<?php` open
+        tag is consumed, while the `<?=` open tag is not, because it
+        starts an echo statement.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::Statement;
+        use tagua_parser::rules::statements::inline_html;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            inline_html(Span::new(b\"?>\\n<b>Hello</b>\\n<?php echo\")),
+            Ok((
+                Span::new_at(b\"echo\", 22, 3, 7),
+                Statement::InlineHtml(Span::new_at(b\"<b>Hello</b>\\n\", 3, 2, 1))
+            ))
+        );
+        # }
+        ```
+    "],
+    pub inline_html<Span, Statement>,
+    preceded!(
+        terminated!(
+            tag!(tokens::CLOSE_TAG),
+            opt!(
+                alt_complete!(
+                    tag!(b"\r\n")
+                  | tag!(b"\n")
+                  | tag!(b"\r")
+                )
+            )
+        ),
+        leading_inline_html
+    )
+);
+
+/// Recognize raw text up to the next open tag, or up to the end of
+/// the input. The open tag is not consumed.
+fn html(span: Span) -> Result<Span, Span> {
+    let input = span.as_slice();
+    let input_length = span.input_len();
+    let mut index = 0;
+
+    while index < input_length {
+        if input[index] == b'<' {
+            let rest = span.slice(index..);
+
+            if input[index..].starts_with(tokens::OPEN_TAG_WITH_ECHO) || open_tag(rest).is_ok() {
+                break;
+            }
+        }
+
+        index += 1;
+    }
+
+    Ok((span.slice(index..), span.slice(..index)))
+}
+
+named_attr!(
+    #[doc="
+        Recognize an open tag with echo, i.e. `<?= $x, $y ?>`, which is
+        strictly equivalent to `<?php echo $x, $y ?>`.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{Expression, Statement, Variable};
+        use tagua_parser::rules::statements::echo_tag;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            echo_tag(Span::new(b\"<?= $x, $y ?>\")),
+            Ok((
+                Span::new_at(b\"?>\", 11, 1, 12),
+                Statement::Expression(
+                    Expression::Echo(vec![
+                        Expression::Variable(Variable(Span::new_at(b\"x\", 5, 1, 6))),
+                        Expression::Variable(Variable(Span::new_at(b\"y\", 9, 1, 10)))
+                    ])
+                )
+            ))
+        );
+        # }
+        ```
+    "],
+    pub echo_tag<Span, Statement>,
+    do_parse!(
+        tag!(tokens::OPEN_TAG_WITH_ECHO) >>
+        head: first!(expression) >>
+        expressions: fold_into_vector_many0!(
+            preceded!(
+                first!(tag!(tokens::COMMA)),
+                first!(expression)
+            ),
+            vec![head]
+        ) >>
+        first!(required_terminator) >>
+        (Statement::Expression(Expression::Echo(expressions)))
+    )
+);
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::ast::{ControlSyntax, Expression, Literal, Statement, Variable};
+    use super::super::super::internal::{Context, Error, ErrorKind};
+    use super::super::super::tokens::{Span, Token};
+    use super::{
+        compound_statement, declare_statement, echo_tag, expression_statement, global_declaration,
+        inline_html, leading_inline_html, open_tag, statement, static_declaration, terminator,
+    };
+    use std::borrow::Cow;
+
+    #[test]
+    fn case_open_tag() {
+        let input = Span::new(b"<?php echo");
+        let output = Ok((Span::new_at(b"echo", 6, 1, 7), Span::new(b"<?php")));
+
+        assert_eq!(open_tag(input), output);
+    }
+
+    #[test]
+    fn case_open_tag_with_carriage_return_line_feed() {
+        let input = Span::new(b"<?php\r\necho");
+        let output = Ok((Span::new_at(b"echo", 7, 2, 1), Span::new(b"<?php")));
+
+        assert_eq!(open_tag(input), output);
+    }
+
+    #[test]
+    fn case_open_tag_at_the_end() {
+        let input = Span::new(b"<?php");
+        let output = Ok((Span::new_at(b"", 5, 1, 6), Span::new(b"<?php")));
+
+        assert_eq!(open_tag(input), output);
+    }
+
+    #[test]
+    fn case_invalid_open_tag_without_whitespace() {
+        let input = Span::new(b"<?phpecho");
+        let output = Err(Error::Error(Context::Code(
+            Span::new_at(b"echo", 5, 1, 6),
+            ErrorKind::Alt,
+        )));
+
+        assert_eq!(open_tag(input), output);
+    }
+
+    #[test]
+    fn case_terminator_semicolon() {
+        let input = Span::new(b";?>");
+        let output = Ok((Span::new_at(b"?>", 1, 1, 2), Span::new(b";")));
+
+        assert_eq!(terminator(input), output);
+    }
+
+    #[test]
+    fn case_terminator_close_tag() {
+        let input = Span::new(b"?>");
+        let output = Ok((Span::new(b"?>"), Span::new(b"?>")));
+
+        assert_eq!(terminator(input), output);
+    }
+
+    #[test]
+    fn case_leading_inline_html_empty() {
+        let input = Span::new(b"<?php echo");
+        let output = Ok((
+            Span::new_at(b"echo", 6, 1, 7),
+            Statement::InlineHtml(Span::new(b"")),
+        ));
+
+        assert_eq!(leading_inline_html(input), output);
+    }
+
+    #[test]
+    fn case_leading_inline_html_without_open_tag() {
+        let input = Span::new(b"<?xml version=\"1.0\"?><?phpx");
+        let output = Ok((Span::new_at(b"", 27, 1, 28), Statement::InlineHtml(input)));
+
+        assert_eq!(leading_inline_html(input), output);
+    }
+
+    #[test]
+    fn case_inline_html() {
+        let input = Span::new(b"?>foo<?php bar");
+        let output = Ok((
+            Span::new_at(b"bar", 11, 1, 12),
+            Statement::InlineHtml(Span::new_at(b"foo", 2, 1, 3)),
+        ));
+
+        assert_eq!(inline_html(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_inline_html_swallows_one_newline_only() {
+        let input = Span::new(b"?>\n\nfoo");
+        let output = Ok((
+            Span::new_at(b"", 7, 3, 4),
+            Statement::InlineHtml(Span::new_at(b"\nfoo", 3, 2, 1)),
+        ));
+
+        assert_eq!(inline_html(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_inline_html_swallows_carriage_return_line_feed() {
+        let input = Span::new(b"?>\r\nfoo");
+        let output = Ok((
+            Span::new_at(b"", 7, 2, 4),
+            Statement::InlineHtml(Span::new_at(b"foo", 4, 2, 1)),
+        ));
+
+        assert_eq!(inline_html(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_inline_html_before_open_tag_with_echo() {
+        let input = Span::new(b"?>foo<?= $x ?>");
+        let output = Ok((
+            Span::new_at(b"<?= $x ?>", 5, 1, 6),
+            Statement::InlineHtml(Span::new_at(b"foo", 2, 1, 3)),
+        ));
+
+        assert_eq!(inline_html(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_echo_tag() {
+        let input = Span::new(b"<?=$x;");
+        let output = Ok((
+            Span::new_at(b"", 6, 1, 7),
+            Statement::Expression(Expression::Echo(vec![Expression::Variable(Variable(
+                Span::new_at(b"x", 4, 1, 5),
+            ))])),
+        ));
+
+        assert_eq!(echo_tag(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_echo_tag_without_terminator() {
+        let input = Span::new(b"<?= $x");
+
+        assert!(echo_tag(input).is_err());
+    }
+
+    #[test]
+    fn case_compound_statement_empty() {
+        let input = Span::new(b"{}");
+        let output = Ok((Span::new_at(b"", 2, 1, 3), vec![]));
+
+        assert_eq!(compound_statement(input), output);
+        assert_eq!(
+            statement(input),
+            Ok((Span::new_at(b"", 2, 1, 3), Statement::Block(vec![])))
+        );
+    }
+
+    #[test]
+    fn case_compound_statement_nested() {
+        let input = Span::new(b"{ $x; { ; } }");
+        let output = Ok((
+            Span::new_at(b"", 13, 1, 14),
+            vec![
+                Statement::Expression(Expression::Variable(Variable(Span::new_at(b"x", 3, 1, 4)))),
+                Statement::Block(vec![Statement::Empty]),
+            ],
+        ));
+
+        assert_eq!(compound_statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_compound_statement_not_closed() {
+        let input = Span::new(b"{ $x; )");
+        let output = Err(Error::Failure(Context::Code(
+            Span::new_at(b")", 6, 1, 7),
+            ErrorKind::Tag,
+        )));
+
+        assert_eq!(compound_statement(input), output);
+    }
+
+    #[test]
+    fn case_empty_statement() {
+        let input = Span::new(b";");
+        let output = Ok((Span::new_at(b"", 1, 1, 2), Statement::Empty));
+
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_expression_statement() {
+        let input = Span::new(b"echo 1, $x;");
+        let output = Ok((
+            Span::new_at(b"", 11, 1, 12),
+            Statement::Expression(Expression::Echo(vec![
+                Expression::Literal(Literal::Integer(Token::new(
+                    1i64,
+                    Span::new_at(b"1", 5, 1, 6),
+                ))),
+                Expression::Variable(Variable(Span::new_at(b"x", 9, 1, 10))),
+            ])),
+        ));
+
+        assert_eq!(expression_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_expression_statement_before_close_tag() {
+        let input = Span::new(b"$x ?>");
+        let output = Ok((
+            Span::new_at(b"?>", 3, 1, 4),
+            Statement::Expression(Expression::Variable(Variable(Span::new(b"x")))),
+        ));
+
+        assert_eq!(expression_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_expression_statement_without_terminator() {
+        let input = Span::new(b"$x $y");
+        let output = Err(Error::Failure(Context::Code(
+            Span::new_at(b"$y", 3, 1, 4),
+            ErrorKind::Alt,
+        )));
+
+        assert_eq!(expression_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_global_declaration() {
+        let input = Span::new(b"GLOBAL $x;");
+        let output = Ok((
+            Span::new_at(b"", 10, 1, 11),
+            Statement::Global(vec![Variable(Span::new_at(b"x", 8, 1, 9))]),
+        ));
+
+        assert_eq!(global_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_global_declaration_without_variables() {
+        let input = Span::new(b"global;");
+
+        assert!(global_declaration(input).is_err());
+    }
+
+    #[test]
+    fn case_static_declaration() {
+        let input = Span::new(b"static $x, $y = 7;");
+        let output = Ok((
+            Span::new_at(b"", 18, 1, 19),
+            Statement::Static(vec![
+                (Variable(Span::new_at(b"x", 8, 1, 9)), None),
+                (
+                    Variable(Span::new_at(b"y", 12, 1, 13)),
+                    Some(Expression::Literal(Literal::Integer(Token::new(
+                        7i64,
+                        Span::new_at(b"7", 16, 1, 17),
+                    )))),
+                ),
+            ]),
+        ));
+
+        assert_eq!(static_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_declare_statement() {
+        let input = Span::new(b"declare(ticks=1, encoding='UTF-8') { $x; }");
+        let output = Ok((
+            Span::new_at(b"", 42, 1, 43),
+            Statement::Declare {
+                directives: vec![
+                    (
+                        Span::new_at(b"ticks", 8, 1, 9),
+                        Expression::Literal(Literal::Integer(Token::new(
+                            1i64,
+                            Span::new_at(b"1", 14, 1, 15),
+                        ))),
+                    ),
+                    (
+                        Span::new_at(b"encoding", 17, 1, 18),
+                        Expression::Literal(Literal::String(Token::new(
+                            Cow::from(&b"UTF-8"[..]),
+                            Span::new_at(b"'UTF-8'", 26, 1, 27),
+                        ))),
+                    ),
+                ],
+                body: vec![Statement::Expression(Expression::Variable(Variable(
+                    Span::new_at(b"x", 38, 1, 39),
+                )))],
+                syntax: ControlSyntax::Standard,
+            },
+        ));
+
+        assert_eq!(declare_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_declare_statement_alternative_syntax() {
+        let input = Span::new(b"declare(ticks=1): $x; enddeclare;");
+        let output = Ok((
+            Span::new_at(b"", 33, 1, 34),
+            Statement::Declare {
+                directives: vec![(
+                    Span::new_at(b"ticks", 8, 1, 9),
+                    Expression::Literal(Literal::Integer(Token::new(
+                        1i64,
+                        Span::new_at(b"1", 14, 1, 15),
+                    ))),
+                )],
+                body: vec![Statement::Expression(Expression::Variable(Variable(
+                    Span::new_at(b"x", 19, 1, 20),
+                )))],
+                syntax: ControlSyntax::Alternative,
+            },
+        ));
+
+        assert_eq!(declare_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_declare_statement_without_directives() {
+        let input = Span::new(b"declare() {}");
+
+        assert!(declare_statement(input).is_err());
+    }
+
+    #[test]
+    fn case_invalid_declare_statement_with_a_variable_value() {
+        let input = Span::new(b"declare(ticks=$x);");
+
+        assert!(declare_statement(input).is_err());
+    }
+
+    #[test]
+    fn case_throw_statement() {
+        let input = Span::new(b"throw $e;");
+        let output = Ok((
+            Span::new_at(b"", 9, 1, 10),
+            Statement::Expression(Expression::Throw(Box::new(Expression::Variable(Variable(
+                Span::new_at(b"e", 7, 1, 8),
+            ))))),
+        ));
+
+        assert_eq!(expression_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+}
@@ -0,0 +1,508 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2017, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of namespace rules.
+//!
+//! The namespace declaration and the `use` declaration are provided by
+//! the PHP Language Specification in the [Grammar chapter, Namespaces
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#namespaces).
+
+use super::super::super::ast::{Name, Statement, Use, UseKind};
+use super::super::super::tokens;
+use super::super::super::tokens::Span;
+use super::super::tokens::{name, qualified_name};
+use super::{compound_statement, required_terminator, terminator};
+use smallvec::SmallVec;
+use std::result::Result as StdResult;
+
+named_attr!(
+    #[doc="
+        Recognize a namespace declaration, either followed by a
+        terminator, or by a body. A namespace with a body can have no
+        name, then it represents the global namespace.
+
+        # Examples
+
+        ```
+        # extern crate smallvec;
+        # #[macro_use]
+        # extern crate tagua_parser;
+        use tagua_parser::Result;
+        use tagua_parser::ast::Statement;
+        use tagua_parser::rules::statements::namespace::namespace_declaration;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            namespace_declaration(Span::new(b\"namespace Foo\\\\Bar;\")),
+            Ok((
+                Span::new_at(b\"\", 18, 1, 19),
+                Statement::Namespace {
+                    name: Some(smallvec![
+                        Span::new_at(b\"Foo\", 10, 1, 11),
+                        Span::new_at(b\"Bar\", 14, 1, 15)
+                    ]),
+                    body: None
+                }
+            ))
+        );
+        # }
+        ```
+    "],
+    pub namespace_declaration<Span, Statement>,
+    do_parse!(
+        statement_keyword!(tokens::NAMESPACE) >>
+        declaration: alt_complete!(
+            do_parse!(
+                name: first!(namespace_name) >>
+                body: alt_complete!(
+                    first!(compound_statement) => { Some }
+                  | first!(terminator) => { |_| None }
+                ) >>
+                (Some(name), body)
+            )
+          | first!(compound_statement) => { |body| (None, Some(body)) }
+        ) >>
+        (
+            Statement::Namespace {
+                name: declaration.0,
+                body: declaration.1
+            }
+        )
+    )
+);
+
+named!(
+    namespace_name<Span, SmallVec<[Span; 5]>>,
+    map_res!(qualified_name, namespace_name_mapper)
+);
+
+#[inline]
+fn namespace_name_mapper(name: Name) -> StdResult<SmallVec<[Span; 5]>, ()> {
+    match name {
+        Name::Unqualified(name) => Ok(smallvec![name]),
+        Name::Qualified(names) => Ok(names),
+        _ => Err(()),
+    }
+}
+
+named_attr!(
+    #[doc="
+        Recognize a `use` declaration, i.e. imports of classes,
+        functions or constants, with optional aliases. A group use,
+        like `use A\\{B, C};`, is flattened.
+
+        # Examples
+
+        ```
+        # extern crate smallvec;
+        # #[macro_use]
+        # extern crate tagua_parser;
+        use tagua_parser::Result;
+        use tagua_parser::ast::{Statement, Use, UseKind};
+        use tagua_parser::rules::statements::namespace::use_declaration;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            use_declaration(Span::new(b\"use const A\\\\B as C, D;\")),
+            Ok((
+                Span::new_at(b\"\", 22, 1, 23),
+                Statement::Use(vec![
+                    Use {
+                        kind : UseKind::Constant,
+                        name : smallvec![Span::new_at(b\"A\", 10, 1, 11), Span::new_at(b\"B\", 12, 1, 13)],
+                        alias: Some(Span::new_at(b\"C\", 17, 1, 18))
+                    },
+                    Use {
+                        kind : UseKind::Constant,
+                        name : smallvec![Span::new_at(b\"D\", 20, 1, 21)],
+                        alias: None
+                    }
+                ])
+            ))
+        );
+        # }
+        ```
+    "],
+    pub use_declaration<Span, Statement>,
+    map_res!(
+        do_parse!(
+            statement_keyword!(tokens::USE) >>
+            kind: opt!(complete!(first!(use_kind))) >>
+            items: alt_complete!(
+                first!(group_use_items)
+              | first!(use_items)
+            ) >>
+            first!(required_terminator) >>
+            (kind, items)
+        ),
+        uses_mapper
+    )
+);
+
+named!(
+    use_kind<Span, UseKind>,
+    alt_complete!(
+        statement_keyword!(tokens::FUNCTION) => { |_| UseKind::Function }
+      | statement_keyword!(tokens::CONST) => { |_| UseKind::Constant }
+    )
+);
+
+/// An import, with its optional kind when it belongs to a group use.
+type UseItem<'a> = (Option<UseKind>, SmallVec<[Span<'a>; 5]>, Option<Span<'a>>);
+
+named!(
+    use_items<Span, Vec<UseItem>>,
+    do_parse!(
+        accumulator: map!(use_item, |(name, alias)| vec![(None, name, alias)]) >>
+        result: fold_into_vector_many0!(
+            map!(
+                preceded!(
+                    first!(tag!(tokens::COMMA)),
+                    first!(use_item)
+                ),
+                |(name, alias)| (None, name, alias)
+            ),
+            accumulator
+        ) >>
+        (result)
+    )
+);
+
+named!(
+    group_use_items<Span, Vec<UseItem>>,
+    do_parse!(
+        prefix: use_name >>
+        first!(tag!(tokens::NAMESPACE_SEPARATOR)) >>
+        first!(tag!(tokens::LEFT_CURLY_BRACKET)) >>
+        accumulator: map!(first!(group_use_item), |item| vec![item]) >>
+        result: fold_into_vector_many0!(
+            preceded!(
+                first!(tag!(tokens::COMMA)),
+                first!(group_use_item)
+            ),
+            accumulator
+        ) >>
+        opt!(first!(tag!(tokens::COMMA))) >>
+        first!(tag!(tokens::RIGHT_CURLY_BRACKET)) >>
+        (
+            result
+                .into_iter()
+                .map(
+                    |(kind, name, alias)| {
+                        let mut prefixed_name = prefix.clone();
+                        prefixed_name.extend(name);
+
+                        (kind, prefixed_name, alias)
+                    }
+                )
+                .collect()
+        )
+    )
+);
+
+named!(
+    group_use_item<Span, UseItem>,
+    do_parse!(
+        kind: opt!(complete!(use_kind)) >>
+        item: first!(use_item) >>
+        (kind, item.0, item.1)
+    )
+);
+
+named!(
+    use_item<Span, (SmallVec<[Span; 5]>, Option<Span>)>,
+    do_parse!(
+        imported_name: use_name >>
+        alias: opt!(
+            complete!(
+                preceded!(
+                    first!(statement_keyword!(tokens::AS)),
+                    first!(name)
+                )
+            )
+        ) >>
+        (imported_name, alias)
+    )
+);
+
+named!(
+    use_name<Span, SmallVec<[Span; 5]>>,
+    map_res!(qualified_name, use_name_mapper)
+);
+
+#[inline]
+fn use_name_mapper(name: Name) -> StdResult<SmallVec<[Span; 5]>, ()> {
+    match name {
+        Name::FullyQualified(names) => Ok(names),
+        name => namespace_name_mapper(name),
+    }
+}
+
+/// Map the imports to a `use` statement. The kind of an import in a
+/// group use cannot be specified if the kind of the declaration is.
+#[inline]
+fn uses_mapper<'a>(
+    (kind, items): (Option<UseKind>, Vec<UseItem<'a>>),
+) -> StdResult<Statement<'a>, ()> {
+    let mut uses = Vec::with_capacity(items.len());
+
+    for (item_kind, name, alias) in items {
+        let kind = match (kind, item_kind) {
+            (Some(_), Some(_)) => {
+                return Err(());
+            }
+
+            (Some(kind), None) | (None, Some(kind)) => kind,
+
+            (None, None) => UseKind::Class,
+        };
+
+        uses.push(Use { kind, name, alias });
+    }
+
+    Ok(Statement::Use(uses))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::super::ast::{
+        Arity, Function, FunctionKind, Statement, Ty, Use, UseKind,
+    };
+    use super::super::super::super::internal::{Context, Error, ErrorKind};
+    use super::super::super::super::tokens::Span;
+    use super::super::statement;
+    use super::{namespace_declaration, use_declaration};
+
+    #[test]
+    fn case_namespace_with_a_terminator() {
+        let input = Span::new(b"namespace Foo;");
+        let output = Ok((
+            Span::new_at(b"", 14, 1, 15),
+            Statement::Namespace {
+                name: Some(smallvec![Span::new_at(b"Foo", 10, 1, 11)]),
+                body: None,
+            },
+        ));
+
+        assert_eq!(namespace_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_namespace_with_a_close_tag() {
+        let input = Span::new(b"namespace Foo?>");
+        let output = Ok((
+            Span::new_at(b"?>", 13, 1, 14),
+            Statement::Namespace {
+                name: Some(smallvec![Span::new_at(b"Foo", 10, 1, 11)]),
+                body: None,
+            },
+        ));
+
+        assert_eq!(namespace_declaration(input), output);
+    }
+
+    #[test]
+    fn case_namespace_with_a_body() {
+        let input = Span::new(b"namespace A\\B\\C { function f() {} }");
+        let output = Ok((
+            Span::new_at(b"", 35, 1, 36),
+            Statement::Namespace {
+                name: Some(smallvec![
+                    Span::new_at(b"A", 10, 1, 11),
+                    Span::new_at(b"B", 12, 1, 13),
+                    Span::new_at(b"C", 14, 1, 15)
+                ]),
+                body: Some(vec![Statement::Function(Function {
+                    attributes: vec![],
+                    name: Span::new_at(b"f", 27, 1, 28),
+                    inputs: Arity::Constant,
+                    output: Ty::Copy(None),
+                    kind: FunctionKind::Regular,
+                    body: vec![],
+                })]),
+            },
+        ));
+
+        assert_eq!(namespace_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_namespace_global() {
+        let input = Span::new(b"NAMESPACE { }");
+        let output = Ok((
+            Span::new_at(b"", 13, 1, 14),
+            Statement::Namespace {
+                name: None,
+                body: Some(vec![]),
+            },
+        ));
+
+        assert_eq!(namespace_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_namespace_without_a_name_nor_a_body() {
+        let input = Span::new(b"namespace;");
+
+        assert_eq!(
+            namespace_declaration(input),
+            Err(Error::Error(Context::Code(
+                Span::new_at(b";", 9, 1, 10),
+                ErrorKind::Alt
+            )))
+        );
+    }
+
+    #[test]
+    fn case_invalid_namespace_with_a_fully_qualified_name() {
+        let input = Span::new(b"namespace \\Foo;");
+
+        assert!(namespace_declaration(input).is_err());
+    }
+
+    #[test]
+    fn case_relative_name_is_not_a_namespace_declaration() {
+        let input = Span::new(b"namespace\\f();");
+
+        assert!(namespace_declaration(input).is_err());
+        assert!(match statement(input) {
+            Ok((_, Statement::Expression(_))) => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn case_use() {
+        let input = Span::new(b"use A\\B, \\C\\D as E;");
+        let output = Ok((
+            Span::new_at(b"", 19, 1, 20),
+            Statement::Use(vec![
+                Use {
+                    kind: UseKind::Class,
+                    name: smallvec![Span::new_at(b"A", 4, 1, 5), Span::new_at(b"B", 6, 1, 7)],
+                    alias: None,
+                },
+                Use {
+                    kind: UseKind::Class,
+                    name: smallvec![Span::new_at(b"C", 10, 1, 11), Span::new_at(b"D", 12, 1, 13)],
+                    alias: Some(Span::new_at(b"E", 17, 1, 18)),
+                },
+            ]),
+        ));
+
+        assert_eq!(use_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_use_function_group() {
+        let input = Span::new(b"use function A\\{b, c\\d as e,};");
+        let output = Ok((
+            Span::new_at(b"", 30, 1, 31),
+            Statement::Use(vec![
+                Use {
+                    kind: UseKind::Function,
+                    name: smallvec![Span::new_at(b"A", 13, 1, 14), Span::new_at(b"b", 16, 1, 17)],
+                    alias: None,
+                },
+                Use {
+                    kind: UseKind::Function,
+                    name: smallvec![
+                        Span::new_at(b"A", 13, 1, 14),
+                        Span::new_at(b"c", 19, 1, 20),
+                        Span::new_at(b"d", 21, 1, 22)
+                    ],
+                    alias: Some(Span::new_at(b"e", 26, 1, 27)),
+                },
+            ]),
+        ));
+
+        assert_eq!(use_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_use_mixed_group() {
+        let input = Span::new(b"use A\\{B, function c, const D};");
+        let output = Ok((
+            Span::new_at(b"", 31, 1, 32),
+            Statement::Use(vec![
+                Use {
+                    kind: UseKind::Class,
+                    name: smallvec![Span::new_at(b"A", 4, 1, 5), Span::new_at(b"B", 7, 1, 8)],
+                    alias: None,
+                },
+                Use {
+                    kind: UseKind::Function,
+                    name: smallvec![Span::new_at(b"A", 4, 1, 5), Span::new_at(b"c", 19, 1, 20)],
+                    alias: None,
+                },
+                Use {
+                    kind: UseKind::Constant,
+                    name: smallvec![Span::new_at(b"A", 4, 1, 5), Span::new_at(b"D", 28, 1, 29)],
+                    alias: None,
+                },
+            ]),
+        ));
+
+        assert_eq!(use_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_use_group_with_kinds_twice() {
+        let input = Span::new(b"use function A\\{function b};");
+
+        assert_eq!(
+            use_declaration(input),
+            Err(Error::Error(Context::Code(input, ErrorKind::MapRes)))
+        );
+    }
+
+    #[test]
+    fn case_invalid_use_relative_name() {
+        let input = Span::new(b"use namespace\\A;");
+
+        assert!(use_declaration(input).is_err());
+    }
+
+    #[test]
+    fn case_invalid_use_empty_group() {
+        let input = Span::new(b"use A\\{};");
+
+        assert!(use_declaration(input).is_err());
+    }
+}
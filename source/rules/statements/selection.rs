@@ -0,0 +1,676 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2017, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of selection statement rules.
+//!
+//! The list of all selection statements is provided by the PHP Language
+//! Specification in the [Grammar chapter, Selection Statements
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#selection-statements).
+
+use super::super::super::ast::{ControlSyntax, Expression, Statement, SwitchCase};
+use super::super::super::tokens;
+use super::super::super::tokens::Span;
+use super::super::expressions::expression;
+use super::{alternative_block, inline_html, required_terminator, statement, statement_body};
+
+named_attr!(
+    #[doc="
+        Recognize all kind of selection statements.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{ControlSyntax, Expression, Statement, Variable};
+        use tagua_parser::rules::statements::selection::selection_statement;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            selection_statement(Span::new(b\"if ($x) {}\")),
+            Ok((
+                Span::new_at(b\"\", 10, 1, 11),
+                Statement::If {
+                    condition: Expression::Variable(Variable(Span::new_at(b\"x\", 5, 1, 6))),
+                    body     : vec![],
+                    else_ifs : vec![],
+                    else_body: None,
+                    syntax   : ControlSyntax::Standard
+                }
+            ))
+        );
+        # }
+        ```
+    "],
+    pub selection_statement<Span, Statement>,
+    alt_complete!(
+        if_statement
+      | switch_statement
+    )
+);
+
+named_attr!(
+    #[doc="
+        Recognize an `if` statement, with its `elseif` and `else`
+        clauses.
+
+        Note that `else if` is an `else` clause containing another
+        `if` statement. In the alternative syntax, e.g. `if (…): …
+        elseif (…): … else: … endif;`, only `elseif` is allowed.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{ControlSyntax, Expression, Statement, Variable};
+        use tagua_parser::rules::statements::selection::if_statement;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            if_statement(Span::new(b\"if ($x) $y; else $z;\")),
+            Ok((
+                Span::new_at(b\"\", 20, 1, 21),
+                Statement::If {
+                    condition: Expression::Variable(Variable(Span::new_at(b\"x\", 5, 1, 6))),
+                    body     : vec![
+                        Statement::Expression(Expression::Variable(Variable(Span::new_at(b\"y\", 9, 1, 10))))
+                    ],
+                    else_ifs : vec![],
+                    else_body: Some(vec![
+                        Statement::Expression(Expression::Variable(Variable(Span::new_at(b\"z\", 18, 1, 19))))
+                    ]),
+                    syntax   : ControlSyntax::Standard
+                }
+            ))
+        );
+        # }
+        ```
+    "],
+    pub if_statement<Span, Statement>,
+    do_parse!(
+        statement_keyword!(tokens::IF) >>
+        condition: first!(parenthesized_expression) >>
+        clauses: first!(
+            alt_complete!(
+                if_clauses
+              | alternative_if_clauses
+            )
+        ) >>
+        (into_if_statement(condition, clauses))
+    )
+);
+
+/// The clauses of an `if` statement, i.e. the body, the `elseif`
+/// clauses, the `else` clause, and the syntax.
+type IfClauses<'a> = (
+    Vec<Statement<'a>>,
+    Vec<(Expression<'a>, Vec<Statement<'a>>)>,
+    Option<Vec<Statement<'a>>>,
+    ControlSyntax,
+);
+
+named!(
+    if_clauses<Span, IfClauses>,
+    do_parse!(
+        body: statement_body >>
+        else_ifs: fold_into_vector_many0!(
+            complete!(
+                do_parse!(
+                    first!(statement_keyword!(tokens::ELSEIF)) >>
+                    condition: first!(parenthesized_expression) >>
+                    body: first!(statement_body) >>
+                    ((condition, body))
+                )
+            ),
+            Vec::new()
+        ) >>
+        else_body: opt!(
+            complete!(
+                preceded!(
+                    first!(statement_keyword!(tokens::ELSE)),
+                    first!(statement_body)
+                )
+            )
+        ) >>
+        ((body, else_ifs, else_body, ControlSyntax::Standard))
+    )
+);
+
+named!(
+    alternative_if_clauses<Span, IfClauses>,
+    do_parse!(
+        body: alternative_block >>
+        else_ifs: fold_into_vector_many0!(
+            complete!(
+                do_parse!(
+                    first!(statement_keyword!(tokens::ELSEIF)) >>
+                    condition: first!(parenthesized_expression) >>
+                    body: first!(alternative_block) >>
+                    ((condition, body))
+                )
+            ),
+            Vec::new()
+        ) >>
+        else_body: opt!(
+            complete!(
+                preceded!(
+                    first!(statement_keyword!(tokens::ELSE)),
+                    first!(alternative_block)
+                )
+            )
+        ) >>
+        first!(statement_keyword!(tokens::ENDIF)) >>
+        first!(required_terminator) >>
+        ((body, else_ifs, else_body, ControlSyntax::Alternative))
+    )
+);
+
+#[inline]
+fn into_if_statement<'a>(condition: Expression<'a>, clauses: IfClauses<'a>) -> Statement<'a> {
+    let (body, else_ifs, else_body, syntax) = clauses;
+
+    Statement::If {
+        condition,
+        body,
+        else_ifs,
+        else_body,
+        syntax,
+    }
+}
+
+named_attr!(
+    #[doc="
+        Recognize an expression surrounded by parenthesis, like the
+        condition of a selection or an iteration statement.
+    "],
+    pub parenthesized_expression<Span, Expression>,
+    delimited!(
+        tag!(tokens::LEFT_PARENTHESIS),
+        first!(expression),
+        first!(tag!(tokens::RIGHT_PARENTHESIS))
+    )
+);
+
+named_attr!(
+    #[doc="
+        Recognize a `switch` statement.
+
+        A case condition can be terminated by a colon or by a
+        semicolon. Several `default` cases are allowed. The cases are
+        delimited by curly braces, or by a colon and `endswitch;` in
+        the alternative syntax.
+
+        # Examples
+
+        ```
+        use tagua_parser::Result;
+        use tagua_parser::ast::{ControlSyntax, Expression, Statement, SwitchCase, Variable};
+        use tagua_parser::rules::statements::selection::switch_statement;
+        use tagua_parser::tokens::Span;
+
+        # fn main() {
+        assert_eq!(
+            switch_statement(Span::new(b\"switch ($x) { default; $y; }\")),
+            Ok((
+                Span::new_at(b\"\", 28, 1, 29),
+                Statement::Switch {
+                    subject: Expression::Variable(Variable(Span::new_at(b\"x\", 9, 1, 10))),
+                    cases  : vec![
+                        SwitchCase {
+                            condition: None,
+                            body     : vec![
+                                Statement::Expression(Expression::Variable(Variable(Span::new_at(b\"y\", 24, 1, 25))))
+                            ]
+                        }
+                    ],
+                    syntax : ControlSyntax::Standard
+                }
+            ))
+        );
+        # }
+        ```
+    "],
+    pub switch_statement<Span, Statement>,
+    do_parse!(
+        statement_keyword!(tokens::SWITCH) >>
+        subject: first!(parenthesized_expression) >>
+        cases: first!(
+            alt_complete!(
+                delimited!(
+                    tag!(tokens::LEFT_CURLY_BRACKET),
+                    switch_cases,
+                    first!(tag!(tokens::RIGHT_CURLY_BRACKET))
+                ) => { |cases| (cases, ControlSyntax::Standard) }
+              | delimited!(
+                    tag!(tokens::ALTERNATIVE_BLOCK_OPEN),
+                    switch_cases,
+                    terminated!(
+                        first!(statement_keyword!(tokens::ENDSWITCH)),
+                        first!(required_terminator)
+                    )
+                ) => { |cases| (cases, ControlSyntax::Alternative) }
+            )
+        ) >>
+        ({
+            let (cases, syntax) = cases;
+
+            Statement::Switch {
+                subject,
+                cases,
+                syntax
+            }
+        })
+    )
+);
+
+named!(
+    switch_cases<Span, Vec<SwitchCase>>,
+    preceded!(
+        opt!(first!(switch_cases_gap)),
+        fold_into_vector_many0!(
+            first!(switch_case),
+            Vec::new()
+        )
+    )
+);
+
+named_attr!(
+    #[doc="
+        Recognize what can precede the first case: An empty statement,
+        or inline HTML made of whitespaces only, e.g. `?>\\n<?php` in a
+        template.
+    "],
+    switch_cases_gap<Span, ()>,
+    alt_complete!(
+        tag!(tokens::SEMICOLON) => { |_| () }
+      | map_opt!(inline_html, into_switch_cases_gap)
+    )
+);
+
+#[inline]
+fn into_switch_cases_gap(statement: Statement) -> Option<()> {
+    match statement {
+        Statement::InlineHtml(ref html)
+            if html
+                .as_slice()
+                .iter()
+                .all(|byte| byte.is_ascii_whitespace()) =>
+        {
+            Some(())
+        }
+
+        _ => None,
+    }
+}
+
+named!(
+    switch_case<Span, SwitchCase>,
+    do_parse!(
+        condition: alt_complete!(
+            preceded!(
+                statement_keyword!(tokens::CASE),
+                first!(expression)
+            ) => { Some }
+          | statement_keyword!(tokens::DEFAULT) => { |_| None }
+        ) >>
+        first!(
+            alt_complete!(
+                tag!(tokens::CASE_SEPARATOR)
+              | tag!(tokens::SEMICOLON)
+            )
+        ) >>
+        body: fold_into_vector_many0!(
+            first!(statement),
+            Vec::new()
+        ) >>
+        (
+            SwitchCase {
+                condition,
+                body
+            }
+        )
+    )
+);
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::super::ast::{
+        ControlSyntax, Expression, Literal, Name, Statement, SwitchCase, Variable,
+    };
+    use super::super::super::super::tokens::{Span, Token};
+    use super::super::statement;
+    use super::{if_statement, selection_statement, switch_statement};
+
+    #[test]
+    fn case_if() {
+        let input = Span::new(b"if ($x) { $y; }");
+        let output = Ok((
+            Span::new_at(b"", 15, 1, 16),
+            Statement::If {
+                condition: Expression::Variable(Variable(Span::new_at(b"x", 5, 1, 6))),
+                body: vec![Statement::Expression(Expression::Variable(Variable(
+                    Span::new_at(b"y", 11, 1, 12),
+                )))],
+                else_ifs: vec![],
+                else_body: None,
+                syntax: ControlSyntax::Standard,
+            },
+        ));
+
+        assert_eq!(if_statement(input), output);
+        assert_eq!(selection_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_if_elseif_else() {
+        let input = Span::new(b"IF ($x) {} elseif ($y) {} ElseIf ($z) ; else {}");
+        let output = Ok((
+            Span::new_at(b"", 47, 1, 48),
+            Statement::If {
+                condition: Expression::Variable(Variable(Span::new_at(b"x", 5, 1, 6))),
+                body: vec![],
+                else_ifs: vec![
+                    (
+                        Expression::Variable(Variable(Span::new_at(b"y", 20, 1, 21))),
+                        vec![],
+                    ),
+                    (
+                        Expression::Variable(Variable(Span::new_at(b"z", 35, 1, 36))),
+                        vec![Statement::Empty],
+                    ),
+                ],
+                else_body: Some(vec![]),
+                syntax: ControlSyntax::Standard,
+            },
+        ));
+
+        assert_eq!(if_statement(input), output);
+        assert_eq!(selection_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_if_else_if() {
+        let input = Span::new(b"if ($x) {} else if ($y) {}");
+        let output = Ok((
+            Span::new_at(b"", 26, 1, 27),
+            Statement::If {
+                condition: Expression::Variable(Variable(Span::new_at(b"x", 5, 1, 6))),
+                body: vec![],
+                else_ifs: vec![],
+                else_body: Some(vec![Statement::If {
+                    condition: Expression::Variable(Variable(Span::new_at(b"y", 21, 1, 22))),
+                    body: vec![],
+                    else_ifs: vec![],
+                    else_body: None,
+                    syntax: ControlSyntax::Standard,
+                }]),
+                syntax: ControlSyntax::Standard,
+            },
+        ));
+
+        assert_eq!(if_statement(input), output);
+        assert_eq!(selection_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_if_is_not_a_prefix_of_a_name() {
+        let input = Span::new(b"iffy;");
+
+        assert!(if_statement(input).is_err());
+        assert_eq!(
+            statement(input),
+            Ok((
+                Span::new_at(b"", 5, 1, 6),
+                Statement::Expression(Expression::Name(Name::Unqualified(Span::new(b"iffy"))))
+            ))
+        );
+    }
+
+    #[test]
+    fn case_switch_empty() {
+        let input = Span::new(b"switch ($x) {}");
+        let output = Ok((
+            Span::new_at(b"", 14, 1, 15),
+            Statement::Switch {
+                subject: Expression::Variable(Variable(Span::new_at(b"x", 9, 1, 10))),
+                cases: vec![],
+                syntax: ControlSyntax::Standard,
+            },
+        ));
+
+        assert_eq!(switch_statement(input), output);
+        assert_eq!(selection_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_switch_with_many_cases() {
+        let input = Span::new(b"switch ($x) { case 1: case 2; $y; break; default: $z; }");
+        let output = Ok((
+            Span::new_at(b"", 55, 1, 56),
+            Statement::Switch {
+                subject: Expression::Variable(Variable(Span::new_at(b"x", 9, 1, 10))),
+                cases: vec![
+                    SwitchCase {
+                        condition: Some(Expression::Literal(Literal::Integer(Token::new(
+                            1i64,
+                            Span::new_at(b"1", 19, 1, 20),
+                        )))),
+                        body: vec![],
+                    },
+                    SwitchCase {
+                        condition: Some(Expression::Literal(Literal::Integer(Token::new(
+                            2i64,
+                            Span::new_at(b"2", 27, 1, 28),
+                        )))),
+                        body: vec![
+                            Statement::Expression(Expression::Variable(Variable(Span::new_at(
+                                b"y", 31, 1, 32,
+                            )))),
+                            Statement::Break(None),
+                        ],
+                    },
+                    SwitchCase {
+                        condition: None,
+                        body: vec![Statement::Expression(Expression::Variable(Variable(
+                            Span::new_at(b"z", 51, 1, 52),
+                        )))],
+                    },
+                ],
+                syntax: ControlSyntax::Standard,
+            },
+        ));
+
+        assert_eq!(switch_statement(input), output);
+        assert_eq!(selection_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_switch_without_closing_bracket() {
+        let input = Span::new(b"switch ($x) { case 1: ");
+
+        assert!(switch_statement(input).is_err());
+    }
+
+    #[test]
+    fn case_if_alternative_syntax() {
+        let input = Span::new(b"if ($x): $y; elseif ($z): else: $y; $z; endif;");
+        let output = Ok((
+            Span::new_at(b"", 46, 1, 47),
+            Statement::If {
+                condition: Expression::Variable(Variable(Span::new_at(b"x", 5, 1, 6))),
+                body: vec![Statement::Expression(Expression::Variable(Variable(
+                    Span::new_at(b"y", 10, 1, 11),
+                )))],
+                else_ifs: vec![(
+                    Expression::Variable(Variable(Span::new_at(b"z", 22, 1, 23))),
+                    vec![],
+                )],
+                else_body: Some(vec![
+                    Statement::Expression(Expression::Variable(Variable(Span::new_at(
+                        b"y", 33, 1, 34,
+                    )))),
+                    Statement::Expression(Expression::Variable(Variable(Span::new_at(
+                        b"z", 37, 1, 38,
+                    )))),
+                ]),
+                syntax: ControlSyntax::Alternative,
+            },
+        ));
+
+        assert_eq!(if_statement(input), output);
+        assert_eq!(selection_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_if_alternative_syntax_with_a_nested_standard_if() {
+        let input = Span::new(b"if ($x): if ($y) {} else: endif ?>");
+        let output = Ok((
+            Span::new_at(b"?>", 32, 1, 33),
+            Statement::If {
+                condition: Expression::Variable(Variable(Span::new_at(b"x", 5, 1, 6))),
+                body: vec![Statement::If {
+                    condition: Expression::Variable(Variable(Span::new_at(b"y", 14, 1, 15))),
+                    body: vec![],
+                    else_ifs: vec![],
+                    else_body: None,
+                    syntax: ControlSyntax::Standard,
+                }],
+                else_ifs: vec![],
+                else_body: Some(vec![]),
+                syntax: ControlSyntax::Alternative,
+            },
+        ));
+
+        assert_eq!(if_statement(input), output);
+        assert_eq!(selection_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_if_alternative_syntax_without_endif() {
+        let input = Span::new(b"if ($x): $y; else: $z;");
+
+        assert!(if_statement(input).is_err());
+    }
+
+    #[test]
+    fn case_invalid_if_alternative_syntax_with_else_if() {
+        let input = Span::new(b"if ($x): else if ($y): endif;");
+
+        assert!(if_statement(input).is_err());
+    }
+
+    #[test]
+    fn case_switch_alternative_syntax() {
+        let input = Span::new(b"switch ($x): default: $y; ENDSWITCH;");
+        let output = Ok((
+            Span::new_at(b"", 36, 1, 37),
+            Statement::Switch {
+                subject: Expression::Variable(Variable(Span::new_at(b"x", 9, 1, 10))),
+                cases: vec![SwitchCase {
+                    condition: None,
+                    body: vec![Statement::Expression(Expression::Variable(Variable(
+                        Span::new_at(b"y", 23, 1, 24),
+                    )))],
+                }],
+                syntax: ControlSyntax::Alternative,
+            },
+        ));
+
+        assert_eq!(switch_statement(input), output);
+        assert_eq!(selection_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_switch_alternative_syntax_in_a_template() {
+        let input = Span::new(b"switch ($a): ?>\n<?php case 1: ?>x<?php endswitch; ?>");
+        let output = Ok((
+            Span::new_at(b" ?>", 49, 2, 34),
+            Statement::Switch {
+                subject: Expression::Variable(Variable(Span::new_at(b"a", 9, 1, 10))),
+                cases: vec![SwitchCase {
+                    condition: Some(Expression::Literal(Literal::Integer(Token::new(
+                        1i64,
+                        Span::new_at(b"1", 27, 2, 12),
+                    )))),
+                    body: vec![Statement::InlineHtml(Span::new_at(b"x", 32, 2, 17))],
+                }],
+                syntax: ControlSyntax::Alternative,
+            },
+        ));
+
+        assert_eq!(switch_statement(input), output);
+        assert_eq!(selection_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_switch_with_an_empty_statement_before_the_first_case() {
+        let input = Span::new(b"switch ($x): ; default: endswitch;");
+        let output = Ok((
+            Span::new_at(b"", 34, 1, 35),
+            Statement::Switch {
+                subject: Expression::Variable(Variable(Span::new_at(b"x", 9, 1, 10))),
+                cases: vec![SwitchCase {
+                    condition: None,
+                    body: vec![],
+                }],
+                syntax: ControlSyntax::Alternative,
+            },
+        ));
+
+        assert_eq!(switch_statement(input), output);
+        assert_eq!(selection_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_switch_with_inline_html_before_the_first_case() {
+        let input = Span::new(b"switch ($a): ?>x<?php case 1: endswitch;");
+
+        assert!(switch_statement(input).is_err());
+    }
+
+    #[test]
+    fn case_invalid_switch_mixed_syntaxes() {
+        let input = Span::new(b"switch ($x): default: }");
+
+        assert!(switch_statement(input).is_err());
+    }
+}
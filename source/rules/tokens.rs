@@ -123,13 +123,13 @@ named_attr!(
             )
         ) >>
         accumulator: map_res!(
-            exclude!(first!(name), tokens::keywords),
+            exclude!(first!(name), keyword),
             wrap_into_vector_mapper
         ) >>
         result: fold_into_vector_many0!(
             preceded!(
                 first!(tag!(tokens::NAMESPACE_SEPARATOR)),
-                exclude!(first!(name), tokens::keywords)
+                exclude!(first!(name), keyword)
             ),
             accumulator
         ) >>
@@ -155,6 +155,11 @@ named_attr!(
     )
 );
 
+named!(
+    keyword<Span, Span>,
+    terminated!(tokens::keywords, eof!())
+);
+
 #[inline]
 fn wrap_into_vector_mapper(span: Span) -> Result<SmallVec<[Span; 5]>, ()> {
     Ok(smallvec![span])
@@ -320,6 +325,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn case_qualified_name_starting_with_a_keyword() {
+        let input = Span::new(b"Classes\\intdiv\\print_r");
+        let output = Ok((
+            Span::new_at(b"", 22, 1, 23),
+            Name::Qualified(smallvec![
+                Span::new(b"Classes"),
+                Span::new_at(b"intdiv", 8, 1, 9),
+                Span::new_at(b"print_r", 15, 1, 16)
+            ]),
+        ));
+
+        assert_eq!(qualified_name(input), output);
+    }
+
     #[test]
     fn case_relative_qualified_name() {
         let input = Span::new(b"namespace\\Foo\\Bar\\Baz");
@@ -129,9 +129,9 @@ mod tests {
     }
 
     #[test]
-    fn case_whitespace_too_short() {
+    fn case_invalid_whitespace_too_short() {
         let input = Span::new(b"");
-        let output = Ok((Span::new_at(b"", 0, 1, 1), input));
+        let output = Err(Error::Error(Context::Code(input, ErrorKind::IsA)));
 
         assert_eq!(whitespace(input), output);
     }
@@ -46,7 +46,7 @@ use bytecount;
 use memchr;
 use nom::{
     AtEof, Compare, CompareResult, Context, Err, ErrorKind, FindSubstring, IResult, InputIter,
-    InputLength, InputTake, InputTakeAtPosition, Needed, Offset, Slice,
+    InputLength, InputTake, InputTakeAtPosition, Offset, Slice,
 };
 use rules::whitespaces::whitespace;
 use std::iter::Enumerate;
@@ -86,6 +86,10 @@ token!(
     pub ADD_AND_ASSIGN: b"+=";
     "The `ADD_AND_ASSIGN` token.\n\nRepresent the addition assignment operator, e.g. `$x += $y;`."
 );
+token!(
+    pub ALTERNATIVE_BLOCK_OPEN: COLON;
+    "The `ALTERNATIVE_BLOCK_OPEN` token.\n\nRepresent the beginning of a block in the alternative syntax of a control structure, e.g. `if (…): … endif;`."
+);
 token!(
     pub AND: b"and";
     "The `AND` token.\n\nRepresent the conjunction operator, used in a logical expression, e.g. `$x and $y`."
@@ -102,6 +106,14 @@ token!(
     pub ASSIGN: b"=";
     "The `ASSIGN` token.\n\nRepresent a binding of a value to a variable, e.g. `$x = 42`."
 );
+token!(
+    pub ATTRIBUTE_CLOSE: RIGHT_SQUARE_BRACKET;
+    "The `ATTRIBUTE_CLOSE` token.\n\nRepresent the end of a group of attributes, e.g. `#[A, B(42)]`."
+);
+token!(
+    pub ATTRIBUTE_OPEN: b"#[";
+    "The `ATTRIBUTE_OPEN` token.\n\nRepresent the beginning of a group of attributes, e.g. `#[A, B(42)]`."
+);
 token!(
     pub BITWISE_AND: b"&";
     "The `BITWISE_AND` token.\n\nRepresent the bitwise conjunction operator, e.g. `$x & $y`."
@@ -135,7 +147,7 @@ token!(
     "The `BITWISE_RIGHT_SHIFT` token.\n\nRepresent the bitwise right shift operator, e.g. `$x >> $y`."
 );
 token!(
-    pub BITWISE_RIGHT_SHIFT_AND_ASSIGN: b"<<=";
+    pub BITWISE_RIGHT_SHIFT_AND_ASSIGN: b">>=";
     "The `BITWISE_RIGHT_SHIFT_AND_ASSIGN` token.\n\nRepresent the bitwise right shift assignment operator, e.g. `$x >>= $y;`."
 );
 token!(
@@ -182,6 +194,10 @@ token!(
     pub CASE: b"case";
     "The `CASE` token.\n\nRepresent a case in a `switch` control structure, e.g. `switch (…) { case …: …; }`."
 );
+token!(
+    pub CASE_SEPARATOR: COLON;
+    "The `CASE_SEPARATOR` token.\n\nRepresent the end of a case condition in a `switch` control structure, e.g. `switch (…) { case …: …; }`."
+);
 token!(
     pub CATCH: b"catch";
     "The `CATCH` token.\n\nRepresent the `catch` block of a `try`/`catch` control structure, e.g. `try { … } catch (Exception $e) { … }`."
@@ -194,10 +210,18 @@ token!(
     pub CLONE: b"clone";
     "The `CLONE` token.\n\nRepresent the clone operator, e.g. `clone $x`."
 );
+token!(
+    pub CLOSE_TAG: b"?>";
+    "The `CLOSE_TAG` token.\n\nRepresent the end of a PHP block, e.g. `<?php echo 'foo' ?>`."
+);
 token!(
     pub COALESCE: b"??";
     "The `COALESCE` token.\n\nRepresent the null coalescing operator, e.g. `$x ?? $y`."
 );
+token!(
+    pub COALESCE_AND_ASSIGN: b"??=";
+    "The `COALESCE_AND_ASSIGN` token.\n\nRepresent the null coalescing assignment operator, e.g. `$x ??= $y;`."
+);
 token!(
     pub COMMA: b",";
     "The `COMMA` token.\n\nRepresent the list item separator, e.g. `($x, $y, $z)`."
@@ -218,6 +242,10 @@ token!(
     pub CONST: b"const";
     "The `CONST` token.\n\nRepresent the constant declaration operator, e.g. `const ANSWER = 42;`."
 );
+token!(
+    pub CONSTRUCT: b"__construct";
+    "The `CONSTRUCT` token.\n\nRepresent the name of a constructor, e.g. `function __construct() {}`."
+);
 token!(
     pub CONTINUE: b"continue";
     "The `CONTINUE` token.\n\nRepresent the control flow continuer operator, e.g. `continue 2;`."
@@ -298,10 +326,22 @@ token!(
     pub ENDWHILE: b"endwhile";
     "The `ENDWHILE` token.\n\nRepresent the end of a `while` block, e.g. `while(…): … endwhile`."
 );
+token!(
+    pub ENUM: b"enum";
+    "The `ENUM` token.\n\nRepresent the enumeration declaration operator, e.g. `enum E { … }`."
+);
+token!(
+    pub ENUM_BACKING_TYPE: COLON;
+    "The `ENUM_BACKING_TYPE` token.\n\nRepresent the enumeration backing type declaration operator, e.g. `enum E: string { … }`."
+);
 token!(
     pub EQUAL: b"==";
     "The `EQUAL` token.\n\nRepresent the equality comparison operator, e.g. `$x == $y`."
 );
+token!(
+    pub ERROR_CONTROL: b"@";
+    "The `ERROR_CONTROL` token.\n\nRepresent the error control operator, e.g. `@f()`."
+);
 token!(
     pub EVAL: b"eval";
     "The `EVAL` token.\n\nRepresent the late-evaluation operator, e.g. `eval($x)`."
@@ -314,6 +354,10 @@ token!(
     pub EXTENDS: b"extends";
     "The `EXTENDS` token.\n\nRepresent the inheritance operator, e.g. `class C extends D { … }`."
 );
+token!(
+    pub FALSE: b"false";
+    "The `FALSE` token.\n\nRepresent the false type, e.g. `function f(): A|false { … }`."
+);
 token!(
     pub FINAL: b"final";
     "The `FINAL` token.\n\nRepresent a final entity, e.g. `final class C { … }`."
@@ -326,6 +370,10 @@ token!(
     pub FLOAT: b"float";
     "The `FLOAT` token.\n\nRepresent the float type, e.g. `function (float $x) { … }`."
 );
+token!(
+    pub FN: b"fn";
+    "The `FN` token.\n\nRepresent the arrow function declaration operator, e.g. `fn ($x) => $x * 2`."
+);
 token!(
     pub FOR: b"for";
     "The `FOR` token.\n\nRepresent a `for` loop, e.g. `for (…; …; …) { … }`."
@@ -338,6 +386,10 @@ token!(
     pub FUNCTION: b"function";
     "The `FUNCTION` token.\n\nRepresent the function declaration operator, e.g. `function f(…) { … }`."
 );
+token!(
+    pub FUNCTION_ARROW: MAP;
+    "The `FUNCTION_ARROW` token.\n\nRepresent the arrow function body declaration operator, e.g. `fn ($x) => $x * 2`."
+);
 token!(
     pub FUNCTION_OUTPUT: COLON;
     "The `FUNCTION_OUTPUT` token.\n\nRepresent the function return type declaration operator, e.g. `function f(…): … { … }`."
@@ -438,10 +490,54 @@ token!(
     pub LIST: b"list";
     "The `LIST` token.\n\nRepresent the destructuring operator, e.g. `list($x, $y) = $a`."
 );
+token!(
+    pub MAGIC_CLASS: b"__CLASS__";
+    "The `MAGIC_CLASS` token.\n\nRepresent the magic constant of the name of the enclosing class, e.g. `echo __CLASS__;`."
+);
+token!(
+    pub MAGIC_DIRECTORY: b"__DIR__";
+    "The `MAGIC_DIRECTORY` token.\n\nRepresent the magic constant of the directory of the current file, e.g. `echo __DIR__;`."
+);
+token!(
+    pub MAGIC_FILE: b"__FILE__";
+    "The `MAGIC_FILE` token.\n\nRepresent the magic constant of the path of the current file, e.g. `echo __FILE__;`."
+);
+token!(
+    pub MAGIC_FUNCTION: b"__FUNCTION__";
+    "The `MAGIC_FUNCTION` token.\n\nRepresent the magic constant of the name of the enclosing function, e.g. `echo __FUNCTION__;`."
+);
+token!(
+    pub MAGIC_LINE: b"__LINE__";
+    "The `MAGIC_LINE` token.\n\nRepresent the magic constant of the current line number, e.g. `echo __LINE__;`."
+);
+token!(
+    pub MAGIC_METHOD: b"__METHOD__";
+    "The `MAGIC_METHOD` token.\n\nRepresent the magic constant of the name of the enclosing method, e.g. `echo __METHOD__;`."
+);
+token!(
+    pub MAGIC_NAMESPACE: b"__NAMESPACE__";
+    "The `MAGIC_NAMESPACE` token.\n\nRepresent the magic constant of the name of the current namespace, e.g. `echo __NAMESPACE__;`."
+);
+token!(
+    pub MAGIC_TRAIT: b"__TRAIT__";
+    "The `MAGIC_TRAIT` token.\n\nRepresent the magic constant of the name of the enclosing trait, e.g. `echo __TRAIT__;`."
+);
 token!(
     pub MAP: b"=>";
     "The `MAP` token.\n\nRepresent the mapping operator in an array, e.g. `[42 => 'foo']`."
 );
+token!(
+    pub MATCH: b"match";
+    "The `MATCH` token.\n\nRepresent the match expression operator, e.g. `match ($x) { 1, 2 => 'a', default => 'b' }`."
+);
+token!(
+    pub MATCH_ARROW: MAP;
+    "The `MATCH_ARROW` token.\n\nRepresent the separator between the conditions and the expression of a match arm, e.g. `match ($x) { 1, 2 => 'a' }`."
+);
+token!(
+    pub MIXED: b"mixed";
+    "The `MIXED` token.\n\nRepresent the mixed type, i.e. all possible types, e.g. `function f(mixed $x) { … }`."
+);
 token!(
     pub MODULO: b"%";
     "The `MODULO` token.\n\nRepresent the modulus operator, e.g. `$x % $y`."
@@ -458,6 +554,10 @@ token!(
     pub MULTIPLY_AND_ASSIGN: b"*=";
     "The `MULTIPLY_AND_ASSIGN` token.\n\nRepresent the multiplication assignment operator, e.g. `$x *= $y;`."
 );
+token!(
+    pub NAMED_ARGUMENT: COLON;
+    "The `NAMED_ARGUMENT` token.\n\nRepresent the separator between the name and the value of a named argument, e.g. `f(x: …)`."
+);
 token!(
     pub NAMESPACE: b"namespace";
     "The `NAMESPACE` token.\n\nRepresent the namespace declaration operator or the current namespace name, e.g. `namespace N;`."
@@ -466,6 +566,10 @@ token!(
     pub NAMESPACE_SEPARATOR: b"\\";
     "The `NAMESPACE_SEPARATOR` token.\n\nRepresent the namespace separator, e.g. `A\\B\\C`."
 );
+token!(
+    pub NEVER: b"never";
+    "The `NEVER` token.\n\nRepresent the never type, i.e. a function that never returns, e.g. `function f(): never { … }`."
+);
 token!(
     pub NEW: b"new";
     "The `NEW` token.\n\nRepresent the instanciation operator, e.g. `new C()`."
@@ -482,10 +586,30 @@ token!(
     pub NOT_IDENTICAL: b"!==";
     "The `NOT_IDENTICAL` token.\n\nRepresent the strict not equal comparison operator, e.g. `$x !== $y`."
 );
+token!(
+    pub NULL: b"null";
+    "The `NULL` token.\n\nRepresent the null type, e.g. `function f(): A|null { … }`."
+);
 token!(
     pub NULLABLE: QUESTION_MARK;
     "The `NULLABLE` token.\n\nRepresent the nullable operation, e.g. `function f(?int $x) { … }`."
 );
+token!(
+    pub NULLSAFE_DYNAMIC_CALL: b"?->";
+    "The `NULLSAFE_DYNAMIC_CALL` token.\n\nRepresent the nullsafe dynamic method call operator, e.g. `$object?->method()`."
+);
+token!(
+    pub OBJECT: b"object";
+    "The `OBJECT` token.\n\nRepresent the object type, e.g. `function f(object $x) { … }`."
+);
+token!(
+    pub OPEN_TAG: b"<?php";
+    "The `OPEN_TAG` token.\n\nRepresent the beginning of a PHP block, e.g. `<?php echo 'foo';`."
+);
+token!(
+    pub OPEN_TAG_WITH_ECHO: b"<?=";
+    "The `OPEN_TAG_WITH_ECHO` token.\n\nRepresent the beginning of a PHP block echoing expressions, e.g. `<?= $x ?>`."
+);
 token!(
     pub OR: b"or";
     "The `OR` token.\n\nRepresent the inclusive disjunction operator, used in a logical expression, e.g. `$x or $y`."
@@ -518,6 +642,10 @@ token!(
     pub PUBLIC: b"public";
     "The `PUBLIC` token.\n\nRepresent the public visibility operator, e.g. `public $x`."
 );
+token!(
+    pub READONLY: b"readonly";
+    "The `READONLY` token.\n\nRepresent the read-only modifier, e.g. `public readonly int $x`."
+);
 token!(
     pub REFERENCE: b"&";
     "The `REFERENCE` token.\n\nRepresent the reference operator, e.g. `&$x`."
@@ -594,10 +722,22 @@ token!(
     pub TRAIT: b"trait";
     "The `TRAIT` token.\n\nRepresent the trait declaration operator, e.g. `trait T { … }`."
 );
+token!(
+    pub TRUE: b"true";
+    "The `TRUE` token.\n\nRepresent the true type, e.g. `function f(): A|true { … }`."
+);
 token!(
     pub TRY: b"try";
     "The `TRY` token.\n\nRepresent the `try` block of a `try`/`catch` control structure, e.g. `try { … } catch (Exception $e) { … }`."
 );
+token!(
+    pub TYPE_INTERSECTION: BITWISE_AND;
+    "The `TYPE_INTERSECTION` token.\n\nRepresent the intersection of types, e.g. `function f(A&B $x) { … }`."
+);
+token!(
+    pub TYPE_UNION: BITWISE_OR;
+    "The `TYPE_UNION` token.\n\nRepresent the union of types, e.g. `function f(A|B $x) { … }`."
+);
 token!(
     pub UNSET: b"unset";
     "The `UNSET` token.\n\nRepresent the destruction operator, e.g. `unset($x);`."
@@ -614,6 +754,10 @@ token!(
     pub VARIABLE: b"$";
     "The `VARIABLE` token.\n\nRepresent the variable declaration operator, e.g. `$foo`."
 );
+token!(
+    pub VOID: b"void";
+    "The `VOID` token.\n\nRepresent the void type, e.g. `function f(): void { … }`."
+);
 token!(
     pub WHILE: b"while";
     "The `WHILE` token.\n\nRepresent a `while` loop, e.g. `while (…) { … }`."
@@ -626,6 +770,10 @@ token!(
     pub YIELD: b"yield";
     "The `YIELD` token.\n\nRepresent the generator operator, e.g. `yield …;`."
 );
+token!(
+    pub YIELD_ARROW: MAP;
+    "The `YIELD_ARROW` token.\n\nRepresent the separator between the key and the value of a yield, e.g. `yield $k => $v`."
+);
 token!(
     pub YIELD_FROM: b"yield from";
     "The `YIELD_FROM` token.\n\nRepresent the delegated generator operator, e.g. `yield from …;`."
@@ -633,7 +781,7 @@ token!(
 
 token!(
     COLON: b":";
-    "The `COLON` private token.\n\nSee `FUNCTION_OUTPUT` and `TERNARY_ELSE`."
+    "The `COLON` private token.\n\nSee `ALTERNATIVE_BLOCK_OPEN`, `CASE_SEPARATOR`, `ENUM_BACKING_TYPE`, `FUNCTION_OUTPUT`, `NAMED_ARGUMENT` and `TERNARY_ELSE`."
 );
 token!(
     QUESTION_MARK: b"?";
@@ -701,6 +849,7 @@ named_attr!(
       | keyword!(FINALLY)
       | keyword!(FINAL)
       | keyword!(FLOAT)
+      | keyword!(FN)
       | keyword!(FOREACH)
       | keyword!(FOR)
       | keyword!(FUNCTION)
@@ -717,6 +866,7 @@ named_attr!(
       | keyword!(ISSET)
       | keyword!(ITERABLE)
       | keyword!(LIST)
+      | keyword!(MATCH)
       | keyword!(NAMESPACE)
       | keyword!(NEW)
       | keyword!(OR)
@@ -724,6 +874,7 @@ named_attr!(
       | keyword!(PRIVATE)
       | keyword!(PROTECTED)
       | keyword!(PUBLIC)
+      | keyword!(READONLY)
       | keyword!(REQUIRE_ONCE)
       | keyword!(REQUIRE)
       | keyword!(RETURN)
@@ -792,7 +943,7 @@ impl<'a, T> Token<'a, T> {
 /// A span is a set of meta information about a token.
 ///
 /// The `Span` structure can be used as an input of the nom parsers.
-#[derive(Debug, PartialEq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
 pub struct Span<'a> {
     /// The offset represents the position of the slice relatively to
     /// the input of the parser. It starts at offset 0.
@@ -928,10 +1079,12 @@ impl<'a> InputLength for Span<'a> {
 /// as an input of the parsers.
 ///
 /// This trait aims at determining whether the current span is at the
-/// end of the input.
+/// end of the input. A span always represents a complete datum: The
+/// parser does not stream, so reaching the end of a span is reaching
+/// the end of the input.
 impl<'a> AtEof for Span<'a> {
     fn at_eof(&self) -> bool {
-        self.slice.at_eof()
+        true
     }
 }
 
@@ -1077,7 +1230,7 @@ impl<'a> InputTakeAtPosition for Span<'a> {
     {
         match (0..self.slice.len()).find(|b| predicate(self.slice[*b])) {
             Some(i) => Ok((self.slice(i..), self.slice(..i))),
-            None => Err(Err::Incomplete(Needed::Size(1))),
+            None => Ok((self.slice(self.slice.len()..), *self)),
         }
     }
     fn split_at_position1<P>(&self, predicate: P, e: ErrorKind<u32>) -> IResult<Self, Self, u32>
@@ -1087,7 +1240,8 @@ impl<'a> InputTakeAtPosition for Span<'a> {
         match (0..self.slice.len()).find(|b| predicate(self.slice[*b])) {
             Some(0) => Err(Err::Error(Context::Code(*self, e))),
             Some(i) => Ok((self.slice(i..), self.slice(..i))),
-            None => Err(Err::Incomplete(Needed::Size(1))),
+            None if self.slice.is_empty() => Err(Err::Error(Context::Code(*self, e))),
+            None => Ok((self.slice(self.slice.len()..), *self)),
         }
     }
 }
@@ -1252,7 +1406,10 @@ mod tests {
     use super::super::internal::{Context, Error, ErrorKind};
     use super::keywords;
     use super::Span;
-    use nom::{Compare, CompareResult, FindSubstring, InputIter, InputLength, Slice};
+    use nom::{
+        AtEof, Compare, CompareResult, FindSubstring, InputIter, InputLength, InputTakeAtPosition,
+        Slice,
+    };
     use std::str;
 
     macro_rules! test_keyword {
@@ -1304,6 +1461,7 @@ mod tests {
     test_keyword!(case_keyword_final:        (b"final", super::FINAL));
     test_keyword!(case_keyword_finally:      (b"finally", super::FINALLY));
     test_keyword!(case_keyword_float:        (b"float", super::FLOAT));
+    test_keyword!(case_keyword_fn:           (b"fn", super::FN));
     test_keyword!(case_keyword_for:          (b"for", super::FOR));
     test_keyword!(case_keyword_foreach:      (b"foreach", super::FOREACH));
     test_keyword!(case_keyword_function:     (b"function", super::FUNCTION));
@@ -1320,6 +1478,7 @@ mod tests {
     test_keyword!(case_keyword_isset:        (b"isset", super::ISSET));
     test_keyword!(case_keyword_iterable:     (b"iterable", super::ITERABLE));
     test_keyword!(case_keyword_list:         (b"list", super::LIST));
+    test_keyword!(case_keyword_match:        (b"match", super::MATCH));
     test_keyword!(case_keyword_namespace:    (b"namespace", super::NAMESPACE));
     test_keyword!(case_keyword_new:          (b"new", super::NEW));
     test_keyword!(case_keyword_or:           (b"or", super::OR));
@@ -1327,6 +1486,7 @@ mod tests {
     test_keyword!(case_keyword_private:      (b"private", super::PRIVATE));
     test_keyword!(case_keyword_protected:    (b"protected", super::PROTECTED));
     test_keyword!(case_keyword_public:       (b"public", super::PUBLIC));
+    test_keyword!(case_keyword_readonly:     (b"readonly", super::READONLY));
     test_keyword!(case_keyword_require:      (b"require", super::REQUIRE));
     test_keyword!(case_keyword_require_once: (b"require_once", super::REQUIRE_ONCE));
     test_keyword!(case_keyword_return:       (b"return", super::RETURN));
@@ -1636,6 +1796,119 @@ mod tests {
         assert_eq!(input.compare(b"foobar"), output);
     }
 
+    #[test]
+    fn case_span_empty_at_eof() {
+        let input = Span::new(b"");
+
+        assert!(input.at_eof());
+    }
+
+    #[test]
+    fn case_span_at_eof() {
+        let input = Span::new(b"foobar");
+
+        assert!(input.at_eof());
+    }
+
+    #[test]
+    fn case_span_empty_split_at_position() {
+        let input = Span::new(b"");
+        let output = Ok((input, input));
+
+        assert_eq!(input.split_at_position(|x| x == b'b'), output);
+    }
+
+    #[test]
+    fn case_span_split_at_position() {
+        let input = Span::new(b"foobar");
+        let output = Ok((Span::new_at(b"bar", 3, 1, 4), Span::new(b"foo")));
+
+        assert_eq!(input.split_at_position(|x| x == b'b'), output);
+    }
+
+    #[test]
+    fn case_span_split_at_position_not_found() {
+        let input = Span::new(b"foobar");
+        let output = Ok((Span::new_at(b"", 6, 1, 7), input));
+
+        assert_eq!(input.split_at_position(|x| x == b'z'), output);
+    }
+
+    #[test]
+    fn case_span_empty_split_at_position1() {
+        let input = Span::new(b"");
+        let output = Err(Error::Error(Context::Code(input, ErrorKind::AlphaNumeric)));
+
+        assert_eq!(
+            input.split_at_position1(|x| x == b'b', ErrorKind::AlphaNumeric),
+            output
+        );
+    }
+
+    #[test]
+    fn case_span_split_at_position1() {
+        let input = Span::new(b"foobar");
+        let output = Ok((Span::new_at(b"bar", 3, 1, 4), Span::new(b"foo")));
+
+        assert_eq!(
+            input.split_at_position1(|x| x == b'b', ErrorKind::AlphaNumeric),
+            output
+        );
+    }
+
+    #[test]
+    fn case_span_split_at_position1_at_the_beginning() {
+        let input = Span::new(b"foobar");
+        let output = Err(Error::Error(Context::Code(input, ErrorKind::AlphaNumeric)));
+
+        assert_eq!(
+            input.split_at_position1(|x| x == b'f', ErrorKind::AlphaNumeric),
+            output
+        );
+    }
+
+    #[test]
+    fn case_span_split_at_position1_not_found() {
+        let input = Span::new(b"foobar");
+        let output = Ok((Span::new_at(b"", 6, 1, 7), input));
+
+        assert_eq!(
+            input.split_at_position1(|x| x == b'z', ErrorKind::AlphaNumeric),
+            output
+        );
+    }
+
+    #[test]
+    fn case_span_in_a_streaming_parser() {
+        named!(
+            test<Span, (Vec<Span>, Span, Option<Span>)>,
+            tuple!(
+                many0!(tag!(b"ab")),
+                take_while1!(|x| x == b'c'),
+                opt!(tag!(b"def"))
+            )
+        );
+
+        let input = Span::new(b"ababccde");
+        let output = Ok((
+            Span::new_at(b"de", 6, 1, 7),
+            (
+                vec![Span::new(b"ab"), Span::new_at(b"ab", 2, 1, 3)],
+                Span::new_at(b"cc", 4, 1, 5),
+                None,
+            ),
+        ));
+
+        assert_eq!(test(input), output);
+        assert_eq!(
+            test(Span::new(b"abab")),
+            Err(Error::Error(Context::Code(
+                Span::new_at(b"", 4, 1, 5),
+                ErrorKind::TakeWhile1
+            )))
+        );
+    }
+
     #[test]
     fn case_span_slice_with_range() {
         let range = 2..5;
@@ -0,0 +1,727 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2017, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! AST traversal.
+//!
+//! The `Visitor` trait walks an AST, statement by statement and
+//! expression by expression. Each method has a default implementation
+//! that walks the children of the node with the `walk_*` functions, so
+//! that an implementation only overrides the methods of the nodes it is
+//! interested in, and calls the matching `walk_*` function to continue
+//! the walk. A node is not walked if its method does not call it.
+//!
+//! The names are visited with their kind, i.e. whether they designate
+//! a class, a function or a constant, see `Visitor::visit_name`.
+
+use super::ast::{
+    AnonymousFunction, Argument, Arguments, Arity, ArrayItem, ArrowFunction, Attribute,
+    BinaryOperator, ClassMember, DereferencableExpression, Expression, Literal, MagicConstant,
+    MemberName, NAryOperation, Name, ScopeResolver, Statement, StringFragment, TraitAdaptation, Ty,
+    Type, UseKind,
+};
+
+/// A visitor of an AST.
+///
+/// # Examples
+///
+/// Count the `yield` expressions of a program:
+///
+/// ```
+/// # extern crate tagua_parser;
+/// use tagua_parser::ast::Expression;
+/// use tagua_parser::parse_program;
+/// use tagua_parser::tokens::Span;
+/// use tagua_parser::visitor::{walk_statements, walk_expression, Visitor};
+///
+/// # fn main() {
+/// struct Yields(usize);
+///
+/// impl<'a> Visitor<'a> for Yields {
+///     fn visit_expression(&mut self, expression: &Expression<'a>) {
+///         if let Expression::Yield(..) = *expression {
+///             self.0 += 1;
+///         }
+///
+///         walk_expression(self, expression);
+///     }
+/// }
+///
+/// let program = parse_program(Span::new(b"<?php yield 1; if ($x) { yield 2; }")).unwrap();
+/// let mut yields = Yields(0);
+/// walk_statements(&mut yields, &program.statements);
+///
+/// assert_eq!(yields.0, 2);
+/// # }
+/// ```
+pub trait Visitor<'a> {
+    /// Visit a statement.
+    fn visit_statement(&mut self, statement: &Statement<'a>) {
+        walk_statement(self, statement);
+    }
+
+    /// Visit a member of a class, an enumeration, an interface or a
+    /// trait.
+    fn visit_class_member(&mut self, member: &ClassMember<'a>) {
+        walk_class_member(self, member);
+    }
+
+    /// Visit an expression.
+    fn visit_expression(&mut self, expression: &Expression<'a>) {
+        walk_expression(self, expression);
+    }
+
+    /// Visit a name, with the kind of the declaration it designates.
+    /// The names of the declarations themselves are not visited.
+    fn visit_name(&mut self, _name: &Name<'a>, _kind: UseKind) {}
+}
+
+/// Walk a list of statements.
+pub fn walk_statements<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, statements: &[Statement<'a>]) {
+    for statement in statements {
+        visitor.visit_statement(statement);
+    }
+}
+
+/// Walk the children of a statement.
+pub fn walk_statement<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, statement: &Statement<'a>) {
+    match *statement {
+        Statement::Block(ref body) => walk_statements(visitor, body),
+
+        Statement::Class(ref class) => {
+            walk_attributes(visitor, &class.attributes);
+
+            if let Some(ref extends) = class.extends {
+                visitor.visit_name(extends, UseKind::Class);
+            }
+
+            walk_names(visitor, &class.implements, UseKind::Class);
+            walk_class_members(visitor, &class.members);
+        }
+
+        Statement::Declare {
+            ref directives,
+            ref body,
+            ..
+        } => {
+            for &(_, ref value) in directives {
+                visitor.visit_expression(value);
+            }
+
+            walk_statements(visitor, body);
+        }
+
+        Statement::DoWhile {
+            ref body,
+            ref condition,
+        } => {
+            walk_statements(visitor, body);
+            visitor.visit_expression(condition);
+        }
+
+        Statement::Enum(ref enumeration) => {
+            walk_attributes(visitor, &enumeration.attributes);
+
+            if let Some(ref backing_type) = enumeration.backing_type {
+                visitor.visit_name(backing_type, UseKind::Class);
+            }
+
+            walk_names(visitor, &enumeration.implements, UseKind::Class);
+
+            for case in &enumeration.cases {
+                walk_attributes(visitor, &case.attributes);
+
+                if let Some(ref value) = case.value {
+                    visitor.visit_expression(value);
+                }
+            }
+
+            walk_class_members(visitor, &enumeration.members);
+        }
+
+        Statement::Expression(ref expression) => visitor.visit_expression(expression),
+
+        Statement::For {
+            ref initializers,
+            ref conditions,
+            ref steps,
+            ref body,
+            ..
+        } => {
+            walk_expressions(visitor, initializers);
+            walk_expressions(visitor, conditions);
+            walk_expressions(visitor, steps);
+            walk_statements(visitor, body);
+        }
+
+        Statement::Foreach {
+            ref subject,
+            ref key,
+            ref value,
+            ref body,
+            ..
+        } => {
+            visitor.visit_expression(subject);
+
+            if let Some(ref key) = *key {
+                visitor.visit_expression(key);
+            }
+
+            visitor.visit_expression(value);
+            walk_statements(visitor, body);
+        }
+
+        Statement::Function(ref function) => {
+            walk_attributes(visitor, &function.attributes);
+            walk_arity(visitor, &function.inputs);
+            walk_ty(visitor, &function.output);
+            walk_statements(visitor, &function.body);
+        }
+
+        Statement::If {
+            ref condition,
+            ref body,
+            ref else_ifs,
+            ref else_body,
+            ..
+        } => {
+            visitor.visit_expression(condition);
+            walk_statements(visitor, body);
+
+            for &(ref condition, ref body) in else_ifs {
+                visitor.visit_expression(condition);
+                walk_statements(visitor, body);
+            }
+
+            if let Some(ref else_body) = *else_body {
+                walk_statements(visitor, else_body);
+            }
+        }
+
+        Statement::Interface(ref interface) => {
+            walk_attributes(visitor, &interface.attributes);
+            walk_names(visitor, &interface.extends, UseKind::Class);
+            walk_class_members(visitor, &interface.members);
+        }
+
+        Statement::Namespace { ref body, .. } => {
+            if let Some(ref body) = *body {
+                walk_statements(visitor, body);
+            }
+        }
+
+        Statement::Return(ref value) => {
+            if let Some(ref value) = *value {
+                visitor.visit_expression(value);
+            }
+        }
+
+        Statement::Static(ref variables) => {
+            for &(_, ref value) in variables {
+                if let Some(ref value) = *value {
+                    visitor.visit_expression(value);
+                }
+            }
+        }
+
+        Statement::Switch {
+            ref subject,
+            ref cases,
+            ..
+        } => {
+            visitor.visit_expression(subject);
+
+            for case in cases {
+                if let Some(ref condition) = case.condition {
+                    visitor.visit_expression(condition);
+                }
+
+                walk_statements(visitor, &case.body);
+            }
+        }
+
+        Statement::Trait(ref trait_declaration) => {
+            walk_attributes(visitor, &trait_declaration.attributes);
+            walk_class_members(visitor, &trait_declaration.members);
+        }
+
+        Statement::Try {
+            ref body,
+            ref catches,
+            ref finally,
+            ..
+        } => {
+            walk_statements(visitor, body);
+
+            for catch in catches {
+                walk_names(visitor, &catch.types, UseKind::Class);
+                walk_statements(visitor, &catch.body);
+            }
+
+            if let Some(ref finally) = *finally {
+                walk_statements(visitor, &finally.body);
+            }
+        }
+
+        Statement::While {
+            ref condition,
+            ref body,
+            ..
+        } => {
+            visitor.visit_expression(condition);
+            walk_statements(visitor, body);
+        }
+
+        Statement::Break(_)
+        | Statement::Continue(_)
+        | Statement::Empty
+        | Statement::Global(_)
+        | Statement::InlineHtml(_)
+        | Statement::Use(_) => {}
+    }
+}
+
+fn walk_class_members<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, members: &[ClassMember<'a>]) {
+    for member in members {
+        visitor.visit_class_member(member);
+    }
+}
+
+/// Walk the children of a class member.
+pub fn walk_class_member<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, member: &ClassMember<'a>) {
+    match *member {
+        ClassMember::Constant {
+            ref attributes,
+            ref constants,
+            ..
+        } => {
+            walk_attributes(visitor, attributes);
+
+            for &(_, ref value) in constants {
+                visitor.visit_expression(value);
+            }
+        }
+
+        ClassMember::Property {
+            ref attributes,
+            ref ty,
+            ref properties,
+            ..
+        } => {
+            walk_attributes(visitor, attributes);
+            walk_ty(visitor, ty);
+
+            for &(_, ref value) in properties {
+                if let Some(ref value) = *value {
+                    visitor.visit_expression(value);
+                }
+            }
+        }
+
+        ClassMember::Method {
+            ref attributes,
+            ref inputs,
+            ref output,
+            ref body,
+            ..
+        } => {
+            walk_attributes(visitor, attributes);
+            walk_arity(visitor, inputs);
+            walk_ty(visitor, output);
+
+            if let Some(ref body) = *body {
+                walk_statements(visitor, body);
+            }
+        }
+
+        ClassMember::TraitUse {
+            ref traits,
+            ref adaptations,
+        } => {
+            walk_names(visitor, traits, UseKind::Class);
+
+            for adaptation in adaptations {
+                match *adaptation {
+                    TraitAdaptation::Insteadof {
+                        ref trait_name,
+                        ref insteadof,
+                        ..
+                    } => {
+                        visitor.visit_name(trait_name, UseKind::Class);
+                        walk_names(visitor, insteadof, UseKind::Class);
+                    }
+
+                    TraitAdaptation::Alias { ref trait_name, .. } => {
+                        if let Some(ref trait_name) = *trait_name {
+                            visitor.visit_name(trait_name, UseKind::Class);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Walk a list of expressions.
+pub fn walk_expressions<'a, V: Visitor<'a> + ?Sized>(
+    visitor: &mut V,
+    expressions: &[Expression<'a>],
+) {
+    for expression in expressions {
+        visitor.visit_expression(expression);
+    }
+}
+
+/// Walk the children of an expression.
+pub fn walk_expression<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, expression: &Expression<'a>) {
+    match *expression {
+        Expression::AnonymousFunction(AnonymousFunction {
+            ref attributes,
+            ref inputs,
+            ref output,
+            ref enclosing_scope,
+            ref body,
+            ..
+        }) => {
+            walk_attributes(visitor, attributes);
+
+            if let Some(ref enclosing_scope) = *enclosing_scope {
+                walk_expressions(visitor, enclosing_scope);
+            }
+
+            walk_arity(visitor, inputs);
+            walk_ty(visitor, output);
+            walk_statements(visitor, body);
+        }
+
+        Expression::Array(ref items) => {
+            for item in items {
+                match *item {
+                    ArrayItem::Pair(ref key, ref value) => {
+                        if let Some(ref key) = *key {
+                            visitor.visit_expression(key);
+                        }
+
+                        visitor.visit_expression(value);
+                    }
+
+                    ArrayItem::Unpacked(ref value) => visitor.visit_expression(value),
+                }
+            }
+        }
+
+        Expression::ArrayAccess(ref array, ref index) => {
+            walk_dereferencable_expression(visitor, array);
+
+            if let Some(ref index) = *index {
+                visitor.visit_expression(index);
+            }
+        }
+
+        Expression::ArrowFunction(ArrowFunction {
+            ref attributes,
+            ref inputs,
+            ref output,
+            ref body,
+            ..
+        }) => {
+            walk_attributes(visitor, attributes);
+            walk_arity(visitor, inputs);
+            walk_ty(visitor, output);
+            visitor.visit_expression(body);
+        }
+
+        Expression::Call(ref callee, ref arguments) => {
+            match **callee {
+                Expression::Name(ref name) => visitor.visit_name(name, UseKind::Function),
+                ref callee => visitor.visit_expression(callee),
+            }
+
+            walk_arguments(visitor, arguments);
+        }
+
+        Expression::ClassConstantAccess(ref scope, _)
+        | Expression::MagicConstant(MagicConstant::ClassName(ref scope, _))
+        | Expression::StaticPropertyAccess(ref scope, _) => walk_scope_resolver(visitor, scope),
+
+        Expression::Clone(ref expression)
+        | Expression::Empty(ref expression)
+        | Expression::Eval(ref expression)
+        | Expression::Import(_, ref expression)
+        | Expression::Print(ref expression)
+        | Expression::Reference(ref expression)
+        | Expression::Throw(ref expression)
+        | Expression::YieldFrom(ref expression) => visitor.visit_expression(expression),
+
+        Expression::Echo(ref expressions)
+        | Expression::Isset(ref expressions)
+        | Expression::Unset(ref expressions) => walk_expressions(visitor, expressions),
+
+        Expression::Exit(ref code) => {
+            if let Some(ref code) = *code {
+                visitor.visit_expression(code);
+            }
+        }
+
+        Expression::List(ref pairs) => {
+            for pair in pairs {
+                if let Some((ref key, ref value)) = *pair {
+                    if let Some(ref key) = *key {
+                        visitor.visit_expression(key);
+                    }
+
+                    visitor.visit_expression(value);
+                }
+            }
+        }
+
+        Expression::Match(ref subject, ref arms) => {
+            visitor.visit_expression(subject);
+
+            for arm in arms {
+                if let Some(ref conditions) = arm.conditions {
+                    walk_expressions(visitor, conditions);
+                }
+
+                visitor.visit_expression(&arm.expression);
+            }
+        }
+
+        Expression::MethodCall(ref object, ref member, ref arguments)
+        | Expression::NullsafeMethodCall(ref object, ref member, ref arguments) => {
+            walk_dereferencable_expression(visitor, object);
+            walk_member_name(visitor, member);
+            walk_arguments(visitor, arguments);
+        }
+
+        Expression::Name(ref name) => visitor.visit_name(name, UseKind::Constant),
+
+        Expression::NAryOperation(ref operation) => walk_n_ary_operation(visitor, operation),
+
+        Expression::New(ref scope, ref arguments) => {
+            walk_scope_resolver(visitor, scope);
+
+            if let Some(ref arguments) = *arguments {
+                walk_arguments(visitor, arguments);
+            }
+        }
+
+        Expression::NullsafePropertyAccess(ref object, ref member)
+        | Expression::PropertyAccess(ref object, ref member) => {
+            walk_dereferencable_expression(visitor, object);
+            walk_member_name(visitor, member);
+        }
+
+        Expression::StaticMethodCall(ref scope, ref member, ref arguments) => {
+            walk_scope_resolver(visitor, scope);
+            walk_member_name(visitor, member);
+            walk_arguments(visitor, arguments);
+        }
+
+        Expression::Yield(ref key, ref value) => {
+            if let Some(ref key) = *key {
+                visitor.visit_expression(key);
+            }
+
+            if let Some(ref value) = *value {
+                visitor.visit_expression(value);
+            }
+        }
+
+        Expression::Literal(ref literal) => walk_literal(visitor, literal),
+
+        Expression::MagicConstant(_) | Expression::Variable(_) => {}
+    }
+}
+
+fn walk_literal<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, literal: &Literal<'a>) {
+    if let Literal::InterpolatedString(ref string) = *literal {
+        for fragment in &string.value {
+            if let StringFragment::Expression(ref expression) = *fragment {
+                visitor.visit_expression(&expression.value);
+            }
+        }
+    }
+}
+
+fn walk_n_ary_operation<'a, V: Visitor<'a> + ?Sized>(
+    visitor: &mut V,
+    operation: &NAryOperation<'a>,
+) {
+    match *operation {
+        NAryOperation::Nullary(ref expression) => visitor.visit_expression(expression),
+
+        NAryOperation::Unary { ref operand, .. } => walk_n_ary_operation(visitor, operand),
+
+        NAryOperation::Binary {
+            operator: BinaryOperator::InstanceOf,
+            ref left_operand,
+            ref right_operand,
+        } => {
+            walk_n_ary_operation(visitor, left_operand);
+
+            // The right operand of `instanceof` is a class name, not a
+            // constant name.
+            match **right_operand {
+                NAryOperation::Nullary(ref expression) => match **expression {
+                    Expression::Name(ref name) => visitor.visit_name(name, UseKind::Class),
+                    ref expression => visitor.visit_expression(expression),
+                },
+
+                ref right_operand => walk_n_ary_operation(visitor, right_operand),
+            }
+        }
+
+        NAryOperation::Binary {
+            ref left_operand,
+            ref right_operand,
+            ..
+        } => {
+            walk_n_ary_operation(visitor, left_operand);
+            walk_n_ary_operation(visitor, right_operand);
+        }
+
+        NAryOperation::Ternary {
+            ref left_operand,
+            ref middle_operand,
+            ref right_operand,
+            ..
+        } => {
+            walk_n_ary_operation(visitor, left_operand);
+            visitor.visit_expression(middle_operand);
+            walk_n_ary_operation(visitor, right_operand);
+        }
+    }
+}
+
+fn walk_dereferencable_expression<'a, V: Visitor<'a> + ?Sized>(
+    visitor: &mut V,
+    expression: &DereferencableExpression<'a>,
+) {
+    match *expression {
+        DereferencableExpression::Expression(ref expression)
+        | DereferencableExpression::Array(ref expression) => visitor.visit_expression(expression),
+
+        DereferencableExpression::String(ref literal) => walk_literal(visitor, literal),
+
+        DereferencableExpression::Variable(_) => {}
+    }
+}
+
+fn walk_scope_resolver<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, scope: &ScopeResolver<'a>) {
+    match *scope {
+        ScopeResolver::ByName(ref name) => visitor.visit_name(name, UseKind::Class),
+
+        ScopeResolver::ByExpression(ref expression) => {
+            walk_dereferencable_expression(visitor, expression)
+        }
+
+        ScopeResolver::ByRelative(_) => {}
+    }
+}
+
+fn walk_member_name<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, member: &MemberName<'a>) {
+    match *member {
+        MemberName::Expression(ref expression) => visitor.visit_expression(expression),
+
+        MemberName::Name(_) => {}
+    }
+}
+
+fn walk_arguments<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, arguments: &Arguments<'a>) {
+    match *arguments {
+        Arguments::List(ref arguments) => {
+            for argument in arguments {
+                match *argument {
+                    Argument::Positional(ref value)
+                    | Argument::Unpacked(ref value)
+                    | Argument::Named(_, ref value) => visitor.visit_expression(value),
+                }
+            }
+        }
+
+        Arguments::FirstClassCallable => {}
+    }
+}
+
+fn walk_names<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, names: &[Name<'a>], kind: UseKind) {
+    for name in names {
+        visitor.visit_name(name, kind);
+    }
+}
+
+fn walk_attributes<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, attributes: &[Attribute<'a>]) {
+    for attribute in attributes {
+        visitor.visit_name(&attribute.name, UseKind::Class);
+
+        if let Some(ref arguments) = attribute.arguments {
+            walk_arguments(visitor, arguments);
+        }
+    }
+}
+
+fn walk_arity<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, arity: &Arity<'a>) {
+    match *arity {
+        Arity::Finite(ref parameters) | Arity::Infinite(ref parameters) => {
+            for parameter in parameters {
+                walk_attributes(visitor, &parameter.attributes);
+                walk_ty(visitor, &parameter.ty);
+
+                if let Some(ref value) = parameter.value {
+                    visitor.visit_expression(value);
+                }
+            }
+        }
+
+        Arity::Constant => {}
+    }
+}
+
+fn walk_ty<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, ty: &Ty<'a>) {
+    match *ty {
+        Ty::Copy(Some(ref ty)) | Ty::Reference(Some(ref ty)) => walk_type(visitor, ty),
+
+        Ty::Copy(None) | Ty::Reference(None) => {}
+    }
+}
+
+fn walk_type<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, ty: &Type<'a>) {
+    match *ty {
+        Type::Name(ref name) | Type::Nullable(ref name) => visitor.visit_name(name, UseKind::Class),
+
+        Type::Union(ref types) => {
+            for ty in types {
+                walk_type(visitor, ty);
+            }
+        }
+
+        Type::Intersection(ref names) => walk_names(visitor, names, UseKind::Class),
+    }
+}